@@ -1,22 +1,96 @@
 #![forbid(unsafe_code)]
 use std::{
     fs,
+    io::Write,
     path::{Path, PathBuf},
     rc::Rc,
 };
 
-use clap::{builder::PossibleValue, ArgGroup, CommandFactory, Parser};
+use clap::{
+    builder::PossibleValue, ArgGroup, Args, CommandFactory, Parser, Subcommand,
+    ValueEnum,
+};
 use indicate::{
-    advisory::AdvisoryClient, execute_query_with_adapter, query::FullQuery,
-    query::FullQueryBuilder, repo::github::GitHubClient,
-    util::transparent_results, CargoOpt, IndicateAdapter,
-    IndicateAdapterBuilder, ManifestPath,
+    advisory::AdvisoryClient,
+    capabilities::Capabilities,
+    checkpoint::{execute_query_resumable, Checkpoint},
+    compare::{compare_results, ResultComparison},
+    config::{IndicateConfig, CONFIG_FILE_NAME},
+    cost::DEFAULT_MAX_COST,
+    crates_io::CratesIoClient,
+    doctor::{diagnose, CheckStatus},
+    execute_query_interruptible, execute_query_with_adapter,
+    feature_matrix::{feature_gated_dependencies, FeatureCombination},
+    humanize::ValueStyle,
+    interrupt::{self, INTERRUPTED_EXIT_CODE},
+    lockfile::Lockfile,
+    parquet, protobuf,
+    query::FullQuery,
+    query::FullQueryBuilder,
+    release_notes::{bumped_dependencies, ReleaseNotes},
+    repo::gitea::GiteaClient,
+    repo::github::GitHubClient,
+    report::ReportFormat,
+    reproducibility::reproducibility_report,
+    target_matrix::resolve_target_matrix,
+    util::transparent_results,
+    vendor::VendoredSources,
+    worksheet::{worksheets, write_worksheets, WorksheetConfig},
+    CargoOpt, IndicateAdapter, IndicateAdapterBuilder, ManifestPath,
 };
+use serde::Serialize;
 mod util;
 
+/// Formats query results can be written in
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum OutputFormat {
+    /// Pretty-printed JSON
+    #[default]
+    Json,
+
+    /// Length-delimited Protocol Buffers messages, one per query, following
+    /// the schema printed by `--show-protobuf-schema`
+    Protobuf,
+
+    /// Apache Parquet files, one per query; Requires one output file per
+    /// query, or `--output-dir`, if more than one query is provided
+    Parquet,
+}
+
+impl OutputFormat {
+    /// Extension used for files in `--output-dir`
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Json => "out.json",
+            OutputFormat::Protobuf => "out.pb",
+            OutputFormat::Parquet => "out.parquet",
+        }
+    }
+
+    /// Separator between the results of multiple queries written to the same
+    /// output
+    fn separator(self) -> &'static [u8] {
+        match self {
+            OutputFormat::Json => b"\n",
+            OutputFormat::Protobuf | OutputFormat::Parquet => b"",
+        }
+    }
+}
+
+/// The output of `--capabilities`
+#[derive(Debug, Serialize)]
+struct CapabilitiesReport {
+    version: &'static str,
+    output_formats: Vec<String>,
+
+    #[serde(flatten)]
+    capabilities: Capabilities,
+}
+
 /// Run GraphQL-like queries on Rust projects and their dependencies
 #[derive(Parser, Debug, Clone)]
 #[command(author = "Emil Jonathan Eriksson", version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 #[command(group(
     ArgGroup::new("query_inputs")
         .multiple(true) // We can have `--query-dir` AND `--query-with-args`
@@ -100,7 +174,7 @@ struct IndicateCli {
     /// Path to a Cargo.toml file, or a directory containing one
     #[arg(
         last(true),
-        required_unless_present = "show_schema",
+        required_unless_present_any = ["show_schema", "show_protobuf_schema", "capabilities"],
         default_value = "./",
         value_hint = clap::ValueHint::AnyPath
     )]
@@ -132,7 +206,8 @@ struct IndicateCli {
     /// directories if needed
     ///
     /// The results will be placed in files in accordance with their filename
-    /// with the extension replaced with `.out.json`.
+    /// with the extension replaced with `.out.json` (or `.out.pb` for
+    /// protobuf).
     #[arg(
         short = 'O',
         long,
@@ -143,11 +218,34 @@ struct IndicateCli {
     )]
     output_dir: Option<PathBuf>,
 
+    /// The format query results are written in
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    output_format: OutputFormat,
+
+    /// Also write a descriptor of the outputs of each query and their
+    /// Trustfall types next to the results, as `<name>.descriptor.json`
+    ///
+    /// The descriptor contains one entry per query written to that output,
+    /// in order, so that the results can be loaded into typed tables.
+    /// Requires `--output` or `--output-dir`.
+    #[arg(long)]
+    result_descriptor: bool,
+
     /// The max number of query results to evaluate,
     /// use to limit for example third party API calls
     #[arg(short = 'm', long, value_name = "INTEGER")]
     max_results: Option<usize>,
 
+    /// Records the results and progress of queries in a file, and resumes
+    /// from it if it exists, so that an interrupted run continues where it
+    /// stopped; The file is removed once all queries have completed
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    checkpoint: Option<PathBuf>,
+
     /// Outputs the schema that is used to write queries,
     /// in a GraphQL format, and exits
     #[arg(
@@ -157,6 +255,19 @@ struct IndicateCli {
     )]
     show_schema: bool,
 
+    /// Outputs the Protocol Buffers schema used by `--output-format protobuf`,
+    /// and exits
+    #[arg(long, group = "query_inputs")]
+    show_protobuf_schema: bool,
+
+    /// Outputs a JSON report of the version, entry points, output formats,
+    /// integrations and their configuration, and required external binaries
+    /// and if they are installed, and exits
+    ///
+    /// Useful to verify an environment before running queries.
+    #[arg(long, group = "query_inputs")]
+    capabilities: bool,
+
     /// Use all available features when resolving metadata for this package
     #[arg(
         long,
@@ -173,6 +284,29 @@ struct IndicateCli {
     #[arg(short, long, num_args=0.., conflicts_with = "all_features")]
     features: Option<Vec<String>>,
 
+    /// Use a named feature profile when resolving metadata for this package,
+    /// as defined in `indicate.toml`
+    ///
+    /// The closest `indicate.toml` file in the package directory or any of
+    /// its parent directories is used, unless `--config` is provided.
+    #[arg(
+        long,
+        env = "INDICATE_PROFILE",
+        value_name = "NAME",
+        conflicts_with_all = ["all_features", "no_default_features", "features"]
+    )]
+    profile: Option<String>,
+
+    /// Configuration file to read feature profiles from, instead of the
+    /// closest `indicate.toml`
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "profile",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    config: Option<PathBuf>,
+
     /// Use a local `advisory-db` database instead of fetching the default
     /// from GitHub
     #[arg(long, value_hint = clap::ValueHint::DirPath)]
@@ -190,33 +324,992 @@ struct IndicateCli {
     /// invocations where execution time is not important.
     #[arg(long)]
     await_github_quota: bool,
+
+    /// Hosts of self-hosted Gitea instances, such as `gitea.example.com`,
+    /// whose repositories are retrieved as `GiteaRepository`; Codeberg is
+    /// always included
+    #[arg(long, value_name = "HOST", value_delimiter = ',')]
+    gitea_host: Vec<String>,
+
+    /// Only use packages added or updated since a git reference, such as
+    /// `origin/main`, as starting points of `Dependencies` and
+    /// `TransitiveDependencies`
+    ///
+    /// The packages are found by comparing `Cargo.lock` to its version at
+    /// that reference, making it useful for only analyzing what a pull
+    /// request introduces.
+    #[arg(long, value_name = "GIT_REF")]
+    changed_since: Option<String>,
+
+    /// Read the sources of packages from crates.io from a directory created
+    /// by `cargo vendor`, instead of downloading them
+    ///
+    /// Useful in environments without network access, where
+    /// `~/.cargo/registry` is not available. Affects everything using the
+    /// sources of packages, such as code stats, `cargo-geiger` and license
+    /// files.
+    #[arg(long, value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    vendor_dir: Option<PathBuf>,
+
+    /// Like `--vendor-dir`, but with a directory of `.crate` archives, such
+    /// as `libc-0.2.139.crate`, which are extracted before use
+    #[arg(
+        long,
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath,
+        conflicts_with = "vendor_dir"
+    )]
+    crate_archives: Option<PathBuf>,
+
+    /// The maximum estimated number of requests a query may make to external
+    /// services, such as GitHub and crates.io
+    ///
+    /// Queries estimated to exceed it are not executed, to avoid using up a
+    /// shared API quota by accident. The estimate assumes that edges to
+    /// packages lead to all packages in the dependency graph.
+    #[arg(
+        long,
+        env = "INDICATE_MAX_COST",
+        value_name = "REQUESTS",
+        default_value_t = DEFAULT_MAX_COST
+    )]
+    max_cost: u64,
+
+    /// Execute queries even if they are estimated to exceed `--max-cost`
+    #[arg(long)]
+    allow_expensive: bool,
+
+    #[command(subcommand)]
+    command: Option<IndicateCommand>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum IndicateCommand {
+    /// Run queries against two packages, such as two releases of the same
+    /// package, and output what result rows were added, removed or changed
+    Compare(CompareArgs),
+
+    /// Resolve the dependencies of a package under a matrix of feature
+    /// combinations, and output dependencies that are only resolved under
+    /// some of them
+    ///
+    /// The matrix contains the default features, no features, all features
+    /// and each feature of the package on its own.
+    FeatureMatrix(FeatureMatrixArgs),
+
+    /// Resolve the dependencies of a package for a number of target
+    /// platforms, and output the dependencies of each target and the
+    /// dependencies not used by all of them
+    TargetMatrix(TargetMatrixArgs),
+
+    /// Summarize if a build of a package can be reproduced from its lock
+    /// file, for release sign-off
+    ///
+    /// Reports the checksum coverage and hash of `Cargo.lock`, git
+    /// dependencies and whether they are locked to a commit, and path
+    /// dependencies.
+    Reproducibility(ReproducibilityArgs),
+
+    /// Summarize the GitHub release notes of all dependencies bumped between
+    /// two `Cargo.lock` files, in Markdown
+    ///
+    /// Repositories are found using crates.io, so only bumped dependencies
+    /// from crates.io with a GitHub repository have release notes.
+    ReleaseNotes(ReleaseNotesArgs),
+
+    /// Write one pre-filled Markdown worksheet per direct dependency to a
+    /// directory, for manually reviewing them
+    ///
+    /// Each worksheet contains the metadata and known advisories of a
+    /// dependency, and optionally its unsafety and repository health,
+    /// followed by a checklist for the reviewer.
+    AuditWorksheets(AuditWorksheetsArgs),
+
+    /// Check that the environment is set up correctly, and print how to fix
+    /// any problems found
+    ///
+    /// Checks that `cargo-geiger` is installed, that the GitHub token is
+    /// valid, that `advisory-db` can be reached and that cache directories
+    /// can be written to. Exits with a non-zero status if any check fails
+    /// with an error.
+    Doctor,
+}
+
+/// Formats a reproducibility report can be written in
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum ReportOutputFormat {
+    #[default]
+    Json,
+    Markdown,
+}
+
+impl From<ReportOutputFormat> for ReportFormat {
+    fn from(format: ReportOutputFormat) -> Self {
+        match format {
+            ReportOutputFormat::Json => ReportFormat::Json,
+            ReportOutputFormat::Markdown => ReportFormat::Markdown,
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+#[command(group(
+    ArgGroup::new("compare_inputs")
+        .multiple(true)
+        .required(true)
+))]
+struct CompareArgs {
+    /// Path to a Cargo.toml file, or a directory containing one, of the
+    /// package before the change
+    #[arg(value_hint = clap::ValueHint::AnyPath)]
+    before: PathBuf,
+
+    /// Path to a Cargo.toml file, or a directory containing one, of the
+    /// package after the change
+    #[arg(value_hint = clap::ValueHint::AnyPath)]
+    after: PathBuf,
+
+    /// Indicate queries in a supported file format to be run against both
+    /// packages, containing arguments
+    #[arg(
+        short = 'Q',
+        long,
+        group = "compare_inputs",
+        num_args = 1..,
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    query_with_args: Option<Vec<PathBuf>>,
+
+    /// A directory containing indicate queries in a supported file format,
+    /// containing arguments
+    #[arg(
+        short = 'd',
+        long,
+        group = "compare_inputs",
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath
+    )]
+    query_dir: Option<PathBuf>,
+
+    /// Exclude files containing this substring when using `--query-dir`
+    #[arg(short = 'x', num_args = 0.., long, requires = "query_dir")]
+    exclude: Vec<String>,
+
+    /// Outputs used to match rows between the two packages; Rows with the
+    /// same key but different values are considered changed
+    #[arg(
+        short,
+        long,
+        num_args = 1..,
+        default_value = indicate::compare::DEFAULT_KEY_FIELD
+    )]
+    key: Vec<String>,
+
+    /// Define another output than stdout for the comparison
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    output: Option<PathBuf>,
+
+    /// The max number of query results to evaluate per package
+    #[arg(short = 'm', long, value_name = "INTEGER")]
+    max_results: Option<usize>,
+}
+
+#[derive(Args, Debug, Clone)]
+struct FeatureMatrixArgs {
+    /// Path to a Cargo.toml file, or a directory containing one
+    #[arg(default_value = "./", value_hint = clap::ValueHint::AnyPath)]
+    package: PathBuf,
+
+    /// Specify the package name that is to be parsed from the package path, if
+    /// it might be a workspace
+    #[arg(short = 'p', long = "package")]
+    package_name: Option<String>,
+
+    /// Also include all feature profiles defined in `indicate.toml` in the
+    /// matrix
+    #[arg(long)]
+    profiles: bool,
+
+    /// Configuration file to read feature profiles from, instead of the
+    /// closest `indicate.toml`
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "profiles",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    config: Option<PathBuf>,
+
+    /// Define another output than stdout for the report
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    output: Option<PathBuf>,
 }
 
+#[derive(Args, Debug, Clone)]
+struct TargetMatrixArgs {
+    /// Path to a Cargo.toml file, or a directory containing one
+    #[arg(default_value = "./", value_hint = clap::ValueHint::AnyPath)]
+    package: PathBuf,
+
+    /// Specify the package name that is to be parsed from the package path, if
+    /// it might be a workspace
+    #[arg(short = 'p', long = "package")]
+    package_name: Option<String>,
+
+    /// Target platforms to resolve dependencies for, such as
+    /// `x86_64-unknown-linux-gnu,wasm32-unknown-unknown`
+    #[arg(
+        short,
+        long,
+        required = true,
+        value_delimiter = ',',
+        value_name = "TARGET"
+    )]
+    targets: Vec<String>,
+
+    /// Use a named feature profile from `indicate.toml` when resolving
+    /// metadata
+    #[arg(long, env = "INDICATE_PROFILE", value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Configuration file to read feature profiles from, instead of the
+    /// closest `indicate.toml`
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "profile",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    config: Option<PathBuf>,
+
+    /// Define another output than stdout for the report
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+struct ReproducibilityArgs {
+    /// Path to a Cargo.toml file, or a directory containing one
+    #[arg(default_value = "./", value_hint = clap::ValueHint::AnyPath)]
+    package: PathBuf,
+
+    /// Specify the package name that is to be parsed from the package path, if
+    /// it might be a workspace
+    #[arg(short = 'p', long = "package")]
+    package_name: Option<String>,
+
+    /// Use a named feature profile from `indicate.toml` when resolving
+    /// metadata
+    #[arg(long, env = "INDICATE_PROFILE", value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Configuration file to read feature profiles from, instead of the
+    /// closest `indicate.toml`
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "profile",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    config: Option<PathBuf>,
+
+    /// The format the report is written in
+    #[arg(long, value_enum, default_value_t = ReportOutputFormat::Json)]
+    format: ReportOutputFormat,
+
+    /// Define another output than stdout for the report
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    output: Option<PathBuf>,
+
+    /// Exit with a non-zero exit code if the build is not reproducible, such
+    /// as if a git dependency is not locked to a commit
+    #[arg(long)]
+    strict: bool,
+
+    /// Render values as they are in Markdown, instead of as `1.2M`, `4.5 MB`
+    /// or `3 years ago`
+    #[arg(long)]
+    raw: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+struct AuditWorksheetsArgs {
+    /// Path to a Cargo.toml file, or a directory containing one
+    #[arg(default_value = "./", value_hint = clap::ValueHint::AnyPath)]
+    package: PathBuf,
+
+    /// Specify the package name that is to be parsed from the package path, if
+    /// it might be a workspace
+    #[arg(short = 'p', long = "package")]
+    package_name: Option<String>,
+
+    /// Use a named feature profile from `indicate.toml` when resolving
+    /// metadata
+    #[arg(long, env = "INDICATE_PROFILE", value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Configuration file to read feature profiles from, instead of the
+    /// closest `indicate.toml`
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "profile",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    config: Option<PathBuf>,
+
+    /// Directory to write the worksheets to, one per dependency
+    #[arg(
+        short,
+        long,
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath
+    )]
+    output_dir: PathBuf,
+
+    /// Include unsafety, requires `cargo-geiger` to be installed
+    #[arg(long)]
+    geiger: bool,
+
+    /// Include repository health, requires `USER_AGENT` and
+    /// `GITHUB_API_TOKEN` to be set
+    #[arg(long)]
+    github: bool,
+
+    /// Use a local `advisory-db` database instead of fetching the default
+    /// from GitHub
+    #[arg(long, value_hint = clap::ValueHint::DirPath)]
+    advisory_db_dir: Option<PathBuf>,
+
+    /// Attempt to use a cached version of `advisory-db` from the default
+    /// location; Will fetch a new one if not present
+    #[arg(long, conflicts_with = "advisory_db_dir")]
+    cached_advisory_db: bool,
+
+    /// Render values as they are, instead of as `1.2K` or `3 years ago`
+    #[arg(long)]
+    raw: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+struct ReleaseNotesArgs {
+    /// The `Cargo.lock` file before the dependency bump
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    old: PathBuf,
+
+    /// The `Cargo.lock` file after the dependency bump
+    #[arg(default_value = "Cargo.lock", value_hint = clap::ValueHint::FilePath)]
+    new: PathBuf,
+
+    /// Define another output than stdout for the summary
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    output: Option<PathBuf>,
+
+    /// If the program should sleep while awaiting a new GitHub API quota, if it
+    /// is reached during execution
+    #[arg(long)]
+    await_github_quota: bool,
+
+    /// Render when releases were published as Unix timestamps, instead of
+    /// such as `3 years ago`
+    #[arg(long)]
+    raw: bool,
+}
+
+/// The JSON output of a query interrupted before it finished
+#[derive(Debug, Serialize)]
+struct PartialResults<T> {
+    partial: bool,
+    results: T,
+}
+
+/// Executes queries, and encodes their results in the output format,
+/// returning if they are partial
+///
+/// If a query stops early since it was interrupted, its results are marked
+/// as partial (in JSON), and the remaining queries are not executed. Other
+/// formats have no such marker. If a checkpoint is used, it is saved after
+/// each query.
 fn execute_queries(
     full_queries: &Vec<FullQuery>,
     adapter: &Rc<IndicateAdapter>,
     max_results: Option<usize>,
-) -> Vec<String> {
-    let mut res_strings = Vec::with_capacity(full_queries.len());
+    output_format: OutputFormat,
+    checkpoint_path: Option<&Path>,
+) -> (Vec<Vec<u8>>, bool) {
+    let mut checkpoint = checkpoint_path.map(|path| {
+        Checkpoint::load(path).unwrap_or_else(|e| {
+            panic!(
+                "could not read checkpoint {} due to error: {e}",
+                path.to_string_lossy()
+            )
+        })
+    });
+
+    let mut res_bytes = Vec::with_capacity(full_queries.len());
+    let mut partial = false;
     for query in full_queries {
-        let res = execute_query_with_adapter(
-            query,
-            Rc::clone(adapter),
-            max_results,
-        );
-        let transparent_res = transparent_results(res);
-        res_strings.push(
-            serde_json::to_string_pretty(&transparent_res)
-                .expect("could not serialize result"),
+        let res = match (&mut checkpoint, checkpoint_path) {
+            (Some(checkpoint), Some(path)) => {
+                let res = execute_query_resumable(
+                    checkpoint,
+                    query,
+                    Rc::clone(adapter),
+                    max_results,
+                );
+                checkpoint.save(path).unwrap_or_else(|e| {
+                    eprintln!(
+                        "could not write checkpoint {} due to error: {e}",
+                        path.to_string_lossy()
+                    );
+                });
+                res
+            }
+            _ => execute_query_interruptible(
+                query,
+                Rc::clone(adapter),
+                max_results,
+            ),
+        };
+        partial = res.partial;
+        let res = res.rows;
+        let bytes = match output_format {
+            OutputFormat::Json if partial => {
+                let partial_res = PartialResults {
+                    partial,
+                    results: transparent_results(res),
+                };
+                serde_json::to_string_pretty(&partial_res)
+                    .expect("could not serialize result")
+                    .into_bytes()
+            }
+            OutputFormat::Json => {
+                let transparent_res = transparent_results(res);
+                serde_json::to_string_pretty(&transparent_res)
+                    .expect("could not serialize result")
+                    .into_bytes()
+            }
+            OutputFormat::Protobuf => {
+                protobuf::encode_results_length_delimited(&res)
+            }
+            OutputFormat::Parquet => parquet::encode_results(&res)
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "could not encode result as parquet due to error: {e}"
+                    )
+                }),
+        };
+        res_bytes.push(bytes);
+
+        if partial {
+            break;
+        }
+    }
+
+    if let (Some(checkpoint), Some(path)) = (checkpoint, checkpoint_path) {
+        if checkpoint.is_complete() && !partial {
+            fs::remove_file(path).unwrap_or_else(|e| {
+                eprintln!(
+                    "could not remove completed checkpoint {} due to error: {e}",
+                    path.to_string_lossy()
+                );
+            });
+        }
+    }
+
+    (res_bytes, partial)
+}
+
+/// Writes the result descriptors of queries written to an output file next
+/// to it
+fn write_result_descriptors(full_queries: &[FullQuery], output_path: &Path) {
+    let descriptors = full_queries
+        .iter()
+        .map(|q| {
+            // Queries are validated before being executed
+            q.result_descriptor()
+                .expect("could not describe the outputs of a valid query")
+        })
+        .collect::<Vec<_>>();
+    let path = util::descriptor_path(output_path);
+    let json = serde_json::to_string_pretty(&descriptors)
+        .expect("could not serialize result descriptors");
+    fs::write(&path, json).unwrap_or_else(|e| {
+        eprintln!(
+            "could not write result descriptor to {} due to error: {e}",
+            path.to_string_lossy()
         );
+    });
+}
+
+/// Retrieves the paths of all queries in a directory, except those with a
+/// file name in `exclude`
+fn read_query_dir(
+    cmd: &mut clap::Command,
+    dir_path: &Path,
+    exclude: &[String],
+) -> Vec<PathBuf> {
+    let files = fs::read_dir(dir_path).unwrap_or_else(|e| {
+        cmd.error(
+            clap::error::ErrorKind::InvalidValue,
+            format!(
+                "could not read queries in directory {} due to error: {e}",
+                dir_path.to_string_lossy()
+            ),
+        )
+        .exit();
+    });
+
+    let mut q = Vec::new();
+    for f in files {
+        let file_path = f
+            .unwrap_or_else(|e| {
+                panic!(
+                    "could not read file in {} due to error {e}",
+                    dir_path.to_string_lossy()
+                )
+            })
+            .path();
+
+        if file_path.is_dir() {
+            let msg = format!(
+                "nested directories with --query-dir not supported, found {}",
+                file_path.to_string_lossy()
+            );
+            cmd.error(clap::error::ErrorKind::ValueValidation, msg)
+                .exit();
+        } else if exclude.contains(
+            &file_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into(),
+        ) {
+            // Don't add this, it is included in list of excluded files
+            continue;
+        } else {
+            q.push(file_path);
+        }
     }
 
-    res_strings
+    q
+}
+
+/// The comparison of a single query, as written by `compare`
+#[derive(Serialize)]
+struct QueryComparison {
+    query: String,
+    #[serde(flatten)]
+    comparison: ResultComparison,
+}
+
+/// Runs queries against two packages, and writes what rows were added,
+/// removed or changed between them
+fn compare(args: CompareArgs) {
+    // Used to report errors
+    let mut cmd = IndicateCli::command();
+
+    let mut query_paths = args.query_with_args.unwrap_or_default();
+    if let Some(dir_path) = &args.query_dir {
+        query_paths.extend(read_query_dir(&mut cmd, dir_path, &args.exclude));
+    }
+    if query_paths.is_empty() {
+        cmd.error(clap::error::ErrorKind::TooFewValues, "no queries provided")
+            .exit();
+    }
+
+    let full_queries = query_paths
+        .iter()
+        .map(|path| {
+            FullQuery::from_path(path).unwrap_or_else(|e| {
+                panic!(
+                    "could not parse query file {} due to error: {e}",
+                    path.to_string_lossy()
+                );
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let before = Rc::new(IndicateAdapter::new(ManifestPath::new(&args.before)));
+    let after = Rc::new(IndicateAdapter::new(ManifestPath::new(&args.after)));
+    let key_fields = args.key.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let comparisons = query_paths
+        .iter()
+        .zip(full_queries.iter())
+        .map(|(path, query)| {
+            let before_res = execute_query_with_adapter(
+                query,
+                Rc::clone(&before),
+                args.max_results,
+            );
+            let after_res = execute_query_with_adapter(
+                query,
+                Rc::clone(&after),
+                args.max_results,
+            );
+            QueryComparison {
+                query: util::file_prefix(path)
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy()
+                    .into(),
+                comparison: compare_results(before_res, after_res, &key_fields),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    write_report(&comparisons, args.output);
+}
+
+/// Reads the configuration file at `config_path`, or the closest
+/// `indicate.toml` to the package, exiting with an error if it cannot be read
+fn read_config(
+    cmd: &mut clap::Command,
+    config_path: Option<PathBuf>,
+    manifest_path: &ManifestPath,
+) -> IndicateConfig {
+    let config_path = config_path
+        .or_else(|| IndicateConfig::find(manifest_path))
+        .unwrap_or_else(|| {
+            let msg = format!("no {CONFIG_FILE_NAME} found");
+            cmd.error(clap::error::ErrorKind::ValueValidation, msg)
+                .exit();
+        });
+    IndicateConfig::from_path(&config_path).unwrap_or_else(|e| {
+        cmd.error(clap::error::ErrorKind::ValueValidation, e).exit();
+    })
+}
+
+/// Reads the features of a feature profile, exiting with an error if it
+/// cannot be found
+fn read_profile_features(
+    cmd: &mut clap::Command,
+    config_path: Option<PathBuf>,
+    manifest_path: &ManifestPath,
+    profile_name: &str,
+) -> Vec<CargoOpt> {
+    let config = read_config(cmd, config_path, manifest_path);
+    let profile = config.profile(profile_name).unwrap_or_else(|e| {
+        cmd.error(clap::error::ErrorKind::InvalidValue, e).exit();
+    });
+    profile.cargo_opts()
+}
+
+/// Writes a report to a file, or stdout if no file is provided
+fn write_report(report: &impl Serialize, output: Option<PathBuf>) {
+    let res = serde_json::to_string_pretty(report)
+        .expect("could not serialize report");
+    if let Some(path) = output {
+        util::ensure_parents_exist(&path).unwrap_or_else(|e| {
+            panic!(
+                "could not create parent directories for {} due to error: {e}",
+                path.to_string_lossy()
+            )
+        });
+        fs::write(&path, res).unwrap_or_else(|e| {
+            panic!(
+                "could not write output to {} due to error: {e}",
+                path.to_string_lossy()
+            );
+        });
+    } else {
+        println!("{res}");
+    }
+}
+
+/// Writes the dependencies of a package that are only resolved under some
+/// feature combinations
+fn feature_matrix(args: FeatureMatrixArgs) {
+    // Used to report errors
+    let mut cmd = IndicateCli::command();
+
+    let manifest_path = if let Some(package_name) = &args.package_name {
+        ManifestPath::with_package_name(&args.package, package_name)
+    } else {
+        ManifestPath::new(&args.package)
+    };
+
+    let metadata = manifest_path.metadata(Vec::new()).unwrap_or_else(|e| {
+        panic!("could not resolve metadata due to error: {e}");
+    });
+    let mut combinations = FeatureCombination::for_each_feature(&metadata);
+    if args.profiles {
+        let config = read_config(&mut cmd, args.config, &manifest_path);
+        combinations.extend(FeatureCombination::for_each_profile(&config));
+    }
+
+    let gated = feature_gated_dependencies(&manifest_path, &combinations)
+        .unwrap_or_else(|e| {
+            panic!("could not resolve feature matrix due to error: {e}");
+        });
+    write_report(&gated, args.output);
+}
+
+/// Writes the dependencies of a package for each target platform
+fn target_matrix(args: TargetMatrixArgs) {
+    // Used to report errors
+    let mut cmd = IndicateCli::command();
+
+    let manifest_path = if let Some(package_name) = &args.package_name {
+        ManifestPath::with_package_name(&args.package, package_name)
+    } else {
+        ManifestPath::new(&args.package)
+    };
+
+    let features = match &args.profile {
+        Some(profile_name) => read_profile_features(
+            &mut cmd,
+            args.config,
+            &manifest_path,
+            profile_name,
+        ),
+        None => Vec::new(),
+    };
+
+    let matrix =
+        resolve_target_matrix(&manifest_path, &features, &args.targets)
+            .unwrap_or_else(|e| {
+                panic!("could not resolve target matrix due to error: {e}");
+            });
+    write_report(&matrix, args.output);
+}
+
+/// Writes a report on whether a build of a package can be reproduced from its
+/// lock file
+fn reproducibility(args: ReproducibilityArgs) {
+    // Used to report errors
+    let mut cmd = IndicateCli::command();
+
+    let manifest_path = if let Some(package_name) = &args.package_name {
+        ManifestPath::with_package_name(&args.package, package_name)
+    } else {
+        ManifestPath::new(&args.package)
+    };
+
+    let features = match &args.profile {
+        Some(profile_name) => read_profile_features(
+            &mut cmd,
+            args.config,
+            &manifest_path,
+            profile_name,
+        ),
+        None => Vec::new(),
+    };
+
+    let report = reproducibility_report(manifest_path, features)
+        .unwrap_or_else(|e| {
+            panic!("could not create reproducibility report due to error: {e}");
+        });
+    let res = report.render(args.format.into(), value_style(args.raw));
+    if let Some(path) = args.output {
+        util::ensure_parents_exist(&path).unwrap_or_else(|e| {
+            panic!(
+                "could not create parent directories for {} due to error: {e}",
+                path.to_string_lossy()
+            )
+        });
+        fs::write(&path, res).unwrap_or_else(|e| {
+            panic!(
+                "could not write output to {} due to error: {e}",
+                path.to_string_lossy()
+            );
+        });
+    } else {
+        println!("{res}");
+    }
+
+    if args.strict && !report.is_reproducible() {
+        eprintln!("build is not reproducible from the lock file");
+        std::process::exit(1);
+    }
+}
+
+/// How values are rendered in Markdown, depending on if `--raw` was passed
+fn value_style(raw: bool) -> ValueStyle {
+    if raw {
+        ValueStyle::Raw
+    } else {
+        ValueStyle::Human
+    }
+}
+
+/// Writes the release notes of all dependencies bumped between two lock files
+fn release_notes(args: ReleaseNotesArgs) {
+    // Used to report errors
+    let mut cmd = IndicateCli::command();
+
+    let read_lockfile = |cmd: &mut clap::Command, path: &Path| {
+        Lockfile::from_path(path).unwrap_or_else(|e| {
+            let msg = format!(
+                "could not read lock file {} due to error: {e}",
+                path.to_string_lossy()
+            );
+            cmd.error(clap::error::ErrorKind::InvalidValue, msg).exit();
+        })
+    };
+    let old = read_lockfile(&mut cmd, &args.old);
+    let new = read_lockfile(&mut cmd, &args.new);
+
+    let bumped = bumped_dependencies(&old, &new);
+    let notes = if bumped.is_empty() {
+        ReleaseNotes::default()
+    } else {
+        // Only created if needed, since they require a user agent
+        ReleaseNotes::collect(
+            bumped,
+            &mut CratesIoClient::default(),
+            &mut GitHubClient::new(args.await_github_quota),
+        )
+    };
+
+    let res = notes.to_markdown(value_style(args.raw));
+    if let Some(path) = args.output {
+        util::ensure_parents_exist(&path).unwrap_or_else(|e| {
+            panic!(
+                "could not create parent directories for {} due to error: {e}",
+                path.to_string_lossy()
+            )
+        });
+        fs::write(&path, res).unwrap_or_else(|e| {
+            panic!(
+                "could not write output to {} due to error: {e}",
+                path.to_string_lossy()
+            );
+        });
+    } else {
+        print!("{res}");
+    }
+}
+
+/// Writes one worksheet per direct dependency of a package
+fn audit_worksheets(args: AuditWorksheetsArgs) {
+    // Used to report errors
+    let mut cmd = IndicateCli::command();
+
+    let manifest_path = if let Some(package_name) = &args.package_name {
+        ManifestPath::with_package_name(&args.package, package_name)
+    } else {
+        ManifestPath::new(&args.package)
+    };
+
+    let features = match &args.profile {
+        Some(profile_name) => read_profile_features(
+            &mut cmd,
+            args.config,
+            &manifest_path,
+            profile_name,
+        ),
+        None => Vec::new(),
+    };
+
+    let advisory_client = if let Some(p) = args.advisory_db_dir {
+        Some(AdvisoryClient::from_path(p.as_path()).unwrap_or_else(|e| {
+            panic!(
+                "could not parse advisory-db in {} due to error: {e}",
+                p.to_string_lossy()
+            )
+        }))
+    } else if args.cached_advisory_db {
+        Some(AdvisoryClient::from_default_path().unwrap_or_else(|_| {
+            AdvisoryClient::new().unwrap_or_else(|e| {
+                panic!("could not fetch advisory-db due to error: {e} (cache also failed)")
+            })
+        }))
+    } else {
+        None
+    };
+
+    let config = WorksheetConfig {
+        features,
+        advisory_client,
+        geiger: args.geiger,
+        github: args.github,
+        style: value_style(args.raw),
+    };
+    let paths =
+        write_worksheets(&worksheets(manifest_path, config), &args.output_dir)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "could not write worksheets to {} due to error: {e}",
+                    args.output_dir.to_string_lossy()
+                );
+            });
+    for path in paths {
+        println!("{}", path.to_string_lossy());
+    }
+}
+
+fn doctor() {
+    let checks = diagnose();
+    for check in &checks {
+        println!("{check}");
+    }
+    if checks.iter().any(|c| c.status == CheckStatus::Error) {
+        std::process::exit(1);
+    }
 }
 
 fn main() {
     let cli = IndicateCli::parse();
 
+    match cli.command {
+        Some(IndicateCommand::Compare(args)) => {
+            compare(args);
+            return;
+        }
+        Some(IndicateCommand::FeatureMatrix(args)) => {
+            feature_matrix(args);
+            return;
+        }
+        Some(IndicateCommand::TargetMatrix(args)) => {
+            target_matrix(args);
+            return;
+        }
+        Some(IndicateCommand::Reproducibility(args)) => {
+            reproducibility(args);
+            return;
+        }
+        Some(IndicateCommand::ReleaseNotes(args)) => {
+            release_notes(args);
+            return;
+        }
+        Some(IndicateCommand::AuditWorksheets(args)) => {
+            audit_worksheets(args);
+            return;
+        }
+        Some(IndicateCommand::Doctor) => {
+            doctor();
+            return;
+        }
+        None => {}
+    }
+
     // Used to report errors
     let mut cmd = IndicateCli::command();
 
@@ -225,64 +1318,46 @@ fn main() {
         return;
     }
 
-    // Aggregate query paths from `--query-with-args` and `--query-dir` flags
-    let query_paths: Option<Vec<PathBuf>> = if cli.query_with_args.is_some()
-        || cli.query_dir.is_some()
-    {
-        let mut q = Vec::new();
+    if cli.show_protobuf_schema {
+        print!("{}", protobuf::RESULTS_PROTO);
+        return;
+    }
 
-        if let Some(query_paths) = cli.query_with_args {
-            q.extend(query_paths);
-        }
+    if cli.capabilities {
+        let report = CapabilitiesReport {
+            version: env!("CARGO_PKG_VERSION"),
+            output_formats: OutputFormat::value_variants()
+                .iter()
+                .filter_map(|f| f.to_possible_value())
+                .map(|v| v.get_name().to_string())
+                .collect(),
+            capabilities: Capabilities::detect(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report)
+                .expect("could not serialize capabilities")
+        );
+        return;
+    }
 
-        if let Some(dir_path) = cli.query_dir {
-            let files = fs::read_dir(&dir_path).unwrap_or_else(|e| {
-                cmd.error(
-                    clap::error::ErrorKind::InvalidValue,
-                format!(
-                            "could not read queries in directory {} due to error: {e}",
-                            dir_path.to_string_lossy()
-                            ),
-                    )
-                    .exit();
-            });
+    // Aggregate query paths from `--query-with-args` and `--query-dir` flags
+    let query_paths: Option<Vec<PathBuf>> =
+        if cli.query_with_args.is_some() || cli.query_dir.is_some() {
+            let mut q = Vec::new();
 
-            for f in files {
-                let file_path = f
-                    .unwrap_or_else(|e| {
-                        panic!(
-                            "could not read file in {} due to error {e}",
-                            dir_path.to_string_lossy()
-                        )
-                    })
-                    .path();
+            if let Some(query_paths) = cli.query_with_args {
+                q.extend(query_paths);
+            }
 
-                if file_path.is_dir() {
-                    let msg = format!(
-                        "nested directories with --query-dir not supported, found {}",
-                        file_path.to_string_lossy()
-                    );
-                    cmd.error(clap::error::ErrorKind::ValueValidation, msg)
-                        .exit();
-                } else if cli.exclude.contains(
-                    &file_path
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .into(),
-                ) {
-                    // Don't add this, it is included in list of excluded files
-                    continue;
-                } else {
-                    q.push(file_path);
-                }
+            if let Some(dir_path) = cli.query_dir {
+                q.extend(read_query_dir(&mut cmd, &dir_path, &cli.exclude));
             }
-        }
 
-        Some(q)
-    } else {
-        None
-    };
+            Some(q)
+        } else {
+            None
+        };
 
     let mut full_queries: Vec<FullQuery>;
     if let Some(query_paths) = &query_paths {
@@ -372,6 +1447,43 @@ fn main() {
         }
     }
 
+    // Validate queries and their arguments before anything expensive is done
+    for (i, query) in full_queries.iter().enumerate() {
+        if let Err(e) = query.validate() {
+            let name = query_paths.as_ref().map_or_else(
+                || format!("query {}", i + 1),
+                |paths| paths[i].to_string_lossy().to_string(),
+            );
+            cmd.error(
+                clap::error::ErrorKind::ValueValidation,
+                format!("{name}: {e}"),
+            )
+            .exit();
+        }
+    }
+
+    // Parquet files cannot be concatenated, so each query needs its own output
+    if cli.output_format == OutputFormat::Parquet
+        && full_queries.len() > 1
+        && cli.output_dir.is_none()
+        && cli.output.as_ref().is_none_or(|o| o.len() == 1)
+    {
+        cmd.error(
+            clap::error::ErrorKind::ArgumentConflict,
+            "parquet output of more than one query requires one output path per query, or --output-dir",
+        )
+        .exit();
+    }
+
+    if cli.result_descriptor && cli.output.is_none() && cli.output_dir.is_none()
+    {
+        cmd.error(
+            clap::error::ErrorKind::MissingRequiredArgument,
+            "--result-descriptor requires --output or --output-dir",
+        )
+        .exit();
+    }
+
     let manifest_path = if let Some(package_name) = cli.package_name {
         ManifestPath::with_package_name(&cli.package, &package_name)
     } else {
@@ -381,11 +1493,16 @@ fn main() {
     // How we execute the query depends on if the user defined any special
     // requirements for the adapter
 
-    let mut b = IndicateAdapterBuilder::new(manifest_path);
-
     // Clap will ensure that these do not mismatch
-    if cli.all_features {
-        b = b.features(vec![CargoOpt::AllFeatures]);
+    let features = if let Some(profile_name) = &cli.profile {
+        read_profile_features(
+            &mut cmd,
+            cli.config,
+            &manifest_path,
+            profile_name,
+        )
+    } else if cli.all_features {
+        vec![CargoOpt::AllFeatures]
     } else {
         let mut features = Vec::with_capacity(2);
         if let Some(f) = cli.features {
@@ -394,7 +1511,10 @@ fn main() {
         if cli.no_default_features {
             features.push(CargoOpt::NoDefaultFeatures);
         }
-    }
+        features
+    };
+
+    let mut b = IndicateAdapterBuilder::new(manifest_path).features(features);
 
     // These two are mutually exclusive, but that is checked by clap already
     if let Some(p) = cli.advisory_db_dir {
@@ -418,9 +1538,64 @@ fn main() {
         b = b.github_client(GitHubClient::new(true));
     }
 
+    if !cli.gitea_host.is_empty() {
+        b = b.gitea_client(GiteaClient::with_hosts(cli.gitea_host));
+    }
+
+    if let Some(git_ref) = cli.changed_since {
+        b = b.changed_since(git_ref);
+    }
+
+    // These two are mutually exclusive, but that is checked by clap already
+    let vendored_sources = if let Some(p) = &cli.vendor_dir {
+        Some(VendoredSources::from_directory(p))
+    } else {
+        cli.crate_archives
+            .as_ref()
+            .map(|p| VendoredSources::from_crate_archives(p))
+    };
+    if let Some(v) = vendored_sources {
+        let v = v.unwrap_or_else(|e| {
+            panic!("could not use vendored sources due to error: {e}")
+        });
+        b = b.vendored_sources(v);
+    }
+
     // Reuse the same adapter for multiple queries
     let adapter = Rc::new(b.build());
-    let res_strings = execute_queries(&full_queries, &adapter, cli.max_results);
+
+    if !cli.allow_expensive {
+        for (i, query) in full_queries.iter().enumerate() {
+            // Queries are validated before the adapter is built
+            let cost = adapter
+                .estimate_cost(query)
+                .expect("could not estimate the cost of a valid query");
+            if cost.total() > cli.max_cost {
+                let name = query_paths.as_ref().map_or_else(
+                    || format!("query {}", i + 1),
+                    |paths| paths[i].to_string_lossy().to_string(),
+                );
+                cmd.error(
+                    clap::error::ErrorKind::ValueValidation,
+                    format!(
+                        "{name}: estimated cost of {cost} exceeds --max-cost {}, use --allow-expensive to execute it anyway",
+                        cli.max_cost
+                    ),
+                )
+                .exit();
+            }
+        }
+    }
+    // Write the results computed so far if interrupted, instead of losing
+    // everything
+    interrupt::install_handler();
+    let (res_bytes, partial) = execute_queries(
+        &full_queries,
+        &adapter,
+        cli.max_results,
+        cli.output_format,
+        cli.checkpoint.as_deref(),
+    );
 
     // Use provided outputs, or create them in a directory, bases on the query
     // file names. `cli.output` and `cli.output_dir` are exclusive, guaranteed
@@ -456,6 +1631,7 @@ fn main() {
                 .map(AsRef::as_ref)
                 .collect::<Vec<_>>(),
             &dir_root,
+            cli.output_format.extension(),
         ))
     } else {
         None
@@ -469,7 +1645,8 @@ fn main() {
                 let path = single_path[0].as_path();
 
                 // Write all queries to a single file
-                let concat_res = res_strings.join("\n");
+                let concat_res =
+                    res_bytes.join(cli.output_format.separator());
 
                 util::ensure_parents_exist(path).unwrap_or_else(|e| {
                     panic!("could not create parent directories for {} due to error: {e}", path.to_string_lossy())
@@ -483,27 +1660,42 @@ fn main() {
                         path.to_string_lossy()
                     );
                 });
+
+                if cli.result_descriptor {
+                    write_result_descriptors(&full_queries, path);
+                }
             },
             multiple_paths if output_paths.len() > 1 => {
                 // We would have panicked already if these are not equal
-                for (res, path) in res_strings.iter().zip(multiple_paths.iter()) {
+                for ((res, path), query) in res_bytes.iter().zip(multiple_paths.iter()).zip(&full_queries) {
                     // It's quite wasteful to throw out all other results, so
                     // skip this one if it fails
                     if let Err(e) = util::ensure_parents_exist(path) {
                         eprintln!("could not write some output to {} due to error: {e}, skipping", path.to_string_lossy());
                         continue;
                     }
-                    
+
                     fs::write(path.as_path(), res).unwrap_or_else(|e| {
                         eprintln!("could not write output to {} due to error: {e}, skipping",
                             path.to_string_lossy());
                     });
+
+                    if cli.result_descriptor {
+                        write_result_descriptors(std::slice::from_ref(query), path);
+                    }
                 }
             }
             _ => unreachable!("if more than one output path is defined, it must match the amount of queries"),
         }
     } else {
-        let concat_res = res_strings.join("\n");
-        print!("{concat_res}");
+        let concat_res = res_bytes.join(cli.output_format.separator());
+        std::io::stdout()
+            .write_all(&concat_res)
+            .expect("could not write results to stdout");
+    }
+
+    if partial {
+        eprintln!("results are partial, since the queries were interrupted");
+        std::process::exit(INTERRUPTED_EXIT_CODE);
     }
 }
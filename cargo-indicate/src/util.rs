@@ -14,13 +14,15 @@ pub(crate) fn ensure_parents_exist(path: &Path) -> Result<(), std::io::Error> {
     Ok(())
 }
 
-/// Creates paths for output files, named according to the input queries
+/// Creates paths for output files, named according to the input queries, with
+/// an extension such as `out.json`
 ///
 /// To avoid overwriting when we have duplicate query name prefixes, a number is
 /// appended to the prefix if a duplicate is found.
 pub(crate) fn create_output_paths(
     query_paths: &[&Path],
     output_dir: &Path,
+    extension: &str,
 ) -> Vec<PathBuf> {
     let mut used_file_prefix: BTreeSet<OsString> = BTreeSet::new();
     let mut res = Vec::with_capacity(query_paths.len());
@@ -30,7 +32,7 @@ pub(crate) fn create_output_paths(
 
         // TODO: Replace `util::file_prefix` with `Path::file_prefix` once
         // stabilized
-        let Some (true_file_prefix) = file_prefix(p) else {
+        let Some(true_file_prefix) = file_prefix(p) else {
             panic!(
                 "could not extract file prefix from {}",
                 p.to_string_lossy()
@@ -54,7 +56,7 @@ pub(crate) fn create_output_paths(
         };
 
         pb.push(file_prefix);
-        pb.set_extension("out.json"); // first  `.` inserted automatically
+        pb.set_extension(extension); // first  `.` inserted automatically
 
         res.push(pb);
     }
@@ -62,6 +64,16 @@ pub(crate) fn create_output_paths(
     res
 }
 
+/// Creates the path of the result descriptor written next to an output file,
+/// such as `results.descriptor.json` for `results.out.json`
+pub(crate) fn descriptor_path(output_path: &Path) -> PathBuf {
+    let mut file_name = file_prefix(output_path)
+        .unwrap_or(OsStr::new("results"))
+        .to_os_string();
+    file_name.push(".descriptor.json");
+    output_path.with_file_name(file_name)
+}
+
 /// Extracts the prefix of a filename; stand-in for [`Path::file_prefix`] with
 /// a naive implementation
 ///
@@ -102,7 +114,7 @@ pub(crate) fn file_prefix(path: &Path) -> Option<&OsStr> {
         };
 
         // Split the file name to at most 2 parts, separated by '.'
-        let mut iter = trimmed.splitn(2, |c| c == '.');
+        let mut iter = trimmed.splitn(2, '.');
         let before = iter.next();
         let after = iter.next();
 
@@ -189,15 +201,26 @@ mod test {
         let query_paths =
             query_path_strs.iter().map(Path::new).collect::<Vec<_>>();
         let output_dir = Path::new(output_dir_str);
-        let res = util::create_output_paths(query_paths.as_slice(), output_dir);
+        let res = util::create_output_paths(
+            query_paths.as_slice(),
+            output_dir,
+            "out.json",
+        );
 
         let expected = expected_strs
             .iter()
-            .map(|s| PathBuf::from_str(*s).unwrap())
+            .map(|s| PathBuf::from_str(s).unwrap())
             .collect::<Vec<_>>();
         assert_eq!(res, expected);
     }
 
+    #[test_case("results.out.json" => PathBuf::from("results.descriptor.json") ; "output file")]
+    #[test_case("output_dir/results.out.parquet" => PathBuf::from("output_dir/results.descriptor.json") ; "output file in dir")]
+    #[test_case("results" => PathBuf::from("results.descriptor.json") ; "no extension")]
+    fn test_descriptor_path(path_str: &str) -> PathBuf {
+        util::descriptor_path(Path::new(path_str))
+    }
+
     #[test_case("" => None ; "empty filename")]
     #[test_case("some_name" => Some(OsStr::new("some_name")) ; "no period")]
     #[test_case(".some_name" => Some(OsStr::new(".some_name")) ; "only leading period")]
@@ -0,0 +1,4 @@
+
+
+
+name*dummy_crate
\ No newline at end of file
@@ -0,0 +1,35 @@
+/*
+ * C API of `indicate`, available when built with the `c-api` feature:
+ *
+ *     cargo rustc -p indicate --release --features c-api --crate-type cdylib
+ *
+ * See `src/c_api.rs` for more information.
+ */
+#ifndef INDICATE_H
+#define INDICATE_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Runs a query against the package at `manifest_path` (a `Cargo.toml` file,
+ * or a directory containing one).
+ *
+ * `query_json` is a JSON object with a `query` and `args`, such as
+ * `{ "query": "{ RootPackage { name @output } }", "args": {} }`.
+ *
+ * Returns `{ "results": [...] }`, or `{ "error": "..." }` if the query could
+ * not be executed. The returned string must be freed using
+ * `indicate_free_string`.
+ */
+char *indicate_run_query(const char *manifest_path, const char *query_json);
+
+/* Frees a string returned by `indicate_run_query` */
+void indicate_free_string(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* INDICATE_H */
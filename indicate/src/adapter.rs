@@ -1,40 +1,263 @@
-use cargo_metadata::{CargoOpt, Metadata, Package, PackageId};
+use cargo_metadata::{CargoOpt, DependencyKind, Metadata, Package, PackageId};
 use chrono::{NaiveDate, NaiveDateTime};
-use once_cell::unsync::OnceCell;
+use octorust::types::IssuesListState;
+use once_cell::unsync::{Lazy, OnceCell};
 use std::{
-    cell::RefCell, collections::HashMap, rc::Rc, str::FromStr, sync::Arc,
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    error::Error,
+    num::NonZeroUsize,
+    path::Path,
+    rc::Rc,
+    str::FromStr,
+    sync::Arc,
 };
 use trustfall::{
     provider::{
         accessor_property, field_property, resolve_neighbors_with,
-        resolve_property_with, BasicAdapter, ContextIterator,
-        ContextOutcomeIterator, EdgeParameters, VertexIterator,
+        resolve_property_with, Adapter, ContextIterator,
+        ContextOutcomeIterator, EdgeParameters, ResolveEdgeInfo, ResolveInfo,
+        Typename, VertexIterator,
     },
     FieldValue,
 };
 
 use crate::{
-    advisory::AdvisoryClient,
+    advisory::{AdvisoryClient, AuditSummary, PackageAdvisory},
+    bloat::BloatClient,
     geiger::GeigerClient,
-    repo::{github::GitHubClient, RepoId},
+    interrupt,
+    repo::{
+        github::{self, GitHubClient, GitHubContributor, GitHubRepositoryId},
+        RepoId,
+    },
+    udeps::UdepsClient,
+    vendor::VendoredSources,
     vertex::Vertex,
     ManifestPath,
 };
 use crate::{
+    binaries::{
+        get_bundled_binaries, get_suspicious_files, DEFAULT_MIN_BINARY_SIZE,
+        DEFAULT_MIN_BLOB_SIZE,
+    },
+    build_script::{
+        get_build_script_risks, has_build_script, BuildScript, BuildScriptRisk,
+    },
     code_stats::{get_code_stats, CodeStats},
+    deny::DenyConfig,
+    features::{get_enabled_dependencies, get_enabled_features, get_features},
+    ffi::{get_ffi_stats, FfiStats},
+    future_incompat::{self, FutureIncompatMap},
+    license::{
+        get_license_files, get_licenses, group_by_license,
+        parse_license_expression,
+    },
+    lockfile::Lockfile,
+    requirement::get_dependency_requirements,
+    source::{registry_url, GitSource, SourceKind},
+    suspicion::{get_suspicion, Suspicion},
+    typosquat::{get_typosquat_candidates, DEFAULT_MAX_TYPOSQUAT_DISTANCE},
     util,
+    vet::{self, PackageVetAudit, VetAuditMap},
 };
 use crate::{
-    crates_io::CratesIoClient, geiger::GeigerOutput, IndicateAdapterBuilder,
-    NameVersion,
+    clippy::ClippyClient,
+    cost::QueryCost,
+    crates_io::CratesIoClient,
+    crev::CrevClient,
+    deps_dev::DepsDevClient,
+    docs_rs::{DocsRsBuild, DocsRsClient},
+    epss::EpssClient,
+    errors::QueryValidationError,
+    geiger::GeigerOutput,
+    ghsa::{GhsaClient, PackageGhsaVulnerability},
+    kev::KevClient,
+    nvd::NvdClient,
+    osv::OsvClient,
+    query::FullQuery,
+    repo::{
+        gitea::GiteaClient, gitlab::GitLabClient, scorecard::ScorecardClient,
+        sourcehut::SourcehutClient,
+    },
+    semver_checks::SemverChecksClient,
+    sparse_index::SparseIndexClient,
+    IndicateAdapterBuilder, NameVersion,
 };
 
 pub mod adapter_builder;
+mod schema_coverage;
+
+/// The value of an optional text field, which GitHub returns as an empty
+/// string if it is not set
+fn non_empty(s: &str) -> FieldValue {
+    if s.is_empty() {
+        FieldValue::Null
+    } else {
+        s.into()
+    }
+}
+
+/// Creates the configuration used for `codeStats` from the parameters of
+/// the edge
+fn tokei_config(parameters: &EdgeParameters) -> tokei::Config {
+    // Either they are passed and _must_ be a bool according to schema, or
+    // they are undefined
+    let get_stat_bool_param =
+        |pname| parameters.get(pname).and_then(FieldValue::as_bool);
+
+    tokei::Config {
+        columns: None, // Unused for library
+        hidden: get_stat_bool_param("hidden"),
+        no_ignore: get_stat_bool_param("noIgnore"),
+        no_ignore_parent: get_stat_bool_param("noIgnoreParent"),
+        no_ignore_dot: get_stat_bool_param("noIgnoreDot"),
+        no_ignore_vcs: get_stat_bool_param("noIgnoreVcs"),
+        treat_doc_strings_as_comments: get_stat_bool_param(
+            "treatDocStringsAsComments",
+        ),
+        types: parameters.get("types").and_then(|t| {
+            t.as_vec_with(|i| {
+                let language_str = i.as_str().unwrap();
+                let lt = tokei::LanguageType::from_str(language_str)
+                    .unwrap_or_else(|_| {
+                        panic!("parameter error: {language_str} is not a valid language name");
+                    });
+                Some(lt)
+            })
+        }),
+        sort: None, // TODO: Not implemented
+    }
+}
+
+/// Reads an optional integer edge parameter that must not be negative, such
+/// as a size in bytes
+///
+/// # Panics
+///
+/// Panics if the parameter is negative.
+fn non_negative_parameter(
+    parameters: &EdgeParameters,
+    name: &str,
+) -> Option<u64> {
+    let value = parameters.get(name)?;
+    value.as_u64().or_else(|| {
+        value.as_i64().map(|i| {
+            u64::try_from(i).unwrap_or_else(|_| {
+                panic!("parameter error: {name} must not be negative, got {i}")
+            })
+        })
+    })
+}
+
+/// A client that is created the first time it is dereferenced, since
+/// creating some clients is expensive or requires network access
+pub(crate) type LazyClient<T> = Rc<Lazy<T, Box<dyn FnOnce() -> T>>>;
+
+/// Creates a [`LazyClient`] using `init` the first time it is used
+pub(crate) fn lazy_client<T>(
+    init: impl FnOnce() -> T + 'static,
+) -> LazyClient<T> {
+    Rc::new(Lazy::new(Box::new(init)))
+}
+
+/// Creates a [`LazyClient`] running `cargo-geiger` for the features and
+/// manifest path used by an adapter
+///
+/// Workspaces with several members have each member analyzed in parallel.
+pub(crate) fn lazy_geiger_client(
+    manifest_path: Rc<ManifestPath>,
+    features: Vec<CargoOpt>,
+    metadata: Rc<Metadata>,
+    vendored_sources: Option<Rc<VendoredSources>>,
+) -> LazyClient<GeigerClient> {
+    lazy_client(move || {
+        let working_dir = vendored_sources.as_deref().map(|v| v.config_dir());
+        let members = &metadata.workspace_members;
+        if members.len() > 1 {
+            let member_paths = metadata
+                .packages
+                .iter()
+                .filter(|p| members.contains(&p.id))
+                .map(|p| ManifestPath::from(&p.manifest_path))
+                .collect::<Vec<_>>();
+            let max_concurrency = std::thread::available_parallelism()
+                .unwrap_or(NonZeroUsize::MIN);
+            GeigerClient::for_members_in(
+                &member_paths,
+                &features,
+                max_concurrency,
+                working_dir,
+            )
+        } else {
+            GeigerClient::new_in(&manifest_path, features, working_dir)
+        }
+        .unwrap_or_else(|e| {
+            eprintln!("failed to create geiger data due to error: {e}\nrunning query without");
+            GeigerClient::from(GeigerOutput::default())
+        })
+    })
+}
+
+/// Creates a [`LazyClient`] running `cargo-bloat` for the features and
+/// manifest path used by an adapter
+pub(crate) fn lazy_bloat_client(
+    manifest_path: Rc<ManifestPath>,
+    features: Vec<CargoOpt>,
+) -> LazyClient<BloatClient> {
+    lazy_client(move || {
+        BloatClient::new(&manifest_path, &features).unwrap_or_else(|e| {
+            eprintln!("failed to measure binary size due to error: {e}\nrunning query without");
+            BloatClient::default()
+        })
+    })
+}
+
+/// Creates a [`LazyClient`] running `cargo-udeps` for the features and
+/// manifest path used by an adapter
+pub(crate) fn lazy_udeps_client(
+    manifest_path: Rc<ManifestPath>,
+    features: Vec<CargoOpt>,
+    metadata: Rc<Metadata>,
+) -> LazyClient<UdepsClient> {
+    lazy_client(move || {
+        UdepsClient::new(&manifest_path, &features, &metadata).unwrap_or_else(
+            |e| {
+                eprintln!("failed to find unused dependencies due to error: {e}\nrunning query without");
+                UdepsClient::default()
+            },
+        )
+    })
+}
+
+/// Resolves the vertices of a starting edge using its parameters
+type StartingVerticesResolver =
+    fn(&IndicateAdapter, &EdgeParameters) -> VertexIterator<'static, Vertex>;
 
 /// Direct dependencies to a package, i.e. _not_ dependencies to dependencies
 pub(crate) type DirectDependencyMap = HashMap<PackageId, Rc<Vec<PackageId>>>;
 pub(crate) type PackageMap = HashMap<PackageId, Rc<Package>>;
 
+/// Checksums in `Cargo.lock`, keyed by name, version and source
+pub(crate) type ChecksumMap = HashMap<(String, String, String), String>;
+
+/// Values computed once per package, such as by parsing its source files
+pub(crate) type PackageCache<T> = Rc<RefCell<HashMap<PackageId, T>>>;
+
+/// Retrieves the value of a package from `cache`, computing it from the
+/// local path of the package using `f` if it is not cached
+fn cached_for_package<T: Clone>(
+    cache: &PackageCache<T>,
+    package: &Package,
+    f: impl FnOnce(&Path) -> T,
+) -> T {
+    cache
+        .borrow_mut()
+        .entry(package.id.clone())
+        .or_insert_with(|| f(&util::local_package_path(package)))
+        .clone()
+}
+
 macro_rules! resolve_code_stats {
     ($getter:ident) => {
         |v| {
@@ -65,13 +288,59 @@ macro_rules! resolve_code_stats {
 pub struct IndicateAdapter {
     manifest_path: Rc<ManifestPath>,
     features: Vec<CargoOpt>,
+
+    /// If set, packages from crates.io are read from these sources
+    vendored_sources: Option<Rc<VendoredSources>>,
     metadata: Rc<Metadata>,
     packages: OnceCell<Rc<PackageMap>>,
-    direct_dependencies: OnceCell<Rc<DirectDependencyMap>>,
+    direct_dependencies:
+        RefCell<HashMap<Option<DependencyKind>, Rc<DirectDependencyMap>>>,
+    dependents: OnceCell<Rc<DirectDependencyMap>>,
+    depths: OnceCell<Rc<HashMap<PackageId, u64>>>,
+    paths_from_root: OnceCell<Rc<HashMap<PackageId, Rc<Vec<PackageId>>>>>,
+    checksums: OnceCell<Rc<ChecksumMap>>,
+    vet_audits: OnceCell<Rc<VetAuditMap>>,
+    deny_config: OnceCell<Option<Rc<DenyConfig>>>,
+    future_incompat: OnceCell<Rc<FutureIncompatMap>>,
+    ffi_stats: PackageCache<FfiStats>,
+    suspicion: PackageCache<Rc<Suspicion>>,
     gh_client: Rc<RefCell<GitHubClient>>,
-    advisory_client: OnceCell<Rc<AdvisoryClient>>,
-    geiger_client: OnceCell<Rc<GeigerClient>>,
-    crates_io_client: OnceCell<Rc<RefCell<CratesIoClient>>>,
+    gitlab_client: Rc<RefCell<GitLabClient>>,
+    gitea_client: Rc<RefCell<GiteaClient>>,
+    sourcehut_client: Rc<RefCell<SourcehutClient>>,
+    advisory_client: LazyClient<AdvisoryClient>,
+    geiger_client: LazyClient<GeigerClient>,
+    udeps_client: LazyClient<UdepsClient>,
+    bloat_client: LazyClient<BloatClient>,
+    crates_io_client: LazyClient<RefCell<CratesIoClient>>,
+    sparse_index_client: Rc<RefCell<SparseIndexClient>>,
+    osv_client: Rc<RefCell<OsvClient>>,
+    ghsa_client: Rc<RefCell<GhsaClient>>,
+    epss_client: Rc<RefCell<EpssClient>>,
+    kev_client: Rc<RefCell<KevClient>>,
+    nvd_client: Rc<RefCell<NvdClient>>,
+    crev_client: Rc<RefCell<CrevClient>>,
+    semver_checks_client: Rc<RefCell<SemverChecksClient>>,
+    clippy_client: Rc<RefCell<ClippyClient>>,
+    docs_rs_client: Rc<RefCell<DocsRsClient>>,
+    deps_dev_client: Rc<RefCell<DepsDevClient>>,
+    scorecard_client: Rc<RefCell<ScorecardClient>>,
+
+    /// If set, only these packages are used as starting points for
+    /// dependencies
+    changed_packages: Option<Rc<HashSet<NameVersion>>>,
+
+    /// Starting vertices skipped by the next query, used to resume queries
+    /// from a [`Checkpoint`](crate::checkpoint::Checkpoint)
+    skip_starting_vertices: Cell<usize>,
+
+    /// The number of starting vertices resolved by the last query, including
+    /// those skipped
+    starting_vertices_resolved: Rc<Cell<usize>>,
+
+    /// If resolution stopped early since the query was interrupted, see
+    /// [`until_interrupted`]
+    stopped_early: Rc<Cell<bool>>,
 }
 
 /// The functions here are essentially the fields on the `RootQuery`
@@ -82,6 +351,23 @@ impl IndicateAdapter {
         Box::new(std::iter::once(v))
     }
 
+    /// Retrieves all members of the workspace, including the root package if
+    /// there is one
+    fn workspace_members(&self) -> VertexIterator<'static, Vertex> {
+        let members = self
+            .metadata
+            .workspace_members
+            .iter()
+            .map(|pid| {
+                // Workspace members are always part of the dependency graph
+                Vertex::Package(Rc::clone(self.packages().get(pid).unwrap()))
+            })
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        Box::new(members)
+    }
+
     /// Retrieves an iterator over all package IDs of normal dependencies
     /// (transitive and direct)
     fn dependency_ids(&self, include_root: bool) -> Vec<PackageId> {
@@ -125,10 +411,10 @@ impl IndicateAdapter {
         // this is a starting node
         let dependencies = dependency_package_ids
             .iter()
-            .map(|pid| {
-                // We must be able to find it, since packages is based on this
-                Vertex::Package(Rc::clone(self.packages().get(pid).unwrap()))
-            })
+            // We must be able to find it, since packages is based on this
+            .map(|pid| Rc::clone(self.packages().get(pid).unwrap()))
+            .filter(|p| self.is_changed(p))
+            .map(Vertex::Package)
             .collect::<Vec<_>>()
             .into_iter();
 
@@ -180,15 +466,63 @@ impl IndicateAdapter {
         // this is a starting node
         let dependencies = dependency_package_ids
             .iter()
-            .map(|pid| {
-                // We must be able to find it, since packages is based on this
-                Vertex::Package(Rc::clone(self.packages().get(pid).unwrap()))
-            })
+            // We must be able to find it, since packages is based on this
+            .map(|pid| Rc::clone(self.packages().get(pid).unwrap()))
+            .filter(|p| self.is_changed(p))
+            .map(Vertex::Package)
             .collect::<Vec<_>>()
             .into_iter();
 
         Box::new(dependencies)
     }
+
+    /// Summarizes the advisories of all packages in the dependency graph,
+    /// resolving to a single vertex
+    fn audit_summary(&self) -> VertexIterator<'static, Vertex> {
+        let advisory_client = self.advisory_client();
+        let advisories_by_package = self
+            .packages()
+            .values()
+            .filter(|p| self.is_changed(p))
+            .map(|package| {
+                let name = rustsec::package::Name::from_str(&package.name)
+                    .unwrap_or_else(|e| {
+                        panic!(
+                            "package name {} not valid due to error: {e}",
+                            package.name
+                        )
+                    });
+                advisory_client
+                    .all_advisories_for_package(name, false, None, None, None)
+                    .into_iter()
+                    .map(|a| {
+                        PackageAdvisory::new(a.clone(), package.version.clone())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let v = Vertex::AuditSummary(AuditSummary::new(advisories_by_package));
+        Box::new(std::iter::once(v))
+    }
+
+    /// Groups all packages in the dependency graph by their license
+    /// expression
+    fn license_summary(&self) -> VertexIterator<'static, Vertex> {
+        let packages = self
+            .packages()
+            .values()
+            .filter(|p| self.is_changed(p))
+            .map(Rc::clone)
+            .collect::<Vec<_>>();
+        let groups = group_by_license(packages)
+            .into_iter()
+            .map(|g| Vertex::LicenseGroup(Rc::new(g)))
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        Box::new(groups)
+    }
 }
 
 /// Helper methods to resolve fields using the metadata
@@ -203,6 +537,158 @@ impl IndicateAdapter {
         IndicateAdapterBuilder::new(manifest_path).build()
     }
 
+    /// Estimates how many requests a query makes to external services when
+    /// executed using this adapter, see [`QueryCost`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the query is not valid according to the
+    /// schema.
+    pub fn estimate_cost(
+        &self,
+        query: &FullQuery,
+    ) -> Result<QueryCost, QueryValidationError> {
+        QueryCost::estimate(query, self.metadata.packages.len())
+    }
+
+    /// Re-runs `cargo metadata` after a manifest has changed, replacing only
+    /// the packages and direct dependencies that changed
+    ///
+    /// `changed_manifest` is the `Cargo.toml` file that was edited, such as
+    /// that of a workspace member, and its package is always replaced. Other
+    /// packages are only replaced if their metadata differs. Caches of API
+    /// clients (GitHub, GitLab, Gitea, sourcehut, crates.io and its sparse
+    /// index, `advisory-db`, OSV.dev, GHSA, EPSS, CISA KEV, NVD, docs.rs,
+    /// deps.dev, OpenSSF Scorecard and `cargo-crev`) are kept, while `cargo-geiger`,
+    /// `cargo-udeps`, `cargo-bloat`, `cargo-semver-checks` and Clippy data
+    /// and data derived from the dependency graph is evaluated again when
+    /// needed.
+    ///
+    /// Useful for long running processes, such as watching a workspace for
+    /// changes, where creating a new adapter for each change is too slow.
+    ///
+    /// Returns the IDs of all packages that were added, changed or removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if metadata cannot be resolved, in which case
+    /// the adapter is left unchanged.
+    pub fn refresh(
+        &mut self,
+        changed_manifest: &ManifestPath,
+    ) -> Result<HashSet<PackageId>, Box<dyn Error>> {
+        let metadata = match &self.vendored_sources {
+            Some(v) => self
+                .manifest_path
+                .metadata_with_vendored_sources(self.features.clone(), v)?,
+            None => self.manifest_path.metadata(self.features.clone())?,
+        };
+
+        let old_packages = self.packages();
+        let mut packages = PackageMap::with_capacity(metadata.packages.len());
+        let mut changed = HashSet::new();
+        for p in &metadata.packages {
+            let in_changed_manifest =
+                p.manifest_path.as_std_path() == changed_manifest.as_path();
+            let package = match old_packages.get(&p.id) {
+                Some(old) if !in_changed_manifest && **old == *p => {
+                    Rc::clone(old)
+                }
+                _ => {
+                    changed.insert(p.id.clone());
+                    Rc::new(p.clone())
+                }
+            };
+            packages.insert(p.id.clone(), package);
+        }
+        changed.extend(
+            old_packages
+                .keys()
+                .filter(|id| !packages.contains_key(id))
+                .cloned(),
+        );
+
+        // Only kinds that have been used so far are cached
+        for (kind, direct_dependencies) in self.direct_dependencies.get_mut() {
+            let mut new = util::get_direct_dependencies(&metadata, *kind);
+            for (id, deps) in &mut new {
+                match direct_dependencies.get(id) {
+                    Some(old) if old == deps => *deps = Rc::clone(old),
+                    _ => {}
+                }
+            }
+            *direct_dependencies = Rc::new(new);
+        }
+
+        self.metadata = Rc::new(metadata);
+        self.packages = OnceCell::with_value(Rc::new(packages));
+        self.dependents.take();
+        self.depths.take();
+        self.paths_from_root.take();
+        self.checksums.take();
+        self.vet_audits.take();
+        self.deny_config.take();
+        self.future_incompat.take();
+        self.ffi_stats.borrow_mut().clear();
+        self.suspicion.borrow_mut().clear();
+        self.geiger_client = lazy_geiger_client(
+            Rc::clone(&self.manifest_path),
+            self.features.clone(),
+            Rc::clone(&self.metadata),
+            self.vendored_sources.clone(),
+        );
+        self.udeps_client = lazy_udeps_client(
+            Rc::clone(&self.manifest_path),
+            self.features.clone(),
+            Rc::clone(&self.metadata),
+        );
+        self.bloat_client = lazy_bloat_client(
+            Rc::clone(&self.manifest_path),
+            self.features.clone(),
+        );
+        self.semver_checks_client.replace(SemverChecksClient::new());
+        self.clippy_client.replace(ClippyClient::new());
+
+        Ok(changed)
+    }
+
+    /// Skips the first `n` starting vertices of the next query executed,
+    /// such as packages of `Dependencies`
+    pub(crate) fn skip_starting_vertices(&self, n: usize) {
+        self.skip_starting_vertices.set(n);
+    }
+
+    /// The number of starting vertices resolved so far by the query being
+    /// executed, including those skipped
+    pub(crate) fn starting_vertices_resolved(&self) -> Rc<Cell<usize>> {
+        Rc::clone(&self.starting_vertices_resolved)
+    }
+
+    /// If the query being executed has stopped resolving vertices or
+    /// properties since it was interrupted; Reset by the caller before
+    /// executing a query
+    pub(crate) fn stopped_early(&self) -> Rc<Cell<bool>> {
+        Rc::clone(&self.stopped_early)
+    }
+
+    /// The manifest of the package queried
+    pub(crate) fn manifest_path(&self) -> &ManifestPath {
+        &self.manifest_path
+    }
+
+    /// The features used when resolving dependencies
+    pub(crate) fn features(&self) -> &[CargoOpt] {
+        &self.features
+    }
+
+    /// If a package was added or updated since the git reference set using
+    /// [`IndicateAdapterBuilder::changed_since`], or `true` if none was set
+    fn is_changed(&self, package: &Rc<Package>) -> bool {
+        self.changed_packages
+            .as_ref()
+            .is_none_or(|c| c.contains(&NameVersion::from(package)))
+    }
+
     /// Retrieves a new counted reference to this adapters [`PackageMap`], or
     /// retrieves it from metadata if it does not exist
     #[must_use]
@@ -214,68 +700,216 @@ impl IndicateAdapter {
         Rc::clone(p)
     }
 
-    /// Retrieves a new counted reference to this adapters [`PackageMap`], or
-    /// retrieves it from metadata if it does not exist
+    /// Retrieves a new counted reference to this adapters
+    /// [`DirectDependencyMap`] of normal dependencies, or retrieves it from
+    /// metadata if it does not exist
     #[must_use]
     fn direct_dependencies(&self) -> Rc<DirectDependencyMap> {
-        let dd = self.direct_dependencies.get_or_init(|| {
+        self.direct_dependencies_of_kind(Some(DependencyKind::Normal))
+    }
+
+    /// Retrieves a new counted reference to this adapters
+    /// [`DirectDependencyMap`] of dependencies of some kind (all kinds if
+    /// `None`), or retrieves it from metadata if it does not exist
+    #[must_use]
+    fn direct_dependencies_of_kind(
+        &self,
+        kind: Option<DependencyKind>,
+    ) -> Rc<DirectDependencyMap> {
+        let mut dds = self.direct_dependencies.borrow_mut();
+        let dd = dds.entry(kind).or_insert_with(|| {
             let direct_dependencies =
-                util::get_direct_dependencies(&self.metadata);
+                util::get_direct_dependencies(&self.metadata, kind);
             Rc::new(direct_dependencies)
         });
         Rc::clone(dd)
     }
 
-    /// Retrieves a new counted reference to this adapters [`GitHubClient`]
+    /// Retrieves a new counted reference to a map from a package to the
+    /// packages with it as a normal, direct dependency
     #[must_use]
-    fn gh_client(&self) -> Rc<RefCell<GitHubClient>> {
-        Rc::clone(&self.gh_client)
+    fn dependents(&self) -> Rc<DirectDependencyMap> {
+        let d = self.dependents.get_or_init(|| {
+            let dependents =
+                util::invert_dependency_map(&self.direct_dependencies());
+            Rc::new(dependents)
+        });
+        Rc::clone(d)
     }
 
-    /// Retrieve or create a [`AdvisoryClient`]
-    ///
-    /// Since this is an expensive operation, it should only be done when the
-    /// data *must* be used.
+    /// Retrieves a new counted reference to the depth of each package
+    /// reachable from the root package through normal dependencies
     #[must_use]
-    fn advisory_client(&self) -> Rc<AdvisoryClient> {
-        let sac = self.advisory_client.get_or_init(|| {
-            let ac = AdvisoryClient::new().unwrap_or_else(|e| {
-                panic!("could not create advisory client due to error: {e}")
-            });
-            Rc::new(ac)
+    fn depths(&self) -> Rc<HashMap<PackageId, u64>> {
+        let d = self.depths.get_or_init(|| {
+            let depths =
+                util::get_depths(&self.metadata, &self.direct_dependencies());
+            Rc::new(depths)
+        });
+        Rc::clone(d)
+    }
+
+    /// Retrieves a new counted reference to a shortest path of normal
+    /// dependencies from the root package to each reachable package
+    #[must_use]
+    fn paths_from_root(&self) -> Rc<HashMap<PackageId, Rc<Vec<PackageId>>>> {
+        let p = self.paths_from_root.get_or_init(|| {
+            let paths = util::get_paths_from_root(
+                &self.metadata,
+                &self.direct_dependencies(),
+            );
+            Rc::new(paths)
         });
-        Rc::clone(sac)
+        Rc::clone(p)
     }
 
-    /// Retrieve or evaluate a [`GeigerClient`] for the features and manifest
-    /// path used by this adapter
+    /// Retrieves a new counted reference to the checksums in the lock file of
+    /// the workspace, parsing it if it has not been parsed before
     ///
-    /// Since this is an expensive operation, it should only be done when the
-    /// data *must* be used.
+    /// If there is no lock file, or it cannot be parsed, no checksums are
+    /// found.
     #[must_use]
-    fn geiger_client(&self) -> Rc<GeigerClient> {
-        let sgc = self.geiger_client.get_or_init(|| {
-            let gc = GeigerClient::new(
-                &self.manifest_path,
-                self.features.clone(),
-            )
-            .unwrap_or_else(|e| {
-                eprintln!("failed to create geiger data due to error: {e}\nrunning query without");
-                GeigerClient::from(GeigerOutput::default())
-            });
-            Rc::new(gc)
+    fn checksums(&self) -> Rc<ChecksumMap> {
+        let c = self.checksums.get_or_init(|| {
+            let lockfile_path = self
+                .metadata
+                .workspace_root
+                .join("Cargo.lock")
+                .into_std_path_buf();
+            let checksums = Lockfile::from_path(&lockfile_path)
+                .map(|l| l.checksums())
+                .unwrap_or_else(|e| {
+                    eprintln!(
+                        "could not read checksums from {} due to error: {e}",
+                        lockfile_path.to_string_lossy()
+                    );
+                    ChecksumMap::new()
+                });
+            Rc::new(checksums)
+        });
+        Rc::clone(c)
+    }
+
+    /// Retrieves a new counted reference to the `cargo vet` audits of the
+    /// workspace
+    ///
+    /// If the audits cannot be read or parsed, no audits are used.
+    fn vet_audits(&self) -> Rc<VetAuditMap> {
+        let a = self.vet_audits.get_or_init(|| {
+            let workspace_root =
+                self.metadata.workspace_root.as_std_path().to_path_buf();
+            let audits =
+                vet::read_audits(&workspace_root).unwrap_or_else(|e| {
+                    eprintln!(
+                    "could not read cargo vet audits in {} due to error: {e}",
+                    workspace_root.to_string_lossy()
+                );
+                    VetAuditMap::new()
+                });
+            Rc::new(audits)
+        });
+        Rc::clone(a)
+    }
+
+    /// Retrieves a new counted reference to the `deny.toml` of the workspace,
+    /// `None` if it has none
+    ///
+    /// If the configuration cannot be read or parsed, it is not used.
+    fn deny_config(&self) -> Option<Rc<DenyConfig>> {
+        self.deny_config
+            .get_or_init(|| {
+                let workspace_root = self.metadata.workspace_root.as_std_path();
+                DenyConfig::from_workspace_root(workspace_root)
+                    .unwrap_or_else(|e| {
+                        eprintln!(
+                            "could not read deny.toml in {} due to error: {e}",
+                            workspace_root.to_string_lossy()
+                        );
+                        None
+                    })
+                    .map(Rc::new)
+            })
+            .clone()
+    }
+
+    /// Retrieves a new counted reference to the latest future
+    /// incompatibility report of cargo in the target directory
+    ///
+    /// If the report cannot be read or parsed, no incompatibilities are used.
+    fn future_incompat(&self) -> Rc<FutureIncompatMap> {
+        let r = self.future_incompat.get_or_init(|| {
+            let target_dir = self.metadata.target_directory.as_std_path();
+            let reports = future_incompat::read_reports(target_dir)
+                .unwrap_or_else(|e| {
+                    eprintln!(
+                        "could not read future incompatibility report in {} due to error: {e}",
+                        target_dir.to_string_lossy()
+                    );
+                    FutureIncompatMap::new()
+                });
+            Rc::new(reports)
         });
+        Rc::clone(r)
+    }
 
-        Rc::clone(sgc)
+    /// Retrieves a new counted reference to this adapters [`GitHubClient`]
+    #[must_use]
+    fn gh_client(&self) -> Rc<RefCell<GitHubClient>> {
+        Rc::clone(&self.gh_client)
     }
 
-    /// Retrieves or creates a new default [`CratesIoClient`] if none is set
+    /// Retrieves a new counted reference to this adapters [`GitLabClient`]
     #[must_use]
-    fn crates_io_client(&self) -> Rc<RefCell<CratesIoClient>> {
-        let c = self
-            .crates_io_client
-            .get_or_init(|| Rc::new(RefCell::new(CratesIoClient::default())));
-        Rc::clone(c)
+    fn gitlab_client(&self) -> Rc<RefCell<GitLabClient>> {
+        Rc::clone(&self.gitlab_client)
+    }
+
+    /// Retrieves a new counted reference to this adapters [`GiteaClient`]
+    #[must_use]
+    fn gitea_client(&self) -> Rc<RefCell<GiteaClient>> {
+        Rc::clone(&self.gitea_client)
+    }
+
+    /// Retrieves a new counted reference to this adapters
+    /// [`SourcehutClient`]
+    #[must_use]
+    fn sourcehut_client(&self) -> Rc<RefCell<SourcehutClient>> {
+        Rc::clone(&self.sourcehut_client)
+    }
+
+    /// Retrieves a new counted reference to this adapters [`AdvisoryClient`],
+    /// which is created the first time it is used
+    #[must_use]
+    fn advisory_client(&self) -> LazyClient<AdvisoryClient> {
+        Rc::clone(&self.advisory_client)
+    }
+
+    /// Retrieves a new counted reference to this adapters [`GeigerClient`],
+    /// which runs `cargo-geiger` the first time it is used
+    #[must_use]
+    fn geiger_client(&self) -> LazyClient<GeigerClient> {
+        Rc::clone(&self.geiger_client)
+    }
+
+    /// Retrieves a new counted reference to this adapters [`UdepsClient`],
+    /// which runs `cargo-udeps` the first time it is used
+    #[must_use]
+    fn udeps_client(&self) -> LazyClient<UdepsClient> {
+        Rc::clone(&self.udeps_client)
+    }
+
+    /// Retrieves a new counted reference to this adapters [`BloatClient`],
+    /// which runs `cargo-bloat` the first time it is used
+    #[must_use]
+    fn bloat_client(&self) -> LazyClient<BloatClient> {
+        Rc::clone(&self.bloat_client)
+    }
+
+    /// Retrieves a new counted reference to this adapters [`CratesIoClient`],
+    /// which is created the first time it is used
+    #[must_use]
+    fn crates_io_client(&self) -> LazyClient<RefCell<CratesIoClient>> {
+        Rc::clone(&self.crates_io_client)
     }
 
     fn get_dependencies(
@@ -308,8 +942,11 @@ impl IndicateAdapter {
     fn get_repository_from_url(
         url: &str,
         gh_client: &Rc<RefCell<GitHubClient>>,
+        gitlab_client: &Rc<RefCell<GitLabClient>>,
+        gitea_client: &Rc<RefCell<GiteaClient>>,
     ) -> Vertex {
-        match RepoId::from(url) {
+        let repo_id = RepoId::parse(url, gitea_client.borrow().hosts());
+        match repo_id {
             RepoId::GitHub(gh_id) => {
                 if let Some(fr) = gh_client.borrow_mut().get_repository(&gh_id)
                 {
@@ -319,46 +956,68 @@ impl IndicateAdapter {
                     Vertex::Repository(String::from(url))
                 }
             }
-            RepoId::GitLab(gl_url) => Vertex::Repository(String::from(gl_url)),
+            RepoId::GitLab(gl_id) => {
+                match gitlab_client.borrow_mut().get_project(&gl_id) {
+                    Some(p) => Vertex::GitLabRepository(p),
+                    None => Vertex::Repository(String::from(url)),
+                }
+            }
+            RepoId::Gitea(gt_id) => {
+                match gitea_client.borrow_mut().get_repository(&gt_id) {
+                    Some(r) => Vertex::GiteaRepository(r),
+                    None => Vertex::Repository(String::from(url)),
+                }
+            }
+            // Recognized from the url alone, properties requiring the API
+            // are resolved when used
+            RepoId::Sourcehut(r) => Vertex::SourcehutRepository(Rc::new(r)),
             RepoId::Unknown(url) => Vertex::Webpage(String::from(url)),
         }
     }
 }
 
-impl<'a> BasicAdapter<'a> for IndicateAdapter {
-    type Vertex = Vertex;
-
-    fn resolve_starting_vertices(
-        &self,
-        edge_name: &str,
-        parameters: &EdgeParameters,
-    ) -> VertexIterator<'a, Self::Vertex> {
-        match edge_name {
+/// Resolvers for each starting vertex, property and edge in the schema
+///
+/// Each returns `None` or `Err` if there is no resolver for a field, so that
+/// [`IndicateAdapter::missing_resolvers`] can check that the whole schema is
+/// covered without running any queries.
+impl IndicateAdapter {
+    /// Unlike properties and edges, starting vertices are retrieved directly,
+    /// so the resolver is returned without being called
+    fn starting_vertices(edge_name: &str) -> Option<StartingVerticesResolver> {
+        let resolver: StartingVerticesResolver = match edge_name {
             // These edge names should match 1:1 for `schema.trustfall.graphql`
-            "RootPackage" => self.root_package(),
-            "Dependencies" => {
+            "RootPackage" => |adapter, _| adapter.root_package(),
+            "Dependencies" => |adapter, parameters| {
                 // The unwrap is OK since trustfall will verify the parimeters
                 // to match the schema
                 let include_root =
                     parameters.get("includeRoot").unwrap().as_bool().unwrap();
-                self.dependencies(include_root)
-            }
-            "TransitiveDependencies" => self.transitive_dependencies(),
-            e => {
-                unreachable!("edge {e} has no resolution as a starting vertex")
+                adapter.dependencies(include_root)
+            },
+            "TransitiveDependencies" => {
+                |adapter, _| adapter.transitive_dependencies()
             }
-        }
+            "WorkspaceMembers" => |adapter, _| adapter.workspace_members(),
+            "AuditSummary" => |adapter, _| adapter.audit_summary(),
+            "LicenseSummary" => |adapter, _| adapter.license_summary(),
+            _ => return None,
+        };
+        Some(resolver)
     }
 
-    fn resolve_property(
+    fn property(
         &self,
-        contexts: ContextIterator<'a, Self::Vertex>,
+        contexts: ContextIterator<'static, Vertex>,
         type_name: &str,
         property_name: &str,
-    ) -> ContextOutcomeIterator<'a, Self::Vertex, FieldValue> {
+    ) -> Result<
+        ContextOutcomeIterator<'static, Vertex, FieldValue>,
+        ContextIterator<'static, Vertex>,
+    > {
         // This match statement must contain _all_ possible types provided
         // by `schema.trustfall.graphql`
-        match (type_name, property_name) {
+        let resolved = match (type_name, property_name) {
             ("Package", "id") => resolve_property_with(contexts, |v| {
                 if let Some(s) = v.as_package() {
                     FieldValue::String(s.id.to_string())
@@ -391,6 +1050,86 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                 contexts,
                 field_property!(as_package, categories),
             ),
+            ("Package", "authors") => resolve_property_with(
+                contexts,
+                field_property!(as_package, authors),
+            ),
+            ("Package", "description") => resolve_property_with(
+                contexts,
+                field_property!(as_package, description),
+            ),
+            ("Package", "homepage") => resolve_property_with(
+                contexts,
+                field_property!(as_package, homepage),
+            ),
+            ("Package", "documentation") => resolve_property_with(
+                contexts,
+                field_property!(as_package, documentation),
+            ),
+            ("Package", "edition") => resolve_property_with(
+                contexts,
+                field_property!(as_package, edition, {
+                    edition.as_str().into()
+                }),
+            ),
+            ("Package", "rustVersion") => resolve_property_with(
+                contexts,
+                field_property!(as_package, rust_version, {
+                    // `cargo_metadata` parses the MSRV as a requirement, which
+                    // adds a leading `^` not present in the manifest
+                    match rust_version {
+                        Some(rv) => {
+                            let rv = rv.to_string();
+                            rv.strip_prefix('^').unwrap_or(&rv).into()
+                        }
+                        None => FieldValue::Null,
+                    }
+                }),
+            ),
+            ("Package", "isDirectDependency") => {
+                let direct = self
+                    .metadata
+                    .root_package()
+                    .and_then(|root| {
+                        self.direct_dependencies().get(&root.id).cloned()
+                    })
+                    .map(|ids| ids.iter().cloned().collect::<HashSet<_>>())
+                    .unwrap_or_default();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    direct.contains(&package.id).into()
+                })
+            }
+            ("Package", "isTransitive") => {
+                let transitive = if self.metadata.root_package().is_some() {
+                    self.transitive_dependency_ids()
+                        .into_iter()
+                        .collect::<HashSet<_>>()
+                } else {
+                    HashSet::new()
+                };
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    transitive.contains(&package.id).into()
+                })
+            }
+            ("Package", "publishRestriction") => resolve_property_with(
+                contexts,
+                field_property!(as_package, publish, {
+                    match publish.as_deref() {
+                        None => "unrestricted",
+                        Some([]) => "unpublishable",
+                        Some(_) => "registries",
+                    }
+                    .into()
+                }),
+            ),
+            ("Package", "publishRegistries") => resolve_property_with(
+                contexts,
+                field_property!(as_package, publish, {
+                    publish.clone().into()
+                }),
+            ),
             ("Package", "manifestPath") => {
                 resolve_property_with(contexts, |v| {
                     let package = v.as_package().unwrap();
@@ -405,47 +1144,347 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                     util::local_package_path(package).to_string_lossy().into(),
                 )
             }),
-            ("CratesIoStats", "totalDownloads") => {
-                let crates_io_client = self.crates_io_client();
+            ("Package", "depth") => {
+                let depths = self.depths();
                 resolve_property_with(contexts, move |v| {
-                    let nv = v.as_crates_io_stats().unwrap();
-                    match crates_io_client
-                        .borrow_mut()
-                        .total_downloads(&nv.name)
-                    {
-                        Some(n) => FieldValue::Uint64(n),
+                    let package = v.as_package().unwrap();
+                    match depths.get(&package.id) {
+                        Some(d) => FieldValue::Uint64(*d),
                         None => FieldValue::Null,
                     }
                 })
             }
-            ("CratesIoStats", "recentDownloads") => {
+            ("Package", "sourceKind") => resolve_property_with(contexts, |v| {
+                let package = v.as_package().unwrap();
+                SourceKind::of(package).as_str().into()
+            }),
+            ("Package", "isProcMacro") => {
+                resolve_property_with(contexts, |v| {
+                    let package = v.as_package().unwrap();
+                    package
+                        .targets
+                        .iter()
+                        .any(|t| t.kind.iter().any(|k| k == "proc-macro"))
+                        .into()
+                })
+            }
+            ("Package", "versionPublishedBy") => {
                 let crates_io_client = self.crates_io_client();
                 resolve_property_with(contexts, move |v| {
-                    let nv = v.as_crates_io_stats().unwrap();
-                    match crates_io_client
-                        .borrow_mut()
-                        .recent_downloads(&nv.name)
-                    {
-                        Some(n) => FieldValue::Uint64(n),
-                        None => FieldValue::Null,
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
                     }
+                    crates_io_client
+                        .borrow_mut()
+                        .version_published_by(&NameVersion::from(package))
+                        .into()
                 })
             }
-            ("CratesIoStats", "versionDownloads") => {
+            ("Package", "ownerChangedRecently") => {
                 let crates_io_client = self.crates_io_client();
                 resolve_property_with(contexts, move |v| {
-                    let nv = v.as_crates_io_stats().unwrap();
-                    match crates_io_client.borrow_mut().version_downloads(nv) {
-                        Some(n) => FieldValue::Uint64(n),
-                        None => FieldValue::Null,
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
                     }
+                    crates_io_client
+                        .borrow_mut()
+                        .owner_changed_recently(&package.name)
+                        .into()
                 })
             }
-            ("CratesIoStats", "versionsCount") => {
+            ("Package", "daysSinceOwnerAdded") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client
+                        .borrow_mut()
+                        .days_since_owner_added(&package.name)
+                        .into()
+                })
+            }
+            ("Package", "cratesIoCreatedAt") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client
+                        .borrow_mut()
+                        .created_at(&package.name)
+                        .into()
+                })
+            }
+            ("Package", "cratesIoUpdatedAt") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client
+                        .borrow_mut()
+                        .updated_at(&package.name)
+                        .into()
+                })
+            }
+            ("Package", "cratesIoVersionPublishedAt") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client
+                        .borrow_mut()
+                        .version_published_at(&NameVersion::from(package))
+                        .into()
+                })
+            }
+            ("Package", "cratesIoLatestVersion") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client
+                        .borrow_mut()
+                        .latest_version(&package.name)
+                        .map(|v| v.to_string())
+                        .into()
+                })
+            }
+            ("Package", "isOutdated") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client
+                        .borrow_mut()
+                        .latest_version(&package.name)
+                        .map(|latest| package.version < latest)
+                        .into()
+                })
+            }
+            ("Package", "versionsBehind") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client
+                        .borrow_mut()
+                        .versions_behind(&NameVersion::from(package))
+                        .map(|n| n as u64)
+                        .into()
+                })
+            }
+            ("Package", "cratesIoReverseDependenciesCount") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client
+                        .borrow_mut()
+                        .reverse_dependencies_count(&package.name)
+                        .into()
+                })
+            }
+            ("Package", "cratesIoKeywords") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client.borrow_mut().keywords(&package.name).into()
+                })
+            }
+            ("Package", "cratesIoCategories") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return FieldValue::Null;
+                    }
+                    crates_io_client
+                        .borrow_mut()
+                        .categories(&package.name)
+                        .into()
+                })
+            }
+            ("Package", "registryUrl") => {
+                resolve_property_with(contexts, |v| {
+                    let package = v.as_package().unwrap();
+                    package
+                        .source
+                        .as_ref()
+                        .and_then(|s| registry_url(&s.repr))
+                        .into()
+                })
+            }
+            ("Package", "checksum") => {
+                let checksums = self.checksums();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    let Some(source) = &package.source else {
+                        return FieldValue::Null;
+                    };
+                    let key = (
+                        package.name.clone(),
+                        package.version.to_string(),
+                        source.repr.clone(),
+                    );
+                    checksums.get(&key).cloned().into()
+                })
+            }
+            ("Package", "hasBuildScript") => {
+                resolve_property_with(contexts, |v| {
+                    let package = v.as_package().unwrap();
+                    has_build_script(package).into()
+                })
+            }
+            ("Package", "externFnCount") => {
+                let ffi_stats = Rc::clone(&self.ffi_stats);
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    let stats =
+                        cached_for_package(&ffi_stats, package, get_ffi_stats);
+                    FieldValue::Uint64(stats.extern_fn_count() as u64)
+                })
+            }
+            ("Package", "buildScriptRiskFlags") => {
+                resolve_property_with(contexts, |v| {
+                    let package = v.as_package().unwrap();
+                    get_build_script_risks(package)
+                        .iter()
+                        .map(BuildScriptRisk::as_str)
+                        .collect::<Vec<_>>()
+                        .into()
+                })
+            }
+            ("Package", "suspicionScore") => {
+                let suspicion = Rc::clone(&self.suspicion);
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    cached_for_package(&suspicion, package, |p| {
+                        Rc::new(get_suspicion(p))
+                    })
+                    .score()
+                    .into()
+                })
+            }
+            ("Package", "suspicionReasons") => {
+                let suspicion = Rc::clone(&self.suspicion);
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    cached_for_package(&suspicion, package, |p| {
+                        Rc::new(get_suspicion(p))
+                    })
+                    .reasons
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .into()
+                })
+            }
+            ("Package", "exposesCAbi") => {
+                let ffi_stats = Rc::clone(&self.ffi_stats);
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    cached_for_package(&ffi_stats, package, get_ffi_stats)
+                        .exposes_c_abi()
+                        .into()
+                })
+            }
+            ("Package", "isUnused") => {
+                let udeps_client = self.udeps_client();
+                resolve_property_with(contexts, move |v| {
+                    let package = v.as_package().unwrap();
+                    udeps_client.is_unused(&package.id).into()
+                })
+            }
+            ("CratesIoOwner", "kind") => resolve_property_with(
+                contexts,
+                field_property!(as_crates_io_owner, kind, {
+                    // Users have no kind in older responses
+                    kind.as_deref().unwrap_or("user").into()
+                }),
+            ),
+            ("CratesIoOwner", "login") => resolve_property_with(
+                contexts,
+                field_property!(as_crates_io_owner, login),
+            ),
+            ("CratesIoOwner", "name") => resolve_property_with(
+                contexts,
+                field_property!(as_crates_io_owner, name),
+            ),
+            ("CratesIoOwner", "url") => resolve_property_with(
+                contexts,
+                field_property!(as_crates_io_owner, url),
+            ),
+            ("CratesIoStats", "totalDownloads") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return FieldValue::Null;
+                    };
+                    match crates_io_client
+                        .borrow_mut()
+                        .total_downloads(&nv.name)
+                    {
+                        Some(n) => FieldValue::Uint64(n),
+                        None => FieldValue::Null,
+                    }
+                })
+            }
+            ("CratesIoStats", "recentDownloads") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_property_with(contexts, move |v| {
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return FieldValue::Null;
+                    };
+                    match crates_io_client
+                        .borrow_mut()
+                        .recent_downloads(&nv.name)
+                    {
+                        Some(n) => FieldValue::Uint64(n),
+                        None => FieldValue::Null,
+                    }
+                })
+            }
+            ("CratesIoStats", "versionDownloads") => {
                 let crates_io_client = self.crates_io_client();
                 resolve_property_with(contexts, move |v| {
-                    let nv = v.as_crates_io_stats().unwrap();
-                    match crates_io_client.borrow_mut().versions_count(&nv.name)
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return FieldValue::Null;
+                    };
+                    match crates_io_client.borrow_mut().version_downloads(nv) {
+                        Some(n) => FieldValue::Uint64(n),
+                        None => FieldValue::Null,
+                    }
+                })
+            }
+            ("CratesIoStats", "versionsCount") => {
+                let sparse_index_client = Rc::clone(&self.sparse_index_client);
+                resolve_property_with(contexts, move |v| {
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return FieldValue::Null;
+                    };
+                    match sparse_index_client
+                        .borrow_mut()
+                        .versions_count(&nv.name)
                     {
                         Some(n) => FieldValue::Uint64(n as u64),
                         None => FieldValue::Null,
@@ -453,20 +1492,24 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                 })
             }
             ("CratesIoStats", "yanked") => {
-                let crates_io_client = self.crates_io_client();
+                let sparse_index_client = Rc::clone(&self.sparse_index_client);
                 resolve_property_with(contexts, move |v| {
-                    let nv = v.as_crates_io_stats().unwrap();
-                    match crates_io_client.borrow_mut().yanked(nv) {
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return FieldValue::Null;
+                    };
+                    match sparse_index_client.borrow_mut().yanked(nv) {
                         Some(b) => b.into(),
                         None => FieldValue::Null,
                     }
                 })
             }
             ("CratesIoStats", "yankedVersions") => {
-                let crates_io_client = self.crates_io_client();
+                let sparse_index_client = Rc::clone(&self.sparse_index_client);
                 resolve_property_with(contexts, move |v| {
-                    let nv = v.as_crates_io_stats().unwrap();
-                    match crates_io_client
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return FieldValue::Null;
+                    };
+                    match sparse_index_client
                         .borrow_mut()
                         .yanked_versions(&nv.name)
                     {
@@ -476,10 +1519,12 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                 })
             }
             ("CratesIoStats", "yankedVersionsCount") => {
-                let crates_io_client = self.crates_io_client();
+                let sparse_index_client = Rc::clone(&self.sparse_index_client);
                 resolve_property_with(contexts, move |v| {
-                    let nv = v.as_crates_io_stats().unwrap();
-                    match crates_io_client
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return FieldValue::Null;
+                    };
+                    match sparse_index_client
                         .borrow_mut()
                         .yanked_versions_count(&nv.name)
                     {
@@ -489,21 +1534,41 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                 })
             }
             ("CratesIoStats", "yankedRatio") => {
-                let crates_io_client = self.crates_io_client();
+                let sparse_index_client = Rc::clone(&self.sparse_index_client);
                 resolve_property_with(contexts, move |v| {
-                    let nv = v.as_crates_io_stats().unwrap();
-                    match crates_io_client.borrow_mut().yanked_ratio(&nv.name) {
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return FieldValue::Null;
+                    };
+                    match sparse_index_client
+                        .borrow_mut()
+                        .yanked_ratio(&nv.name)
+                    {
                         Some(n) => FieldValue::Float64(n),
                         None => FieldValue::Null,
                     }
                 })
             }
-            ("Webpage" | "Repository" | "GitHubRepository", "url") => {
-                resolve_property_with(contexts, |v| match v.as_webpage() {
-                    Some(url) => FieldValue::String(url.to_owned()),
-                    None => FieldValue::Null,
+            ("CratesIoStats", "checksum") => {
+                let sparse_index_client = Rc::clone(&self.sparse_index_client);
+                resolve_property_with(contexts, move |v| {
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return FieldValue::Null;
+                    };
+                    sparse_index_client.borrow_mut().checksum(nv).into()
                 })
             }
+            (
+                "Webpage"
+                | "Repository"
+                | "GitHubRepository"
+                | "GitLabRepository"
+                | "GiteaRepository"
+                | "SourcehutRepository",
+                "url",
+            ) => resolve_property_with(contexts, |v| match v.as_webpage() {
+                Some(url) => FieldValue::String(url.to_owned()),
+                None => FieldValue::Null,
+            }),
             ("GitHubRepository", "name") => resolve_property_with(
                 contexts,
                 field_property!(as_git_hub_repository, name),
@@ -536,56 +1601,760 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                 contexts,
                 field_property!(as_git_hub_repository, fork),
             ),
-            ("GitHubUser", "username") => resolve_property_with(
+            ("GitHubRepository", "topics") => resolve_property_with(
                 contexts,
-                field_property!(as_git_hub_user, login),
+                field_property!(as_git_hub_repository, topics),
             ),
-            ("GitHubUser", "unixCreatedAt") => resolve_property_with(
+            ("GitHubRepository", "defaultBranch") => resolve_property_with(
                 contexts,
-                field_property!(as_git_hub_user, created_at, {
-                    created_at.map(|d| d.timestamp()).into() // Convert to Unix timestamp
+                field_property!(as_git_hub_repository, default_branch),
+            ),
+            ("GitHubRepository", "licenseSpdxId") => {
+                resolve_property_with(contexts, |v| {
+                    let gh_repo = v.as_git_hub_repository().unwrap();
+                    github::license_spdx_id(gh_repo).into()
+                })
+            }
+            ("GitHubRepository", "unixLastPushedAt") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_repository, pushed_at, {
+                    pushed_at.map(|d| d.timestamp()).into()
                 }),
             ),
-            ("GitHubUser", "followersCount") => resolve_property_with(
+            ("GitHubRepository", "hasCiWorkflows") => {
+                let gh_client = self.gh_client();
+                resolve_property_with(contexts, move |v| {
+                    let gh_repo = v.as_git_hub_repository().unwrap();
+                    gh_client
+                        .borrow_mut()
+                        .get_workflows(&GitHubRepositoryId::from(&**gh_repo))
+                        .map(|w| !w.is_empty())
+                        .into()
+                })
+            }
+            ("GitHubRepository", "unixLastCommitAt") => {
+                let gh_client = self.gh_client();
+                resolve_property_with(contexts, move |v| {
+                    let gh_repo = v.as_git_hub_repository().unwrap();
+                    gh_client.borrow_mut().get_last_commit_at(gh_repo).into()
+                })
+            }
+            ("GitHubRepository", "defaultBranchProtected") => {
+                let gh_client = self.gh_client();
+                resolve_property_with(contexts, move |v| {
+                    let gh_repo = v.as_git_hub_repository().unwrap();
+                    gh_client
+                        .borrow_mut()
+                        .get_default_branch_protected(gh_repo)
+                        .into()
+                })
+            }
+            ("GitHubRepository", "defaultBranchRequiresReviews") => {
+                let gh_client = self.gh_client();
+                resolve_property_with(contexts, move |v| {
+                    let gh_repo = v.as_git_hub_repository().unwrap();
+                    gh_client
+                        .borrow_mut()
+                        .get_default_branch_requires_reviews(gh_repo)
+                        .into()
+                })
+            }
+            ("GitHubRepository", "hasSecurityPolicy") => {
+                let gh_client = self.gh_client();
+                resolve_property_with(contexts, move |v| {
+                    let gh_repo = v.as_git_hub_repository().unwrap();
+                    gh_client
+                        .borrow_mut()
+                        .get_has_security_policy(&GitHubRepositoryId::from(
+                            &**gh_repo,
+                        ))
+                        .into()
+                })
+            }
+            ("GitLabRepository", "name") => resolve_property_with(
                 contexts,
-                field_property!(as_git_hub_user, followers),
+                field_property!(as_git_lab_repository, name),
             ),
-            ("GitHubUser", "email") => resolve_property_with(
+            ("GitLabRepository", "fullName") => resolve_property_with(
                 contexts,
-                field_property!(as_git_hub_user, email),
+                field_property!(as_git_lab_repository, path_with_namespace),
             ),
-            ("Advisory", "id") => resolve_property_with(
+            ("GitLabRepository", "starsCount") => resolve_property_with(
                 contexts,
-                accessor_property!(as_advisory, id, { id.to_string().into() }),
+                field_property!(as_git_lab_repository, star_count),
             ),
-            ("Advisory", "title") => resolve_property_with(
+            ("GitLabRepository", "forksCount") => resolve_property_with(
                 contexts,
-                accessor_property!(as_advisory, title),
+                field_property!(as_git_lab_repository, forks_count),
             ),
-            ("Advisory", "description") => resolve_property_with(
+            ("GitLabRepository", "openIssuesCount") => resolve_property_with(
                 contexts,
-                accessor_property!(as_advisory, description),
+                field_property!(as_git_lab_repository, open_issues_count),
             ),
-            ("Advisory", "unixDateReported") => resolve_property_with(
+            ("GitLabRepository", "archived") => resolve_property_with(
                 contexts,
-                accessor_property!(as_advisory, date, {
-                    // TODO: This assumes the advisory being posted 00:00 UTC,
-                    // which might or might not be a good idea
-                    let dt: NaiveDateTime = NaiveDate::from_ymd_opt(
-                        date.year() as i32,
-                        date.month(),
-                        date.day(),
-                    )
-                    .expect("could not parse advisory unix date")
-                    .and_hms_opt(0, 0, 0)
-                    .expect("could not create advisory timestamp");
-                    dt.timestamp().into()
-                }),
+                field_property!(as_git_lab_repository, archived),
             ),
-            ("Advisory", "unixDateWithdrawn") => resolve_property_with(
+            ("GitLabRepository", "unixLastActivityAt") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_git_lab_repository()
+                        .unwrap()
+                        .unix_last_activity_at()
+                        .into()
+                })
+            }
+            ("GitLabNamespace", "name") => resolve_property_with(
                 contexts,
-                field_property!(as_advisory, metadata, {
-                    // TODO: This assumes the advisory being withdrawn 00:00 UTC,
+                field_property!(as_git_lab_namespace, name),
+            ),
+            ("GitLabNamespace", "fullPath") => resolve_property_with(
+                contexts,
+                field_property!(as_git_lab_namespace, full_path),
+            ),
+            ("GitLabNamespace", "kind") => resolve_property_with(
+                contexts,
+                field_property!(as_git_lab_namespace, kind),
+            ),
+            ("GitLabNamespace", "url") => resolve_property_with(
+                contexts,
+                field_property!(as_git_lab_namespace, web_url),
+            ),
+            ("GiteaRepository", "name") => resolve_property_with(
+                contexts,
+                field_property!(as_gitea_repository, name),
+            ),
+            ("GiteaRepository", "fullName") => resolve_property_with(
+                contexts,
+                field_property!(as_gitea_repository, full_name),
+            ),
+            ("GiteaRepository", "starsCount") => resolve_property_with(
+                contexts,
+                field_property!(as_gitea_repository, stars_count),
+            ),
+            ("GiteaRepository", "forksCount") => resolve_property_with(
+                contexts,
+                field_property!(as_gitea_repository, forks_count),
+            ),
+            ("GiteaRepository", "openIssuesCount") => resolve_property_with(
+                contexts,
+                field_property!(as_gitea_repository, open_issues_count),
+            ),
+            ("GiteaRepository", "archived") => resolve_property_with(
+                contexts,
+                field_property!(as_gitea_repository, archived),
+            ),
+            ("GiteaRepository", "unixLastActivityAt") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_gitea_repository().unwrap().unix_updated_at().into()
+                })
+            }
+            ("SourcehutRepository", "owner") => resolve_property_with(
+                contexts,
+                field_property!(as_sourcehut_repository, owner),
+            ),
+            ("SourcehutRepository", "name") => resolve_property_with(
+                contexts,
+                field_property!(as_sourcehut_repository, name),
+            ),
+            ("SourcehutRepository", "unixLastCommitAt") => {
+                let sourcehut_client = self.sourcehut_client();
+                resolve_property_with(contexts, move |v| {
+                    let repo = v.as_sourcehut_repository().unwrap();
+                    sourcehut_client
+                        .borrow_mut()
+                        .get_last_commit_at(repo)
+                        .into()
+                })
+            }
+            ("Scorecard", "score") => resolve_property_with(
+                contexts,
+                field_property!(as_scorecard, score, {
+                    FieldValue::Float64(*score)
+                }),
+            ),
+            ("Scorecard", "date") => resolve_property_with(
+                contexts,
+                field_property!(as_scorecard, date),
+            ),
+            ("Scorecard", "commit") => resolve_property_with(
+                contexts,
+                field_property!(as_scorecard, repo, {
+                    repo.commit.clone().into()
+                }),
+            ),
+            ("Scorecard", "url") => resolve_property_with(contexts, |v| {
+                v.as_scorecard().unwrap().url().into()
+            }),
+            ("ScorecardCheck", "name") => resolve_property_with(
+                contexts,
+                field_property!(as_scorecard_check, name),
+            ),
+            ("ScorecardCheck", "score") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_scorecard_check().unwrap().conclusive_score().into()
+                })
+            }
+            ("ScorecardCheck", "reason") => resolve_property_with(
+                contexts,
+                field_property!(as_scorecard_check, reason),
+            ),
+            ("ScorecardCheck", "documentationUrl") => resolve_property_with(
+                contexts,
+                field_property!(as_scorecard_check, documentation, {
+                    documentation.as_ref().and_then(|d| d.url.clone()).into()
+                }),
+            ),
+            ("GitHubContributor", "login") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_contributor, login),
+            ),
+            ("GitHubContributor", "contributions") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_contributor, contributions),
+            ),
+            ("GitHubContributor", "share") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_contributor, share, {
+                    FieldValue::Float64(*share)
+                }),
+            ),
+            ("GitHubRelease", "tagName") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_release, tag_name),
+            ),
+            ("GitHubRelease", "name") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_release, name),
+            ),
+            ("GitHubRelease", "url") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_release, html_url),
+            ),
+            ("GitHubRelease", "unixPublishedAt") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_release, published_at, {
+                    published_at.map(|d| d.timestamp()).into()
+                }),
+            ),
+            ("GitHubRelease", "prerelease") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_release, prerelease),
+            ),
+            ("GitHubRelease", "draft") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_release, draft),
+            ),
+            ("GitHubWorkflow", "name") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_workflow, name),
+            ),
+            ("GitHubWorkflow", "path") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_workflow, path),
+            ),
+            ("GitHubWorkflow", "url") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_workflow, html_url),
+            ),
+            ("GitHubIssue", "title") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_issue, title),
+            ),
+            ("GitHubIssue", "number") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_issue, number),
+            ),
+            ("GitHubIssue", "state") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_issue, state),
+            ),
+            ("GitHubIssue", "url") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_issue, html_url),
+            ),
+            ("GitHubIssue", "unixCreatedAt") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_issue, created_at, {
+                    created_at.map(|d| d.timestamp()).into()
+                }),
+            ),
+            ("GitHubIssue", "labels") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_issue, labels, {
+                    labels
+                        .iter()
+                        .map(|l| l.name.clone())
+                        .collect::<Vec<_>>()
+                        .into()
+                }),
+            ),
+            ("GitHubUser", "username") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_user, login),
+            ),
+            ("GitHubUser", "unixCreatedAt") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_user, created_at, {
+                    created_at.map(|d| d.timestamp()).into() // Convert to Unix timestamp
+                }),
+            ),
+            ("GitHubUser", "followersCount") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_user, followers),
+            ),
+            ("GitHubUser", "email") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_user, email),
+            ),
+            ("GitHubUser", "publicReposCount") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_user, public_repos),
+            ),
+            ("GitHubUser", "company") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_user, company, {
+                    non_empty(company)
+                }),
+            ),
+            ("GitHubUser", "location") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_user, location, {
+                    non_empty(location)
+                }),
+            ),
+            ("GitHubUser", "bio") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_user, bio, { non_empty(bio) }),
+            ),
+            ("GitHubOrganization", "login") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_organization, login),
+            ),
+            ("GitHubOrganization", "description") => resolve_property_with(
+                contexts,
+                field_property!(as_git_hub_organization, description, {
+                    non_empty(description)
+                }),
+            ),
+            ("GitHubOrganization", "url") => {
+                resolve_property_with(contexts, |v| {
+                    let org = v.as_git_hub_organization().unwrap();
+                    format!("https://github.com/{}", org.login).into()
+                })
+            }
+            ("DocsRsBuild", "buildSucceeded") => resolve_property_with(
+                contexts,
+                field_property!(as_docs_rs_build, build_succeeded),
+            ),
+            ("DocsRsBuild", "url") => resolve_property_with(
+                contexts,
+                field_property!(as_docs_rs_build, url),
+            ),
+            ("DocsRsBuild", "defaultTarget") => resolve_property_with(
+                contexts,
+                field_property!(as_docs_rs_build, default_target),
+            ),
+            ("DocsRsBuild", "targets") => resolve_property_with(
+                contexts,
+                field_property!(as_docs_rs_build, targets),
+            ),
+            ("DepsDev", "url") => resolve_property_with(
+                contexts,
+                field_property!(as_deps_dev, url),
+            ),
+            ("DepsDev", "knownVersions") => resolve_property_with(
+                contexts,
+                field_property!(as_deps_dev, known_versions),
+            ),
+            ("DepsDev", "isDefaultVersion") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_deps_dev().unwrap().version.is_default.into()
+                })
+            }
+            ("DepsDev", "licenses") => resolve_property_with(contexts, |v| {
+                v.as_deps_dev().unwrap().version.licenses.clone().into()
+            }),
+            ("DepsDev", "advisoryIds") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_deps_dev().unwrap().advisory_ids().into()
+                })
+            }
+            ("DepsDev", "directDependenciesCount") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_deps_dev().unwrap().direct_dependencies_count().into()
+                })
+            }
+            ("DepsDev", "indirectDependenciesCount") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_deps_dev()
+                        .unwrap()
+                        .indirect_dependencies_count()
+                        .into()
+                })
+            }
+            ("DepsDev", "sourceRepository") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_deps_dev().unwrap().source_repository().into()
+                })
+            }
+            ("DepsDev", "scorecardUrl") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_deps_dev().unwrap().scorecard_url().into()
+                })
+            }
+            ("OsvAdvisory", "id") => resolve_property_with(
+                contexts,
+                field_property!(as_osv_advisory, id),
+            ),
+            ("OsvAdvisory", "summary") => resolve_property_with(
+                contexts,
+                field_property!(as_osv_advisory, summary),
+            ),
+            ("OsvAdvisory", "details") => resolve_property_with(
+                contexts,
+                field_property!(as_osv_advisory, details),
+            ),
+            ("OsvAdvisory", "aliases") => resolve_property_with(
+                contexts,
+                field_property!(as_osv_advisory, aliases),
+            ),
+            ("OsvAdvisory", "published") => resolve_property_with(
+                contexts,
+                field_property!(as_osv_advisory, published),
+            ),
+            ("OsvAdvisory", "modified") => resolve_property_with(
+                contexts,
+                field_property!(as_osv_advisory, modified),
+            ),
+            ("OsvAdvisory", "withdrawn") => resolve_property_with(
+                contexts,
+                field_property!(as_osv_advisory, withdrawn),
+            ),
+            ("OsvAdvisory", "severity") => resolve_property_with(
+                contexts,
+                field_property!(as_osv_advisory, severity, {
+                    severity
+                        .iter()
+                        .map(|s| s.score.clone())
+                        .collect::<Vec<_>>()
+                        .into()
+                }),
+            ),
+            ("OsvAdvisory", "referenceUrls") => resolve_property_with(
+                contexts,
+                field_property!(as_osv_advisory, references, {
+                    references
+                        .iter()
+                        .map(|r| r.url.clone())
+                        .collect::<Vec<_>>()
+                        .into()
+                }),
+            ),
+            ("GhsaAdvisory", "id") => resolve_property_with(contexts, |v| {
+                v.as_ghsa_advisory()
+                    .unwrap()
+                    .vulnerability
+                    .advisory
+                    .ghsa_id
+                    .clone()
+                    .into()
+            }),
+            ("GhsaAdvisory", "summary") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_ghsa_advisory()
+                        .unwrap()
+                        .vulnerability
+                        .advisory
+                        .summary
+                        .clone()
+                        .into()
+                })
+            }
+            ("GhsaAdvisory", "severity") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_ghsa_advisory()
+                        .unwrap()
+                        .vulnerability
+                        .advisory
+                        .severity
+                        .to_lowercase()
+                        .into()
+                })
+            }
+            ("GhsaAdvisory", "url") => resolve_property_with(contexts, |v| {
+                v.as_ghsa_advisory()
+                    .unwrap()
+                    .vulnerability
+                    .advisory
+                    .permalink
+                    .clone()
+                    .into()
+            }),
+            ("GhsaAdvisory", "aliases") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_ghsa_advisory()
+                        .unwrap()
+                        .vulnerability
+                        .advisory
+                        .aliases()
+                        .into()
+                })
+            }
+            ("GhsaAdvisory", "cvssScore") => {
+                resolve_property_with(contexts, |v| {
+                    match v
+                        .as_ghsa_advisory()
+                        .unwrap()
+                        .vulnerability
+                        .advisory
+                        .cvss_score()
+                    {
+                        Some(s) => FieldValue::Float64(s),
+                        None => FieldValue::Null,
+                    }
+                })
+            }
+            ("GhsaAdvisory", "unixPublishedAt") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_ghsa_advisory()
+                        .unwrap()
+                        .vulnerability
+                        .advisory
+                        .unix_published_at()
+                        .into()
+                })
+            }
+            ("GhsaAdvisory", "unixWithdrawnAt") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_ghsa_advisory()
+                        .unwrap()
+                        .vulnerability
+                        .advisory
+                        .unix_withdrawn_at()
+                        .into()
+                })
+            }
+            ("GhsaAdvisory", "vulnerableVersionRange") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_ghsa_advisory()
+                        .unwrap()
+                        .vulnerability
+                        .vulnerable_version_range
+                        .clone()
+                        .into()
+                })
+            }
+            ("GhsaAdvisory", "firstPatchedVersion") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_ghsa_advisory()
+                        .unwrap()
+                        .vulnerability
+                        .first_patched_version
+                        .as_ref()
+                        .map(|p| p.identifier.clone())
+                        .into()
+                })
+            }
+            ("GhsaAdvisory", "currentVersionAffected") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_ghsa_advisory()
+                        .unwrap()
+                        .current_version_affected
+                        .into()
+                })
+            }
+            ("Advisory", "inKnownExploitedCatalog") => {
+                let kev_client = Rc::clone(&self.kev_client);
+                resolve_property_with(contexts, move |v| {
+                    let cves = v
+                        .as_advisory()
+                        .unwrap()
+                        .metadata
+                        .aliases
+                        .iter()
+                        .filter(|a| a.is_cve())
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>();
+                    kev_client.borrow_mut().contains_any(&cves).into()
+                })
+            }
+            ("CrevReview", "reviewerId") => resolve_property_with(
+                contexts,
+                field_property!(as_crev_review, reviewer_id),
+            ),
+            ("CrevReview", "reviewerUrl") => resolve_property_with(
+                contexts,
+                field_property!(as_crev_review, reviewer_url),
+            ),
+            ("CrevReview", "version") => resolve_property_with(
+                contexts,
+                field_property!(as_crev_review, version),
+            ),
+            ("CrevReview", "unixDate") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_crev_review().unwrap().unix_date().into()
+                })
+            }
+            ("CrevReview", "rating") => resolve_property_with(
+                contexts,
+                field_property!(as_crev_review, rating),
+            ),
+            ("CrevReview", "thoroughness") => resolve_property_with(
+                contexts,
+                field_property!(as_crev_review, thoroughness),
+            ),
+            ("CrevReview", "understanding") => resolve_property_with(
+                contexts,
+                field_property!(as_crev_review, understanding),
+            ),
+            ("CrevReview", "comment") => resolve_property_with(
+                contexts,
+                field_property!(as_crev_review, comment),
+            ),
+            ("DenyPolicy", "isDeniedByPolicy") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_deny_policy().unwrap().is_denied().into()
+                })
+            }
+            ("DenyPolicy", "matchedBan") => resolve_property_with(
+                contexts,
+                field_property!(as_deny_policy, matched_ban),
+            ),
+            ("DenyPolicy", "licenseAllowed") => resolve_property_with(
+                contexts,
+                field_property!(as_deny_policy, license_allowed),
+            ),
+            ("VetAudit", "who") => resolve_property_with(contexts, |v| {
+                v.as_vet_audit().unwrap().audit.who.clone().into()
+            }),
+            ("VetAudit", "criteria") => resolve_property_with(contexts, |v| {
+                v.as_vet_audit().unwrap().audit.criteria.clone().into()
+            }),
+            ("VetAudit", "version") => resolve_property_with(contexts, |v| {
+                v.as_vet_audit().unwrap().audit.version.clone().into()
+            }),
+            ("VetAudit", "delta") => resolve_property_with(contexts, |v| {
+                v.as_vet_audit().unwrap().audit.delta.clone().into()
+            }),
+            ("VetAudit", "notes") => resolve_property_with(contexts, |v| {
+                v.as_vet_audit().unwrap().audit.notes.clone().into()
+            }),
+            ("VetAudit", "importedFrom") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_vet_audit().unwrap().audit.imported_from.clone().into()
+                })
+            }
+            ("VetAudit", "coversCurrentVersion") => resolve_property_with(
+                contexts,
+                field_property!(as_vet_audit, covers_current_version),
+            ),
+            ("CveRecord", "id") => resolve_property_with(
+                contexts,
+                field_property!(as_cve_record, id),
+            ),
+            ("CveRecord", "status") => resolve_property_with(
+                contexts,
+                field_property!(as_cve_record, vuln_status),
+            ),
+            ("CveRecord", "description") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_cve_record().unwrap().description().into()
+                })
+            }
+            ("CveRecord", "unixPublishedAt") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_cve_record().unwrap().unix_published_at().into()
+                })
+            }
+            ("CveRecord", "unixLastModifiedAt") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_cve_record().unwrap().unix_last_modified_at().into()
+                })
+            }
+            ("CveRecord", "cvssScore") => {
+                resolve_property_with(contexts, |v| {
+                    match v.as_cve_record().unwrap().cvss_v31() {
+                        Some(c) => FieldValue::Float64(c.base_score),
+                        None => FieldValue::Null,
+                    }
+                })
+            }
+            ("CveRecord", "cvssVector") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_cve_record()
+                        .unwrap()
+                        .cvss_v31()
+                        .map(|c| c.vector_string.clone())
+                        .into()
+                })
+            }
+            ("CveRecord", "cvssSeverity") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_cve_record()
+                        .unwrap()
+                        .cvss_v31()
+                        .map(|c| c.base_severity.to_lowercase())
+                        .into()
+                })
+            }
+            ("EpssScore", "cve") => resolve_property_with(
+                contexts,
+                field_property!(as_epss_score, cve),
+            ),
+            ("EpssScore", "probability") => resolve_property_with(
+                contexts,
+                field_property!(as_epss_score, epss, {
+                    match epss.parse::<f64>() {
+                        Ok(p) => FieldValue::Float64(p),
+                        Err(_) => FieldValue::Null,
+                    }
+                }),
+            ),
+            ("EpssScore", "percentile") => resolve_property_with(
+                contexts,
+                field_property!(as_epss_score, percentile, {
+                    match percentile.parse::<f64>() {
+                        Ok(p) => FieldValue::Float64(p),
+                        Err(_) => FieldValue::Null,
+                    }
+                }),
+            ),
+            ("EpssScore", "date") => resolve_property_with(
+                contexts,
+                field_property!(as_epss_score, date),
+            ),
+            ("Advisory", "id") => resolve_property_with(
+                contexts,
+                accessor_property!(as_advisory, id, { id.to_string().into() }),
+            ),
+            ("Advisory", "title") => resolve_property_with(
+                contexts,
+                accessor_property!(as_advisory, title),
+            ),
+            ("Advisory", "description") => resolve_property_with(
+                contexts,
+                accessor_property!(as_advisory, description),
+            ),
+            ("Advisory", "unixDateReported") => resolve_property_with(
+                contexts,
+                accessor_property!(as_advisory, date, {
+                    // TODO: This assumes the advisory being posted 00:00 UTC,
+                    // which might or might not be a good idea
+                    let dt: NaiveDateTime = NaiveDate::from_ymd_opt(
+                        date.year() as i32,
+                        date.month(),
+                        date.day(),
+                    )
+                    .expect("could not parse advisory unix date")
+                    .and_hms_opt(0, 0, 0)
+                    .expect("could not create advisory timestamp");
+                    dt.and_utc().timestamp().into()
+                }),
+            ),
+            ("Advisory", "unixDateWithdrawn") => resolve_property_with(
+                contexts,
+                field_property!(as_advisory, metadata, {
+                    // TODO: This assumes the advisory being withdrawn 00:00 UTC,
                     // which might or might not be a good idea
                     match &metadata.withdrawn {
                         Some(date) => {
@@ -597,7 +2366,7 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                             .expect("could not parse advisory unix date")
                             .and_hms_opt(0, 0, 0)
                             .expect("could not create advisory timestamp");
-                            dt.timestamp().into()
+                            dt.and_utc().timestamp().into()
                         }
                         None => FieldValue::Null,
                     }
@@ -662,15 +2431,74 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                     }
                 }),
             ),
-            // ("Advisory", "cvss") => resolve_property_with(
-            //     contexts,
-            //     field_property!(as_advisory, metadata, {
-            //         match &metadata.cvss {
-            //             Some(_base) => todo!("enums not yet implemented"),
-            //             None => FieldValue::Null,
-            //         }
-            //     }),
-            // ),
+            ("Advisory", "currentVersionAffected") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_advisory().unwrap().current_version_affected().into()
+                })
+            }
+            ("Advisory", "recommendedVersion") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_advisory()
+                        .unwrap()
+                        .recommended_version()
+                        .map(|v| v.to_string())
+                        .into()
+                })
+            }
+            ("Advisory", "patchAvailable") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_advisory().unwrap().patch_available().into()
+                })
+            }
+            ("Advisory", "informational") => resolve_property_with(
+                contexts,
+                field_property!(as_advisory, metadata, {
+                    match &metadata.informational {
+                        Some(i) => FieldValue::String(i.to_string()),
+                        None => FieldValue::Null,
+                    }
+                }),
+            ),
+            ("Advisory", "aliases") => resolve_property_with(
+                contexts,
+                field_property!(as_advisory, metadata, {
+                    metadata
+                        .aliases
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<String>>()
+                        .into()
+                }),
+            ),
+            ("Advisory", "references") => resolve_property_with(
+                contexts,
+                field_property!(as_advisory, metadata, {
+                    metadata
+                        .references
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<String>>()
+                        .into()
+                }),
+            ),
+            ("Advisory", "cvssScore") => resolve_property_with(
+                contexts,
+                field_property!(as_advisory, metadata, {
+                    match &metadata.cvss {
+                        Some(base) => FieldValue::Float64(base.score().value()),
+                        None => FieldValue::Null,
+                    }
+                }),
+            ),
+            ("Advisory", "cvssVector") => resolve_property_with(
+                contexts,
+                field_property!(as_advisory, metadata, {
+                    match &metadata.cvss {
+                        Some(base) => FieldValue::String(base.to_string()),
+                        None => FieldValue::Null,
+                    }
+                }),
+            ),
             ("AffectedFunctionVersions", "functionPath") => {
                 resolve_property_with(contexts, |vertex| {
                     let afv = vertex.as_affected_function_versions().unwrap();
@@ -687,6 +2515,124 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                         .into()
                 })
             }
+            ("AuditSummary", "totalAdvisories") => resolve_property_with(
+                contexts,
+                field_property!(as_audit_summary, total_advisories),
+            ),
+            ("AuditSummary", "affectedPackages") => resolve_property_with(
+                contexts,
+                field_property!(as_audit_summary, affected_packages),
+            ),
+            ("AuditSummary", "criticalPackages") => resolve_property_with(
+                contexts,
+                field_property!(as_audit_summary, critical_packages),
+            ),
+            ("AuditSummary", "highPackages") => resolve_property_with(
+                contexts,
+                field_property!(as_audit_summary, high_packages),
+            ),
+            ("AuditSummary", "mediumPackages") => resolve_property_with(
+                contexts,
+                field_property!(as_audit_summary, medium_packages),
+            ),
+            ("AuditSummary", "lowPackages") => resolve_property_with(
+                contexts,
+                field_property!(as_audit_summary, low_packages),
+            ),
+            ("AuditSummary", "unscoredPackages") => resolve_property_with(
+                contexts,
+                field_property!(as_audit_summary, unscored_packages),
+            ),
+            ("AuditSummary", "unmaintainedWarnings") => resolve_property_with(
+                contexts,
+                field_property!(as_audit_summary, unmaintained_warnings),
+            ),
+            ("AuditSummary", "unsoundWarnings") => resolve_property_with(
+                contexts,
+                field_property!(as_audit_summary, unsound_warnings),
+            ),
+            ("SemverViolation", "lint") => resolve_property_with(
+                contexts,
+                field_property!(as_semver_violation, lint),
+            ),
+            ("SemverViolation", "description") => resolve_property_with(
+                contexts,
+                field_property!(as_semver_violation, description),
+            ),
+            ("SemverViolation", "level") => resolve_property_with(
+                contexts,
+                field_property!(as_semver_violation, level),
+            ),
+            ("SemverViolation", "reference") => resolve_property_with(
+                contexts,
+                field_property!(as_semver_violation, reference),
+            ),
+            ("SemverViolation", "requiredBump") => resolve_property_with(
+                contexts,
+                field_property!(as_semver_violation, required_bump),
+            ),
+            ("SemverViolation", "locations") => resolve_property_with(
+                contexts,
+                field_property!(as_semver_violation, locations),
+            ),
+            ("BinarySizeContribution", "bytes") => resolve_property_with(
+                contexts,
+                field_property!(as_binary_size_contribution, bytes),
+            ),
+            ("BinarySizeContribution", "percentageOfText") => {
+                resolve_property_with(
+                    contexts,
+                    field_property!(
+                        as_binary_size_contribution,
+                        percentage_of_text,
+                        { FieldValue::Float64(*percentage_of_text) }
+                    ),
+                )
+            }
+            ("BinarySizeContribution", "percentageOfFile") => {
+                resolve_property_with(
+                    contexts,
+                    field_property!(
+                        as_binary_size_contribution,
+                        percentage_of_file,
+                        { FieldValue::Float64(*percentage_of_file) }
+                    ),
+                )
+            }
+            ("ClippyDiagnostics", "warningCount") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_clippy_diagnostics().unwrap().count("warning").into()
+                })
+            }
+            ("ClippyDiagnostics", "errorCount") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_clippy_diagnostics().unwrap().count("error").into()
+                })
+            }
+            ("ClippyLint", "name") => resolve_property_with(
+                contexts,
+                field_property!(as_clippy_lint, name),
+            ),
+            ("ClippyLint", "level") => resolve_property_with(
+                contexts,
+                field_property!(as_clippy_lint, level),
+            ),
+            ("ClippyLint", "count") => resolve_property_with(
+                contexts,
+                field_property!(as_clippy_lint, count),
+            ),
+            ("FutureIncompatLint", "lint") => resolve_property_with(
+                contexts,
+                field_property!(as_future_incompat_lint, lint),
+            ),
+            ("FutureIncompatLint", "message") => resolve_property_with(
+                contexts,
+                field_property!(as_future_incompat_lint, message),
+            ),
+            ("FutureIncompatLint", "rendered") => resolve_property_with(
+                contexts,
+                field_property!(as_future_incompat_lint, rendered),
+            ),
             ("GeigerUnsafety", "forbidsUnsafe") => resolve_property_with(
                 contexts,
                 field_property!(as_geiger_unsafety, forbids_unsafe),
@@ -711,62 +2657,265 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                     FieldValue::Float64(percentage)
                 })
             }
-            ("LanguageCodeStats" | "LanguageBlob", "language") => {
-                resolve_property_with(
-                    contexts,
-                    resolve_code_stats!(language, String),
-                )
-            }
-            ("LanguageCodeStats" | "LanguageBlob", "files") => {
+            (
+                "CodeStats" | "LanguageCodeStats" | "LanguageBlob",
+                "language",
+            ) => resolve_property_with(
+                contexts,
+                resolve_code_stats!(language, String),
+            ),
+            ("CodeStats" | "LanguageCodeStats" | "LanguageBlob", "files") => {
                 resolve_property_with(contexts, resolve_code_stats!(files))
             }
-            ("LanguageCodeStats" | "LanguageBlob", "lines") => {
+            ("CodeStats" | "LanguageCodeStats" | "LanguageBlob", "lines") => {
                 resolve_property_with(contexts, resolve_code_stats!(lines))
             }
-            ("LanguageCodeStats" | "LanguageBlob", "blanks") => {
+            ("CodeStats" | "LanguageCodeStats" | "LanguageBlob", "blanks") => {
                 resolve_property_with(contexts, resolve_code_stats!(blanks))
             }
-            ("LanguageCodeStats" | "LanguageBlob", "code") => {
+            ("CodeStats" | "LanguageCodeStats" | "LanguageBlob", "code") => {
                 resolve_property_with(contexts, resolve_code_stats!(code))
             }
-            ("LanguageCodeStats" | "LanguageBlob", "comments") => {
-                resolve_property_with(contexts, resolve_code_stats!(comments))
-            }
-            ("LanguageCodeStats" | "LanguageBlob", "commentsToCode") => {
-                resolve_property_with(
-                    contexts,
-                    resolve_code_stats!(comments_to_code, Float64),
-                )
-            }
+            (
+                "CodeStats" | "LanguageCodeStats" | "LanguageBlob",
+                "comments",
+            ) => resolve_property_with(contexts, resolve_code_stats!(comments)),
+            (
+                "CodeStats" | "LanguageCodeStats" | "LanguageBlob",
+                "commentsToCode",
+            ) => resolve_property_with(
+                contexts,
+                resolve_code_stats!(comments_to_code, Float64),
+            ),
             ("LanguageCodeStats", "inaccurate") => resolve_property_with(
                 contexts,
                 accessor_property!(as_language_code_stats, inaccurate),
             ),
-            (t, p) => {
-                unreachable!("unreachable property combination: {t}, {p}")
+            ("GitSource", "url") => resolve_property_with(
+                contexts,
+                field_property!(as_git_source, url),
+            ),
+            ("GitSource", "rev") => resolve_property_with(
+                contexts,
+                field_property!(as_git_source, rev),
+            ),
+            ("GitSource", "branch") => resolve_property_with(
+                contexts,
+                field_property!(as_git_source, branch),
+            ),
+            ("GitSource", "tag") => resolve_property_with(
+                contexts,
+                field_property!(as_git_source, tag),
+            ),
+            ("LicenseGroup", "license") => resolve_property_with(
+                contexts,
+                field_property!(as_license_group, license),
+            ),
+            ("LicenseGroup", "packageCount") => resolve_property_with(
+                contexts,
+                field_property!(as_license_group, packages, {
+                    (packages.len() as u64).into()
+                }),
+            ),
+            ("License", "spdxId") => resolve_property_with(
+                contexts,
+                field_property!(as_license, spdx_id),
+            ),
+            ("License", "isOsiApproved") => resolve_property_with(
+                contexts,
+                field_property!(as_license, is_osi_approved),
+            ),
+            ("License", "isFsfLibre") => resolve_property_with(
+                contexts,
+                field_property!(as_license, is_fsf_libre),
+            ),
+            ("License", "isCopyleft") => resolve_property_with(
+                contexts,
+                field_property!(as_license, is_copyleft),
+            ),
+            ("LicenseFile", "fileName") => resolve_property_with(
+                contexts,
+                field_property!(as_license_file, file_name),
+            ),
+            ("LicenseFile", "path") => resolve_property_with(
+                contexts,
+                field_property!(as_license_file, path),
+            ),
+            ("LicenseFile", "contentPreview") => resolve_property_with(
+                contexts,
+                field_property!(as_license_file, content_preview),
+            ),
+            ("LicenseFile", "isDeclared") => resolve_property_with(
+                contexts,
+                field_property!(as_license_file, is_declared),
+            ),
+            ("BuildScript", "path") => resolve_property_with(contexts, |v| {
+                v.as_build_script().unwrap().relative_path().into()
+            }),
+            ("BuildScript", "sourceFiles") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_build_script().unwrap().relative_sources().into()
+                })
             }
-        }
+            ("Target", "name") => resolve_property_with(
+                contexts,
+                field_property!(as_target, name),
+            ),
+            ("Target", "kind") => resolve_property_with(
+                contexts,
+                field_property!(as_target, kind),
+            ),
+            ("Target", "crateTypes") => resolve_property_with(
+                contexts,
+                field_property!(as_target, crate_types),
+            ),
+            ("Target", "srcPath") => resolve_property_with(
+                contexts,
+                field_property!(as_target, src_path, {
+                    src_path.to_string().into()
+                }),
+            ),
+            ("Target", "requiredFeatures") => resolve_property_with(
+                contexts,
+                field_property!(as_target, required_features),
+            ),
+            ("Target", "edition") => resolve_property_with(
+                contexts,
+                field_property!(as_target, edition, {
+                    edition.as_str().into()
+                }),
+            ),
+            ("DependencyRequirement", "name") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_dependency_requirement().unwrap().name().into()
+                })
+            }
+            ("DependencyRequirement", "versionReq") => {
+                resolve_property_with(contexts, |v| {
+                    let requirement = v.as_dependency_requirement().unwrap();
+                    requirement.dependency.req.to_string().into()
+                })
+            }
+            ("DependencyRequirement", "kind") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_dependency_requirement().unwrap().kind().into()
+                })
+            }
+            ("DependencyRequirement", "optional") => {
+                resolve_property_with(contexts, |v| {
+                    let requirement = v.as_dependency_requirement().unwrap();
+                    requirement.dependency.optional.into()
+                })
+            }
+            ("DependencyRequirement", "usesDefaultFeatures") => {
+                resolve_property_with(contexts, |v| {
+                    let requirement = v.as_dependency_requirement().unwrap();
+                    requirement.dependency.uses_default_features.into()
+                })
+            }
+            ("DependencyRequirement", "features") => {
+                resolve_property_with(contexts, |v| {
+                    let requirement = v.as_dependency_requirement().unwrap();
+                    requirement.dependency.features.clone().into()
+                })
+            }
+            ("DependencyRequirement", "renamedFrom") => {
+                resolve_property_with(contexts, |v| {
+                    v.as_dependency_requirement().unwrap().renamed_from().into()
+                })
+            }
+            ("DependencyRequirement", "targetCfg") => {
+                resolve_property_with(contexts, |v| {
+                    let requirement = v.as_dependency_requirement().unwrap();
+                    requirement
+                        .dependency
+                        .target
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .into()
+                })
+            }
+            ("Feature", "name") => resolve_property_with(
+                contexts,
+                field_property!(as_feature, name),
+            ),
+            ("Feature", "enabled") => resolve_property_with(
+                contexts,
+                field_property!(as_feature, enabled),
+            ),
+            ("BundledBinary", "path") => resolve_property_with(
+                contexts,
+                field_property!(as_bundled_binary, path),
+            ),
+            ("BundledBinary", "extension") => resolve_property_with(
+                contexts,
+                field_property!(as_bundled_binary, extension),
+            ),
+            ("BundledBinary", "sizeBytes") => resolve_property_with(
+                contexts,
+                field_property!(as_bundled_binary, size_bytes),
+            ),
+            ("TyposquatCandidate", "popularCrate") => resolve_property_with(
+                contexts,
+                field_property!(as_typosquat_candidate, popular_crate),
+            ),
+            ("TyposquatCandidate", "popularityRank") => resolve_property_with(
+                contexts,
+                field_property!(as_typosquat_candidate, popularity_rank),
+            ),
+            ("TyposquatCandidate", "distance") => resolve_property_with(
+                contexts,
+                field_property!(as_typosquat_candidate, distance),
+            ),
+            ("SuspiciousFile", "path") => resolve_property_with(
+                contexts,
+                field_property!(as_suspicious_file, path),
+            ),
+            ("SuspiciousFile", "kind") => resolve_property_with(
+                contexts,
+                field_property!(as_suspicious_file, kind),
+            ),
+            ("SuspiciousFile", "detectedBy") => resolve_property_with(
+                contexts,
+                field_property!(as_suspicious_file, detected_by),
+            ),
+            ("SuspiciousFile", "sizeBytes") => resolve_property_with(
+                contexts,
+                field_property!(as_suspicious_file, size_bytes),
+            ),
+            _ => return Err(contexts),
+        };
+        Ok(resolved)
     }
 
-    fn resolve_neighbors(
+    #[allow(clippy::type_complexity)]
+    fn neighbors(
         &self,
-        contexts: ContextIterator<'a, Self::Vertex>,
+        contexts: ContextIterator<'static, Vertex>,
         type_name: &str,
         edge_name: &str,
         parameters: &EdgeParameters,
-    ) -> ContextOutcomeIterator<
-        'a,
-        Self::Vertex,
-        VertexIterator<'a, Self::Vertex>,
+    ) -> Result<
+        ContextOutcomeIterator<
+            'static,
+            Vertex,
+            VertexIterator<'static, Vertex>,
+        >,
+        ContextIterator<'static, Vertex>,
     > {
         // These are all possible neighboring vertexes, i.e. parts of a vertex
         // that are not scalar values (`FieldValue`)
-        match (type_name, edge_name) {
+        let resolved = match (type_name, edge_name) {
             ("Package", "dependencies") => {
                 // Must be done here to ensure they live long enough (and are
                 // not lazily evaluated)
                 let packages = self.packages();
-                let direct_dependencies = self.direct_dependencies();
+                let kind = parameters.get("kind").unwrap().as_str().unwrap();
+                let kind = util::parse_dependency_kind(kind).unwrap_or_else(|| {
+                    panic!("parameter error: {kind} is not a valid dependency kind")
+                });
+                let direct_dependencies =
+                    self.direct_dependencies_of_kind(kind);
                 resolve_neighbors_with(contexts, move |vertex| {
                     // This is in fact a Package, otherwise it would be `None`
                     // First get all dependencies, and then resolve their package
@@ -779,14 +2928,256 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                     )
                 })
             }
-            ("Package", "cratesIo") => resolve_neighbors_with(contexts, |v| {
-                let package = v.as_package().unwrap();
-                Box::new(std::iter::once(Vertex::CratesIoStats(
-                    NameVersion::from(package),
-                )))
-            }),
+            ("Package", "dependents") => {
+                let packages = self.packages();
+                let dependents = self.dependents();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    Self::get_dependencies(
+                        Rc::clone(&packages),
+                        &dependents,
+                        &package.id,
+                    )
+                })
+            }
+            ("Package", "pathFromRoot") => {
+                let packages = self.packages();
+                let paths = self.paths_from_root();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let path = paths
+                        .get(&package.id)
+                        .map(|ids| {
+                            ids.iter()
+                                .map(|id| {
+                                    Vertex::Package(Rc::clone(&packages[id]))
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+                    Box::new(path.into_iter())
+                })
+            }
+            ("Package", "duplicateVersions") => {
+                let packages = self.packages();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let mut duplicates = packages
+                        .values()
+                        .filter(|p| {
+                            p.name == package.name && p.id != package.id
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    duplicates.sort_by(|a, b| a.version.cmp(&b.version));
+                    Box::new(duplicates.into_iter().map(Vertex::Package))
+                })
+            }
+            ("Package", "dependencyRequirements") => {
+                let kind = parameters.get("kind").unwrap().as_str().unwrap();
+                let kind = util::parse_dependency_kind(kind).unwrap_or_else(|| {
+                    panic!("parameter error: {kind} is not a valid dependency kind")
+                });
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let requirements =
+                        get_dependency_requirements(package, kind);
+                    Box::new(
+                        requirements
+                            .into_iter()
+                            .map(|r| Vertex::DependencyRequirement(Rc::new(r))),
+                    )
+                })
+            }
+            ("DependencyRequirement", "resolvedPackage") => {
+                let metadata = Rc::clone(&self.metadata);
+                let packages = self.packages();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let requirement =
+                        vertex.as_dependency_requirement().unwrap();
+                    let resolved = util::resolve_dependency(
+                        &metadata,
+                        &packages,
+                        &requirement.package,
+                        &requirement.dependency,
+                    );
+                    Box::new(resolved.into_iter().map(Vertex::Package))
+                })
+            }
+            ("Package", "gitSource") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let source = GitSource::of(package);
+                    Box::new(
+                        source
+                            .into_iter()
+                            .map(|s| Vertex::GitSource(Rc::new(s))),
+                    )
+                })
+            }
+            ("Package", "licenseExpression") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    Box::new(
+                        get_licenses(package)
+                            .into_iter()
+                            .map(|l| Vertex::License(Rc::new(l))),
+                    )
+                })
+            }
+            ("LicenseGroup", "licenses") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let group = vertex.as_license_group().unwrap();
+                    Box::new(
+                        group
+                            .license
+                            .as_deref()
+                            .map(parse_license_expression)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|l| Vertex::License(Rc::new(l))),
+                    )
+                })
+            }
+            ("LicenseGroup", "packages") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let group = vertex.as_license_group().unwrap();
+                    let res = group
+                        .packages
+                        .iter()
+                        .map(|p| Vertex::Package(Rc::clone(p)))
+                        .collect::<Vec<_>>() // Collect OK: Clone the references
+                        .into_iter();
+
+                    Box::new(res)
+                })
+            }
+            ("Package", "licenseFiles") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    Box::new(
+                        get_license_files(package)
+                            .into_iter()
+                            .map(|l| Vertex::LicenseFile(Rc::new(l))),
+                    )
+                })
+            }
+            ("Package", "buildScript") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    match BuildScript::from_package(package) {
+                        Some(b) => Box::new(std::iter::once(
+                            Vertex::BuildScript(Rc::new(b)),
+                        )),
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("BuildScript", "codeStats") => {
+                let config = tokei_config(parameters);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let build_script = vertex.as_build_script().unwrap();
+                    let code_stats = get_code_stats(
+                        &build_script.package_root,
+                        &[],
+                        Some(build_script.relative_sources()),
+                        &config,
+                    );
+                    Box::new(
+                        code_stats
+                            .into_iter()
+                            .map(|cs| Vertex::LanguageCodeStats(Rc::new(cs))),
+                    )
+                })
+            }
+            ("BuildScript", "geiger") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let build_script = vertex.as_build_script().unwrap();
+                    Box::new(std::iter::once(Vertex::GeigerCategories(
+                        build_script.unsafety(),
+                    )))
+                })
+            }
+            ("Package", "targets") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let targets = package
+                        .targets
+                        .iter()
+                        .map(|t| Vertex::Target(Rc::new(t.clone())))
+                        .collect::<Vec<_>>();
+                    Box::new(targets.into_iter())
+                })
+            }
+            ("Package", "features") => {
+                let metadata = Rc::clone(&self.metadata);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let features = get_features(&metadata, package);
+                    Box::new(
+                        features
+                            .into_iter()
+                            .map(|f| Vertex::Feature(Rc::new(f))),
+                    )
+                })
+            }
+            ("Feature", "enablesDependencies") => {
+                let metadata = Rc::clone(&self.metadata);
+                let packages = self.packages();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let feature = vertex.as_feature().unwrap();
+                    let dependencies =
+                        get_enabled_dependencies(&metadata, &packages, feature);
+                    Box::new(dependencies.into_iter().map(Vertex::Package))
+                })
+            }
+            ("Feature", "enablesFeatures") => {
+                let metadata = Rc::clone(&self.metadata);
+                let packages = self.packages();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let feature = vertex.as_feature().unwrap();
+                    let features =
+                        get_enabled_features(&metadata, &packages, feature);
+                    Box::new(
+                        features
+                            .into_iter()
+                            .map(|f| Vertex::Feature(Rc::new(f))),
+                    )
+                })
+            }
+            ("Package", "cratesIo") => resolve_neighbors_with(contexts, |v| {
+                let package = v.as_package().unwrap();
+                // Packages from other sources may share the name of an
+                // unrelated crate on crates.io, so they get no stats
+                let nv = (SourceKind::of(package) == SourceKind::CratesIo)
+                    .then(|| NameVersion::from(package));
+                Box::new(std::iter::once(Vertex::CratesIoStats(nv)))
+            }),
+            ("CratesIoStats", "owners") => {
+                let crates_io_client = self.crates_io_client();
+                resolve_neighbors_with(contexts, move |v| {
+                    let Some(nv) = v.as_crates_io_stats().unwrap() else {
+                        return Box::new(std::iter::empty());
+                    };
+                    let owners = crates_io_client
+                        .borrow_mut()
+                        .owners(&nv.name)
+                        .map(|owners| {
+                            owners
+                                .iter()
+                                .map(|o| {
+                                    Vertex::CratesIoOwner(Rc::new(o.clone()))
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+                    Box::new(owners.into_iter())
+                })
+            }
             ("Package", "repository") => {
                 let gh_client = self.gh_client();
+                let gitlab_client = self.gitlab_client();
+                let gitea_client = self.gitea_client();
                 resolve_neighbors_with(contexts, move |v| {
                     // Must be package
                     let package = v.as_package().unwrap();
@@ -794,7 +3185,9 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                         Some(url) => Box::new(std::iter::once(
                             Self::get_repository_from_url(
                                 url,
-                                &Rc::clone(&gh_client),
+                                &gh_client,
+                                &gitlab_client,
+                                &gitea_client,
                             ),
                         )),
                         None => Box::new(std::iter::empty()),
@@ -808,6 +3201,14 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                 let arch = parameters.get("arch").cloned();
                 let os = parameters.get("os").cloned();
                 let min_severity = parameters.get("minSeverity").cloned();
+                let only_affecting_current_version = parameters
+                    .get("onlyAffectingCurrentVersion")
+                    .and_then(FieldValue::as_bool)
+                    .unwrap_or(false);
+                let informational_kinds: Option<Vec<String>> = parameters
+                    .get("informationalKinds")
+                    .and_then(|k| k.as_vec_with(FieldValue::as_str))
+                    .map(|v| v.into_iter().map(String::from).collect());
 
                 resolve_neighbors_with(contexts, move |vertex| {
                     let package = vertex.as_package().unwrap();
@@ -860,31 +3261,340 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                             min_severity,
                         )
                         .iter()
-                        .map(|a| Vertex::Advisory(Rc::new((*a).clone())))
+                        // Vulnerabilities are always included
+                        .filter(|a| {
+                            match (
+                                &a.metadata.informational,
+                                &informational_kinds,
+                            ) {
+                                (Some(i), Some(kinds)) => {
+                                    kinds.iter().any(|k| k == i.as_str())
+                                }
+                                _ => true,
+                            }
+                        })
+                        .map(|a| {
+                            PackageAdvisory::new(
+                                (*a).clone(),
+                                package.version.clone(),
+                            )
+                        })
+                        .filter(|a| {
+                            !only_affecting_current_version
+                                || a.current_version_affected()
+                        })
+                        .map(|a| Vertex::Advisory(Rc::new(a)))
                         .collect::<Vec<_>>() // Collect OK: We just convert back to vec
                         .into_iter();
 
                     Box::new(res)
                 })
             }
+            ("Package", "osvAdvisories") => {
+                let osv_client = Rc::clone(&self.osv_client);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return Box::new(std::iter::empty());
+                    }
+
+                    let vulnerabilities = osv_client
+                        .borrow_mut()
+                        .vulnerabilities(&NameVersion::from(package))
+                        .unwrap_or_default();
+                    let res = vulnerabilities
+                        .iter()
+                        .map(|v| Vertex::OsvAdvisory(Rc::new(v.clone())))
+                        .collect::<Vec<_>>() // Collect OK: Release the borrow
+                        .into_iter();
+
+                    Box::new(res)
+                })
+            }
+            ("Package", "crevReviews") => {
+                let crev_client = Rc::clone(&self.crev_client);
+                let all_versions = parameters
+                    .get("allVersions")
+                    .and_then(FieldValue::as_bool)
+                    .unwrap_or(false);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let reviews = crev_client
+                        .borrow_mut()
+                        .reviews(&package.name)
+                        .unwrap_or_default();
+                    let version = package.version.to_string();
+                    let res = reviews
+                        .iter()
+                        .filter(|r| all_versions || r.version == version)
+                        .map(|r| Vertex::CrevReview(Rc::new(r.clone())))
+                        .collect::<Vec<_>>() // Collect OK: Release the borrow
+                        .into_iter();
+
+                    Box::new(res)
+                })
+            }
+            ("Package", "binarySizeContribution") => {
+                let bloat_client = self.bloat_client();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    match bloat_client.contribution(package) {
+                        Some(c) => Box::new(std::iter::once(
+                            Vertex::BinarySizeContribution(c),
+                        )),
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("Package", "clippy") => {
+                let clippy_client = Rc::clone(&self.clippy_client);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let diagnostics = clippy_client.borrow_mut().diagnostics(
+                        &NameVersion::from(package),
+                        package.manifest_path.as_std_path(),
+                    );
+                    match diagnostics {
+                        Some(d) => Box::new(std::iter::once(
+                            Vertex::ClippyDiagnostics(d),
+                        )),
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("ClippyDiagnostics", "lints") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let diagnostics = vertex.as_clippy_diagnostics().unwrap();
+                    let res = diagnostics
+                        .lints
+                        .iter()
+                        .map(|l| Vertex::ClippyLint(Rc::new(l.clone())))
+                        .collect::<Vec<_>>() // Collect OK: Release the borrow
+                        .into_iter();
+
+                    Box::new(res)
+                })
+            }
+            ("Package", "futureIncompat") => {
+                let future_incompat = self.future_incompat();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let lints = future_incompat
+                        .get(&(package.name.clone(), package.version.clone()))
+                        .map(|lints| {
+                            lints
+                                .iter()
+                                .map(|l| {
+                                    Vertex::FutureIncompatLint(Rc::clone(l))
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+                    Box::new(lints.into_iter())
+                })
+            }
+            ("Package", "semverViolations") => {
+                let semver_checks_client =
+                    Rc::clone(&self.semver_checks_client);
+                let workspace_members = self
+                    .metadata
+                    .workspace_members
+                    .iter()
+                    .cloned()
+                    .collect::<HashSet<_>>();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    if !workspace_members.contains(&package.id) {
+                        return Box::new(std::iter::empty());
+                    }
+
+                    let violations = semver_checks_client
+                        .borrow_mut()
+                        .violations(
+                            package.manifest_path.as_std_path(),
+                            &package.name,
+                        )
+                        .unwrap_or_default();
+                    let res = violations
+                        .iter()
+                        .map(|v| Vertex::SemverViolation(Rc::new(v.clone())))
+                        .collect::<Vec<_>>() // Collect OK: Release the borrow
+                        .into_iter();
+
+                    Box::new(res)
+                })
+            }
+            ("Package", "denyPolicy") => {
+                let deny_config = self.deny_config();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let Some(config) = &deny_config else {
+                        return Box::new(std::iter::empty());
+                    };
+                    let package = vertex.as_package().unwrap();
+                    let evaluation = config.evaluate(
+                        &package.name,
+                        &package.version,
+                        package.license.as_deref(),
+                    );
+                    Box::new(std::iter::once(Vertex::DenyPolicy(Rc::new(
+                        evaluation,
+                    ))))
+                })
+            }
+            ("Package", "vetAudits") => {
+                let vet_audits = self.vet_audits();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let audits = vet_audits
+                        .get(&package.name)
+                        .map(|audits| {
+                            audits
+                                .iter()
+                                .map(|a| {
+                                    Vertex::VetAudit(Rc::new(PackageVetAudit {
+                                        audit: Rc::clone(a),
+                                        covers_current_version: a
+                                            .covers(&package.version),
+                                    }))
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+                    Box::new(audits.into_iter())
+                })
+            }
+            ("Package", "ghsaAdvisories") => {
+                let ghsa_client = Rc::clone(&self.ghsa_client);
+                let only_affecting_current_version = parameters
+                    .get("onlyAffectingCurrentVersion")
+                    .and_then(FieldValue::as_bool)
+                    .unwrap_or(false);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    if SourceKind::of(package) != SourceKind::CratesIo {
+                        return Box::new(std::iter::empty());
+                    }
+
+                    let vulnerabilities = ghsa_client
+                        .borrow_mut()
+                        .vulnerabilities(&NameVersion::from(package))
+                        .unwrap_or_default();
+                    let res = vulnerabilities
+                        .iter()
+                        .map(|v| PackageGhsaVulnerability {
+                            vulnerability: v.clone(),
+                            current_version_affected: v
+                                .affects(&package.version),
+                        })
+                        .filter(|v| {
+                            !only_affecting_current_version
+                                || v.current_version_affected == Some(true)
+                        })
+                        .map(|v| Vertex::GhsaAdvisory(Rc::new(v)))
+                        .collect::<Vec<_>>() // Collect OK: Release the borrow
+                        .into_iter();
+
+                    Box::new(res)
+                })
+            }
+            ("Package", "docsRs") => {
+                let docs_rs_client = Rc::clone(&self.docs_rs_client);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let status = docs_rs_client
+                        .borrow_mut()
+                        .status(&NameVersion::from(package));
+                    match status {
+                        Some(s) => {
+                            Box::new(std::iter::once(Vertex::DocsRsBuild(
+                                Rc::new(DocsRsBuild::new(package, &s)),
+                            )))
+                        }
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("Package", "depsDev") => {
+                let deps_dev_client = Rc::clone(&self.deps_dev_client);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let info = deps_dev_client
+                        .borrow_mut()
+                        .info(&NameVersion::from(package));
+                    match info {
+                        Some(i) => {
+                            Box::new(std::iter::once(Vertex::DepsDev(i)))
+                        }
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
             ("Package", "geiger") => {
                 let geiger_client = self.geiger_client();
                 resolve_neighbors_with(contexts, move |vertex| {
                     let package = vertex.as_package().unwrap();
-                    let gid = package.into();
+                    let gid = NameVersion::from(package);
                     let unsafety = geiger_client.unsafety(&gid);
 
                     if let Some(u) = unsafety {
                         Box::new(std::iter::once(Vertex::GeigerUnsafety(u)))
                     } else {
                         eprintln!(
-                            "failed to resolve geiger unsafety for {} {}",
-                            package.name, package.version
+                            "failed to resolve geiger unsafety for {gid}"
                         );
                         Box::new(std::iter::empty())
                     }
                 })
             }
+            ("Package", "bundledBinaries") => {
+                let min_size =
+                    non_negative_parameter(parameters, "minSizeBytes")
+                        .unwrap_or(DEFAULT_MIN_BINARY_SIZE);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let binaries = get_bundled_binaries(
+                        &util::local_package_path(package),
+                        min_size,
+                    );
+                    Box::new(
+                        binaries
+                            .into_iter()
+                            .map(|b| Vertex::BundledBinary(Rc::new(b))),
+                    )
+                })
+            }
+            ("Package", "typosquatCandidates") => {
+                let max_distance =
+                    non_negative_parameter(parameters, "maxDistance")
+                        .unwrap_or(DEFAULT_MAX_TYPOSQUAT_DISTANCE);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let candidates =
+                        get_typosquat_candidates(&package.name, max_distance);
+                    Box::new(
+                        candidates
+                            .into_iter()
+                            .map(|c| Vertex::TyposquatCandidate(Rc::new(c))),
+                    )
+                })
+            }
+            ("Package", "suspiciousFiles") => {
+                let min_blob_size =
+                    non_negative_parameter(parameters, "minBlobSizeBytes")
+                        .unwrap_or(DEFAULT_MIN_BLOB_SIZE);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let package = vertex.as_package().unwrap();
+                    let files = get_suspicious_files(
+                        &util::local_package_path(package),
+                        min_blob_size,
+                    );
+                    Box::new(
+                        files
+                            .into_iter()
+                            .map(|f| Vertex::SuspiciousFile(Rc::new(f))),
+                    )
+                })
+            }
             ("Package", "codeStats") => {
                 // Parameters verified by `trustfall` and schema
                 let ignored_paths =
@@ -896,33 +3606,7 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                         v.into_iter().map(String::from).collect::<Vec<String>>()
                     });
 
-                // Either they are passed and _must_ be a bool according to
-                // schema, or they are undefined
-                let get_stat_bool_param =
-                    |pname| parameters.get(pname).and_then(FieldValue::as_bool);
-
-                let config = tokei::Config {
-                        columns: None, // Unused for library
-                        hidden: get_stat_bool_param("hidden"),
-                        no_ignore: get_stat_bool_param("noIgnore"),
-                        no_ignore_parent: get_stat_bool_param("noIgnoreParent"),
-                        no_ignore_dot: get_stat_bool_param("noIgnoreDot"),
-                        no_ignore_vcs: get_stat_bool_param("noIgnoreVcs"),
-                        treat_doc_strings_as_comments: get_stat_bool_param(
-                            "treatDocStringsAsComments",
-                        ),
-                        types: parameters.get("types").and_then(|t| {
-                            t.as_vec_with(|i| {
-                                let language_str = i.as_str().unwrap();
-                                let lt = tokei::LanguageType::from_str(language_str).unwrap_or_else(|_| {
-                                    panic!("parameter error: {language_str} is not a valid language name");
-                                });
-                                Some(lt)
-                            })
-                        })
-                            ,
-                        sort: None, // TODO: Not implemented
-                    };
+                let config = tokei_config(parameters);
 
                 resolve_neighbors_with(contexts, move |vertex| {
                     let package = vertex.as_package().unwrap();
@@ -970,6 +3654,168 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                     }
                 })
             }
+            ("GitLabRepository", "owner") => {
+                resolve_neighbors_with(contexts, move |vertex| {
+                    // Must be GitLabRepository according to guarantees from Trustfall
+                    let gl_repo = vertex.as_git_lab_repository().unwrap();
+                    Box::new(std::iter::once(Vertex::GitLabNamespace(Rc::new(
+                        gl_repo.namespace.clone(),
+                    ))))
+                })
+            }
+            ("GitHubUser", "organizations") => {
+                let gh_client = self.gh_client();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let user = vertex.as_git_hub_user().unwrap();
+                    let organizations =
+                        gh_client.borrow_mut().get_organizations(&user.login);
+                    match organizations {
+                        Some(o) => Box::new(
+                            o.iter()
+                                .map(|o| {
+                                    Vertex::GitHubOrganization(Rc::new(
+                                        o.clone(),
+                                    ))
+                                })
+                                .collect::<Vec<_>>()
+                                .into_iter(),
+                        ),
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("GitHubRepository", "scorecard") => {
+                let scorecard_client = Rc::clone(&self.scorecard_client);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let gh_repo = vertex.as_git_hub_repository().unwrap();
+                    let scorecard = scorecard_client
+                        .borrow_mut()
+                        .scorecard(&gh_repo.full_name);
+                    match scorecard {
+                        Some(s) => {
+                            Box::new(std::iter::once(Vertex::Scorecard(s)))
+                        }
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("GitHubRepository", "contributors") => {
+                let gh_client = self.gh_client();
+                let first = parameters
+                    .get("first")
+                    .and_then(FieldValue::as_usize)
+                    .expect("first parameter must be a non-negative integer");
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let gh_repo = vertex.as_git_hub_repository().unwrap();
+                    let contributors = gh_client.borrow_mut().get_contributors(
+                        &GitHubRepositoryId::from(&**gh_repo),
+                    );
+                    match contributors {
+                        Some(c) => Box::new(
+                            GitHubContributor::from_contributors(&c)
+                                .into_iter()
+                                .take(first)
+                                .map(|c| Vertex::GitHubContributor(Rc::new(c)))
+                                .collect::<Vec<_>>()
+                                .into_iter(),
+                        ),
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("GitHubRepository", "releases") => {
+                let gh_client = self.gh_client();
+                let limit = parameters
+                    .get("limit")
+                    .and_then(FieldValue::as_usize)
+                    .expect("limit parameter must be a non-negative integer");
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let gh_repo = vertex.as_git_hub_repository().unwrap();
+                    let releases = gh_client
+                        .borrow_mut()
+                        .get_releases(&GitHubRepositoryId::from(&**gh_repo));
+                    match releases {
+                        Some(r) => Box::new(
+                            r.iter()
+                                .take(limit)
+                                .map(|r| {
+                                    Vertex::GitHubRelease(Rc::new(r.clone()))
+                                })
+                                .collect::<Vec<_>>()
+                                .into_iter(),
+                        ),
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("GitHubRepository", "workflows") => {
+                let gh_client = self.gh_client();
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let gh_repo = vertex.as_git_hub_repository().unwrap();
+                    let workflows = gh_client
+                        .borrow_mut()
+                        .get_workflows(&GitHubRepositoryId::from(&**gh_repo));
+                    match workflows {
+                        Some(w) => Box::new(
+                            w.iter()
+                                .map(|w| {
+                                    Vertex::GitHubWorkflow(Rc::new(w.clone()))
+                                })
+                                .collect::<Vec<_>>()
+                                .into_iter(),
+                        ),
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("GitHubRepository", "issues") => {
+                let gh_client = self.gh_client();
+                let state =
+                    match parameters.get("state").unwrap().as_str().unwrap() {
+                        "open" => IssuesListState::Open,
+                        "closed" => IssuesListState::Closed,
+                        "all" => IssuesListState::All,
+                        s => panic!("unknown state parameter: {s}"),
+                    };
+                let labels: Option<Vec<String>> = parameters
+                    .get("labels")
+                    .and_then(|l| l.as_vec_with(FieldValue::as_str))
+                    .map(|v| v.into_iter().map(String::from).collect());
+                let first = parameters
+                    .get("first")
+                    .and_then(FieldValue::as_usize)
+                    .expect("first parameter must be a non-negative integer");
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let gh_repo = vertex.as_git_hub_repository().unwrap();
+                    let issues =
+                        gh_client.borrow_mut().get_issues_with_any_label(
+                            &GitHubRepositoryId::from(&**gh_repo),
+                            &state,
+                            labels.as_deref(),
+                        );
+                    match issues {
+                        Some(i) => Box::new(
+                            i.into_iter()
+                                .take(first)
+                                .map(|i| Vertex::GitHubIssue(Rc::new(i)))
+                                .collect::<Vec<_>>()
+                                .into_iter(),
+                        ),
+                        None => Box::new(std::iter::empty()),
+                    }
+                })
+            }
+            ("Scorecard", "checks") => {
+                resolve_neighbors_with(contexts, |vertex| {
+                    let scorecard = vertex.as_scorecard().unwrap();
+                    let checks = scorecard
+                        .checks
+                        .iter()
+                        .map(|c| Vertex::ScorecardCheck(Rc::new(c.clone())))
+                        .collect::<Vec<_>>();
+                    Box::new(checks.into_iter())
+                })
+            }
             ("Advisory", "affectedFunctions") => {
                 resolve_neighbors_with(contexts, |vertex| {
                     let advisory = vertex.as_advisory().unwrap();
@@ -984,6 +3830,42 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                     }
                 })
             }
+            ("Advisory", "cve") => {
+                let nvd_client = Rc::clone(&self.nvd_client);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let advisory = vertex.as_advisory().unwrap();
+                    let records = advisory
+                        .metadata
+                        .aliases
+                        .iter()
+                        .filter(|a| a.is_cve())
+                        .filter_map(|a| {
+                            nvd_client.borrow_mut().get_cve(a.as_str())
+                        })
+                        .map(Vertex::CveRecord)
+                        .collect::<Vec<_>>(); // Collect OK: Release the borrow
+                    Box::new(records.into_iter())
+                })
+            }
+            ("Advisory", "epss") => {
+                let epss_client = Rc::clone(&self.epss_client);
+                resolve_neighbors_with(contexts, move |vertex| {
+                    let advisory = vertex.as_advisory().unwrap();
+                    let cves = advisory
+                        .metadata
+                        .aliases
+                        .iter()
+                        .filter(|a| a.is_cve())
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>();
+                    if cves.is_empty() {
+                        return Box::new(std::iter::empty());
+                    }
+
+                    let scores = epss_client.borrow_mut().scores(&cves);
+                    Box::new(scores.into_iter().map(Vertex::EpssScore))
+                })
+            }
             ("GeigerUnsafety", "used") => {
                 resolve_neighbors_with(contexts, |vertex| {
                     let unsafety = vertex.as_geiger_unsafety().unwrap();
@@ -1094,21 +3976,114 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                     )
                 })
             }
-            (t, e) => {
-                unreachable!("unreachable neighbor combination: {t}, {e}")
-            }
+            _ => return Err(contexts),
+        };
+        Ok(resolved)
+    }
+}
+
+/// Panics due to a field in the schema without a resolver, which means that
+/// the schema and the adapter are out of sync
+///
+/// This is checked by the `adapter_resolves_whole_schema` test, so it should
+/// never happen in a release.
+fn missing_resolver(kind: &str, type_name: &str, field_name: &str) -> ! {
+    panic!(
+        "no resolver for {kind} {type_name}.{field_name}, see IndicateAdapter::missing_resolvers"
+    )
+}
+
+/// Stops an iterator once interrupted, so that a running query finishes
+/// early instead of waiting for expensive resolvers
+///
+/// `stopped_early` is set if any item is left out, so that results can be
+/// told apart from those of a query completed just before an interrupt.
+pub(crate) fn until_interrupted<I: Iterator>(
+    iter: I,
+    stopped_early: Rc<Cell<bool>>,
+) -> impl Iterator<Item = I::Item> {
+    iter.take_while(move |_| {
+        let interrupted = interrupt::interrupted();
+        if interrupted {
+            stopped_early.set(true);
+        }
+        !interrupted
+    })
+}
+
+impl Adapter<'static> for IndicateAdapter {
+    type Vertex = Vertex;
+
+    fn resolve_starting_vertices(
+        &self,
+        edge_name: &Arc<str>,
+        parameters: &EdgeParameters,
+        _resolve_info: &ResolveInfo,
+    ) -> VertexIterator<'static, Self::Vertex> {
+        let resolver =
+            Self::starting_vertices(edge_name).unwrap_or_else(|| {
+                missing_resolver("edge", "RootQuery", edge_name)
+            });
+        let skip = self.skip_starting_vertices.take();
+        let resolved = Rc::clone(&self.starting_vertices_resolved);
+        resolved.set(skip);
+        Box::new(
+            until_interrupted(
+                resolver(self, parameters).skip(skip),
+                self.stopped_early(),
+            )
+            .inspect(move |_| resolved.set(resolved.get() + 1)),
+        )
+    }
+
+    fn resolve_property(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &Arc<str>,
+        property_name: &Arc<str>,
+        _resolve_info: &ResolveInfo,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, FieldValue> {
+        let contexts: ContextIterator<'static, Vertex> =
+            Box::new(until_interrupted(contexts, self.stopped_early()));
+        if property_name.as_ref() == "__typename" {
+            return resolve_property_with(contexts, |v| v.typename().into());
         }
+        self.property(contexts, type_name, property_name)
+            .unwrap_or_else(|_| {
+                missing_resolver("property", type_name, property_name)
+            })
+    }
+
+    fn resolve_neighbors(
+        &self,
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &Arc<str>,
+        edge_name: &Arc<str>,
+        parameters: &EdgeParameters,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<
+        'static,
+        Self::Vertex,
+        VertexIterator<'static, Self::Vertex>,
+    > {
+        let contexts: ContextIterator<'static, Vertex> =
+            Box::new(until_interrupted(contexts, self.stopped_early()));
+        self.neighbors(contexts, type_name, edge_name, parameters)
+            .unwrap_or_else(|_| missing_resolver("edge", type_name, edge_name))
     }
 
     fn resolve_coercion(
         &self,
-        contexts: ContextIterator<'a, Self::Vertex>,
-        type_name: &str,
-        coerce_to_type: &str,
-    ) -> ContextOutcomeIterator<'a, Self::Vertex, bool> {
+        contexts: ContextIterator<'static, Self::Vertex>,
+        type_name: &Arc<str>,
+        coerce_to_type: &Arc<str>,
+        _resolve_info: &ResolveInfo,
+    ) -> ContextOutcomeIterator<'static, Self::Vertex, bool> {
+        let contexts: ContextIterator<'static, Vertex> =
+            Box::new(until_interrupted(contexts, self.stopped_early()));
         // Ensure lifetimes by cloning
-        let type_name = type_name.to_owned();
-        let coerce_to_type = coerce_to_type.to_owned();
+        let type_name = Arc::clone(type_name);
+        let coerce_to_type = Arc::clone(coerce_to_type);
         Box::new(
             contexts
                 .map(move |ctx| {
@@ -1118,8 +4093,8 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                     };
 
                     let can_coerce = match (
-                        type_name.as_str(),
-                        coerce_to_type.as_str()
+                        type_name.as_ref(),
+                        coerce_to_type.as_ref()
                     ) {
                         (_, "Repository") => {
                             current_vertex.as_repository().is_some()
@@ -1127,6 +4102,15 @@ impl<'a> BasicAdapter<'a> for IndicateAdapter {
                         (_, "GitHubRepository") => {
                             current_vertex.as_git_hub_repository().is_some()
                         }
+                        (_, "GitLabRepository") => {
+                            current_vertex.as_git_lab_repository().is_some()
+                        }
+                        (_, "GiteaRepository") => {
+                            current_vertex.as_gitea_repository().is_some()
+                        }
+                        (_, "SourcehutRepository") => {
+                            current_vertex.as_sourcehut_repository().is_some()
+                        }
                         (t1, t2) => {
                             unreachable!(
                                 "the coercion from {t1} to {t2} is unhandled but was attempted",
@@ -1,14 +1,41 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use cargo_metadata::{CargoOpt, Metadata};
 use once_cell::unsync::OnceCell;
 
 use crate::{
-    advisory::AdvisoryClient, crates_io::CratesIoClient, geiger::GeigerClient,
-    repo::github::GitHubClient, ManifestPath,
+    advisory::AdvisoryClient,
+    bloat::BloatClient,
+    changes::changed_packages_since,
+    clippy::ClippyClient,
+    crates_io::CratesIoClient,
+    crev::CrevClient,
+    deps_dev::DepsDevClient,
+    docs_rs::DocsRsClient,
+    epss::EpssClient,
+    geiger::GeigerClient,
+    ghsa::GhsaClient,
+    kev::KevClient,
+    nvd::NvdClient,
+    osv::OsvClient,
+    repo::{
+        gitea::GiteaClient, github::GitHubClient, gitlab::GitLabClient,
+        scorecard::ScorecardClient, sourcehut::SourcehutClient,
+    },
+    semver_checks::SemverChecksClient,
+    sparse_index::SparseIndexClient,
+    udeps::UdepsClient,
+    vendor::VendoredSources,
+    ManifestPath,
 };
 
-use super::IndicateAdapter;
+use super::{
+    lazy_bloat_client, lazy_client, lazy_geiger_client, lazy_udeps_client,
+    IndicateAdapter,
+};
 
 /// Builder for [`IndicateAdapter`]
 pub struct IndicateAdapterBuilder {
@@ -16,9 +43,28 @@ pub struct IndicateAdapterBuilder {
     features: Vec<CargoOpt>,
     metadata: Option<Metadata>,
     github_client: Option<GitHubClient>,
+    gitlab_client: Option<GitLabClient>,
+    gitea_client: Option<GiteaClient>,
+    sourcehut_client: Option<SourcehutClient>,
     advisory_client: Option<AdvisoryClient>,
     geiger_client: Option<GeigerClient>,
+    udeps_client: Option<UdepsClient>,
+    bloat_client: Option<BloatClient>,
     crates_io_client: Option<CratesIoClient>,
+    sparse_index_client: Option<SparseIndexClient>,
+    osv_client: Option<OsvClient>,
+    ghsa_client: Option<GhsaClient>,
+    epss_client: Option<EpssClient>,
+    kev_client: Option<KevClient>,
+    nvd_client: Option<NvdClient>,
+    crev_client: Option<CrevClient>,
+    semver_checks_client: Option<SemverChecksClient>,
+    clippy_client: Option<ClippyClient>,
+    docs_rs_client: Option<DocsRsClient>,
+    deps_dev_client: Option<DepsDevClient>,
+    scorecard_client: Option<ScorecardClient>,
+    changed_since: Option<String>,
+    vendored_sources: Option<VendoredSources>,
 }
 
 impl IndicateAdapterBuilder {
@@ -35,9 +81,28 @@ impl IndicateAdapterBuilder {
             features: Vec::new(),
             metadata: None,
             github_client: None,
+            gitlab_client: None,
+            gitea_client: None,
+            sourcehut_client: None,
             advisory_client: None,
             geiger_client: None,
+            udeps_client: None,
+            bloat_client: None,
             crates_io_client: None,
+            sparse_index_client: None,
+            osv_client: None,
+            ghsa_client: None,
+            epss_client: None,
+            kev_client: None,
+            nvd_client: None,
+            crev_client: None,
+            semver_checks_client: None,
+            clippy_client: None,
+            docs_rs_client: None,
+            deps_dev_client: None,
+            scorecard_client: None,
+            changed_since: None,
+            vendored_sources: None,
         }
     }
 
@@ -48,7 +113,12 @@ impl IndicateAdapterBuilder {
     ///
     /// # Panics
     ///
-    /// Panics if both features and metadata have been set manually.
+    /// Panics if
+    ///
+    /// - Both features and metadata have been set manually, or
+    /// - A git reference is set using
+    ///   [`IndicateAdapterBuilder::changed_since`], but the changed packages
+    ///   could not be resolved
     #[must_use]
     pub fn build(self) -> IndicateAdapter {
         assert!(
@@ -56,9 +126,15 @@ impl IndicateAdapterBuilder {
             "features and metadata both set explicitly at the same time"
         );
 
-        let metadata = match self.metadata {
-            Some(m) => m,
-            None => self
+        let metadata = match (self.metadata, &self.vendored_sources) {
+            (Some(m), _) => m,
+            (None, Some(v)) => self
+                .manifest_path
+                .metadata_with_vendored_sources(self.features.clone(), v)
+                .unwrap_or_else(|e| {
+                    panic!("could not generate metadata from vendored sources due to error: {e}")
+                }),
+            (None, None) => self
                 .manifest_path
                 .metadata(self.features.clone())
                 .unwrap_or_else(|e| {
@@ -66,33 +142,139 @@ impl IndicateAdapterBuilder {
                 }),
         };
 
-        // unwrap OK, if-statement above guarantees self.metadata to exist
-        let advisory_client =
-            self.advisory_client.map_or_else(OnceCell::default, |ac| {
-                OnceCell::with_value(Rc::new(ac))
-            });
-        let geiger_client =
-            self.geiger_client.map_or_else(OnceCell::default, |gc| {
-                OnceCell::with_value(Rc::new(gc))
-            });
-        let crates_io_client =
-            self.crates_io_client.map_or_else(OnceCell::default, |c| {
-                OnceCell::with_value(Rc::new(RefCell::new(c)))
-            });
-
-        IndicateAdapter {
-            manifest_path: Rc::new(self.manifest_path),
+        let changed_packages = self.changed_since.map(|git_ref| {
+            let lockfile_path =
+                metadata.workspace_root.join("Cargo.lock").into_std_path_buf();
+            let changed = changed_packages_since(&lockfile_path, &git_ref)
+                .unwrap_or_else(|e| {
+                    panic!("could not resolve packages changed since {git_ref} due to error: {e}")
+                });
+            Rc::new(changed)
+        });
+
+        let manifest_path = Rc::new(self.manifest_path);
+        let metadata = Rc::new(metadata);
+        let vendored_sources = self.vendored_sources.map(Rc::new);
+        let advisory_client = match self.advisory_client {
+            Some(ac) => lazy_client(move || ac),
+            None => lazy_client(|| {
+                AdvisoryClient::new().unwrap_or_else(|e| {
+                    panic!("could not create advisory client due to error: {e}")
+                })
+            }),
+        };
+        let geiger_client = match self.geiger_client {
+            Some(gc) => lazy_client(move || gc),
+            None => lazy_geiger_client(
+                Rc::clone(&manifest_path),
+                self.features.clone(),
+                Rc::clone(&metadata),
+                vendored_sources.clone(),
+            ),
+        };
+        let udeps_client = match self.udeps_client {
+            Some(uc) => lazy_client(move || uc),
+            None => lazy_udeps_client(
+                Rc::clone(&manifest_path),
+                self.features.clone(),
+                Rc::clone(&metadata),
+            ),
+        };
+        let bloat_client = match self.bloat_client {
+            Some(bc) => lazy_client(move || bc),
+            None => lazy_bloat_client(
+                Rc::clone(&manifest_path),
+                self.features.clone(),
+            ),
+        };
+        let crates_io_client = match self.crates_io_client {
+            Some(c) => lazy_client(move || RefCell::new(c)),
+            None => lazy_client(|| RefCell::new(CratesIoClient::default())),
+        };
+
+        let adapter = IndicateAdapter {
+            manifest_path,
             features: self.features,
-            metadata: Rc::new(metadata),
+            vendored_sources,
+            metadata,
             packages: OnceCell::new(),
-            direct_dependencies: OnceCell::new(),
+            direct_dependencies: RefCell::default(),
+            dependents: OnceCell::new(),
+            depths: OnceCell::new(),
+            paths_from_root: OnceCell::new(),
+            checksums: OnceCell::new(),
+            vet_audits: OnceCell::new(),
+            deny_config: OnceCell::new(),
+            future_incompat: OnceCell::new(),
+            ffi_stats: Rc::default(),
+            suspicion: Rc::default(),
             gh_client: Rc::new(RefCell::new(
                 self.github_client.unwrap_or_default(),
             )),
+            gitlab_client: Rc::new(RefCell::new(
+                self.gitlab_client.unwrap_or_default(),
+            )),
+            gitea_client: Rc::new(RefCell::new(
+                self.gitea_client.unwrap_or_default(),
+            )),
+            sourcehut_client: Rc::new(RefCell::new(
+                self.sourcehut_client.unwrap_or_default(),
+            )),
             advisory_client,
             geiger_client,
+            udeps_client,
+            bloat_client,
             crates_io_client,
-        }
+            sparse_index_client: Rc::new(RefCell::new(
+                self.sparse_index_client.unwrap_or_default(),
+            )),
+            osv_client: Rc::new(RefCell::new(
+                self.osv_client.unwrap_or_default(),
+            )),
+            ghsa_client: Rc::new(RefCell::new(
+                self.ghsa_client.unwrap_or_default(),
+            )),
+            epss_client: Rc::new(RefCell::new(
+                self.epss_client.unwrap_or_default(),
+            )),
+            kev_client: Rc::new(RefCell::new(
+                self.kev_client.unwrap_or_default(),
+            )),
+            nvd_client: Rc::new(RefCell::new(
+                self.nvd_client.unwrap_or_default(),
+            )),
+            crev_client: Rc::new(RefCell::new(
+                self.crev_client.unwrap_or_default(),
+            )),
+            semver_checks_client: Rc::new(RefCell::new(
+                self.semver_checks_client.unwrap_or_default(),
+            )),
+            clippy_client: Rc::new(RefCell::new(
+                self.clippy_client.unwrap_or_default(),
+            )),
+            docs_rs_client: Rc::new(RefCell::new(
+                self.docs_rs_client.unwrap_or_default(),
+            )),
+            deps_dev_client: Rc::new(RefCell::new(
+                self.deps_dev_client.unwrap_or_default(),
+            )),
+            scorecard_client: Rc::new(RefCell::new(
+                self.scorecard_client.unwrap_or_default(),
+            )),
+            changed_packages,
+            skip_starting_vertices: Cell::new(0),
+            starting_vertices_resolved: Rc::default(),
+            stopped_early: Rc::default(),
+        };
+
+        // Catch a schema that is out of sync with the adapter at startup,
+        // rather than when a query uses the missing field
+        debug_assert!(
+            adapter.missing_resolvers().is_empty(),
+            "schema fields without resolvers: {:?}",
+            adapter.missing_resolvers()
+        );
+        adapter
     }
 
     /// Features used when generating metadata
@@ -123,6 +305,32 @@ impl IndicateAdapterBuilder {
         self
     }
 
+    /// Manually sets the GitLab client to be used by the adapter
+    #[must_use]
+    pub fn gitlab_client(mut self, gitlab_client: GitLabClient) -> Self {
+        self.gitlab_client = Some(gitlab_client);
+        self
+    }
+
+    /// Manually sets the Gitea client to be used by the adapter, such as to
+    /// configure self-hosted Gitea instances using
+    /// [`GiteaClient::with_hosts`]
+    #[must_use]
+    pub fn gitea_client(mut self, gitea_client: GiteaClient) -> Self {
+        self.gitea_client = Some(gitea_client);
+        self
+    }
+
+    /// Manually sets the sourcehut client to be used by the adapter
+    #[must_use]
+    pub fn sourcehut_client(
+        mut self,
+        sourcehut_client: SourcehutClient,
+    ) -> Self {
+        self.sourcehut_client = Some(sourcehut_client);
+        self
+    }
+
     /// Manually sets the `advisory-db` client to be used by the adapter
     #[must_use]
     pub fn advisory_client(mut self, advisory_client: AdvisoryClient) -> Self {
@@ -142,6 +350,28 @@ impl IndicateAdapterBuilder {
         self
     }
 
+    /// Manually sets the `cargo-udeps` client to be used by the adapter
+    ///
+    /// Like `cargo-geiger`, running `cargo-udeps` is expensive, and a lazily
+    /// evaluated [`UdepsClient`] is available to the adapter if this is not
+    /// set.
+    #[must_use]
+    pub fn udeps_client(mut self, udeps_client: UdepsClient) -> Self {
+        self.udeps_client = Some(udeps_client);
+        self
+    }
+
+    /// Manually sets the `cargo-bloat` client to be used by the adapter
+    ///
+    /// Running `cargo-bloat` builds the root package in release mode, and a
+    /// lazily evaluated [`BloatClient`] is available to the adapter if this
+    /// is not set.
+    #[must_use]
+    pub fn bloat_client(mut self, bloat_client: BloatClient) -> Self {
+        self.bloat_client = Some(bloat_client);
+        self
+    }
+
     /// Manually sets the crates.io client to be used by the adapter
     #[must_use]
     pub fn crates_io_client(
@@ -151,6 +381,126 @@ impl IndicateAdapterBuilder {
         self.crates_io_client = Some(crates_io_client);
         self
     }
+
+    /// Manually sets the crates.io sparse index client to be used by the
+    /// adapter
+    #[must_use]
+    pub fn sparse_index_client(
+        mut self,
+        sparse_index_client: SparseIndexClient,
+    ) -> Self {
+        self.sparse_index_client = Some(sparse_index_client);
+        self
+    }
+
+    /// Manually sets the OSV.dev client to be used by the adapter
+    #[must_use]
+    pub fn osv_client(mut self, osv_client: OsvClient) -> Self {
+        self.osv_client = Some(osv_client);
+        self
+    }
+
+    /// Manually sets the GitHub Security Advisories client to be used by the
+    /// adapter
+    #[must_use]
+    pub fn ghsa_client(mut self, ghsa_client: GhsaClient) -> Self {
+        self.ghsa_client = Some(ghsa_client);
+        self
+    }
+
+    /// Manually sets the EPSS client to be used by the adapter
+    #[must_use]
+    pub fn epss_client(mut self, epss_client: EpssClient) -> Self {
+        self.epss_client = Some(epss_client);
+        self
+    }
+
+    /// Manually sets the CISA KEV client to be used by the adapter
+    #[must_use]
+    pub fn kev_client(mut self, kev_client: KevClient) -> Self {
+        self.kev_client = Some(kev_client);
+        self
+    }
+
+    /// Manually sets the NVD client to be used by the adapter
+    #[must_use]
+    pub fn nvd_client(mut self, nvd_client: NvdClient) -> Self {
+        self.nvd_client = Some(nvd_client);
+        self
+    }
+
+    /// Manually sets the `cargo-crev` client to be used by the adapter
+    #[must_use]
+    pub fn crev_client(mut self, crev_client: CrevClient) -> Self {
+        self.crev_client = Some(crev_client);
+        self
+    }
+
+    /// Manually sets the `cargo-semver-checks` client to be used by the
+    /// adapter
+    #[must_use]
+    pub fn semver_checks_client(
+        mut self,
+        semver_checks_client: SemverChecksClient,
+    ) -> Self {
+        self.semver_checks_client = Some(semver_checks_client);
+        self
+    }
+
+    /// Manually sets the Clippy client to be used by the adapter
+    #[must_use]
+    pub fn clippy_client(mut self, clippy_client: ClippyClient) -> Self {
+        self.clippy_client = Some(clippy_client);
+        self
+    }
+
+    /// Manually sets the docs.rs client to be used by the adapter
+    #[must_use]
+    pub fn docs_rs_client(mut self, docs_rs_client: DocsRsClient) -> Self {
+        self.docs_rs_client = Some(docs_rs_client);
+        self
+    }
+
+    /// Manually sets the deps.dev client to be used by the adapter
+    #[must_use]
+    pub fn deps_dev_client(mut self, deps_dev_client: DepsDevClient) -> Self {
+        self.deps_dev_client = Some(deps_dev_client);
+        self
+    }
+
+    /// Manually sets the OpenSSF Scorecard client to be used by the adapter
+    #[must_use]
+    pub fn scorecard_client(
+        mut self,
+        scorecard_client: ScorecardClient,
+    ) -> Self {
+        self.scorecard_client = Some(scorecard_client);
+        self
+    }
+
+    /// Only use packages added or updated since a git reference (such as
+    /// `origin/main`) as starting points for dependencies, by comparing the
+    /// `Cargo.lock` file of the workspace to its version at that reference
+    ///
+    /// Affects the `Dependencies` and `TransitiveDependencies` entry points.
+    #[must_use]
+    pub fn changed_since(mut self, git_ref: impl Into<String>) -> Self {
+        self.changed_since = Some(git_ref.into());
+        self
+    }
+
+    /// Reads packages from crates.io from vendored sources instead of
+    /// `~/.cargo/registry`, such as in environments without network access
+    ///
+    /// Affects the metadata, unless set manually, and `cargo-geiger`.
+    #[must_use]
+    pub fn vendored_sources(
+        mut self,
+        vendored_sources: VendoredSources,
+    ) -> Self {
+        self.vendored_sources = Some(vendored_sources);
+        self
+    }
 }
 
 impl From<IndicateAdapterBuilder> for IndicateAdapter {
@@ -0,0 +1,156 @@
+//! Checks that [`IndicateAdapter`] has a resolver for every field in the
+//! schema
+//!
+//! Trustfall only validates queries against the schema, so a field added to
+//! the schema without a resolver would otherwise not be noticed until a query
+//! using it panics.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_graphql_parser::{
+    parse_schema,
+    types::{
+        BaseType, InputValueDefinition, Type, TypeKind, TypeSystemDefinition,
+    },
+};
+use trustfall::{provider::EdgeParameters, FieldValue};
+
+use crate::RAW_SCHEMA;
+
+use super::IndicateAdapter;
+
+/// Built-in scalar types; Fields of any other type are edges
+const SCALARS: [&str; 5] = ["String", "Int", "Float", "Boolean", "ID"];
+
+/// Name of the type containing all starting edges
+const ROOT_QUERY: &str = "RootQuery";
+
+/// A field of a type or interface in the schema
+struct SchemaField {
+    type_name: String,
+    field_name: String,
+    is_edge: bool,
+
+    /// Values for all parameters of an edge, to be able to call its resolver
+    parameters: EdgeParameters,
+}
+
+/// Parses all fields of all types and interfaces in the schema
+fn schema_fields() -> Vec<SchemaField> {
+    let document = parse_schema(RAW_SCHEMA)
+        .unwrap_or_else(|e| panic!("could not parse schema due to error: {e}"));
+
+    let mut fields = Vec::new();
+    for definition in document.definitions {
+        let TypeSystemDefinition::Type(t) = definition else {
+            continue;
+        };
+        let type_fields = match t.node.kind {
+            TypeKind::Object(o) => o.fields,
+            TypeKind::Interface(i) => i.fields,
+            _ => continue,
+        };
+        for f in type_fields {
+            let f = f.node;
+            fields.push(SchemaField {
+                type_name: t.node.name.node.to_string(),
+                field_name: f.name.node.to_string(),
+                is_edge: !SCALARS.contains(&base_type_name(&f.ty.node)),
+                parameters: placeholder_parameters(&f.arguments),
+            });
+        }
+    }
+    fields
+}
+
+/// The name of a type without any list or non-null wrappers
+fn base_type_name(ty: &Type) -> &str {
+    match &ty.base {
+        BaseType::Named(name) => name.as_str(),
+        BaseType::List(inner) => base_type_name(inner),
+    }
+}
+
+/// Uses the default value of each parameter, or a placeholder value of its
+/// type if it has none
+fn placeholder_parameters(
+    arguments: &[async_graphql_parser::Positioned<InputValueDefinition>],
+) -> EdgeParameters {
+    let contents = arguments
+        .iter()
+        .map(|a| {
+            let a = &a.node;
+            let value = match &a.default_value {
+                Some(v) => {
+                    FieldValue::try_from(v.node.clone()).unwrap_or_else(|e| {
+                        panic!("invalid default value for {}: {e}", a.name.node)
+                    })
+                }
+                None => match &a.ty.node.base {
+                    BaseType::List(_) => FieldValue::List(Vec::new()),
+                    BaseType::Named(name) => match name.as_str() {
+                        "Boolean" => FieldValue::Boolean(false),
+                        "Int" => FieldValue::Int64(0),
+                        "Float" => FieldValue::Float64(0.0),
+                        _ => FieldValue::String(String::new()),
+                    },
+                },
+            };
+            (Arc::from(a.name.node.as_str()), value)
+        })
+        .collect::<BTreeMap<Arc<str>, FieldValue>>();
+
+    // `EdgeParameters` can only be created by Trustfall, but can be
+    // deserialized
+    serde_json::from_value(serde_json::json!({ "contents": contents }))
+        .expect("could not create edge parameters")
+}
+
+impl IndicateAdapter {
+    /// Finds all properties and edges in the schema that this adapter has no
+    /// resolver for, as `Type.field`
+    ///
+    /// Each resolver is called without any vertices, and starting vertices
+    /// are not retrieved, so no data is retrieved and no queries are run.
+    /// Querying any of the returned fields would panic, so this should always
+    /// be empty.
+    #[must_use]
+    pub fn missing_resolvers(&self) -> Vec<String> {
+        schema_fields()
+            .into_iter()
+            .filter(|f| {
+                if f.type_name == ROOT_QUERY {
+                    return Self::starting_vertices(&f.field_name).is_none();
+                }
+
+                let contexts = Box::new(std::iter::empty());
+                if f.is_edge {
+                    self.neighbors(
+                        contexts,
+                        &f.type_name,
+                        &f.field_name,
+                        &f.parameters,
+                    )
+                    .is_err()
+                } else {
+                    self.property(contexts, &f.type_name, &f.field_name)
+                        .is_err()
+                }
+            })
+            .map(|f| format!("{}.{}", f.type_name, f.field_name))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{IndicateAdapter, ManifestPath};
+
+    #[test]
+    fn adapter_resolves_whole_schema() {
+        let adapter = IndicateAdapter::new(ManifestPath::from(
+            "test_data/fake_crates/simple_deps",
+        ));
+        assert_eq!(adapter.missing_resolvers(), Vec::<String>::new());
+    }
+}
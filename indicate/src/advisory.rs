@@ -1,13 +1,113 @@
-use std::path::Path;
+use std::{
+    collections::HashSet,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
 
 use cvss::Severity;
 use rustsec::{
+    advisory::Informational,
     database::Query,
     package::Name,
     platforms::{Arch, OS},
-    Advisory, Database,
+    semver::{Comparator, Op},
+    Advisory, Database, Version,
 };
 
+/// An advisory for a package, together with the version of the package it
+/// was retrieved for
+///
+/// Dereferences to the [`Advisory`] itself.
+#[derive(Debug, Clone)]
+pub struct PackageAdvisory {
+    pub advisory: Advisory,
+
+    /// The version of the package the advisory was retrieved for
+    pub version: Version,
+}
+
+impl Deref for PackageAdvisory {
+    type Target = Advisory;
+
+    fn deref(&self) -> &Self::Target {
+        &self.advisory
+    }
+}
+
+impl PackageAdvisory {
+    #[must_use]
+    pub fn new(advisory: Advisory, version: Version) -> Self {
+        Self { advisory, version }
+    }
+
+    /// If the version of the package is affected, according to the patched
+    /// and unaffected version ranges of the advisory
+    #[must_use]
+    pub fn current_version_affected(&self) -> bool {
+        self.advisory.versions.is_vulnerable(&self.version)
+    }
+
+    /// The lowest patched version newer than the version of the package,
+    /// `None` if the version is not affected or no such version is patched
+    ///
+    /// Candidates are the lower bounds of the patched version ranges, such as
+    /// `1.2.3` of `>= 1.2.3`, which are usually, but not necessarily,
+    /// published versions.
+    #[must_use]
+    pub fn recommended_version(&self) -> Option<Version> {
+        if !self.current_version_affected() {
+            return None;
+        }
+        self.advisory
+            .versions
+            .patched()
+            .iter()
+            .flat_map(|req| {
+                req.comparators
+                    .iter()
+                    .filter_map(lower_bound)
+                    .filter(|v| req.matches(v))
+            })
+            .filter(|v| *v > self.version)
+            .min()
+    }
+
+    /// If the [recommended version](Self::recommended_version) is semver
+    /// compatible with the version of the package, so that the advisory can
+    /// be resolved using `cargo update`
+    #[must_use]
+    pub fn patch_available(&self) -> bool {
+        self.recommended_version()
+            .is_some_and(|v| semver_compatible(&self.version, &v))
+    }
+}
+
+/// The lowest version matching a comparator, `None` if it has no lower bound
+fn lower_bound(c: &Comparator) -> Option<Version> {
+    let minor = c.minor.unwrap_or(0);
+    let patch = c.patch.unwrap_or(0);
+    let mut v = match (c.op, c.minor, c.patch) {
+        (Op::Less | Op::LessEq, _, _) => return None,
+        (Op::Greater, _, Some(p)) => Version::new(c.major, minor, p + 1),
+        (Op::Greater, Some(m), None) => Version::new(c.major, m + 1, 0),
+        (Op::Greater, None, None) => Version::new(c.major + 1, 0, 0),
+        _ => Version::new(c.major, minor, patch),
+    };
+    if c.op != Op::Greater {
+        v.pre = c.pre.clone();
+    }
+    Some(v)
+}
+
+/// If `to` is semver compatible with `from`, according to the rules of Cargo
+fn semver_compatible(from: &Version, to: &Version) -> bool {
+    match (from.major, from.minor) {
+        (0, 0) => to.major == 0 && to.minor == 0 && to.patch == from.patch,
+        (0, minor) => to.major == 0 && to.minor == minor,
+        (major, _) => to.major == major,
+    }
+}
+
 /// Wrapper around an advisory database used to perform queries
 #[derive(Debug)]
 pub struct AdvisoryClient {
@@ -67,8 +167,14 @@ impl AdvisoryClient {
     /// If an advisory database is not available in the default path, such as if
     /// has never been fetched, an error variant will be returned.
     pub fn from_default_path() -> Result<Self, rustsec::Error> {
-        let default = format!("{}/advisory-db", env!("CARGO_HOME"));
-        Self::from_path(Path::new(default.as_str()))
+        Self::from_path(&Self::default_path())
+    }
+
+    /// The default local path of the advisory database in the `CARGO_HOME`
+    /// directory (`~./cargo/advisory-db`)
+    #[must_use]
+    pub fn default_path() -> PathBuf {
+        PathBuf::from(format!("{}/advisory-db", env!("CARGO_HOME")))
     }
 
     /// Retrieves all advisories for a package
@@ -109,3 +215,190 @@ impl AdvisoryClient {
         res
     }
 }
+
+/// Aggregate counts of the advisories affecting the current versions of a
+/// set of packages
+///
+/// Withdrawn advisories, and advisories not affecting the version they
+/// were retrieved for, are not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AuditSummary {
+    /// Distinct advisories, including informational ones
+    pub total_advisories: u64,
+
+    /// Packages affected by at least one vulnerability, i.e. a
+    /// non-informational advisory
+    pub affected_packages: u64,
+
+    /// Affected packages by the highest severity of their vulnerabilities
+    ///
+    /// Packages whose vulnerabilities have no CVSS score, or a score of
+    /// severity `none`, are counted as unscored.
+    pub critical_packages: u64,
+    pub high_packages: u64,
+    pub medium_packages: u64,
+    pub low_packages: u64,
+    pub unscored_packages: u64,
+
+    /// Informational advisories of some kind, counted once per affected
+    /// package
+    pub unmaintained_warnings: u64,
+    pub unsound_warnings: u64,
+}
+
+impl AuditSummary {
+    /// Summarizes the advisories of a set of packages, with one item of
+    /// advisories per package
+    #[must_use]
+    pub fn new<I>(advisories_by_package: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<PackageAdvisory>>,
+    {
+        let mut summary = Self::default();
+        let mut ids = HashSet::new();
+
+        for advisories in advisories_by_package {
+            let mut affected = false;
+            let mut highest_severity = None;
+            for advisory in advisories
+                .iter()
+                .filter(|a| !a.withdrawn() && a.current_version_affected())
+            {
+                ids.insert(advisory.id().clone());
+                match &advisory.metadata.informational {
+                    Some(Informational::Unmaintained) => {
+                        summary.unmaintained_warnings += 1;
+                    }
+                    Some(Informational::Unsound) => {
+                        summary.unsound_warnings += 1;
+                    }
+                    Some(_) => {}
+                    None => {
+                        affected = true;
+                        highest_severity =
+                            highest_severity.max(advisory.severity());
+                    }
+                }
+            }
+
+            if affected {
+                summary.affected_packages += 1;
+                match highest_severity {
+                    Some(Severity::Critical) => summary.critical_packages += 1,
+                    Some(Severity::High) => summary.high_packages += 1,
+                    Some(Severity::Medium) => summary.medium_packages += 1,
+                    Some(Severity::Low) => summary.low_packages += 1,
+                    Some(Severity::None) | None => {
+                        summary.unscored_packages += 1;
+                    }
+                }
+            }
+        }
+
+        summary.total_advisories = ids.len() as u64;
+        summary
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use rustsec::{Advisory, Version};
+    use test_case::test_case;
+
+    use super::{AuditSummary, PackageAdvisory};
+
+    /// An advisory patched in `>= 0.2.1, < 0.3.0` and `>= 0.3.2`
+    const ADVISORY: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2099-0001"
+package = "some-package"
+date = "2099-01-01"
+
+[versions]
+patched = [">= 0.2.1, < 0.3.0", ">= 0.3.2"]
+unaffected = ["< 0.2.0"]
+```
+
+# Title of the advisory
+
+Description of the advisory.
+"#;
+
+    #[test_case("0.1.0" => false ; "unaffected")]
+    #[test_case("0.2.0" => true ; "affected")]
+    #[test_case("0.2.1" => false ; "patched")]
+    #[test_case("0.3.1" => true ; "affected after patch of earlier minor")]
+    #[test_case("0.3.2" => false ; "patched in later minor")]
+    fn current_version_affected(version: &str) -> bool {
+        package_advisory(version).current_version_affected()
+    }
+
+    #[test_case("0.1.0" => None ; "unaffected")]
+    #[test_case("0.2.0" => Some(String::from("0.2.1")) ; "compatible patch")]
+    #[test_case("0.3.1" => Some(String::from("0.3.2")) ; "patch in later minor")]
+    #[test_case("0.3.2" => None ; "patched")]
+    fn recommended_version(version: &str) -> Option<String> {
+        package_advisory(version)
+            .recommended_version()
+            .map(|v| v.to_string())
+    }
+
+    #[test_case("0.2.0" => true ; "compatible patch")]
+    #[test_case("0.1.0" => false ; "unaffected")]
+    fn patch_available(version: &str) -> bool {
+        package_advisory(version).patch_available()
+    }
+
+    #[test]
+    fn no_compatible_patch() {
+        let advisory = Advisory::from_str(
+            &ADVISORY.replace(r#"">= 0.2.1, < 0.3.0", "#, ""),
+        )
+        .unwrap();
+        let package_advisory =
+            PackageAdvisory::new(advisory, Version::parse("0.2.0").unwrap());
+        assert_eq!(
+            package_advisory.recommended_version(),
+            Some(Version::new(0, 3, 2))
+        );
+        assert!(!package_advisory.patch_available());
+    }
+
+    #[test]
+    fn audit_summary() {
+        let unmaintained = Advisory::from_str(
+            &ADVISORY
+                .replace("RUSTSEC-2099-0001", "RUSTSEC-2099-0002")
+                .replace(
+                    "[versions]",
+                    "informational = \"unmaintained\"\n\n[versions]",
+                ),
+        )
+        .unwrap();
+        let summary = AuditSummary::new(vec![
+            vec![
+                package_advisory("0.2.0"),
+                PackageAdvisory::new(
+                    unmaintained,
+                    Version::parse("0.2.0").unwrap(),
+                ),
+            ],
+            vec![package_advisory("0.3.1")],
+            vec![package_advisory("0.3.2")],
+        ]);
+
+        assert_eq!(summary.total_advisories, 2);
+        assert_eq!(summary.affected_packages, 2);
+        assert_eq!(summary.unscored_packages, 2);
+        assert_eq!(summary.critical_packages, 0);
+        assert_eq!(summary.unmaintained_warnings, 1);
+        assert_eq!(summary.unsound_warnings, 0);
+    }
+
+    fn package_advisory(version: &str) -> PackageAdvisory {
+        let advisory = Advisory::from_str(ADVISORY).unwrap();
+        PackageAdvisory::new(advisory, Version::parse(version).unwrap())
+    }
+}
@@ -0,0 +1,285 @@
+//! Detection of prebuilt binary files shipped inside packages
+//!
+//! Packages rarely need to ship compiled artifacts, since they are built from
+//! source by Cargo. Large binary files such as shared libraries or WebAssembly
+//! modules can therefore hide code that is never reviewed.
+//!
+//! Besides files with a binary extension, files are also recognized by their
+//! magic bytes, so that executables and libraries can not be hidden by
+//! renaming them.
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use walkdir::WalkDir;
+
+/// File extensions considered to be binary artifacts, and the kind of
+/// file they are recognized as (see [`SuspiciousFile::kind`])
+pub const BINARY_EXTENSIONS: [(&str, &str); 10] = [
+    ("exe", "executable"),
+    ("so", "sharedLibrary"),
+    ("dll", "sharedLibrary"),
+    ("dylib", "sharedLibrary"),
+    ("a", "staticLibrary"),
+    ("lib", "staticLibrary"),
+    ("o", "object"),
+    ("obj", "object"),
+    ("wasm", "wasm"),
+    ("bin", "blob"),
+];
+
+/// Minimum size of a binary file to be reported, unless another threshold is
+/// provided
+pub const DEFAULT_MIN_BINARY_SIZE: u64 = 4096;
+
+/// A binary file found in a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundledBinary {
+    /// Path relative to the package root
+    pub path: String,
+
+    /// File extension, without the leading `.`, or empty if it has none
+    pub extension: String,
+    pub size_bytes: u64,
+}
+
+/// A file of a package
+struct PackageFile {
+    path: PathBuf,
+
+    /// Path relative to the package root
+    relative_path: String,
+
+    /// Lowercase file extension, or empty if it has none
+    extension: String,
+    size_bytes: u64,
+}
+
+/// All files of a package
+///
+/// Anything in a `target` or `.git` directory is ignored, since it is not
+/// part of the published package.
+fn package_files(root_path: &Path) -> impl Iterator<Item = PackageFile> + '_ {
+    WalkDir::new(root_path)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != "target" && e.file_name() != ".git")
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter_map(move |e| {
+            let size_bytes = e.metadata().ok()?.len();
+            let extension = e
+                .path()
+                .extension()
+                .and_then(|x| x.to_str())
+                .map(str::to_lowercase)
+                .unwrap_or_default();
+            let relative_path = e
+                .path()
+                .strip_prefix(root_path)
+                .unwrap_or(e.path())
+                .to_string_lossy()
+                .to_string();
+
+            Some(PackageFile {
+                path: e.into_path(),
+                relative_path,
+                extension,
+                size_bytes,
+            })
+        })
+}
+
+/// Finds all files with a [binary extension](BINARY_EXTENSIONS), or that are
+/// recognized as an executable or library by their magic bytes, that are at
+/// least `min_size` bytes large in a package
+///
+/// Files that are not part of the published package are ignored (see
+/// [`package_files`]). The result is sorted by path.
+pub(crate) fn get_bundled_binaries(
+    root_path: &Path,
+    min_size: u64,
+) -> Vec<BundledBinary> {
+    let mut binaries = package_files(root_path)
+        .filter(|f| f.size_bytes >= min_size)
+        .filter(|f| {
+            kind_from_extension(&f.extension).is_some()
+                || read_head(&f.path)
+                    .is_ok_and(|head| kind_from_magic(&head).is_some())
+        })
+        .map(|f| BundledBinary {
+            path: f.relative_path,
+            extension: f.extension,
+            size_bytes: f.size_bytes,
+        })
+        .collect::<Vec<_>>();
+
+    binaries.sort_by(|a, b| a.path.cmp(&b.path));
+    binaries
+}
+
+/// Minimum size of an opaque binary file without a known format to be
+/// reported, unless another threshold is provided
+pub const DEFAULT_MIN_BLOB_SIZE: u64 = 1024 * 1024;
+
+/// Number of bytes read from the start of each file to detect its format
+const HEAD_SIZE: u64 = 8192;
+
+/// A file in a package that is either an executable, a library or an opaque
+/// binary blob
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuspiciousFile {
+    /// Path relative to the package root
+    pub path: String,
+
+    /// One of `executable`, `sharedLibrary`, `staticLibrary`, `object`,
+    /// `wasm` or `blob`
+    pub kind: String,
+
+    /// `magicBytes` if recognized by the start of the file, `extension` if
+    /// only by its extension, or `content` for blobs
+    pub detected_by: String,
+    pub size_bytes: u64,
+}
+
+/// Reads the first [`HEAD_SIZE`] bytes of a file
+fn read_head(path: &Path) -> io::Result<Vec<u8>> {
+    let mut head = Vec::new();
+    File::open(path)?.take(HEAD_SIZE).read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// Reads a `u16` at `offset`, in little endian unless `big_endian`
+fn read_u16(head: &[u8], offset: usize, big_endian: bool) -> Option<u16> {
+    let bytes = head.get(offset..offset + 2)?.try_into().ok()?;
+    Some(if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    })
+}
+
+/// Reads a `u32` at `offset`, in little endian unless `big_endian`
+fn read_u32(head: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let bytes = head.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    })
+}
+
+/// The kind of a file recognized by its magic bytes
+///
+/// Position independent executables on Linux are shared objects, and are
+/// reported as such.
+fn kind_from_magic(head: &[u8]) -> Option<&'static str> {
+    if head.starts_with(b"\x7fELF") {
+        let big_endian = *head.get(5)? == 2;
+        return Some(match read_u16(head, 16, big_endian)? {
+            1 => "object",
+            3 => "sharedLibrary",
+            _ => "executable",
+        });
+    }
+
+    let magic = read_u32(head, 0, true)?;
+    match magic {
+        // Mach-O, 32 and 64 bit in either byte order
+        0xfeed_face | 0xfeed_facf | 0xcefa_edfe | 0xcffa_edfe => {
+            let big_endian = magic >> 16 == 0xfeed;
+            Some(match read_u32(head, 12, big_endian)? {
+                1 => "object",
+                6 | 8 => "sharedLibrary",
+                _ => "executable",
+            })
+        }
+        // Universal Mach-O binaries share their magic with Java class files,
+        // which instead have a version of at least 45 here
+        0xcafe_babe if read_u32(head, 4, true)? < 45 => Some("executable"),
+        _ if head.starts_with(b"MZ") => {
+            // The DOS header points to the PE header, where the
+            // characteristics tell if it is a DLL
+            let pe_offset = read_u32(head, 0x3c, false)? as usize;
+            if head.get(pe_offset..pe_offset + 4)? != b"PE\0\0" {
+                return None;
+            }
+            let characteristics = read_u16(head, pe_offset + 22, false)?;
+            Some(if characteristics & 0x2000 == 0 {
+                "executable"
+            } else {
+                "sharedLibrary"
+            })
+        }
+        _ if head.starts_with(b"\0asm") => Some("wasm"),
+        _ if head.starts_with(b"!<arch>\n") => Some("staticLibrary"),
+        _ => None,
+    }
+}
+
+/// The kind of a file recognized only by its extension
+fn kind_from_extension(extension: &str) -> Option<&'static str> {
+    BINARY_EXTENSIONS
+        .iter()
+        .find(|(e, _)| *e == extension)
+        .map(|(_, kind)| *kind)
+}
+
+/// If the start of a file looks like UTF-8 text, without control characters
+/// other than whitespace and escapes
+fn is_text(head: &[u8]) -> bool {
+    if head
+        .iter()
+        .any(|b| *b < 0x20 && !b"\t\n\r\x0c\x1b".contains(b))
+    {
+        return false;
+    }
+
+    // The head may end in the middle of a character
+    match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+/// Finds executables, libraries and opaque binary blobs in a package
+///
+/// Files are recognized by their magic bytes, or by their extension if they
+/// are not text. Other files that are not text are reported as blobs if they
+/// are at least `min_blob_size` bytes large, which includes formats such as
+/// images.
+///
+/// Files that are not part of the published package are ignored (see
+/// [`package_files`]). The result is sorted by path.
+pub(crate) fn get_suspicious_files(
+    root_path: &Path,
+    min_blob_size: u64,
+) -> Vec<SuspiciousFile> {
+    let mut files = package_files(root_path)
+        .filter_map(|f| {
+            let head = read_head(&f.path).ok()?;
+            let (kind, detected_by) = if let Some(k) = kind_from_magic(&head) {
+                (k, "magicBytes")
+            } else if is_text(&head) {
+                return None;
+            } else if let Some(k) = kind_from_extension(&f.extension) {
+                (k, "extension")
+            } else if f.size_bytes >= min_blob_size {
+                ("blob", "content")
+            } else {
+                return None;
+            };
+
+            Some(SuspiciousFile {
+                path: f.relative_path,
+                kind: kind.to_string(),
+                detected_by: detected_by.to_string(),
+                size_bytes: f.size_bytes,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
@@ -0,0 +1,213 @@
+//! Binary size contributions found by [`cargo-bloat`](https://github.com/RazrFalcon/cargo-bloat)
+//!
+//! `cargo-bloat` is run once, in release mode, for the binary of the root
+//! package using `cargo bloat --release --crates`. A workspace without a
+//! binary target in its root package can not be analyzed.
+//!
+//! The output of `cargo bloat --message-format json --crates` is on the form
+//! ```json
+//! {
+//!     "file-size": 4194304,
+//!     "text-section-size": 1048576,
+//!     "crates": [
+//!         { "name": "std", "size": 524288 },
+//!         { "name": "syn", "size": 131072 }
+//!     ]
+//! }
+//! ```
+//!
+//! Sizes are attributed to crate names, such as `proc_macro2`, so several
+//! versions of the same package share a contribution.
+
+use std::{
+    error::Error,
+    process::{Command, Stdio},
+    rc::Rc,
+};
+
+use cargo_metadata::{CargoOpt, Package};
+use serde::Deserialize;
+
+use crate::ManifestPath;
+
+/// The output of `cargo bloat --message-format json --crates`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BloatOutput {
+    pub file_size: u64,
+    pub text_section_size: u64,
+    pub crates: Vec<BloatCrate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BloatCrate {
+    /// The name of the crate in code, such as `proc_macro2`
+    pub name: String,
+
+    /// Bytes of the `.text` section
+    pub size: u64,
+}
+
+/// The bytes a package contributes to the binary of the root package
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinarySizeContribution {
+    /// Bytes of the `.text` section, i.e. code
+    pub bytes: u64,
+
+    /// Percentage of the `.text` section
+    pub percentage_of_text: f64,
+
+    /// Percentage of the whole binary file
+    pub percentage_of_file: f64,
+}
+
+/// The name of a package in code, which is what `cargo-bloat` uses
+fn crate_name(package: &Package) -> String {
+    package
+        .targets
+        .iter()
+        .find(|t| {
+            t.kind
+                .iter()
+                .any(|k| ["lib", "rlib", "proc-macro"].contains(&k.as_str()))
+        })
+        .map_or_else(|| package.name.clone(), |t| t.name.clone())
+        .replace('-', "_")
+}
+
+/// A client used to retrieve how much packages contribute to the size of the
+/// binary of the root package, according to `cargo-bloat`
+#[derive(Debug, Clone, Default)]
+pub struct BloatClient {
+    /// `None` if `cargo-bloat` could not be run
+    output: Option<Rc<BloatOutput>>,
+}
+
+impl BloatClient {
+    /// Creates a new client by running `cargo bloat` for the root package
+    /// of `manifest_path`
+    ///
+    /// Requires that `cargo-bloat` is installed. An empty vector of
+    /// features will be handled as default features.
+    ///
+    /// This builds the root package in release mode, so it is often better
+    /// to do this lazily.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if `cargo-bloat` cannot be started, or does
+    /// not produce valid output, such as if the root package has no binary.
+    pub fn new(
+        manifest_path: &ManifestPath,
+        features: &[CargoOpt],
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut cmd = Command::new("cargo-bloat");
+        cmd.args(["bloat", "--release", "--crates"])
+            .args(["--message-format", "json"])
+            // Include all crates, not only the largest ones
+            .args(["-n", "0"])
+            .arg("--manifest-path")
+            .arg(manifest_path.as_path());
+
+        for f in features {
+            match f {
+                CargoOpt::AllFeatures => {
+                    cmd.arg("--all-features");
+                }
+                CargoOpt::NoDefaultFeatures => {
+                    cmd.arg("--no-default-features");
+                }
+                CargoOpt::SomeFeatures(s) => {
+                    if !s.is_empty() {
+                        cmd.arg("--features");
+                        cmd.args(s);
+                    }
+                }
+            }
+        }
+
+        let output = cmd.stdin(Stdio::null()).output()?;
+        if !output.status.success() {
+            return Err(format!(
+                "cargo-bloat failed with stderr: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )
+            .into());
+        }
+        let bloat_output =
+            serde_json::from_slice::<BloatOutput>(&output.stdout)?;
+
+        Ok(Self::from(bloat_output))
+    }
+
+    /// The contribution of a package to the binary, `None` if it does not
+    /// contribute any code, or if `cargo-bloat` could not be run
+    #[must_use]
+    pub fn contribution(
+        &self,
+        package: &Package,
+    ) -> Option<BinarySizeContribution> {
+        let output = self.output.as_ref()?;
+        let name = crate_name(package);
+        let bytes = output.crates.iter().find(|c| c.name == name)?.size;
+
+        let percentage = |total: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                bytes as f64 / total as f64 * 100.0
+            }
+        };
+        Some(BinarySizeContribution {
+            bytes,
+            percentage_of_text: percentage(output.text_section_size),
+            percentage_of_file: percentage(output.file_size),
+        })
+    }
+}
+
+impl From<BloatOutput> for BloatClient {
+    fn from(output: BloatOutput) -> Self {
+        Self {
+            output: Some(Rc::new(output)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use crate::ManifestPath;
+
+    use super::{BloatClient, BloatOutput};
+
+    #[test]
+    fn contribution() {
+        let json_string =
+            fs::read_to_string("test_data/bloat-output/simple_deps.json")
+                .unwrap();
+        let output = serde_json::from_str::<BloatOutput>(&json_string).unwrap();
+        let client = BloatClient::from(output);
+
+        let metadata = ManifestPath::new(Path::new(
+            "test_data/fake_crates/simple_deps/Cargo.toml",
+        ))
+        .metadata(Vec::new())
+        .unwrap();
+        let package = |name: &str| {
+            metadata.packages.iter().find(|p| p.name == name).unwrap()
+        };
+
+        let libc = client.contribution(package("libc")).unwrap();
+        assert_eq!(libc.bytes, 2048);
+        assert_eq!(libc.percentage_of_text, 2.0);
+        assert_eq!(libc.percentage_of_file, 0.5);
+
+        // Uses the name of the crate in code, with underscores
+        assert!(client.contribution(package("proc-macro2")).is_some());
+
+        // Packages without code in the binary have no contribution
+        assert!(client.contribution(package("quote")).is_none());
+    }
+}
@@ -0,0 +1,375 @@
+//! Static scanning of build scripts (`build.rs`) for risky behavior
+//!
+//! Build scripts are run with the privileges of the user running
+//! `cargo build`, so they can access the network, read secrets from the
+//! environment or start other processes. This module does not execute
+//! anything; it only looks for identifiers and string literals that hint at
+//! such behavior, so both false positives and false negatives are expected.
+//!
+//! Unsafe code in build scripts is counted in the same categories as
+//! `cargo-geiger` uses, but by parsing the source files directly, since
+//! `cargo-geiger` does not report build scripts separately.
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use cargo_metadata::Package;
+use syn::{
+    visit::{self, Visit},
+    Expr, ExprUnsafe, Ident, ImplItemFn, ItemFn, ItemImpl, ItemTrait, LitStr,
+};
+
+use crate::{
+    geiger::{GeigerCategories, GeigerCount},
+    util,
+};
+
+/// Identifiers of types and crates used for network access
+const NETWORK_IDENTS: [&str; 11] = [
+    "TcpStream",
+    "TcpListener",
+    "UdpSocket",
+    "ToSocketAddrs",
+    "reqwest",
+    "ureq",
+    "curl",
+    "hyper",
+    "attohttpc",
+    "minreq",
+    "isahc",
+];
+
+/// URL schemes of string literals considered network access
+const NETWORK_SCHEMES: [&str; 4] = ["http://", "https://", "ftp://", "ws://"];
+
+/// Identifiers of functions enumerating the whole environment
+const ENVIRONMENT_IDENTS: [&str; 2] = ["vars", "vars_os"];
+
+/// Parts of environment variable names that often hold secrets
+const SECRET_ENV_PARTS: [&str; 8] = [
+    "TOKEN", "SECRET", "PASSWORD", "PASSWD", "API_KEY", "AWS_", "SSH_",
+    "GITHUB_",
+];
+
+/// Identifiers used when spawning processes
+const PROCESS_IDENTS: [&str; 2] = ["Command", "spawn"];
+
+/// A kind of risky behavior found in a build script
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BuildScriptRisk {
+    /// Uses network types or crates, or contains URLs
+    Network,
+
+    /// Enumerates all environment variables, or reads variables that
+    /// commonly hold secrets
+    Environment,
+
+    /// Spawns other processes
+    Process,
+}
+
+impl BuildScriptRisk {
+    /// The name of this risk as exposed in the schema
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildScriptRisk::Network => "network",
+            BuildScriptRisk::Environment => "environment",
+            BuildScriptRisk::Process => "process",
+        }
+    }
+}
+
+/// Collects identifiers and string literals in a parsed source file
+#[derive(Debug, Default)]
+struct RiskVisitor {
+    risks: BTreeSet<BuildScriptRisk>,
+}
+
+impl<'ast> Visit<'ast> for RiskVisitor {
+    fn visit_ident(&mut self, i: &'ast Ident) {
+        let ident = i.to_string();
+        let ident = ident.as_str();
+        if NETWORK_IDENTS.contains(&ident) {
+            self.risks.insert(BuildScriptRisk::Network);
+        }
+        if ENVIRONMENT_IDENTS.contains(&ident) {
+            self.risks.insert(BuildScriptRisk::Environment);
+        }
+        if PROCESS_IDENTS.contains(&ident) {
+            self.risks.insert(BuildScriptRisk::Process);
+        }
+    }
+
+    fn visit_lit_str(&mut self, i: &'ast LitStr) {
+        let value = i.value();
+        if NETWORK_SCHEMES.iter().any(|s| value.starts_with(s)) {
+            self.risks.insert(BuildScriptRisk::Network);
+        }
+        if SECRET_ENV_PARTS.iter().any(|p| value.contains(p)) {
+            self.risks.insert(BuildScriptRisk::Environment);
+        }
+    }
+}
+
+/// Finds risky behavior in the source of a single file
+///
+/// # Errors
+///
+/// Returns an error variant if the source cannot be parsed as Rust.
+pub fn risks_from_source(
+    source: &str,
+) -> Result<BTreeSet<BuildScriptRisk>, syn::Error> {
+    let file = syn::parse_file(source)?;
+    let mut visitor = RiskVisitor::default();
+    visitor.visit_file(&file);
+    Ok(visitor.risks)
+}
+
+/// Counts safe and unsafe code in a parsed source file
+///
+/// Expressions inside `unsafe` blocks and functions count as unsafe.
+#[derive(Debug, Default)]
+struct UnsafetyVisitor {
+    unsafety: GeigerCategories,
+
+    /// Number of enclosing `unsafe` blocks or functions
+    unsafe_depth: usize,
+}
+
+impl UnsafetyVisitor {
+    fn count(count: &mut GeigerCount, is_unsafe: bool) {
+        if is_unsafe {
+            count.unsafe_ += 1;
+        } else {
+            count.safe += 1;
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for UnsafetyVisitor {
+    fn visit_item_fn(&mut self, i: &'ast ItemFn) {
+        let is_unsafe = i.sig.unsafety.is_some();
+        Self::count(&mut self.unsafety.functions, is_unsafe);
+        self.unsafe_depth += usize::from(is_unsafe);
+        visit::visit_item_fn(self, i);
+        self.unsafe_depth -= usize::from(is_unsafe);
+    }
+
+    fn visit_impl_item_fn(&mut self, i: &'ast ImplItemFn) {
+        let is_unsafe = i.sig.unsafety.is_some();
+        Self::count(&mut self.unsafety.methods, is_unsafe);
+        self.unsafe_depth += usize::from(is_unsafe);
+        visit::visit_impl_item_fn(self, i);
+        self.unsafe_depth -= usize::from(is_unsafe);
+    }
+
+    fn visit_item_impl(&mut self, i: &'ast ItemImpl) {
+        Self::count(&mut self.unsafety.item_impls, i.unsafety.is_some());
+        visit::visit_item_impl(self, i);
+    }
+
+    fn visit_item_trait(&mut self, i: &'ast ItemTrait) {
+        Self::count(&mut self.unsafety.item_traits, i.unsafety.is_some());
+        visit::visit_item_trait(self, i);
+    }
+
+    fn visit_expr(&mut self, i: &'ast Expr) {
+        Self::count(&mut self.unsafety.exprs, self.unsafe_depth > 0);
+        visit::visit_expr(self, i);
+    }
+
+    fn visit_expr_unsafe(&mut self, i: &'ast ExprUnsafe) {
+        self.unsafe_depth += 1;
+        visit::visit_expr_unsafe(self, i);
+        self.unsafe_depth -= 1;
+    }
+}
+
+/// Counts safe and unsafe code in the source of a single file
+///
+/// # Errors
+///
+/// Returns an error variant if the source cannot be parsed as Rust.
+pub fn unsafety_from_source(
+    source: &str,
+) -> Result<GeigerCategories, syn::Error> {
+    let file = syn::parse_file(source)?;
+    let mut visitor = UnsafetyVisitor::default();
+    visitor.visit_file(&file);
+    Ok(visitor.unsafety)
+}
+
+/// The build script of a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildScript {
+    /// The main file of the build script, such as `build.rs`
+    pub path: PathBuf,
+
+    /// The directory of the manifest of the package
+    pub package_root: PathBuf,
+
+    /// All source files of the build script, see [`build_script_sources`]
+    pub sources: Vec<PathBuf>,
+}
+
+impl BuildScript {
+    /// Retrieves the build script of a package, if it has one
+    #[must_use]
+    pub fn from_package(package: &Package) -> Option<Self> {
+        let path = build_script_path(package)?;
+        let package_root = package.manifest_path.parent()?.into();
+        Some(Self {
+            path,
+            package_root,
+            sources: build_script_sources(package),
+        })
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.package_root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// The path to the main file relative to the package root
+    #[must_use]
+    pub fn relative_path(&self) -> String {
+        self.relative(&self.path)
+    }
+
+    /// The paths to all source files relative to the package root
+    #[must_use]
+    pub fn relative_sources(&self) -> Vec<String> {
+        self.sources.iter().map(|p| self.relative(p)).collect()
+    }
+
+    /// Counts safe and unsafe code in all source files
+    ///
+    /// Files that cannot be read or parsed are skipped.
+    #[must_use]
+    pub fn unsafety(&self) -> GeigerCategories {
+        self.sources
+            .iter()
+            .filter_map(|p| {
+                let source = fs::read_to_string(p).ok()?;
+                unsafety_from_source(&source)
+                    .map_err(|e| {
+                        eprintln!(
+                            "skipping build script {} due to error: {e}",
+                            p.to_string_lossy()
+                        );
+                    })
+                    .ok()
+            })
+            .fold(GeigerCategories::default(), |acc, u| acc + u)
+    }
+}
+
+/// Retrieves the path to the build script of a package, if it has one
+#[must_use]
+pub fn build_script_path(package: &Package) -> Option<PathBuf> {
+    package
+        .targets
+        .iter()
+        .find(|t| t.is_custom_build())
+        .map(|t| t.src_path.clone().into_std_path_buf())
+}
+
+/// If a package has a build script
+///
+/// Besides the build script target, a `build.rs` file in the package root
+/// counts as a build script, even if it is disabled using `build = false`,
+/// to err on the side of caution.
+#[must_use]
+pub fn has_build_script(package: &Package) -> bool {
+    build_script_path(package).is_some()
+        || package
+            .manifest_path
+            .parent()
+            .is_some_and(|root| root.join("build.rs").is_file())
+}
+
+/// Retrieves all source files of the build script of a package
+///
+/// If the build script is not in the package root (such as
+/// `build/main.rs`), all Rust files in its directory are included, since they
+/// are most likely its modules.
+pub(crate) fn build_script_sources(package: &Package) -> Vec<PathBuf> {
+    let Some(path) = build_script_path(package) else {
+        return Vec::new();
+    };
+
+    let package_root = package.manifest_path.parent().map(|p| p.as_std_path());
+    match path.parent() {
+        Some(dir) if Some(dir) != package_root => util::rust_source_files(dir),
+        _ => vec![path],
+    }
+}
+
+/// Finds risky behavior in the build script of a package
+///
+/// Files that cannot be read or parsed are skipped. Packages without build
+/// scripts have no risks.
+pub(crate) fn get_build_script_risks(
+    package: &Package,
+) -> BTreeSet<BuildScriptRisk> {
+    build_script_sources(package)
+        .iter()
+        .filter_map(|p| {
+            let source = fs::read_to_string(p).ok()?;
+            risks_from_source(&source)
+                .map_err(|e| {
+                    eprintln!(
+                        "skipping build script {} due to error: {e}",
+                        p.to_string_lossy()
+                    );
+                })
+                .ok()
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{risks_from_source, unsafety_from_source, BuildScriptRisk};
+
+    #[test_case(r#"fn main() { println!("cargo:rerun-if-changed=build.rs"); }"# => Vec::<BuildScriptRisk>::new() ; "harmless")]
+    #[test_case(r#"fn main() { let _ = std::env::var("OUT_DIR"); }"# => Vec::<BuildScriptRisk>::new() ; "reading cargo variables")]
+    #[test_case("use std::net::TcpStream; fn main() {}" => vec![BuildScriptRisk::Network] ; "network type")]
+    #[test_case(r#"fn main() { reqwest::blocking::get("https://example.com"); }"# => vec![BuildScriptRisk::Network] ; "http client")]
+    #[test_case("fn main() { for (k, v) in std::env::vars() {} }" => vec![BuildScriptRisk::Environment] ; "enumerate environment")]
+    #[test_case(r#"fn main() { std::env::var("AWS_SECRET_ACCESS_KEY"); }"# => vec![BuildScriptRisk::Environment] ; "secret variable")]
+    #[test_case(r#"fn main() { std::process::Command::new("sh").spawn(); }"# => vec![BuildScriptRisk::Process] ; "spawn process")]
+    fn build_script_risks(source: &str) -> Vec<BuildScriptRisk> {
+        risks_from_source(source).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn build_script_unsafety() {
+        let unsafety = unsafety_from_source(
+            r#"
+            struct Foo;
+            unsafe impl Send for Foo {}
+            impl Foo {
+                unsafe fn bar(&self) -> i32 { 1 + 2 }
+            }
+            fn main() {
+                let foo = Foo;
+                let _ = unsafe { foo.bar() };
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(unsafety.functions.safe, 1);
+        assert_eq!(unsafety.item_impls.unsafe_, 1);
+        assert_eq!(unsafety.methods.unsafe_, 1);
+        assert!(unsafety.exprs.unsafe_ > 0 && unsafety.exprs.safe > 0);
+    }
+}
@@ -0,0 +1,152 @@
+//! Minimal C API, so that tools not written in Rust can embed `indicate`
+//! instead of running `cargo-indicate`
+//!
+//! Only available with the `c-api` feature. A shared library is built using
+//!
+//! ```text
+//! cargo rustc -p indicate --release --features c-api --crate-type cdylib
+//! ```
+//!
+//! and the functions are declared in `include/indicate.h`. Queries and
+//! results are passed as JSON strings; a query is a JSON object with a
+//! `query` and `args`, the same as a `.json` query file.
+//!
+//! Results are returned as `{ "results": [...] }`, or `{ "error": "..." }`
+//! if the query could not be executed. Strings returned must be freed using
+//! [`indicate_free_string`].
+#![allow(unsafe_code)]
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+};
+
+use serde_json::json;
+
+use crate::{
+    execute_query, query::FullQuery, util::transparent_results, ManifestPath,
+};
+
+/// Runs a query against the package at a path, returning the results or an
+/// error message
+fn run_query(manifest_path: &str, query_json: &str) -> Result<String, String> {
+    let query = serde_json::from_str::<FullQuery>(query_json)
+        .map_err(|e| format!("could not parse query due to error: {e}"))?;
+    query.validate().map_err(|e| e.to_string())?;
+
+    // Resolving metadata and executing queries panics on errors, which must
+    // not unwind into foreign code
+    let results = catch_unwind(AssertUnwindSafe(|| {
+        transparent_results(execute_query(
+            &query,
+            ManifestPath::from(manifest_path),
+            None,
+        ))
+    }))
+    .map_err(|panic| {
+        panic
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| panic.downcast_ref::<&str>().map(|s| (*s).to_string()))
+            .unwrap_or_else(|| String::from("query execution panicked"))
+    })?;
+
+    Ok(json!({ "results": results }).to_string())
+}
+
+/// Reads a C string argument, with an error message if it is null or not
+/// UTF-8
+///
+/// # Safety
+///
+/// `s` must be null, or a valid pointer to a null-terminated string.
+unsafe fn read_arg<'a>(
+    s: *const c_char,
+    name: &str,
+) -> Result<&'a str, String> {
+    if s.is_null() {
+        return Err(format!("{name} is null"));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| format!("{name} is not valid UTF-8: {e}"))
+}
+
+/// Runs a query against the package at `manifest_path` (a `Cargo.toml` file,
+/// or a directory containing one), returning the results as a JSON string
+///
+/// The returned string must be freed using [`indicate_free_string`]. Null is
+/// only returned if the response could not be converted to a C string.
+///
+/// # Safety
+///
+/// Both arguments must be null, or valid pointers to null-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn indicate_run_query(
+    manifest_path: *const c_char,
+    query_json: *const c_char,
+) -> *mut c_char {
+    let response = read_arg(manifest_path, "manifest_path")
+        .and_then(|m| Ok((m, read_arg(query_json, "query_json")?)))
+        .and_then(|(m, q)| run_query(m, q))
+        .unwrap_or_else(|e| json!({ "error": e }).to_string());
+
+    CString::new(response).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Frees a string returned by `indicate`
+///
+/// # Safety
+///
+/// `s` must be null, or a string returned by `indicate` that has not been
+/// freed already.
+#[no_mangle]
+pub unsafe extern "C" fn indicate_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::{CStr, CString};
+
+    use test_case::test_case;
+
+    use super::{indicate_free_string, indicate_run_query};
+
+    fn run(manifest_path: &str, query_json: &str) -> serde_json::Value {
+        let manifest_path = CString::new(manifest_path).unwrap();
+        let query_json = CString::new(query_json).unwrap();
+        unsafe {
+            let res =
+                indicate_run_query(manifest_path.as_ptr(), query_json.as_ptr());
+            let value =
+                serde_json::from_str(CStr::from_ptr(res).to_str().unwrap())
+                    .unwrap();
+            indicate_free_string(res);
+            value
+        }
+    }
+
+    #[test]
+    fn run_query() {
+        let res = run(
+            "test_data/fake_crates/simple_deps",
+            r#"{ "query": "{ RootPackage { name @output } }", "args": {} }"#,
+        );
+        assert_eq!(
+            res,
+            serde_json::json!({ "results": [{ "name": "simple_deps" }] })
+        );
+    }
+
+    #[test_case("test_data/fake_crates/simple_deps", "not json" ; "invalid json")]
+    #[test_case("test_data/fake_crates/simple_deps", r#"{ "query": "{ NotAnEdge { name @output } }", "args": {} }"# ; "invalid query")]
+    #[test_case("test_data/fake_crates/does_not_exist", r#"{ "query": "{ RootPackage { name @output } }", "args": {} }"# ; "missing package")]
+    fn errors_are_returned(manifest_path: &str, query_json: &str) {
+        let res = run(manifest_path, query_json);
+        assert!(res["error"].is_string(), "{res}");
+    }
+}
@@ -0,0 +1,181 @@
+//! A report of what queries can use in the current environment
+//!
+//! Some parts of the schema rely on external services configured through
+//! environment variables, or on external binaries such as `cargo-geiger`.
+//! The report makes it possible to check that an environment is set up
+//! correctly before running queries, such as in a CI pipeline.
+use std::process::Command;
+
+use async_graphql_parser::{
+    parse_schema,
+    types::{TypeKind, TypeSystemDefinition},
+};
+use serde::Serialize;
+
+use crate::RAW_SCHEMA;
+
+/// Name of the type containing all starting edges
+const ROOT_QUERY: &str = "RootQuery";
+
+/// What queries can use in the current environment
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    /// Names of the starting edges of the schema, such as `Dependencies`
+    pub entry_points: Vec<String>,
+    pub integrations: Vec<Integration>,
+    pub external_binaries: Vec<ExternalBinary>,
+}
+
+/// An external service used to resolve parts of the schema
+#[derive(Debug, Clone, Serialize)]
+pub struct Integration {
+    pub name: &'static str,
+
+    /// Environment variables that must be set to use the service
+    pub required_env: Vec<&'static str>,
+
+    /// If all environment variables in `required_env` are set
+    pub configured: bool,
+}
+
+/// A binary that must be installed to use parts of the schema or command
+/// line options
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalBinary {
+    pub name: &'static str,
+
+    /// What the binary is used for
+    pub used_for: &'static str,
+    pub installed: bool,
+
+    /// The first line of the output of `<name> --version`, if installed
+    pub version: Option<String>,
+}
+
+impl Capabilities {
+    /// Checks the environment variables and binaries available
+    ///
+    /// Each external binary is executed once to retrieve its version.
+    #[must_use]
+    pub fn detect() -> Self {
+        Self {
+            entry_points: entry_points(),
+            integrations: vec![
+                Integration::new(
+                    "GitHub",
+                    vec!["USER_AGENT", "GITHUB_API_TOKEN"],
+                ),
+                Integration::new("GitLab", Vec::new()),
+                Integration::new("Gitea", Vec::new()),
+                Integration::new("sourcehut", vec!["SRHT_API_TOKEN"]),
+                Integration::new("crates.io", vec!["USER_AGENT"]),
+                Integration::new("crates.io sparse index", Vec::new()),
+                Integration::new("OSV.dev", Vec::new()),
+                Integration::new("EPSS", Vec::new()),
+                Integration::new("CISA KEV", Vec::new()),
+                Integration::new("NVD", Vec::new()),
+                Integration::new("docs.rs", Vec::new()),
+                Integration::new("deps.dev", Vec::new()),
+                Integration::new("OpenSSF Scorecard", Vec::new()),
+                Integration::new("advisory-db", Vec::new()),
+            ],
+            external_binaries: vec![
+                ExternalBinary::detect("cargo", "resolving package metadata"),
+                ExternalBinary::detect("cargo-geiger", "the `geiger` edge"),
+                ExternalBinary::detect("cargo-crev", "the `crevReviews` edge"),
+                ExternalBinary::detect(
+                    "cargo-udeps",
+                    "the `isUnused` property",
+                ),
+                ExternalBinary::detect("cargo-clippy", "the `clippy` edge"),
+                ExternalBinary::detect(
+                    "cargo-bloat",
+                    "the `binarySizeContribution` edge",
+                ),
+                ExternalBinary::detect(
+                    "cargo-semver-checks",
+                    "the `semverViolations` edge",
+                ),
+                ExternalBinary::detect("git", "`--changed-since`"),
+            ],
+        }
+    }
+}
+
+impl Integration {
+    fn new(name: &'static str, required_env: Vec<&'static str>) -> Self {
+        let configured =
+            required_env.iter().all(|v| std::env::var_os(v).is_some());
+        Self {
+            name,
+            required_env,
+            configured,
+        }
+    }
+}
+
+impl ExternalBinary {
+    pub(crate) fn detect(name: &'static str, used_for: &'static str) -> Self {
+        let version = Command::new(name)
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| {
+                String::from_utf8_lossy(&o.stdout)
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            });
+        Self {
+            name,
+            used_for,
+            installed: version.is_some(),
+            version,
+        }
+    }
+}
+
+/// Parses the names of all starting edges of the schema
+fn entry_points() -> Vec<String> {
+    let document = parse_schema(RAW_SCHEMA)
+        .unwrap_or_else(|e| panic!("could not parse schema due to error: {e}"));
+    document
+        .definitions
+        .into_iter()
+        .find_map(|d| match d {
+            TypeSystemDefinition::Type(t) if t.node.name.node == ROOT_QUERY => {
+                match t.node.kind {
+                    TypeKind::Object(o) => Some(o.fields),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|f| f.node.name.node.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::entry_points;
+
+    #[test]
+    fn schema_entry_points() {
+        assert_eq!(
+            entry_points(),
+            vec![
+                "RootPackage",
+                "Dependencies",
+                "TransitiveDependencies",
+                "WorkspaceMembers",
+                "AuditSummary",
+                "LicenseSummary"
+            ]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Packages added or updated compared to some git reference
+//!
+//! Used to only analyze what a change (such as a pull request) introduces to
+//! the dependency graph, by comparing the current `Cargo.lock` file to the one
+//! at a git reference such as `origin/main`.
+use std::{collections::HashSet, fs, path::Path, process::Command};
+
+use serde::Deserialize;
+
+use crate::{errors::ChangedSinceError, NameVersion};
+
+/// The part of a `Cargo.lock` file we care about, valid for all lock file
+/// versions
+#[derive(Debug, Deserialize)]
+struct LockedPackages {
+    #[serde(default)]
+    package: Vec<NameVersion>,
+}
+
+/// Parses the names and versions of all packages in a `Cargo.lock` file
+fn parse_lockfile(
+    raw_lockfile: &str,
+) -> Result<HashSet<NameVersion>, ChangedSinceError> {
+    let locked: LockedPackages = toml::from_str(raw_lockfile)
+        .map_err(|e| ChangedSinceError::InvalidLockfile(e.to_string()))?;
+    Ok(locked.package.into_iter().collect())
+}
+
+/// Reads the contents of a file as it was at a git reference, using the
+/// repository the file is located in
+fn read_at_ref(
+    path: &Path,
+    git_ref: &str,
+) -> Result<String, ChangedSinceError> {
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Err(ChangedSinceError::Git(format!(
+            "{} is not a file",
+            path.to_string_lossy()
+        )));
+    };
+
+    // `./` makes git resolve the path relative to the current directory,
+    // rather than the repository root
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .arg("show")
+        .arg(format!("{git_ref}:./{}", file_name.to_string_lossy()))
+        .output()
+        .map_err(|e| ChangedSinceError::Git(e.to_string()))?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout)
+            .map_err(|e| ChangedSinceError::InvalidLockfile(e.to_string()))
+    } else {
+        Err(ChangedSinceError::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// Retrieves the packages in `new_lockfile` that are not in `old_lockfile`,
+/// i.e. packages that were added or had their version changed
+///
+/// # Errors
+///
+/// Returns an error variant if either lock file cannot be parsed.
+pub fn changed_packages(
+    old_lockfile: &str,
+    new_lockfile: &str,
+) -> Result<HashSet<NameVersion>, ChangedSinceError> {
+    let old = parse_lockfile(old_lockfile)?;
+    let new = parse_lockfile(new_lockfile)?;
+    Ok(new.difference(&old).cloned().collect())
+}
+
+/// Retrieves the packages in a `Cargo.lock` file that were added or had their
+/// version changed since the git reference `git_ref`
+///
+/// # Errors
+///
+/// Returns an error variant if
+///
+/// - The lock file cannot be read, or
+/// - `git` fails to retrieve the lock file at `git_ref`, such as if it was not
+///   tracked at that time, or
+/// - Either version of the lock file cannot be parsed
+pub fn changed_packages_since(
+    lockfile_path: &Path,
+    git_ref: &str,
+) -> Result<HashSet<NameVersion>, ChangedSinceError> {
+    let new_lockfile = fs::read_to_string(lockfile_path)
+        .map_err(|e| ChangedSinceError::InvalidLockfile(e.to_string()))?;
+    let old_lockfile = read_at_ref(lockfile_path, git_ref)?;
+    changed_packages(&old_lockfile, &new_lockfile)
+}
+
+#[cfg(test)]
+mod test {
+    use rustsec::Version;
+
+    use super::changed_packages;
+    use crate::{source::PackageSource, NameVersion};
+
+    const OLD_LOCKFILE: &str = r#"
+version = 3
+
+[[package]]
+name = "libc"
+version = "0.2.139"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "syn"
+version = "1.0.107"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    const NEW_LOCKFILE: &str = r#"
+version = 4
+
+[[package]]
+name = "libc"
+version = "0.2.139"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "syn"
+version = "2.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "quote"
+version = "1.0.23"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    #[test]
+    fn added_and_updated_packages() {
+        let changed = changed_packages(OLD_LOCKFILE, NEW_LOCKFILE).unwrap();
+        assert_eq!(changed.len(), 2);
+        assert!(changed.contains(&NameVersion::new(
+            String::from("syn"),
+            Version::new(2, 0, 0)
+        )));
+        assert!(changed.contains(&NameVersion::new(
+            String::from("quote"),
+            Version::new(1, 0, 23)
+        )));
+    }
+
+    #[test]
+    fn package_from_other_source_is_changed() {
+        let fork_lockfile = OLD_LOCKFILE.replace(
+            r#"name = "libc"
+version = "0.2.139"
+source = "registry+https://github.com/rust-lang/crates.io-index""#,
+            r#"name = "libc"
+version = "0.2.139"
+source = "git+https://github.com/someone/libc?branch=fix#0227f048f""#,
+        );
+        let changed = changed_packages(OLD_LOCKFILE, &fork_lockfile).unwrap();
+        assert_eq!(changed.len(), 1);
+        assert!(changed.contains(&NameVersion::with_source(
+            String::from("libc"),
+            Version::new(0, 2, 139),
+            PackageSource::Git {
+                url: String::from("https://github.com/someone/libc"),
+                rev: Some(String::from("0227f048f")),
+            }
+        )));
+    }
+
+    #[test]
+    fn invalid_lockfile() {
+        assert!(
+            changed_packages("[[package]]\nname = 3", NEW_LOCKFILE).is_err()
+        );
+    }
+}
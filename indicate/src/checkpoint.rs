@@ -0,0 +1,411 @@
+//! Checkpoints of long-running executions of several queries, so that an
+//! interrupted execution can be resumed
+//!
+//! A [`Checkpoint`] records the results of each query executed using
+//! [`execute_query_resumable`], and if a query was interrupted (see
+//! [`interrupt`](crate::interrupt)), how many of its starting vertices (such
+//! as the packages of `Dependencies`) were completed. When resumed, completed
+//! queries are not executed again, and interrupted queries continue after the
+//! completed starting vertices. External calls made for vertices that are
+//! resolved again are only avoided if they are cached, such as by the GitHub
+//! client.
+//!
+//! Progress relies on rows being produced one starting vertex at a time,
+//! which is the case since all resolvers of `indicate` are lazy.
+//!
+//! A checkpoint is only resumed for the same package, features and maximum
+//! number of results it was created with; otherwise it is started over.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+};
+
+use cargo_metadata::CargoOpt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use trustfall::{execute_query as trustfall_execute_query, FieldValue};
+
+use crate::{
+    adapter::until_interrupted, query::FullQuery, IndicateAdapter,
+    QueryResults, SCHEMA,
+};
+
+/// A row of query results
+pub type Row = BTreeMap<Arc<str>, FieldValue>;
+
+/// The progress of one query
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueryProgress {
+    /// If all rows of the query have been computed
+    pub complete: bool,
+
+    /// The number of starting vertices whose rows have all been computed
+    pub starting_vertices_done: usize,
+    pub rows: Vec<Row>,
+}
+
+/// What the queries of a checkpoint were executed against
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointContext {
+    pub manifest_path: PathBuf,
+
+    /// The features used, as `cargo` arguments
+    pub features: Vec<String>,
+    pub max_results: Option<usize>,
+}
+
+impl CheckpointContext {
+    fn new(adapter: &IndicateAdapter, max_results: Option<usize>) -> Self {
+        let features = adapter
+            .features()
+            .iter()
+            .map(|f| match f {
+                CargoOpt::AllFeatures => String::from("--all-features"),
+                CargoOpt::NoDefaultFeatures => {
+                    String::from("--no-default-features")
+                }
+                CargoOpt::SomeFeatures(s) => {
+                    format!("--features={}", s.join(","))
+                }
+            })
+            .collect();
+        Self {
+            manifest_path: adapter.manifest_path().as_path().to_path_buf(),
+            features,
+            max_results,
+        }
+    }
+}
+
+/// The progress of a number of queries, identified by their query and
+/// arguments
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    #[serde(default)]
+    context: Option<CheckpointContext>,
+    queries: BTreeMap<String, QueryProgress>,
+}
+
+impl Checkpoint {
+    /// Reads a checkpoint from a file, or creates an empty checkpoint if the
+    /// file does not exist
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the file cannot be read, or is not a
+    /// valid checkpoint.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => serde_json::from_str(&s)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes this checkpoint to a file
+    ///
+    /// The checkpoint is first written to a temporary file next to it, so
+    /// that an existing checkpoint is never left half-written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the file cannot be written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut tmp = PathBuf::from(path);
+        tmp.as_mut_os_string().push(".tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, path)
+    }
+
+    /// The progress of a query, if it has been executed
+    #[must_use]
+    pub fn progress(&self, query: &FullQuery) -> Option<&QueryProgress> {
+        self.queries.get(&fingerprint(query))
+    }
+
+    /// Replaces the progress of a query
+    pub fn record(&mut self, query: &FullQuery, progress: QueryProgress) {
+        self.queries.insert(fingerprint(query), progress);
+    }
+
+    /// If all queries recorded have been completed
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.queries.values().all(|p| p.complete)
+    }
+
+    /// What the queries recorded were executed against, if any were
+    #[must_use]
+    pub fn context(&self) -> Option<&CheckpointContext> {
+        self.context.as_ref()
+    }
+
+    /// Sets what the queries are executed against, discarding all progress
+    /// recorded if it differs from the current one
+    ///
+    /// Returns `true` if progress was discarded.
+    pub fn set_context(&mut self, context: CheckpointContext) -> bool {
+        let discarded = self.context.as_ref().is_some_and(|c| *c != context)
+            && !self.queries.is_empty();
+        if discarded {
+            self.queries.clear();
+        }
+        self.context = Some(context);
+        discarded
+    }
+}
+
+/// Identifies a query by the SHA-256 hash of its query and arguments
+fn fingerprint(query: &FullQuery) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(query.query.as_bytes());
+    hasher.update(
+        serde_json::to_vec(&query.resolved_args())
+            .expect("could not serialize query arguments"),
+    );
+    format!("{:x}", hasher.finalize())
+}
+
+/// Executes a query like
+/// [`execute_query_with_adapter`](crate::execute_query_with_adapter), but
+/// resumes from its progress in `checkpoint`, and records its new progress
+///
+/// Returns all rows computed, including those of previous executions, and if
+/// the query stopped early since it was interrupted. If so, rows of the
+/// starting vertex being resolved are returned, but not recorded, since it
+/// may not have been completed.
+///
+/// If `checkpoint` was created for another package, other features or
+/// another `max_results`, its progress is discarded and all queries are
+/// executed from the start.
+///
+/// # Panics
+///
+/// Panics if the query is not valid (see [`FullQuery::validate`]), or if it
+/// could not be executed.
+pub fn execute_query_resumable(
+    checkpoint: &mut Checkpoint,
+    query: &FullQuery,
+    adapter: Rc<IndicateAdapter>,
+    max_results: Option<usize>,
+) -> QueryResults {
+    if checkpoint.set_context(CheckpointContext::new(&adapter, max_results)) {
+        eprintln!(
+            "checkpoint was created for another package, features or maximum \
+            number of results, starting over"
+        );
+    }
+    let previous = checkpoint.progress(query).cloned().unwrap_or_default();
+    if previous.complete {
+        return QueryResults {
+            rows: previous.rows,
+            partial: false,
+        };
+    }
+    if let Err(e) = query.validate() {
+        panic!("{e}, query was:\n{}", query.query);
+    }
+
+    adapter.skip_starting_vertices(previous.starting_vertices_done);
+    let resolved = adapter.starting_vertices_resolved();
+    let stopped_early = adapter.stopped_early();
+    stopped_early.set(false);
+    let max_new = max_results
+        .unwrap_or(usize::MAX)
+        .saturating_sub(previous.rows.len());
+
+    // The starting vertex each row belongs to, counting from 1
+    let new_rows = match trustfall_execute_query(
+        &SCHEMA,
+        adapter,
+        query.query.as_str(),
+        query.resolved_args(),
+    ) {
+        Ok(res) => {
+            until_interrupted(res.take(max_new), Rc::clone(&stopped_early))
+                .map(|row| (resolved.get(), row))
+                .collect::<Vec<_>>()
+        }
+        Err(e) => panic!(
+            "Could not execute query due to error: {e:#?}, query was: {query:#?}"
+        ),
+    };
+
+    let mut progress = previous;
+    if stopped_early.get() {
+        // The starting vertex being resolved when interrupted
+        let current = resolved.get();
+        progress.starting_vertices_done = current
+            .saturating_sub(1)
+            .max(progress.starting_vertices_done);
+        let mut rows = progress.rows.clone();
+        for (vertex, row) in new_rows {
+            if vertex < current {
+                progress.rows.push(row.clone());
+            }
+            rows.push(row);
+        }
+        checkpoint.record(query, progress);
+        QueryResults {
+            rows,
+            partial: true,
+        }
+    } else {
+        progress.complete = true;
+        progress.starting_vertices_done = resolved.get();
+        progress
+            .rows
+            .extend(new_rows.into_iter().map(|(_, row)| row));
+        checkpoint.record(query, progress.clone());
+        QueryResults {
+            rows: progress.rows,
+            partial: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::{
+        execute_query_with_adapter, query::FullQueryBuilder, IndicateAdapter,
+        ManifestPath,
+    };
+
+    use super::{execute_query_resumable, Checkpoint, QueryProgress};
+
+    fn adapter() -> Rc<IndicateAdapter> {
+        adapter_for("simple_deps")
+    }
+
+    fn adapter_for(fake_crate: &str) -> Rc<IndicateAdapter> {
+        Rc::new(IndicateAdapter::new(ManifestPath::from(format!(
+            "test_data/fake_crates/{fake_crate}"
+        ))))
+    }
+
+    #[test]
+    fn completed_queries_are_recorded() {
+        let query = FullQueryBuilder::new(String::from(
+            "{ Dependencies(includeRoot: false) { name @output } }",
+        ))
+        .build();
+        let mut checkpoint = Checkpoint::default();
+        let res =
+            execute_query_resumable(&mut checkpoint, &query, adapter(), None);
+        assert!(!res.partial);
+
+        let rows = res.rows;
+        assert_eq!(rows, execute_query_with_adapter(&query, adapter(), None));
+        let progress = checkpoint.progress(&query).unwrap();
+        assert!(progress.complete);
+        assert_eq!(progress.starting_vertices_done, rows.len());
+        assert!(checkpoint.is_complete());
+    }
+
+    #[test]
+    fn resume_after_completed_starting_vertices() {
+        let query = FullQueryBuilder::new(String::from(
+            "{ Dependencies(includeRoot: false) { name @output } }",
+        ))
+        .build();
+        let all_rows = execute_query_with_adapter(&query, adapter(), None);
+
+        // As if interrupted after the first dependency
+        let mut checkpoint = Checkpoint::default();
+        checkpoint.record(
+            &query,
+            QueryProgress {
+                complete: false,
+                starting_vertices_done: 1,
+                rows: all_rows[..1].to_vec(),
+            },
+        );
+        assert!(!checkpoint.is_complete());
+
+        let rows =
+            execute_query_resumable(&mut checkpoint, &query, adapter(), None)
+                .rows;
+        assert_eq!(rows, all_rows);
+        assert!(checkpoint.is_complete());
+    }
+
+    #[test]
+    fn resume_with_other_package_starts_over() {
+        let query = FullQueryBuilder::new(String::from(
+            "{ RootPackage { name @output } }",
+        ))
+        .build();
+        let mut checkpoint = Checkpoint::default();
+        let simple_deps_rows =
+            execute_query_resumable(&mut checkpoint, &query, adapter(), None)
+                .rows;
+        assert!(checkpoint.progress(&query).unwrap().complete);
+
+        let other = adapter_for("dev_deps");
+        let rows = execute_query_resumable(
+            &mut checkpoint,
+            &query,
+            Rc::clone(&other),
+            None,
+        )
+        .rows;
+        assert_ne!(rows, simple_deps_rows);
+        assert_eq!(
+            rows,
+            execute_query_with_adapter(&query, adapter_for("dev_deps"), None)
+        );
+        assert_eq!(
+            checkpoint.context().unwrap().manifest_path,
+            other.manifest_path().as_path()
+        );
+    }
+
+    #[test]
+    fn resume_with_other_max_results_starts_over() {
+        let query = FullQueryBuilder::new(String::from(
+            "{ Dependencies(includeRoot: false) { name @output } }",
+        ))
+        .build();
+        let mut checkpoint = Checkpoint::default();
+        let rows = execute_query_resumable(
+            &mut checkpoint,
+            &query,
+            adapter(),
+            Some(1),
+        )
+        .rows;
+        assert_eq!(rows.len(), 1);
+
+        let rows =
+            execute_query_resumable(&mut checkpoint, &query, adapter(), None)
+                .rows;
+        assert_eq!(rows, execute_query_with_adapter(&query, adapter(), None));
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir().join("indicate-checkpoint-test.json");
+        let query = FullQueryBuilder::new(String::from(
+            "{ RootPackage { name @output version @output } }",
+        ))
+        .build();
+        let mut checkpoint = Checkpoint::default();
+        execute_query_resumable(&mut checkpoint, &query, adapter(), None);
+
+        checkpoint.save(&path).unwrap();
+        assert_eq!(Checkpoint::load(&path).unwrap(), checkpoint);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(Checkpoint::load(&path).unwrap(), Checkpoint::default());
+    }
+}
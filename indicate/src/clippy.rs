@@ -0,0 +1,221 @@
+//! Diagnostics of [Clippy](https://github.com/rust-lang/rust-clippy) for
+//! the source of a package
+//!
+//! Dependencies are only linted with `--cap-lints allow` when building a
+//! workspace, so each package is linted on its own by running
+//! `cargo clippy --message-format json` with its manifest. The build output
+//! is kept in a shared directory in the temporary directory of the system,
+//! so that dependencies are only built once. As when running `cargo clippy`
+//! by hand, a `Cargo.lock` is created in the source of packages without one.
+//!
+//! Each line of the output is a JSON message, of which only the compiler
+//! messages are used, on the form (some fields omitted)
+//! ```json
+//! {
+//!     "reason": "compiler-message",
+//!     "manifest_path": "/path/to/libc-0.2.139/Cargo.toml",
+//!     "message": {
+//!         "level": "warning",
+//!         "code": { "code": "clippy::needless_return" },
+//!         "spans": [{ "file_name": "src/lib.rs" }]
+//!     }
+//! }
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    rc::Rc,
+};
+
+use serde::Deserialize;
+
+use crate::NameVersion;
+
+/// The directory in the temporary directory of the system where packages
+/// are built
+pub const CLIPPY_TARGET_DIR: &str = "indicate-clippy";
+
+/// A line of the output of `cargo clippy --message-format json`, some
+/// fields omitted
+#[derive(Debug, Clone, Deserialize)]
+struct CargoMessage {
+    reason: String,
+    manifest_path: Option<PathBuf>,
+    message: Option<CompilerMessage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CompilerMessage {
+    level: String,
+    code: Option<DiagnosticCode>,
+    #[serde(default)]
+    spans: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct DiagnosticCode {
+    code: String,
+}
+
+/// The number of diagnostics of a lint, or of an error code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClippyLint {
+    /// Such as `clippy::needless_return` or `E0308`, `None` for diagnostics
+    /// without a code
+    pub name: Option<String>,
+
+    /// Such as `warning` or `error`
+    pub level: String,
+    pub count: u64,
+}
+
+/// The diagnostics of Clippy for the source of a package
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClippyDiagnostics {
+    /// Sorted by level, and then by name
+    pub lints: Vec<ClippyLint>,
+}
+
+impl ClippyDiagnostics {
+    /// The total number of diagnostics of a level, such as `warning`
+    #[must_use]
+    pub fn count(&self, level: &str) -> u64 {
+        self.lints
+            .iter()
+            .filter(|l| l.level == level)
+            .map(|l| l.count)
+            .sum()
+    }
+}
+
+/// Parses the diagnostics for the package of `manifest_path` in the output
+/// of `cargo clippy --message-format json`
+///
+/// Messages of other packages, and summaries such as
+/// `2 warnings emitted` that have no source, are skipped.
+#[must_use]
+pub fn parse_diagnostics(
+    output: &str,
+    manifest_path: &Path,
+) -> ClippyDiagnostics {
+    let mut counts: BTreeMap<(String, Option<String>), u64> = BTreeMap::new();
+    for line in output.lines() {
+        let Ok(message) = serde_json::from_str::<CargoMessage>(line) else {
+            continue;
+        };
+        if message.reason != "compiler-message"
+            || message.manifest_path.as_deref() != Some(manifest_path)
+        {
+            continue;
+        }
+        let Some(m) = message.message else {
+            continue;
+        };
+        if m.spans.is_empty() && m.code.is_none() {
+            continue;
+        }
+        *counts.entry((m.level, m.code.map(|c| c.code))).or_default() += 1;
+    }
+
+    ClippyDiagnostics {
+        lints: counts
+            .into_iter()
+            .map(|((level, name), count)| ClippyLint { name, level, count })
+            .collect(),
+    }
+}
+
+/// Runs Clippy for the source of packages, with caching
+#[derive(Debug, Clone, Default)]
+pub struct ClippyClient {
+    /// Cache between a package and its diagnostics
+    ///
+    /// Packages where Clippy could not be run are cached as `None`, so that
+    /// they are not retried during the same query.
+    cache: HashMap<NameVersion, Option<Rc<ClippyDiagnostics>>>,
+}
+
+impl ClippyClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs Clippy for the package of `manifest_path`
+    ///
+    /// Will return `None` if `cargo clippy` could not be started, or if the
+    /// package could not be built, and will cache this package as such.
+    /// Lint errors in a package that otherwise builds are included.
+    pub fn diagnostics(
+        &mut self,
+        id: &NameVersion,
+        manifest_path: &Path,
+    ) -> Option<Rc<ClippyDiagnostics>> {
+        if let Some(cached) = self.cache.get(id) {
+            return cached.clone();
+        }
+
+        let output = Command::new("cargo")
+            .args(["clippy", "--message-format", "json"])
+            .arg("--manifest-path")
+            .arg(manifest_path)
+            .arg("--target-dir")
+            .arg(std::env::temp_dir().join(CLIPPY_TARGET_DIR))
+            .stdin(Stdio::null())
+            .output();
+        let res = match output {
+            Ok(o) => {
+                let stdout = String::from_utf8_lossy(&o.stdout);
+                let diagnostics = parse_diagnostics(&stdout, manifest_path);
+                // Lint errors also fail the build, so the package only
+                // failed to build if there are no errors to report
+                if o.status.success() || diagnostics.count("error") > 0 {
+                    Some(Rc::new(diagnostics))
+                } else {
+                    eprintln!(
+                        "cargo clippy failed for {id} with stderr: {}",
+                        String::from_utf8_lossy(&o.stderr)
+                    );
+                    None
+                }
+            }
+            Err(e) => {
+                eprintln!(
+                    "cargo clippy failed to start with error: {e}, are you sure Clippy is installed?"
+                );
+                None
+            }
+        };
+
+        self.cache.insert(id.clone(), res.clone());
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use super::parse_diagnostics;
+
+    #[test]
+    fn parse_clippy_diagnostics() {
+        let output =
+            fs::read_to_string("test_data/clippy-output/libc.jsonl").unwrap();
+        let diagnostics = parse_diagnostics(
+            &output,
+            Path::new("/cargo/registry/src/libc-0.2.139/Cargo.toml"),
+        );
+
+        // Summaries and messages of other packages are skipped
+        assert_eq!(diagnostics.count("warning"), 3);
+        assert_eq!(diagnostics.count("error"), 1);
+
+        let lint = &diagnostics.lints[2];
+        assert_eq!(lint.name.as_deref(), Some("clippy::needless_return"));
+        assert_eq!(lint.level, "warning");
+        assert_eq!(lint.count, 2);
+    }
+}
@@ -0,0 +1,225 @@
+//! Comparison of the results of the same query run against two packages
+//!
+//! Used to answer questions like "what changed in our dependencies between
+//! two releases". Rows are matched using one or more key outputs (`name` by
+//! default), so that a row with the same key but other values is reported as
+//! changed rather than as one removed and one added row.
+//!
+//! If several rows share the same key, or the query does not output any of the
+//! key fields, only rows with exactly one unmatched row on each side are
+//! considered changed.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::Serialize;
+use trustfall::{FieldValue, TransparentValue};
+
+use crate::util::transparent_results;
+
+/// Output name used to match rows, unless other key fields are provided
+pub const DEFAULT_KEY_FIELD: &str = "name";
+
+type ResultRow = BTreeMap<Arc<str>, FieldValue>;
+type TransparentRow = BTreeMap<Arc<str>, TransparentValue>;
+
+/// A row that exists in both results, but with different values
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedRow {
+    pub before: TransparentRow,
+    pub after: TransparentRow,
+}
+
+/// The difference between the results of the same query, run before and
+/// after some change
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResultComparison {
+    /// Rows only present after the change
+    pub added: Vec<TransparentRow>,
+
+    /// Rows only present before the change
+    pub removed: Vec<TransparentRow>,
+    pub changed: Vec<ChangedRow>,
+}
+
+impl ResultComparison {
+    /// If the results were identical, disregarding the order of rows
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// Rows sharing the same key
+struct KeyGroup {
+    key: Vec<FieldValue>,
+    before: Vec<ResultRow>,
+    after: Vec<ResultRow>,
+}
+
+fn key_of(row: &ResultRow, key_fields: &[&str]) -> Vec<FieldValue> {
+    key_fields
+        .iter()
+        .map(|k| row.get(*k).cloned().unwrap_or(FieldValue::Null))
+        .collect()
+}
+
+/// Compares the results of a query before and after some change, matching
+/// rows using the values of `key_fields`
+///
+/// The order of rows does not matter, and the order of the returned rows
+/// follows the order in which the keys first appear.
+#[must_use]
+pub fn compare_results(
+    before: Vec<ResultRow>,
+    after: Vec<ResultRow>,
+    key_fields: &[&str],
+) -> ResultComparison {
+    // `FieldValue` is neither `Hash` nor `Ord`, so the groups are searched
+    let mut groups: Vec<KeyGroup> = Vec::new();
+    let mut group_of = |row: &ResultRow| {
+        let key = key_of(row, key_fields);
+        match groups.iter().position(|g| g.key == key) {
+            Some(i) => i,
+            None => {
+                groups.push(KeyGroup {
+                    key,
+                    before: Vec::new(),
+                    after: Vec::new(),
+                });
+                groups.len() - 1
+            }
+        }
+    };
+
+    let before = before
+        .into_iter()
+        .map(|r| (group_of(&r), r))
+        .collect::<Vec<_>>();
+    let after = after
+        .into_iter()
+        .map(|r| (group_of(&r), r))
+        .collect::<Vec<_>>();
+    for (i, r) in before {
+        groups[i].before.push(r);
+    }
+    for (i, r) in after {
+        groups[i].after.push(r);
+    }
+
+    let mut comparison = ResultComparison::default();
+    for mut group in groups {
+        // Rows that are equal on both sides are unchanged
+        group.after.retain(|a| {
+            match group.before.iter().position(|b| b == a) {
+                Some(i) => {
+                    group.before.remove(i);
+                    false
+                }
+                None => true,
+            }
+        });
+
+        if group.before.len() == 1 && group.after.len() == 1 {
+            let mut rows = transparent_results(
+                group.before.into_iter().chain(group.after).collect(),
+            );
+            let after = rows.pop().unwrap();
+            let before = rows.pop().unwrap();
+            comparison.changed.push(ChangedRow { before, after });
+        } else {
+            comparison.removed.extend(transparent_results(group.before));
+            comparison.added.extend(transparent_results(group.after));
+        }
+    }
+
+    comparison
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, sync::Arc};
+
+    use trustfall::FieldValue;
+
+    use super::{compare_results, TransparentRow, DEFAULT_KEY_FIELD};
+
+    /// `TransparentValue` does not implement `PartialEq`, so convert it back
+    fn value(row: &TransparentRow, field: &str) -> FieldValue {
+        FieldValue::from(row[field].clone())
+    }
+
+    fn row(name: &str, version: &str) -> BTreeMap<Arc<str>, FieldValue> {
+        BTreeMap::from([
+            (Arc::from("name"), FieldValue::from(name)),
+            (Arc::from("version"), FieldValue::from(version)),
+        ])
+    }
+
+    #[test]
+    fn compare_by_key() {
+        let before = vec![
+            row("libc", "0.2.139"),
+            row("syn", "1.0.107"),
+            row("quote", "1.0.23"),
+        ];
+        let after = vec![
+            row("syn", "2.0.0"),
+            row("quote", "1.0.23"),
+            row("serde", "1.0.152"),
+        ];
+
+        let comparison = compare_results(before, after, &[DEFAULT_KEY_FIELD]);
+        assert_eq!(comparison.removed.len(), 1);
+        assert_eq!(
+            value(&comparison.removed[0], "name"),
+            FieldValue::from("libc")
+        );
+        assert_eq!(comparison.added.len(), 1);
+        assert_eq!(
+            value(&comparison.added[0], "name"),
+            FieldValue::from("serde")
+        );
+        assert_eq!(comparison.changed.len(), 1);
+        assert_eq!(
+            value(&comparison.changed[0].before, "version"),
+            FieldValue::from("1.0.107")
+        );
+        assert_eq!(
+            value(&comparison.changed[0].after, "version"),
+            FieldValue::from("2.0.0")
+        );
+    }
+
+    #[test]
+    fn duplicate_keys_are_not_changed() {
+        // One of two versions of `syn` was updated, and the other is equal
+        let before = vec![row("syn", "1.0.107"), row("syn", "2.0.0")];
+        let after = vec![row("syn", "2.0.1"), row("syn", "1.0.107")];
+
+        let comparison =
+            compare_results(before.clone(), after, &[DEFAULT_KEY_FIELD]);
+        assert_eq!(comparison.changed.len(), 1);
+        assert!(comparison.added.is_empty() && comparison.removed.is_empty());
+
+        // Two versions of `syn` before, one after; we cannot know which one
+        // was changed
+        let comparison = compare_results(
+            before,
+            vec![row("syn", "3.0.0")],
+            &[DEFAULT_KEY_FIELD],
+        );
+        assert!(comparison.changed.is_empty());
+        assert_eq!(comparison.removed.len(), 2);
+        assert_eq!(comparison.added.len(), 1);
+    }
+
+    #[test]
+    fn identical_results() {
+        let rows = vec![row("libc", "0.2.139"), row("syn", "1.0.107")];
+        let mut reversed = rows.clone();
+        reversed.reverse();
+        assert!(compare_results(rows, reversed, &["name"]).is_empty());
+    }
+}
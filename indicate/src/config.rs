@@ -0,0 +1,203 @@
+//! Configuration shared by a team, read from an `indicate.toml` file
+//!
+//! The configuration currently contains named feature profiles, used to
+//! resolve metadata using the feature configurations that are actually
+//! shipped. A profile is either a list of features, enabled in addition to
+//! the default features, or a table, like
+//! ```toml
+//! [profiles]
+//! server = ["tls", "postgres"]
+//! embedded = { no-default-features = true }
+//! minimal = { features = ["alloc"], no-default-features = true }
+//! everything = { all-features = true }
+//! ```
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use cargo_metadata::CargoOpt;
+use serde::Deserialize;
+
+use crate::{errors::ConfigError, ManifestPath};
+
+/// Name of the configuration file, searched for in the directory of the
+/// package and its parent directories
+pub const CONFIG_FILE_NAME: &str = "indicate.toml";
+
+/// The contents of an `indicate.toml` file
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IndicateConfig {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, FeatureProfile>,
+}
+
+/// Features used when resolving metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "RawFeatureProfile")]
+pub struct FeatureProfile {
+    pub features: Vec<String>,
+    pub no_default_features: bool,
+    pub all_features: bool,
+}
+
+/// A feature profile as written in the configuration file
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawFeatureProfile {
+    Features(Vec<String>),
+
+    #[serde(rename_all = "kebab-case")]
+    Table {
+        #[serde(default)]
+        features: Vec<String>,
+        #[serde(default)]
+        no_default_features: bool,
+        #[serde(default)]
+        all_features: bool,
+    },
+}
+
+impl From<RawFeatureProfile> for FeatureProfile {
+    fn from(raw: RawFeatureProfile) -> Self {
+        match raw {
+            RawFeatureProfile::Features(features) => Self {
+                features,
+                ..Self::default()
+            },
+            RawFeatureProfile::Table {
+                features,
+                no_default_features,
+                all_features,
+            } => Self {
+                features,
+                no_default_features,
+                all_features,
+            },
+        }
+    }
+}
+
+impl FeatureProfile {
+    /// The options passed to `cargo metadata` for this profile
+    #[must_use]
+    pub fn cargo_opts(&self) -> Vec<CargoOpt> {
+        // `cargo metadata` does not accept other options with all features
+        if self.all_features {
+            return vec![CargoOpt::AllFeatures];
+        }
+
+        let mut opts = Vec::with_capacity(2);
+        if !self.features.is_empty() {
+            opts.push(CargoOpt::SomeFeatures(self.features.clone()));
+        }
+        if self.no_default_features {
+            opts.push(CargoOpt::NoDefaultFeatures);
+        }
+        opts
+    }
+}
+
+impl IndicateConfig {
+    /// Reads a configuration file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the file cannot be read or parsed.
+    pub fn from_path(path: &Path) -> Result<Self, ConfigError> {
+        let raw = fs::read_to_string(path).map_err(|e| {
+            ConfigError::Read(path.to_string_lossy().to_string(), e.to_string())
+        })?;
+        toml::from_str(&raw).map_err(|e| {
+            ConfigError::Parse(
+                path.to_string_lossy().to_string(),
+                e.to_string(),
+            )
+        })
+    }
+
+    /// Finds the closest configuration file, starting in the directory of the
+    /// package and moving up to its parent directories, such as the root of a
+    /// workspace
+    #[must_use]
+    pub fn find(manifest_path: &ManifestPath) -> Option<PathBuf> {
+        manifest_path
+            .as_path()
+            .ancestors()
+            .skip(1)
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    /// Retrieves a feature profile by name
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if there is no profile with that name.
+    pub fn profile(&self, name: &str) -> Result<&FeatureProfile, ConfigError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| ConfigError::UnknownProfile {
+                name: name.to_string(),
+                available: self
+                    .profiles
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cargo_metadata::CargoOpt;
+
+    use super::{FeatureProfile, IndicateConfig};
+    use crate::ManifestPath;
+
+    const CONFIG: &str = r#"
+[profiles]
+server = ["tls", "postgres"]
+embedded = { no-default-features = true }
+everything = { all-features = true, features = ["tls"] }
+"#;
+
+    #[test]
+    fn parse_profiles() {
+        let config: IndicateConfig = toml::from_str(CONFIG).unwrap();
+        assert_eq!(
+            config.profile("server").unwrap(),
+            &FeatureProfile {
+                features: vec![String::from("tls"), String::from("postgres")],
+                ..FeatureProfile::default()
+            }
+        );
+        assert!(matches!(
+            config.profile("embedded").unwrap().cargo_opts().as_slice(),
+            [CargoOpt::NoDefaultFeatures]
+        ));
+        assert!(matches!(
+            config
+                .profile("everything")
+                .unwrap()
+                .cargo_opts()
+                .as_slice(),
+            [CargoOpt::AllFeatures]
+        ));
+
+        let err = config.profile("desktop").unwrap_err();
+        assert!(err.to_string().contains("embedded, everything, server"));
+    }
+
+    #[test]
+    fn find_config() {
+        let path = IndicateConfig::find(&ManifestPath::new(
+            "test_data/fake_crates/feature_deps".as_ref(),
+        ))
+        .unwrap();
+        assert!(path.ends_with("feature_deps/indicate.toml"));
+        assert!(IndicateConfig::from_path(&path).is_ok());
+    }
+}
@@ -0,0 +1,432 @@
+//! Estimates of how many requests a query makes to external services
+//!
+//! Some properties and edges are resolved using external APIs, such as
+//! GitHub, that are rate limited or shared by a team through a token. A query
+//! using them on all packages of a large dependency tree can use up a quota
+//! by accident, so the cost of a query can be estimated before it is
+//! executed.
+//!
+//! The estimate counts the requests for each API endpoint that a query uses.
+//! Responses are cached per package, so an endpoint is called at most once
+//! per package in the dependency graph, no matter how many times it is
+//! used in a query. Vertices reached through an edge to packages, such as
+//! `Dependencies` or `dependencies`, are assumed to be all packages, and
+//! other edges are assumed to lead to one vertex.
+use std::{collections::BTreeMap, fmt::Display, sync::Arc};
+
+use trustfall_core::ir::{
+    Argument, FieldRef, IRQueryComponent, LocalField, Operation, Vid,
+};
+
+use crate::{errors::QueryValidationError, query::FullQuery};
+
+/// Default maximum number of requests a query may be estimated to make
+///
+/// Low enough to not use up the hourly GitHub API quota of 5000 requests in
+/// a single query.
+pub const DEFAULT_MAX_COST: u64 = 1000;
+
+/// An external API endpoint called for each vertex using it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Endpoint {
+    GitHubRepository,
+    GitHubUser,
+    GitHubOrganizations,
+    GitHubBranch,
+    GitHubBranchProtection,
+    GitHubContributors,
+    GitHubReleases,
+    GitHubContents,
+    GitHubIssues,
+    GitHubSecurityAdvisories,
+    GitLabProject,
+    GiteaRepository,
+    SourcehutLog,
+    CratesIoCrate,
+    CratesIoOwners,
+    CratesIoReverseDependencies,
+    CratesIoIndex,
+    Osv,
+    Epss,
+    Kev,
+    Nvd,
+    DocsRs,
+    DepsDevVersion,
+    DepsDevPackage,
+    DepsDevDependencies,
+    Scorecard,
+}
+
+impl Endpoint {
+    /// The name of the service this endpoint belongs to
+    #[must_use]
+    pub fn service(self) -> &'static str {
+        match self {
+            Endpoint::GitHubRepository
+            | Endpoint::GitHubUser
+            | Endpoint::GitHubOrganizations
+            | Endpoint::GitHubBranch
+            | Endpoint::GitHubBranchProtection
+            | Endpoint::GitHubContributors
+            | Endpoint::GitHubReleases
+            | Endpoint::GitHubContents
+            | Endpoint::GitHubIssues
+            | Endpoint::GitHubSecurityAdvisories => "GitHub",
+            Endpoint::GitLabProject => "GitLab",
+            Endpoint::GiteaRepository => "Gitea",
+            Endpoint::SourcehutLog => "sourcehut",
+            Endpoint::CratesIoCrate
+            | Endpoint::CratesIoOwners
+            | Endpoint::CratesIoReverseDependencies => "crates.io",
+            Endpoint::CratesIoIndex => "crates.io sparse index",
+            Endpoint::Osv => "OSV",
+            Endpoint::Epss => "EPSS",
+            Endpoint::Kev => "CISA KEV",
+            Endpoint::Nvd => "NVD",
+            Endpoint::DocsRs => "docs.rs",
+            Endpoint::DepsDevVersion
+            | Endpoint::DepsDevPackage
+            | Endpoint::DepsDevDependencies => "deps.dev",
+            Endpoint::Scorecard => "OpenSSF Scorecard",
+        }
+    }
+
+    /// If responses of this endpoint are not specific to a package, such as
+    /// a catalog downloaded in full, so that it is requested at most once
+    /// per query
+    #[must_use]
+    pub fn once_per_query(self) -> bool {
+        matches!(self, Endpoint::Kev)
+    }
+
+    /// The endpoints called when resolving a property or an edge of a vertex
+    #[must_use]
+    pub fn used_by(type_name: &str, field_name: &str) -> &'static [Self] {
+        match (type_name, field_name) {
+            // Where a repository is hosted is only known once the package is
+            // resolved
+            ("Package", "repository") => &[
+                Endpoint::GitHubRepository,
+                Endpoint::GitLabProject,
+                Endpoint::GiteaRepository,
+            ],
+            ("SourcehutRepository", "unixLastCommitAt") => {
+                &[Endpoint::SourcehutLog]
+            }
+            ("GitHubRepository", "owner") => &[Endpoint::GitHubUser],
+            ("GitHubUser", "organizations") => &[Endpoint::GitHubOrganizations],
+            ("GitHubRepository", "scorecard") => &[Endpoint::Scorecard],
+            ("GitHubRepository", "contributors") => {
+                &[Endpoint::GitHubContributors]
+            }
+            ("GitHubRepository", "releases") => &[Endpoint::GitHubReleases],
+            ("GitHubRepository", "issues") => &[Endpoint::GitHubIssues],
+            (
+                "GitHubRepository",
+                "hasCiWorkflows" | "workflows" | "hasSecurityPolicy",
+            ) => &[Endpoint::GitHubContents],
+            (
+                "GitHubRepository",
+                "unixLastCommitAt" | "defaultBranchProtected",
+            ) => &[Endpoint::GitHubBranch],
+            ("GitHubRepository", "defaultBranchRequiresReviews") => {
+                &[Endpoint::GitHubBranch, Endpoint::GitHubBranchProtection]
+            }
+            ("Package", "osvAdvisories") => &[Endpoint::Osv],
+            ("Advisory", "cve") => &[Endpoint::Nvd],
+            ("Advisory", "epss") => &[Endpoint::Epss],
+            ("Advisory", "inKnownExploitedCatalog") => &[Endpoint::Kev],
+            ("Package", "ghsaAdvisories") => {
+                &[Endpoint::GitHubSecurityAdvisories]
+            }
+            ("Package", "docsRs") => &[Endpoint::DocsRs],
+            ("Package", "depsDev") => &[
+                Endpoint::DepsDevVersion,
+                Endpoint::DepsDevPackage,
+                Endpoint::DepsDevDependencies,
+            ],
+            ("Package", "cratesIoReverseDependenciesCount") => {
+                &[Endpoint::CratesIoReverseDependencies]
+            }
+            ("Package", "daysSinceOwnerAdded") => {
+                &[Endpoint::CratesIoCrate, Endpoint::CratesIoOwners]
+            }
+            ("CratesIoStats", "owners") => &[Endpoint::CratesIoOwners],
+            (
+                "CratesIoStats",
+                "versionsCount"
+                | "yanked"
+                | "yankedVersions"
+                | "yankedVersionsCount"
+                | "yankedRatio"
+                | "checksum",
+            ) => &[Endpoint::CratesIoIndex],
+            ("CratesIoStats", _) => &[Endpoint::CratesIoCrate],
+            (
+                "Package",
+                "cratesIoCreatedAt"
+                | "cratesIoUpdatedAt"
+                | "cratesIoVersionPublishedAt"
+                | "cratesIoLatestVersion"
+                | "cratesIoKeywords"
+                | "cratesIoCategories"
+                | "isOutdated"
+                | "versionsBehind"
+                | "versionPublishedBy"
+                | "ownerChangedRecently",
+            ) => &[Endpoint::CratesIoCrate],
+            _ => &[],
+        }
+    }
+}
+
+/// The estimated number of requests a query makes to each external API
+/// endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryCost {
+    pub requests: BTreeMap<Endpoint, u64>,
+}
+
+impl QueryCost {
+    /// Estimates the cost of a query on a dependency graph with
+    /// `package_count` packages
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the query is not valid according to the
+    /// schema.
+    pub fn estimate(
+        query: &FullQuery,
+        package_count: usize,
+    ) -> Result<Self, QueryValidationError> {
+        let indexed_query = query.parse()?;
+        let ir_query = &indexed_query.ir_query;
+        let package_count = package_count as u64;
+
+        // Only `RootPackage` and `AuditSummary` lead to a single vertex
+        let root_count = if matches!(
+            ir_query.root_name.as_ref(),
+            "RootPackage" | "AuditSummary"
+        ) {
+            1
+        } else {
+            package_count
+        };
+
+        let mut cost = Self::default();
+        cost.add_component(&ir_query.root_component, root_count, package_count);
+        Ok(cost)
+    }
+
+    /// The total number of requests to all endpoints
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.requests.values().sum()
+    }
+
+    /// The number of requests to each service, such as `GitHub`
+    #[must_use]
+    pub fn by_service(&self) -> BTreeMap<&'static str, u64> {
+        let mut services = BTreeMap::new();
+        for (endpoint, requests) in &self.requests {
+            *services.entry(endpoint.service()).or_default() += requests;
+        }
+        services
+    }
+
+    /// Adds the requests of all vertices in a component, where the root of
+    /// the component is reached `root_count` times
+    fn add_component(
+        &mut self,
+        component: &IRQueryComponent,
+        root_count: u64,
+        package_count: u64,
+    ) {
+        let mut counts = BTreeMap::from([(component.root, root_count)]);
+        // Edges always lead to vertices with a higher id than the vertex they
+        // start from, so the count of the start vertex is already known
+        let mut edges = component
+            .edges
+            .values()
+            .map(|e| (e.from_vid, e.to_vid))
+            .collect::<Vec<_>>();
+        edges.sort();
+        for (from, to) in edges {
+            counts.insert(
+                to,
+                Self::neighbor_count(
+                    component,
+                    counts[&from],
+                    to,
+                    package_count,
+                ),
+            );
+        }
+
+        let mut used_fields: Vec<(Vid, Arc<str>)> = Vec::new();
+        for vertex in component.vertices.values() {
+            for filter in &vertex.filters {
+                let (left, right) = filter_operands(filter);
+                if let Some(left) = left {
+                    used_fields.push((vertex.vid, left.field_name.clone()));
+                }
+                if let Some(Argument::Tag(FieldRef::ContextField(tag))) = right
+                {
+                    used_fields.push((tag.vertex_id, tag.field_name.clone()));
+                }
+            }
+        }
+        used_fields.extend(
+            component
+                .outputs
+                .values()
+                .map(|o| (o.vertex_id, o.field_name.clone())),
+        );
+        used_fields.extend(
+            component
+                .edges
+                .values()
+                .map(|e| (e.from_vid, e.edge_name.clone())),
+        );
+        used_fields.extend(
+            component
+                .folds
+                .values()
+                .map(|f| (f.from_vid, f.edge_name.clone())),
+        );
+
+        for (vid, field_name) in used_fields {
+            // Tags may be from a vertex outside of this component
+            let (Some(vertex), Some(count)) =
+                (component.vertices.get(&vid), counts.get(&vid))
+            else {
+                continue;
+            };
+            for endpoint in Endpoint::used_by(&vertex.type_name, &field_name) {
+                self.add(*endpoint, *count, package_count);
+            }
+        }
+
+        for fold in component.folds.values() {
+            let count = Self::neighbor_count(
+                &fold.component,
+                counts[&fold.from_vid],
+                fold.to_vid,
+                package_count,
+            );
+            self.add_component(&fold.component, count, package_count);
+        }
+    }
+
+    /// The number of times a vertex is reached through an edge from a vertex
+    /// reached `from_count` times
+    fn neighbor_count(
+        component: &IRQueryComponent,
+        from_count: u64,
+        to: Vid,
+        package_count: u64,
+    ) -> u64 {
+        if component.vertices[&to].type_name.as_ref() == "Package" {
+            package_count
+        } else {
+            from_count
+        }
+    }
+
+    /// Adds the requests for an endpoint used by a vertex reached `count`
+    /// times, where responses are cached for each package
+    fn add(&mut self, endpoint: Endpoint, count: u64, package_count: u64) {
+        let max_requests = if endpoint.once_per_query() {
+            1
+        } else {
+            package_count
+        };
+        let requests = self.requests.entry(endpoint).or_default();
+        *requests = (*requests).max(count.min(max_requests));
+    }
+}
+
+/// The field a filter is applied to and the value it is compared with, if
+/// any
+///
+/// `None` is only returned for operations added to Trustfall after this
+/// was written.
+fn filter_operands(
+    filter: &Operation<LocalField, Argument>,
+) -> (Option<&LocalField>, Option<&Argument>) {
+    match filter {
+        Operation::IsNull(left) | Operation::IsNotNull(left) => {
+            (Some(left), None)
+        }
+        Operation::Equals(left, right)
+        | Operation::NotEquals(left, right)
+        | Operation::LessThan(left, right)
+        | Operation::LessThanOrEqual(left, right)
+        | Operation::GreaterThan(left, right)
+        | Operation::GreaterThanOrEqual(left, right)
+        | Operation::Contains(left, right)
+        | Operation::NotContains(left, right)
+        | Operation::OneOf(left, right)
+        | Operation::NotOneOf(left, right)
+        | Operation::HasPrefix(left, right)
+        | Operation::NotHasPrefix(left, right)
+        | Operation::HasSuffix(left, right)
+        | Operation::NotHasSuffix(left, right)
+        | Operation::HasSubstring(left, right)
+        | Operation::NotHasSubstring(left, right)
+        | Operation::RegexMatches(left, right)
+        | Operation::NotRegexMatches(left, right) => (Some(left), Some(right)),
+        _ => (None, None),
+    }
+}
+
+impl Display for QueryCost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let services = self
+            .by_service()
+            .iter()
+            .map(|(service, requests)| format!("{service}: {requests}"))
+            .collect::<Vec<_>>();
+        if services.is_empty() {
+            write!(f, "0 requests")
+        } else {
+            write!(f, "{} requests ({})", self.total(), services.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::QueryCost;
+    use crate::query::FullQueryBuilder;
+
+    #[test_case("{ RootPackage { name @output } }" => 0 ; "no external services")]
+    #[test_case("{ RootPackage { repository { url @output } } }" => 3 ; "root package repository")]
+    #[test_case("{ Dependencies(includeRoot: false) { repository { url @output } } }" => 30 ; "dependencies repository")]
+    #[test_case(
+        "{ RootPackage { dependencies { repository { ... on GitHubRepository { owner { username @output } } } } } }" => 40 ;
+        "nested repository owner"
+    )]
+    #[test_case(
+        r#"{ Dependencies(includeRoot: false) { name @output isOutdated @filter(op: "=", value: ["$t"]) cratesIo { totalDownloads @output } } }"# => 10 ;
+        "cached crates.io requests"
+    )]
+    #[test_case(
+        "{ Dependencies(includeRoot: false) { cratesIo { yanked @output totalDownloads @output } } }" => 20 ;
+        "crates.io sparse index"
+    )]
+    #[test_case(
+        "{ RootPackage { dependencies @fold { osvAdvisories { id @output } } } }" => 10 ;
+        "fold"
+    )]
+    #[test_case(
+        "{ Dependencies(includeRoot: false) { advisoryHistory(includeWithdrawn: false) { inKnownExploitedCatalog @output } } }" => 1 ;
+        "catalog downloaded once"
+    )]
+    fn estimated_requests(query: &str) -> u64 {
+        let query = FullQueryBuilder::new(query.to_string()).build();
+        QueryCost::estimate(&query, 10).unwrap().total()
+    }
+}
@@ -10,9 +10,14 @@
 
 use std::{collections::HashMap, time::Duration};
 
-use crates_io_api::{Crate, CrateResponse, SyncClient, Version};
+use chrono::Utc;
+use crates_io_api::{Crate, CrateResponse, SyncClient, User, Version};
 
-use crate::NameVersion;
+use crate::{source::PackageSource, NameVersion};
+
+/// Number of versions before the latest version used to determine if the
+/// owner of a crate has changed
+pub const OWNER_HISTORY_VERSIONS: usize = 5;
 
 /// Wrapper around a [`crates_io_api::SyncClient`], with added caching
 pub struct CratesIoClient {
@@ -24,6 +29,13 @@ pub struct CratesIoClient {
     /// same query, so we store if we were able to find it the first time via
     /// the option.
     cache: HashMap<String, Option<CrateResponse>>,
+
+    /// Cache between crate name and its current owners, see `cache`
+    owners_cache: HashMap<String, Option<Vec<User>>>,
+
+    /// Cache between crate name and the number of crates depending on it,
+    /// see `cache`
+    reverse_dependencies_cache: HashMap<String, Option<u64>>,
 }
 
 impl CratesIoClient {
@@ -42,6 +54,8 @@ impl CratesIoClient {
         Self {
             client,
             cache: HashMap::new(),
+            owners_cache: HashMap::new(),
+            reverse_dependencies_cache: HashMap::new(),
         }
     }
 
@@ -64,6 +78,43 @@ impl CratesIoClient {
         }).as_mut()
     }
 
+    /// Retrieves the current owners of a crate, users and teams, from the
+    /// `crates.io` API
+    pub fn owners(&mut self, crate_name: &str) -> Option<&Vec<User>> {
+        self.owners_cache.entry(crate_name.to_string()).or_insert_with(|| {
+            match self.client.crate_owners(crate_name) {
+                Ok(owners) => Some(owners),
+                Err(e) => {
+                    eprintln!("failed to retrieve crates.io owners of {crate_name} due to error: {e}");
+                    None
+                }
+            }
+        }).as_ref()
+    }
+
+    /// Retrieves the number of crates on `crates.io` that depend on any
+    /// version of a crate
+    ///
+    /// Only the first page of reverse dependencies is retrieved, since it
+    /// contains the total count.
+    pub fn reverse_dependencies_count(
+        &mut self,
+        crate_name: &str,
+    ) -> Option<u64> {
+        *self
+            .reverse_dependencies_cache
+            .entry(crate_name.to_string())
+            .or_insert_with(|| {
+                match self.client.crate_reverse_dependency_count(crate_name) {
+                    Ok(count) => Some(count),
+                    Err(e) => {
+                        eprintln!("failed to retrieve crates.io reverse dependencies of {crate_name} due to error: {e}");
+                        None
+                    }
+                }
+            })
+    }
+
     /// Retrieve data about a crate from the `crates.io` API
     pub fn crate_data(&mut self, crate_name: &str) -> Option<&Crate> {
         self.crate_response(crate_name).map(|cr| &cr.crate_data)
@@ -89,12 +140,27 @@ impl CratesIoClient {
         self.crate_data(crate_name).and_then(|c| c.recent_downloads)
     }
 
+    /// Retrieves data about all versions of the crate a package version
+    /// belongs to, or `None` if the package is not from `crates.io`
+    ///
+    /// A package from another source may share its name with an unrelated
+    /// crate on `crates.io`.
+    fn crates_io_versions(
+        &mut self,
+        name_version: &NameVersion,
+    ) -> Option<&Vec<Version>> {
+        if name_version.source != PackageSource::CratesIo {
+            return None;
+        }
+        self.versions(&name_version.name)
+    }
+
     /// Retrieves the total amount of downloads for a specific crate version
     pub fn version_downloads(
         &mut self,
         name_version: &NameVersion,
     ) -> Option<u64> {
-        self.versions(&name_version.name).and_then(|versions| {
+        self.crates_io_versions(name_version).and_then(|versions| {
             versions.iter().find_map(|v| {
             match rustsec::Version::parse(&v.num) {
                     Ok(current_version) => {
@@ -105,7 +171,7 @@ impl CratesIoClient {
                         }
                     }
                     Err(e) => {
-                        eprintln!("could not parse crates.io version for {name_version:?} due to error: {e}");
+                        eprintln!("could not parse crates.io version for {name_version} due to error: {e}");
                         None
                     }
                 }        })        })
@@ -113,7 +179,7 @@ impl CratesIoClient {
 
     /// Returns if this version is yanked from `crates.io`
     pub fn yanked(&mut self, name_version: &NameVersion) -> Option<bool> {
-        self.versions(&name_version.name).and_then(|versions| {
+        self.crates_io_versions(name_version).and_then(|versions| {
             versions.iter().find_map(|v| {
                 match rustsec::Version::parse(&v.num) {
                     Ok(current_version) => {
@@ -124,7 +190,7 @@ impl CratesIoClient {
                         }
                     }
                     Err(e) => {
-                        eprintln!("could not parse crates.io version for {name_version:?} due to error: {e}");
+                        eprintln!("could not parse crates.io version for {name_version} due to error: {e}");
                         None
                     }
                 }
@@ -132,6 +198,118 @@ impl CratesIoClient {
         })
     }
 
+    /// Retrieves data about a specific crate version from the `crates.io` API
+    pub fn version(&mut self, name_version: &NameVersion) -> Option<&Version> {
+        self.crates_io_versions(name_version)?.iter().find(|v| {
+            rustsec::Version::parse(&v.num).is_ok_and(|current_version| {
+                current_version == name_version.version
+            })
+        })
+    }
+
+    /// Retrieves the keywords of a crate as published to `crates.io`
+    pub fn keywords(&mut self, crate_name: &str) -> Option<Vec<String>> {
+        self.crate_response(crate_name)
+            .map(|cr| cr.keywords.iter().map(|k| k.keyword.clone()).collect())
+    }
+
+    /// Retrieves the slugs of the categories of a crate as published to
+    /// `crates.io`, such as `command-line-utilities`
+    pub fn categories(&mut self, crate_name: &str) -> Option<Vec<String>> {
+        self.crate_response(crate_name)
+            .map(|cr| cr.categories.iter().map(|c| c.slug.clone()).collect())
+    }
+
+    /// Retrieves when a crate was first published to `crates.io`, as a unix
+    /// timestamp
+    pub fn created_at(&mut self, crate_name: &str) -> Option<i64> {
+        self.crate_data(crate_name)
+            .map(|c| c.created_at.timestamp())
+    }
+
+    /// Retrieves when a crate was last updated on `crates.io`, as a unix
+    /// timestamp
+    pub fn updated_at(&mut self, crate_name: &str) -> Option<i64> {
+        self.crate_data(crate_name)
+            .map(|c| c.updated_at.timestamp())
+    }
+
+    /// Retrieves when a specific crate version was published to `crates.io`,
+    /// as a unix timestamp
+    pub fn version_published_at(
+        &mut self,
+        name_version: &NameVersion,
+    ) -> Option<i64> {
+        self.version(name_version).map(|v| v.created_at.timestamp())
+    }
+
+    /// Retrieves the login of the `crates.io` user that published this version
+    ///
+    /// Versions published before `crates.io` started recording this have no
+    /// publisher.
+    pub fn version_published_by(
+        &mut self,
+        name_version: &NameVersion,
+    ) -> Option<String> {
+        self.version(name_version)?
+            .published_by
+            .as_ref()
+            .map(|u| u.login.clone())
+    }
+
+    /// Returns if the publisher of the latest version of a crate did not
+    /// publish any of the [`OWNER_HISTORY_VERSIONS`] versions before it
+    ///
+    /// `crates.io` has no history of owners, so the publishers of versions
+    /// are used instead. Returns `None` if there are not enough versions, or
+    /// if any of them has no recorded publisher.
+    pub fn owner_changed_recently(&mut self, crate_name: &str) -> Option<bool> {
+        let mut versions =
+            self.versions(crate_name)?.iter().collect::<Vec<_>>();
+        if versions.len() <= OWNER_HISTORY_VERSIONS {
+            return None;
+        }
+        versions.sort_by_key(|v| std::cmp::Reverse(v.created_at));
+
+        let publisher = |v: &Version| v.published_by.as_ref().map(|u| u.id);
+        let latest = publisher(versions[0])?;
+        let previous = versions[1..=OWNER_HISTORY_VERSIONS]
+            .iter()
+            .map(|v| publisher(v))
+            .collect::<Option<Vec<_>>>()?;
+        Some(!previous.contains(&latest))
+    }
+
+    /// Retrieves the number of days since the most recently added owner of a
+    /// crate was added
+    ///
+    /// `crates.io` does not expose when owners were added, so the first
+    /// version published by each owner is used instead. Owners that have not
+    /// published any version, such as teams, are ignored. Returns `None` if
+    /// no current owner has published a version.
+    pub fn days_since_owner_added(&mut self, crate_name: &str) -> Option<i64> {
+        let owner_ids = self
+            .owners(crate_name)?
+            .iter()
+            .map(|u| u.id)
+            .collect::<Vec<_>>();
+        let versions = self.versions(crate_name)?;
+
+        let most_recently_added = owner_ids
+            .iter()
+            .filter_map(|id| {
+                versions
+                    .iter()
+                    .filter(|v| {
+                        v.published_by.as_ref().is_some_and(|u| u.id == *id)
+                    })
+                    .map(|v| v.created_at)
+                    .min()
+            })
+            .max()?;
+        Some((Utc::now() - most_recently_added).num_days())
+    }
+
     /// Retrieves all versions for a crate that has been marked as yanked
     ///
     /// If only the count of yanked versions is desired, use
@@ -161,6 +339,43 @@ impl CratesIoClient {
             .map(|versions| versions.iter().filter(|v| v.yanked).count())
     }
 
+    /// Retrieves the versions of a crate that are neither yanked nor
+    /// prereleases, sorted from oldest to newest
+    pub fn stable_versions(
+        &mut self,
+        crate_name: &str,
+    ) -> Option<Vec<rustsec::Version>> {
+        self.versions(crate_name).map(|versions| {
+            stable_versions(versions.iter().map(|v| (v.num.as_str(), v.yanked)))
+        })
+    }
+
+    /// Retrieves the latest version of a crate that is neither yanked nor a
+    /// prerelease
+    pub fn latest_version(
+        &mut self,
+        crate_name: &str,
+    ) -> Option<rustsec::Version> {
+        self.stable_versions(crate_name)?.pop()
+    }
+
+    /// Counts the stable versions (see [`CratesIoClient::stable_versions`])
+    /// of a crate newer than a specific version
+    pub fn versions_behind(
+        &mut self,
+        name_version: &NameVersion,
+    ) -> Option<usize> {
+        if name_version.source != PackageSource::CratesIo {
+            return None;
+        }
+        self.stable_versions(&name_version.name).map(|versions| {
+            versions
+                .iter()
+                .filter(|v| **v > name_version.version)
+                .count()
+        })
+    }
+
     /// Calculates the ratio of yanked versions to all crate versions
     pub fn yanked_ratio(&mut self, crate_name: &str) -> Option<f64> {
         self.yanked_versions_count(crate_name).and_then(|y| {
@@ -169,6 +384,22 @@ impl CratesIoClient {
     }
 }
 
+/// Parses versions given as `(version, yanked)` that are neither yanked nor
+/// prereleases, sorted from oldest to newest
+///
+/// Versions that cannot be parsed are ignored.
+fn stable_versions<'a>(
+    versions: impl Iterator<Item = (&'a str, bool)>,
+) -> Vec<rustsec::Version> {
+    let mut stable = versions
+        .filter(|(_, yanked)| !yanked)
+        .filter_map(|(num, _)| rustsec::Version::parse(num).ok())
+        .filter(|v| v.pre.is_empty())
+        .collect::<Vec<_>>();
+    stable.sort();
+    stable
+}
+
 impl Default for CratesIoClient {
     fn default() -> Self {
         let user_agent = std::env::var("USER_AGENT")
@@ -176,3 +407,26 @@ impl Default for CratesIoClient {
         Self::new(&user_agent, Duration::from_secs(1))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::stable_versions;
+
+    #[test]
+    fn stable_versions_ignore_yanked_and_prereleases() {
+        let versions = stable_versions(
+            [
+                ("1.0.0", false),
+                ("1.2.0", true),
+                ("2.0.0-rc.1", false),
+                ("1.1.0", false),
+                ("not a version", false),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            versions.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["1.0.0", "1.1.0"]
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Reviews of packages from [`cargo-crev`](https://github.com/crev-dev/cargo-crev)
+//!
+//! Reviews are retrieved from the local proof repository of `cargo crev`,
+//! which includes fetched reviews of other users, by running
+//! `cargo crev proof find`. Reviews are only as up to date as the last
+//! `cargo crev repo fetch`, and signatures are not verified.
+//!
+//! `cargo-crev` prints proofs as YAML documents, of which only a small and
+//! fixed subset is used, so they are parsed line by line rather than
+//! depending on a YAML parser.
+
+use std::{
+    collections::HashMap,
+    process::{Command, Stdio},
+    rc::Rc,
+};
+
+use chrono::DateTime;
+
+/// Starts a proof, such as `----- BEGIN CREV PROOF -----`, or
+/// `----- BEGIN CREV PACKAGE REVIEW -----` in older proofs
+const PROOF_BEGIN: &str = "----- BEGIN CREV";
+
+/// Ends the body of a proof, after which the signature follows
+const PROOF_SIGN: &str = "----- SIGN CREV";
+
+/// A review of a package version by a crev identity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrevReview {
+    /// The crev ID of the reviewer, such as
+    /// `FYlr8YoYGVvDwHQxqEIs89reKKDy-oWisoO0qXXEfHE`
+    pub reviewer_id: String,
+
+    /// The proof repository of the reviewer
+    pub reviewer_url: Option<String>,
+    pub name: String,
+    pub version: String,
+
+    /// RFC 3339
+    pub date: Option<String>,
+
+    /// `strong`, `positive`, `neutral`, `negative` or `dangerous`
+    pub rating: Option<String>,
+
+    /// `none`, `low`, `medium` or `high`
+    pub thoroughness: Option<String>,
+    pub understanding: Option<String>,
+    pub comment: Option<String>,
+}
+
+impl CrevReview {
+    /// Creates a review from the flattened fields of a proof, such as
+    /// `from.id`, `None` if the proof is not a package review
+    fn from_fields(mut fields: HashMap<String, String>) -> Option<Self> {
+        if fields.get("kind").is_some_and(|k| k != "package review") {
+            return None;
+        }
+        Some(Self {
+            reviewer_id: fields.remove("from.id")?,
+            reviewer_url: fields.remove("from.url"),
+            name: fields.remove("package.name")?,
+            version: fields.remove("package.version")?,
+            date: fields.remove("date"),
+            rating: fields.remove("review.rating"),
+            thoroughness: fields.remove("review.thoroughness"),
+            understanding: fields.remove("review.understanding"),
+            comment: fields.remove("comment").filter(|c| !c.is_empty()),
+        })
+    }
+
+    /// When the review was made as a Unix timestamp, `None` if it could not
+    /// be parsed
+    #[must_use]
+    pub fn unix_date(&self) -> Option<i64> {
+        self.date
+            .as_deref()
+            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.timestamp())
+    }
+}
+
+/// Removes the quotes around a YAML scalar, if any
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(v) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return v.to_string();
+        }
+    }
+    value.to_string()
+}
+
+/// Flattens the body of a proof into fields such as `date` and `from.id`
+///
+/// Block scalars, such as a multi-line `comment`, are joined by newlines.
+fn parse_fields(body: &str) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    let mut section: Option<String> = None;
+    let mut block: Option<(String, Vec<String>)> = None;
+
+    for line in body.lines() {
+        let indented = line.starts_with(' ');
+        if let Some((_, lines)) = &mut block {
+            if indented || line.is_empty() {
+                lines.push(line.trim().to_string());
+                continue;
+            }
+        }
+        if let Some((key, lines)) = block.take() {
+            fields.insert(key, lines.join("\n").trim().to_string());
+        }
+
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let key = if indented {
+            match &section {
+                Some(s) => format!("{s}.{key}"),
+                None => continue,
+            }
+        } else {
+            section = None;
+            key.to_string()
+        };
+
+        match value.trim() {
+            "" if !indented => section = Some(key),
+            "|" | "|-" | ">" | ">-" => block = Some((key, Vec::new())),
+            v => {
+                fields.insert(key, unquote(v));
+            }
+        }
+    }
+    if let Some((key, lines)) = block {
+        fields.insert(key, lines.join("\n").trim().to_string());
+    }
+
+    fields
+}
+
+/// Parses the package reviews among the proofs printed by `cargo crev`
+#[must_use]
+pub fn parse_reviews(output: &str) -> Vec<CrevReview> {
+    output
+        .split(PROOF_BEGIN)
+        .skip(1)
+        .filter_map(|proof| {
+            // Skip the rest of the begin marker
+            let (_, body) = proof.split_once('\n')?;
+            let (body, _) = body.split_once(PROOF_SIGN)?;
+            CrevReview::from_fields(parse_fields(body))
+        })
+        .collect()
+}
+
+/// Retrieves reviews from `cargo crev`, with caching
+#[derive(Debug, Clone, Default)]
+pub struct CrevClient {
+    /// Set if `cargo-crev` could not be started, so that it is not retried
+    unavailable: bool,
+
+    /// Cache between a package name and the reviews of all its versions
+    ///
+    /// Failed runs are cached as `None`, so that they are not retried
+    /// during the same query.
+    cache: HashMap<String, Option<Rc<Vec<CrevReview>>>>,
+}
+
+impl CrevClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves all reviews of a package, in any version
+    ///
+    /// Will return `None` if `cargo-crev` is not installed or fails, and
+    /// will cache this package as such.
+    pub fn reviews(&mut self, name: &str) -> Option<Rc<Vec<CrevReview>>> {
+        if self.unavailable {
+            return None;
+        }
+        if let Some(cached) = self.cache.get(name) {
+            return cached.clone();
+        }
+
+        let output = Command::new("cargo-crev")
+            .args(["crev", "proof", "find", "--crate", name])
+            .stdin(Stdio::null())
+            .output();
+        let res = match output {
+            Ok(o) if o.status.success() => Some(Rc::new(parse_reviews(
+                &String::from_utf8_lossy(&o.stdout),
+            ))),
+            Ok(o) => {
+                eprintln!(
+                    "cargo-crev failed to find reviews of {name} with stderr: {}",
+                    String::from_utf8_lossy(&o.stderr)
+                );
+                None
+            }
+            Err(e) => {
+                eprintln!(
+                    "cargo-crev failed to start with error: {e}, are you sure `cargo-crev` is installed?"
+                );
+                self.unavailable = true;
+                None
+            }
+        };
+
+        self.cache.insert(name.to_string(), res.clone());
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::parse_reviews;
+
+    #[test]
+    fn parse_crev_reviews() {
+        let output =
+            fs::read_to_string("test_data/crev-output/libc.txt").unwrap();
+        let reviews = parse_reviews(&output);
+
+        // The trust proof is not a package review
+        assert_eq!(reviews.len(), 2);
+
+        let review = &reviews[0];
+        assert_eq!(
+            review.reviewer_id,
+            "FYlr8YoYGVvDwHQxqEIs89reKKDy-oWisoO0qXXEfHE"
+        );
+        assert_eq!(
+            review.reviewer_url.as_deref(),
+            Some("https://github.com/dpc/crev-proofs")
+        );
+        assert_eq!(review.version, "0.2.139");
+        assert_eq!(review.rating.as_deref(), Some("positive"));
+        assert_eq!(review.thoroughness.as_deref(), Some("low"));
+        assert_eq!(review.unix_date(), Some(1_673_776_800));
+        assert_eq!(
+            review.comment.as_deref(),
+            Some("Mostly bindings.\nLooked at the build script.")
+        );
+
+        // Older proofs have a different begin marker and no kind
+        assert_eq!(reviews[1].version, "0.2.138");
+        assert_eq!(reviews[1].comment, None);
+    }
+}
@@ -0,0 +1,277 @@
+//! Policies of [`cargo-deny`](https://embarkstudios.github.io/cargo-deny/)
+//! evaluated for each package
+//!
+//! The `deny.toml` of a workspace is read from the workspace root, or from
+//! any other location `cargo deny` looks in by default, see
+//! [`DENY_CONFIG_PATHS`]. Only the parts of the configuration that concern a
+//! single package are evaluated; Bans using `wrappers`, duplicate versions
+//! and `[sources]` are not.
+use std::{fs, io, path::Path};
+
+use rustsec::{Version, VersionReq};
+use serde::Deserialize;
+use spdx::{Expression, ParseMode};
+
+/// Locations of `deny.toml` relative to the workspace root, in the order
+/// `cargo deny` looks for them
+pub const DENY_CONFIG_PATHS: [&str; 3] =
+    ["deny.toml", ".deny.toml", ".cargo/deny.toml"];
+
+/// The parts of a `deny.toml` used to evaluate policies, some fields
+/// omitted
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DenyConfig {
+    #[serde(default)]
+    pub licenses: Option<LicensesConfig>,
+    #[serde(default)]
+    pub bans: BansConfig,
+}
+
+/// The `[licenses]` section of a `deny.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LicensesConfig {
+    /// SPDX licenses allowed for all packages, such as `MIT` or
+    /// `Apache-2.0 WITH LLVM-exception`
+    #[serde(default)]
+    pub allow: Vec<String>,
+
+    /// Licenses denied for all packages, only used by older versions of
+    /// `cargo deny`
+    #[serde(default)]
+    pub deny: Vec<String>,
+
+    /// Licenses allowed only for some packages
+    #[serde(default)]
+    pub exceptions: Vec<LicenseException>,
+}
+
+/// Licenses allowed for a single package
+#[derive(Debug, Clone, Deserialize)]
+pub struct LicenseException {
+    /// A package spec, such as `ring` or `ring@0.17`
+    #[serde(rename = "crate", alias = "name")]
+    pub spec: String,
+    pub allow: Vec<String>,
+}
+
+/// The `[bans]` section of a `deny.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BansConfig {
+    #[serde(default)]
+    pub deny: Vec<BanEntry>,
+}
+
+/// A banned package, either as a package spec such as `openssl@<0.10`, or
+/// as a table as used by older versions of `cargo deny`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum BanEntry {
+    Spec(String),
+    Table {
+        #[serde(rename = "crate", alias = "name")]
+        spec: String,
+        version: Option<String>,
+    },
+}
+
+impl BanEntry {
+    /// The package spec of the ban, such as `openssl` or `openssl@<0.10`
+    #[must_use]
+    pub fn spec(&self) -> String {
+        match self {
+            Self::Spec(s) => s.clone(),
+            Self::Table { spec, version } => match version {
+                Some(v) => format!("{spec}@{v}"),
+                None => spec.clone(),
+            },
+        }
+    }
+}
+
+/// If a package spec, such as `name` or `name@<1.0`, matches a package
+///
+/// As in a manifest, a version such as `1.0.2` is a requirement matching
+/// all semver compatible versions.
+///
+/// A spec with a version requirement that cannot be parsed never matches.
+fn spec_matches(spec: &str, name: &str, version: &Version) -> bool {
+    let (spec_name, req) = match spec.split_once('@') {
+        Some((n, r)) => (n, Some(r)),
+        None => (spec, None),
+    };
+    if spec_name != name {
+        return false;
+    }
+    match req {
+        Some(r) => VersionReq::parse(r).is_ok_and(|r| r.matches(version)),
+        None => true,
+    }
+}
+
+/// The outcome of evaluating a `deny.toml` for a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenyEvaluation {
+    /// The spec of the first ban matching the package
+    pub matched_ban: Option<String>,
+
+    /// `None` if no licenses are configured, or if the package has no valid
+    /// license expression
+    pub license_allowed: Option<bool>,
+}
+
+impl DenyEvaluation {
+    /// If the package is banned, or its license is not allowed
+    #[must_use]
+    pub fn is_denied(&self) -> bool {
+        self.matched_ban.is_some() || self.license_allowed == Some(false)
+    }
+}
+
+impl DenyConfig {
+    /// Parses the contents of a `deny.toml`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the configuration is not valid TOML, or
+    /// a used section has an unexpected format.
+    pub fn parse(raw_config: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(raw_config)
+    }
+
+    /// Reads the `deny.toml` of the workspace in `workspace_root`, `None` if
+    /// the workspace has none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the file cannot be read, or cannot be
+    /// parsed.
+    pub fn from_workspace_root(
+        workspace_root: &Path,
+    ) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        for path in DENY_CONFIG_PATHS {
+            match fs::read_to_string(workspace_root.join(path)) {
+                Ok(raw) => return Ok(Some(Self::parse(&raw)?)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(None)
+    }
+
+    /// The spec of the first ban matching a package, if any
+    #[must_use]
+    pub fn matched_ban(&self, name: &str, version: &Version) -> Option<String> {
+        self.bans
+            .deny
+            .iter()
+            .map(BanEntry::spec)
+            .find(|s| spec_matches(s, name, version))
+    }
+
+    /// If the license expression of a package is satisfied by the allowed
+    /// licenses, including exceptions for the package
+    ///
+    /// Returns `None` if no licenses are configured, or if `license` is not
+    /// a valid SPDX expression.
+    #[must_use]
+    pub fn license_allowed(
+        &self,
+        name: &str,
+        version: &Version,
+        license: Option<&str>,
+    ) -> Option<bool> {
+        let licenses = self.licenses.as_ref()?;
+        let expression =
+            Expression::parse_mode(license?, ParseMode::LAX).ok()?;
+
+        let exceptions = licenses
+            .exceptions
+            .iter()
+            .filter(|e| spec_matches(&e.spec, name, version))
+            .flat_map(|e| &e.allow)
+            .collect::<Vec<_>>();
+        Some(expression.evaluate(|req| {
+            let req = req.to_string();
+            !licenses.deny.contains(&req)
+                && (licenses.allow.contains(&req)
+                    || exceptions.iter().any(|e| **e == req))
+        }))
+    }
+
+    /// Evaluates the policies of this configuration for a package
+    #[must_use]
+    pub fn evaluate(
+        &self,
+        name: &str,
+        version: &Version,
+        license: Option<&str>,
+    ) -> DenyEvaluation {
+        DenyEvaluation {
+            matched_ban: self.matched_ban(name, version),
+            license_allowed: self.license_allowed(name, version, license),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rustsec::Version;
+    use test_case::test_case;
+
+    use super::DenyConfig;
+
+    const CONFIG: &str = r#"
+[licenses]
+allow = ["MIT", "Apache-2.0"]
+exceptions = [
+    { allow = ["Unicode-DFS-2016"], crate = "unicode-ident" },
+]
+
+[bans]
+multiple-versions = "warn"
+deny = [
+    "openssl",
+    { name = "syn", version = "<1.0" },
+    { crate = "libc@0.2.139", reason = "use rustix" },
+]
+
+[sources]
+unknown-registry = "deny"
+"#;
+
+    #[test_case("openssl", "0.10.0" => Some(String::from("openssl")) ; "any version")]
+    #[test_case("syn", "0.15.0" => Some(String::from("syn@<1.0")) ; "older table format")]
+    #[test_case("syn", "1.0.107" => None ; "version not banned")]
+    #[test_case("libc", "0.2.139" => Some(String::from("libc@0.2.139")) ; "spec in table")]
+    #[test_case("quote", "1.0.23" => None ; "not banned")]
+    fn matched_ban(name: &str, version: &str) -> Option<String> {
+        DenyConfig::parse(CONFIG)
+            .unwrap()
+            .matched_ban(name, &Version::parse(version).unwrap())
+    }
+
+    #[test_case("syn", Some("MIT OR Apache-2.0") => Some(true) ; "allowed")]
+    #[test_case("ring", Some("MIT AND ISC") => Some(false) ; "not allowed")]
+    #[test_case("unicode-ident", Some("(MIT OR Apache-2.0) AND Unicode-DFS-2016") => Some(true) ; "allowed by exception")]
+    #[test_case("other", Some("(MIT OR Apache-2.0) AND Unicode-DFS-2016") => Some(false) ; "exception for other package")]
+    #[test_case("syn", None => None ; "no license")]
+    fn license_allowed(name: &str, license: Option<&str>) -> Option<bool> {
+        DenyConfig::parse(CONFIG).unwrap().license_allowed(
+            name,
+            &Version::parse("1.0.0").unwrap(),
+            license,
+        )
+    }
+
+    #[test]
+    fn no_licenses_configured() {
+        let config = DenyConfig::parse("[bans]\ndeny = []").unwrap();
+        let evaluation = config.evaluate(
+            "syn",
+            &Version::parse("1.0.0").unwrap(),
+            Some("MIT"),
+        );
+        assert_eq!(evaluation.license_allowed, None);
+        assert!(!evaluation.is_denied());
+    }
+}
@@ -0,0 +1,285 @@
+//! Client for retrieving package information from the
+//! [deps.dev](https://deps.dev) API by Google
+//!
+//! deps.dev resolves the dependency graph of every version published to
+//! crates.io on its own, and links packages to their source repositories,
+//! making it a second opinion next to crates.io and the local metadata.
+//!
+//! See [the deps.dev API documentation](https://docs.deps.dev/api/v3/) for
+//! more information.
+
+use std::rc::Rc;
+
+use serde::{de::DeserializeOwned, Deserialize};
+use url::form_urlencoded::byte_serialize;
+
+use crate::{
+    http::{self, ResponseCache},
+    source::PackageSource,
+    NameVersion,
+};
+
+/// Base URL of the deps.dev API, for crates.io packages
+pub const DEPS_DEV_API_URL: &str = "https://api.deps.dev/v3/systems/cargo";
+
+/// Base URL of the deps.dev web page of crates.io packages
+pub const DEPS_DEV_URL: &str = "https://deps.dev/cargo";
+
+/// Base URL of the OpenSSF Scorecard viewer, taking a project such as
+/// `github.com/serde-rs/serde`
+pub const SCORECARD_VIEWER_URL: &str =
+    "https://securityscorecards.dev/viewer/?uri=";
+
+/// A version of a package, as returned by the deps.dev `GetVersion` endpoint,
+/// some fields omitted
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepsDevVersion {
+    /// If this is the version deps.dev shows by default, usually the latest
+    #[serde(default)]
+    pub is_default: bool,
+
+    /// SPDX expressions of the licenses of this version
+    #[serde(default)]
+    pub licenses: Vec<String>,
+
+    #[serde(default)]
+    pub advisory_keys: Vec<DepsDevAdvisoryKey>,
+
+    #[serde(default)]
+    pub related_projects: Vec<DepsDevRelatedProject>,
+}
+
+/// Identifies an advisory, such as `GHSA-43w2-9j62-hq99`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DepsDevAdvisoryKey {
+    pub id: String,
+}
+
+/// A project, such as a GitHub repository, related to a package version
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepsDevRelatedProject {
+    pub project_key: DepsDevProjectKey,
+
+    /// Such as `SOURCE_REPO` or `ISSUE_TRACKER`
+    pub relation_type: String,
+}
+
+/// Identifies a project, such as `github.com/serde-rs/serde`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DepsDevProjectKey {
+    pub id: String,
+}
+
+/// A package, as returned by the deps.dev `GetPackage` endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct DepsDevPackage {
+    #[serde(default)]
+    pub versions: Vec<DepsDevPackageVersion>,
+}
+
+/// A version of a package as listed by the `GetPackage` endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepsDevPackageVersion {
+    pub version_key: DepsDevVersionKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DepsDevVersionKey {
+    pub version: String,
+}
+
+/// The resolved dependency graph of a package version, as returned by the
+/// deps.dev `GetDependencies` endpoint, edges omitted
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct DepsDevDependencies {
+    #[serde(default)]
+    pub nodes: Vec<DepsDevDependencyNode>,
+}
+
+/// A package version in a dependency graph
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DepsDevDependencyNode {
+    /// Either `SELF`, `DIRECT` or `INDIRECT`
+    pub relation: String,
+}
+
+impl DepsDevDependencies {
+    fn count(&self, relation: &str) -> u64 {
+        self.nodes.iter().filter(|n| n.relation == relation).count() as u64
+    }
+}
+
+/// What deps.dev knows about a version of a crates.io package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepsDevInfo {
+    pub url: String,
+    pub version: DepsDevVersion,
+
+    /// All versions of the package known to deps.dev
+    pub known_versions: Vec<String>,
+
+    /// `None` if deps.dev could not resolve the dependencies of this version
+    pub dependencies: Option<DepsDevDependencies>,
+}
+
+impl DepsDevInfo {
+    /// The project of the source repository, such as
+    /// `github.com/serde-rs/serde`
+    #[must_use]
+    pub fn source_repository(&self) -> Option<&str> {
+        self.version
+            .related_projects
+            .iter()
+            .find(|p| p.relation_type == "SOURCE_REPO")
+            .map(|p| p.project_key.id.as_str())
+    }
+
+    /// Link to the OpenSSF Scorecard of the source repository
+    #[must_use]
+    pub fn scorecard_url(&self) -> Option<String> {
+        self.source_repository()
+            .map(|r| format!("{SCORECARD_VIEWER_URL}{r}"))
+    }
+
+    #[must_use]
+    pub fn advisory_ids(&self) -> Vec<String> {
+        self.version
+            .advisory_keys
+            .iter()
+            .map(|a| a.id.clone())
+            .collect()
+    }
+
+    #[must_use]
+    pub fn direct_dependencies_count(&self) -> Option<u64> {
+        self.dependencies.as_ref().map(|d| d.count("DIRECT"))
+    }
+
+    #[must_use]
+    pub fn indirect_dependencies_count(&self) -> Option<u64> {
+        self.dependencies.as_ref().map(|d| d.count("INDIRECT"))
+    }
+}
+
+/// Wrapper around a [`reqwest::Client`] querying deps.dev, with caching
+#[derive(Debug, Clone, Default)]
+pub struct DepsDevClient {
+    client: reqwest::Client,
+
+    /// Cache between a package version and what deps.dev knows about it
+    cache: ResponseCache<NameVersion, Rc<DepsDevInfo>>,
+}
+
+impl DepsDevClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves what deps.dev knows about a version of a crates.io package
+    ///
+    /// Makes three requests, for the version, the package and the dependency
+    /// graph of the version. Will return `None` if the package is not from
+    /// crates.io, or if either of the first two requests fails, and will
+    /// cache this package version as such.
+    pub fn info(&mut self, package: &NameVersion) -> Option<Rc<DepsDevInfo>> {
+        if package.source != PackageSource::CratesIo {
+            return None;
+        }
+        self.cache.get_or_fetch(package, || {
+            let name =
+                byte_serialize(package.name.as_bytes()).collect::<String>();
+            let version =
+                byte_serialize(package.version.to_string().as_bytes())
+                    .collect::<String>();
+            let package_url = format!("{DEPS_DEV_API_URL}/packages/{name}");
+            let version_url = format!("{package_url}/versions/{version}");
+
+            let version_info =
+                get::<DepsDevVersion>(&self.client, &version_url, package)?;
+            let package_info =
+                get::<DepsDevPackage>(&self.client, &package_url, package)?;
+            Some(Rc::new(DepsDevInfo {
+                url: format!("{DEPS_DEV_URL}/{name}/{version}"),
+                version: version_info,
+                known_versions: package_info
+                    .versions
+                    .into_iter()
+                    .map(|v| v.version_key.version)
+                    .collect(),
+                dependencies: get::<DepsDevDependencies>(
+                    &self.client,
+                    &format!("{version_url}:dependencies"),
+                    package,
+                ),
+            }))
+        })
+    }
+}
+
+/// Retrieves a resource of the deps.dev API about a package version
+fn get<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+    package: &NameVersion,
+) -> Option<T> {
+    http::send_json(
+        client.get(url),
+        &format!(
+            "deps.dev information for {} {}",
+            package.name, package.version
+        ),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::{
+        DepsDevDependencies, DepsDevInfo, DepsDevPackage, DepsDevVersion,
+    };
+
+    fn read<T: serde::de::DeserializeOwned>(file_name: &str) -> T {
+        let json_string = fs::read_to_string(format!(
+            "test_data/deps-dev-output/{file_name}"
+        ))
+        .unwrap();
+        serde_json::from_str::<T>(&json_string).unwrap()
+    }
+
+    #[test]
+    fn deserialize_deps_dev_responses() {
+        let info = DepsDevInfo {
+            url: String::from("https://deps.dev/cargo/smallvec/1.6.0"),
+            version: read::<DepsDevVersion>("smallvec-version.json"),
+            known_versions: read::<DepsDevPackage>("smallvec-package.json")
+                .versions
+                .into_iter()
+                .map(|v| v.version_key.version)
+                .collect(),
+            dependencies: Some(read::<DepsDevDependencies>(
+                "smallvec-dependencies.json",
+            )),
+        };
+
+        assert_eq!(
+            info.source_repository(),
+            Some("github.com/servo/rust-smallvec")
+        );
+        assert_eq!(
+            info.scorecard_url().as_deref(),
+            Some("https://securityscorecards.dev/viewer/?uri=github.com/servo/rust-smallvec")
+        );
+        assert_eq!(
+            info.advisory_ids(),
+            vec![String::from("GHSA-43w2-9j62-hq99")]
+        );
+        assert_eq!(info.known_versions, vec!["1.6.0", "1.6.1"]);
+        assert_eq!(info.direct_dependencies_count(), Some(1));
+        assert_eq!(info.indirect_dependencies_count(), Some(1));
+    }
+}
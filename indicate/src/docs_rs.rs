@@ -0,0 +1,182 @@
+//! Client for retrieving the documentation build status of crates from
+//! [docs.rs](https://docs.rs)
+//!
+//! docs.rs builds the documentation of every version published to crates.io.
+//! A failing build means that the documentation of that version is not
+//! available, which often indicates that a crate is not maintained.
+//!
+//! docs.rs only reports if a build succeeded, so the targets documentation
+//! is built for are read from the `[package.metadata.docs.rs]` section of
+//! the manifest, the same way docs.rs does. See
+//! [the docs.rs metadata documentation](https://docs.rs/about/metadata).
+
+use std::rc::Rc;
+
+use cargo_metadata::Package;
+use serde::Deserialize;
+
+use crate::{
+    http::{self, ResponseCache},
+    source::PackageSource,
+    NameVersion,
+};
+
+/// Base URL of docs.rs
+pub const DOCS_RS_URL: &str = "https://docs.rs";
+
+/// The target docs.rs builds documentation for if no other is configured
+pub const DEFAULT_TARGET: &str = "x86_64-unknown-linux-gnu";
+
+/// The response of the docs.rs status endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DocsRsStatus {
+    /// The version the status is for
+    pub version: String,
+
+    /// If the documentation built successfully
+    pub doc_status: bool,
+}
+
+/// The documentation build of a package version on docs.rs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocsRsBuild {
+    pub build_succeeded: bool,
+    pub url: String,
+
+    /// The target docs.rs shows documentation for by default
+    pub default_target: String,
+
+    /// All targets documentation is built for, including `default_target`
+    pub targets: Vec<String>,
+}
+
+impl DocsRsBuild {
+    #[must_use]
+    pub fn new(package: &Package, status: &DocsRsStatus) -> Self {
+        let (default_target, targets) = docs_rs_targets(package);
+        Self {
+            build_succeeded: status.doc_status,
+            url: format!("{DOCS_RS_URL}/{}/{}", package.name, package.version),
+            default_target,
+            targets,
+        }
+    }
+}
+
+/// The `[package.metadata.docs.rs]` section of a manifest, other fields
+/// omitted
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct DocsRsMetadata {
+    default_target: Option<String>,
+    targets: Option<Vec<String>>,
+}
+
+/// Determines the default target and all targets docs.rs builds
+/// documentation of a package for
+///
+/// The default target is `default-target`, the first of `targets`, or
+/// [`DEFAULT_TARGET`], in that order. Only the default target is built if
+/// `targets` is not set.
+#[must_use]
+pub fn docs_rs_targets(package: &Package) -> (String, Vec<String>) {
+    let metadata = package
+        .metadata
+        .get("docs")
+        .and_then(|d| d.get("rs"))
+        .and_then(|m| DocsRsMetadata::deserialize(m).ok())
+        .unwrap_or_default();
+
+    let mut targets = metadata.targets.unwrap_or_default();
+    let default_target = metadata
+        .default_target
+        .or_else(|| targets.first().cloned())
+        .unwrap_or_else(|| String::from(DEFAULT_TARGET));
+    if !targets.contains(&default_target) {
+        targets.insert(0, default_target.clone());
+    }
+    (default_target, targets)
+}
+
+/// Wrapper around a [`reqwest::Client`] querying docs.rs, with caching
+#[derive(Debug, Clone, Default)]
+pub struct DocsRsClient {
+    client: reqwest::Client,
+
+    /// Cache between a package version and its build status
+    cache: ResponseCache<NameVersion, Rc<DocsRsStatus>>,
+}
+
+impl DocsRsClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves the documentation build status of a version of a crates.io
+    /// package
+    ///
+    /// Will return `None` if the package is not from crates.io, or if the
+    /// request fails (such as if the version has not been built yet), and
+    /// will cache this package version as such.
+    pub fn status(
+        &mut self,
+        package: &NameVersion,
+    ) -> Option<Rc<DocsRsStatus>> {
+        if package.source != PackageSource::CratesIo {
+            return None;
+        }
+        self.cache.get_or_fetch(package, || {
+            let url = format!(
+                "{DOCS_RS_URL}/crate/{}/{}/status.json",
+                package.name, package.version
+            );
+            http::send_json::<DocsRsStatus>(
+                self.client.get(&url),
+                &format!(
+                    "docs.rs build status for {} {}",
+                    package.name, package.version
+                ),
+            )
+            .map(Rc::new)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cargo_metadata::Package;
+    use test_case::test_case;
+
+    use super::{docs_rs_targets, DocsRsStatus};
+
+    #[test]
+    fn deserialize_docs_rs_status() {
+        let status = serde_json::from_str::<DocsRsStatus>(
+            r#"{ "version": "1.0.0", "doc_status": true }"#,
+        )
+        .unwrap();
+        assert!(status.doc_status);
+    }
+
+    #[test_case(serde_json::Value::Null => ("x86_64-unknown-linux-gnu".to_string(), vec!["x86_64-unknown-linux-gnu".to_string()]) ; "no metadata")]
+    #[test_case(serde_json::json!({ "docs": { "rs": { "targets": ["wasm32-unknown-unknown"] } } }) => ("wasm32-unknown-unknown".to_string(), vec!["wasm32-unknown-unknown".to_string()]) ; "targets")]
+    #[test_case(serde_json::json!({ "docs": { "rs": { "default-target": "x86_64-apple-darwin", "targets": ["x86_64-pc-windows-msvc"] } } }) => ("x86_64-apple-darwin".to_string(), vec!["x86_64-apple-darwin".to_string(), "x86_64-pc-windows-msvc".to_string()]) ; "default target")]
+    fn targets_from_metadata(
+        metadata: serde_json::Value,
+    ) -> (String, Vec<String>) {
+        let mut package =
+            serde_json::from_value::<Package>(serde_json::json!({
+                "name": "some_crate",
+                "version": "1.0.0",
+                "id": "some_crate 1.0.0",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "/some_crate/Cargo.toml",
+            }))
+            .unwrap();
+        package.metadata = metadata;
+        docs_rs_targets(&package)
+    }
+}
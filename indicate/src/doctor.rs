@@ -0,0 +1,308 @@
+//! Checks that the environment is set up correctly, with actionable fixes
+//!
+//! Unlike [`Capabilities`](crate::capabilities::Capabilities), which only
+//! reports what is configured, the checks here make cheap requests to
+//! external services to verify that tokens are valid and that services can
+//! be reached, and check that cache directories can be written to.
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{advisory::AdvisoryClient, capabilities::ExternalBinary, RUNTIME};
+
+/// Endpoint returning the rate limit of the authenticated user, which does
+/// not count against the rate limit itself
+pub const GITHUB_RATE_LIMIT_URL: &str = "https://api.github.com/rate_limit";
+
+/// The outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+
+    /// Parts of the schema or command line options will not work
+    Warning,
+
+    /// Most queries will not work
+    Error,
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warning",
+            CheckStatus::Error => "error",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A check of one part of the environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+
+    /// How to fix the problem, if the check did not succeed
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn failed(
+        name: &'static str,
+        status: CheckStatus,
+        message: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}: {}", self.status, self.name, self.message)?;
+        if let Some(fix) = &self.fix {
+            write!(f, "\n    fix: {fix}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs all checks
+///
+/// Makes one request to the GitHub API if a token is set, and one to the
+/// repository of `advisory-db`.
+#[must_use]
+pub fn diagnose() -> Vec<Check> {
+    vec![
+        check_cargo_geiger(),
+        check_user_agent(),
+        check_github_token(),
+        check_advisory_db(),
+        check_cache_dir(
+            "GitHub cache directory",
+            dirs::home_dir().map(|h| h.join(".github/cache")),
+        ),
+        check_cache_dir(
+            "advisory-db directory",
+            Some(AdvisoryClient::default_path()),
+        ),
+    ]
+}
+
+fn check_cargo_geiger() -> Check {
+    let geiger = ExternalBinary::detect("cargo-geiger", "the `geiger` edge");
+    match geiger.version {
+        Some(version) => Check::ok("cargo-geiger", version),
+        None => Check::failed(
+            "cargo-geiger",
+            CheckStatus::Warning,
+            "not installed, the `geiger` edge will not work",
+            "install it using `cargo install cargo-geiger`",
+        ),
+    }
+}
+
+fn check_user_agent() -> Check {
+    match std::env::var("USER_AGENT") {
+        Ok(user_agent) => Check::ok("USER_AGENT", user_agent),
+        Err(_) => Check::failed(
+            "USER_AGENT",
+            CheckStatus::Error,
+            "not set, GitHub and crates.io cannot be used",
+            "set USER_AGENT to something identifying you to GitHub and crates.io, such as `my-tool (me@example.com)`",
+        ),
+    }
+}
+
+/// The response of the GitHub rate limit endpoint, other fields omitted
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    rate: RateLimit,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimit {
+    limit: u64,
+    remaining: u64,
+}
+
+fn check_github_token() -> Check {
+    const NAME: &str = "GITHUB_API_TOKEN";
+    let Ok(token) = std::env::var(NAME) else {
+        return Check::failed(
+            NAME,
+            CheckStatus::Error,
+            "not set, GitHub cannot be used",
+            "create a token at https://github.com/settings/tokens and set GITHUB_API_TOKEN",
+        );
+    };
+    let user_agent = std::env::var("USER_AGENT")
+        .unwrap_or_else(|_| String::from("cargo-indicate"));
+
+    let future = async {
+        reqwest::Client::new()
+            .get(GITHUB_RATE_LIMIT_URL)
+            .bearer_auth(token)
+            .header(reqwest::header::USER_AGENT, user_agent)
+            .send()
+            .await
+    };
+    match RUNTIME.block_on(future) {
+        Ok(r) if r.status() == reqwest::StatusCode::UNAUTHORIZED => {
+            Check::failed(
+                NAME,
+                CheckStatus::Error,
+                "the token is invalid or has expired",
+                "create a new token at https://github.com/settings/tokens",
+            )
+        }
+        Ok(r) => match RUNTIME
+            .block_on(async { r.error_for_status()?.json().await })
+        {
+            Ok(RateLimitResponse { rate }) if rate.remaining == 0 => {
+                Check::failed(
+                    NAME,
+                    CheckStatus::Warning,
+                    format!("valid, but all {} requests are used", rate.limit),
+                    "wait for a new quota, or use `--await-github-quota`",
+                )
+            }
+            Ok(RateLimitResponse { rate }) => Check::ok(
+                NAME,
+                format!(
+                    "valid, {} of {} requests remaining",
+                    rate.remaining, rate.limit
+                ),
+            ),
+            Err(e) => Check::failed(
+                NAME,
+                CheckStatus::Warning,
+                format!("could not check the token due to error: {e}"),
+                "check that GitHub is not down at https://www.githubstatus.com",
+            ),
+        },
+        Err(e) => Check::failed(
+            NAME,
+            CheckStatus::Error,
+            format!("could not reach GitHub due to error: {e}"),
+            "check your network connection and proxy settings",
+        ),
+    }
+}
+
+fn check_advisory_db() -> Check {
+    const NAME: &str = "advisory-db";
+    // Used by git to discover references, so it is cheap
+    let url = format!(
+        "{}/info/refs?service=git-upload-pack",
+        rustsec::repository::git::DEFAULT_URL
+    );
+    let future = async {
+        reqwest::get(url).await?.error_for_status()?;
+        Ok::<_, reqwest::Error>(())
+    };
+    let local = AdvisoryClient::default_path();
+    match RUNTIME.block_on(future) {
+        Ok(()) => Check::ok(NAME, "reachable"),
+        Err(e) if local.is_dir() => Check::failed(
+            NAME,
+            CheckStatus::Warning,
+            format!("could not be reached due to error: {e}"),
+            format!(
+                "use the local copy in {} using `--cached-advisory-db`",
+                local.to_string_lossy()
+            ),
+        ),
+        Err(e) => Check::failed(
+            NAME,
+            CheckStatus::Error,
+            format!("could not be reached due to error: {e}"),
+            "check your network connection, or clone it and use `--advisory-db-dir`",
+        ),
+    }
+}
+
+fn check_cache_dir(name: &'static str, dir: Option<PathBuf>) -> Check {
+    let Some(dir) = dir else {
+        return Check::failed(
+            name,
+            CheckStatus::Error,
+            "no home directory found",
+            "set the HOME environment variable",
+        );
+    };
+    match writable(&dir) {
+        Ok(()) => {
+            Check::ok(name, format!("{} is writable", dir.to_string_lossy()))
+        }
+        Err(e) => Check::failed(
+            name,
+            CheckStatus::Error,
+            format!(
+                "{} is not writable due to error: {e}",
+                dir.to_string_lossy()
+            ),
+            format!(
+                "make sure the current user owns {}",
+                dir.to_string_lossy()
+            ),
+        ),
+    }
+}
+
+/// Checks that a file can be created in a directory, or in the closest
+/// existing ancestor if the directory does not exist yet
+fn writable(dir: &Path) -> std::io::Result<()> {
+    let existing = dir
+        .ancestors()
+        .find(|a| a.is_dir())
+        .unwrap_or_else(|| Path::new("."));
+    let probe = existing.join(".cargo-indicate-doctor");
+    fs::write(&probe, b"")?;
+    fs::remove_file(&probe)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_cache_dir, Check, CheckStatus};
+
+    #[test]
+    fn missing_cache_dir_in_writable_parent() {
+        let dir = std::env::temp_dir().join("doctor/does/not/exist");
+        assert_eq!(check_cache_dir("cache", Some(dir)).status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn display_failed_check() {
+        let check = Check::failed(
+            "cargo-geiger",
+            CheckStatus::Warning,
+            "not installed",
+            "install it",
+        );
+        assert_eq!(
+            check.to_string(),
+            "[warning] cargo-geiger: not installed\n    fix: install it"
+        );
+    }
+}
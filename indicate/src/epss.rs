@@ -0,0 +1,135 @@
+//! Client for retrieving [Exploit Prediction Scoring System](https://www.first.org/epss/)
+//! (EPSS) scores from the FIRST.org API
+//!
+//! EPSS estimates the probability that a CVE will be exploited in the wild
+//! within the next 30 days, and is only available for advisories with a CVE
+//! ID. No token is required.
+//!
+//! See [the EPSS API documentation](https://www.first.org/epss/api) for more
+//! information.
+
+use std::{collections::HashMap, rc::Rc};
+
+use serde::Deserialize;
+
+use crate::http;
+
+/// Endpoint used to query the EPSS scores of CVEs
+pub const EPSS_API_URL: &str = "https://api.first.org/data/v1/epss";
+
+/// The response of the EPSS API; CVEs without a score are not included
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EpssResponse {
+    #[serde(default)]
+    pub data: Vec<EpssScore>,
+}
+
+/// The EPSS score of a CVE, as returned by the EPSS API
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct EpssScore {
+    /// Such as `CVE-2021-25900`
+    pub cve: String,
+
+    /// The probability of exploitation between 0 and 1, as a decimal string
+    pub epss: String,
+
+    /// The proportion of all scored CVEs with the same or a lower
+    /// probability, as a decimal string
+    pub percentile: String,
+
+    /// The date the score was computed, such as `2024-06-01`
+    pub date: String,
+}
+
+impl EpssScore {
+    /// The probability of exploitation between 0 and 1, `None` if it could
+    /// not be parsed
+    #[must_use]
+    pub fn probability(&self) -> Option<f64> {
+        self.epss.parse().ok()
+    }
+
+    /// The percentile between 0 and 1, `None` if it could not be parsed
+    #[must_use]
+    pub fn percentile(&self) -> Option<f64> {
+        self.percentile.parse().ok()
+    }
+}
+
+/// Wrapper around a [`reqwest::Client`] querying the EPSS API, with caching
+#[derive(Debug, Clone, Default)]
+pub struct EpssClient {
+    client: reqwest::Client,
+
+    /// Cache between a CVE ID and its score
+    ///
+    /// CVEs without a score, and CVEs of failed requests, are cached as
+    /// `None`, so that they are not retried during the same query.
+    cache: HashMap<String, Option<Rc<EpssScore>>>,
+}
+
+impl EpssClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves the EPSS scores of CVEs, such as `CVE-2021-25900`, in the
+    /// same order as `cves`
+    ///
+    /// All CVEs not already cached are retrieved using a single request. CVEs
+    /// without a score, such as recently published ones, are not included,
+    /// and neither are any CVEs if the request fails, in which case they are
+    /// cached as such.
+    pub fn scores(&mut self, cves: &[String]) -> Vec<Rc<EpssScore>> {
+        let uncached = cves
+            .iter()
+            .filter(|c| !self.cache.contains_key(*c))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !uncached.is_empty() {
+            let mut scores = http::send_json::<EpssResponse>(
+                self.client
+                    .get(EPSS_API_URL)
+                    .query(&[("cve", uncached.join(","))]),
+                &format!("EPSS scores of {}", uncached.join(", ")),
+            )
+            .map(|r| {
+                r.data
+                    .into_iter()
+                    .map(|s| (s.cve.clone(), Rc::new(s)))
+                    .collect::<HashMap<_, _>>()
+            })
+            .unwrap_or_default();
+            for cve in uncached {
+                let score = scores.remove(&cve);
+                self.cache.insert(cve, score);
+            }
+        }
+
+        cves.iter()
+            .filter_map(|c| self.cache.get(c).cloned().flatten())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::EpssResponse;
+
+    #[test]
+    fn deserialize_epss_response() {
+        let json_string =
+            fs::read_to_string("test_data/epss-output/scores.json").unwrap();
+        let res = serde_json::from_str::<EpssResponse>(&json_string).unwrap();
+
+        assert_eq!(res.data.len(), 2);
+        assert_eq!(res.data[0].cve, "CVE-2021-25900");
+        assert_eq!(res.data[0].probability(), Some(0.0024));
+        assert_eq!(res.data[0].percentile(), Some(0.61149));
+        assert_eq!(res.data[1].date, "2024-06-01");
+    }
+}
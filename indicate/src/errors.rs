@@ -14,6 +14,9 @@ pub enum FileParseError {
         "could not extract file path from path `{0}`, file does not exist"
     )]
     NotFound(String),
+
+    #[error("file `{0}` includes itself")]
+    IncludeCycle(String),
 }
 
 #[derive(Error, Debug, Clone)]
@@ -32,3 +35,60 @@ pub enum ManifestPathError {
     #[error("could not create a valid absoulute path to a `Cargo`.toml file: Created `{0}")]
     CouldNotCreateValidPath(String),
 }
+
+#[derive(Error, Debug, Clone)]
+pub enum ChangedSinceError {
+    #[error("could not retrieve `Cargo.lock` from git due to error: {0}")]
+    Git(String),
+
+    #[error("could not read `Cargo.lock` due to error: {0}")]
+    InvalidLockfile(String),
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum ConfigError {
+    #[error("could not read configuration file `{0}` due to error: {1}")]
+    Read(String, String),
+
+    #[error("could not parse configuration file `{0}` due to error: {1}")]
+    Parse(String, String),
+
+    #[error("no feature profile named `{name}`, available profiles are: {available}")]
+    UnknownProfile { name: String, available: String },
+}
+
+/// A problem with an argument provided to a query
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QueryArgumentError {
+    #[error("missing argument `{name}` of type `{expected_type}`")]
+    Missing { name: String, expected_type: String },
+
+    #[error("argument `{0}` is not used by the query")]
+    Superfluous(String),
+
+    #[error(
+        "argument `{name}` must be of type `{expected_type}`, but was `{value}`"
+    )]
+    WrongType {
+        name: String,
+        expected_type: String,
+        value: String,
+    },
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum QueryValidationError {
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+
+    #[error("invalid arguments: {}", join_errors(.0))]
+    InvalidArguments(Vec<QueryArgumentError>),
+}
+
+fn join_errors(errors: &[QueryArgumentError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,200 @@
+//! Dependencies that are only resolved under some feature combinations
+//!
+//! Resolving metadata once per feature combination makes it possible to find
+//! heavy dependencies hidden behind a feature, without running the same
+//! queries with different `--features` flags and comparing the results
+//! manually.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+};
+
+use cargo_metadata::{CargoOpt, DependencyKind, Metadata};
+use serde::Serialize;
+
+use crate::{config::IndicateConfig, util, ManifestPath};
+
+/// Features used to resolve metadata once, with a name used in reports
+#[derive(Debug, Clone)]
+pub struct FeatureCombination {
+    pub name: String,
+    pub features: Vec<CargoOpt>,
+}
+
+impl FeatureCombination {
+    #[must_use]
+    pub fn new(name: String, features: Vec<CargoOpt>) -> Self {
+        Self { name, features }
+    }
+
+    /// Combinations for the default features, no features, all features, and
+    /// each feature declared by the root package on its own (without default
+    /// features)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the metadata has no root package.
+    #[must_use]
+    pub fn for_each_feature(metadata: &Metadata) -> Vec<Self> {
+        let root = metadata.root_package().expect("no root package found");
+        let mut combinations = vec![
+            Self::new(String::from("default"), Vec::new()),
+            Self::new(
+                String::from("no-default-features"),
+                vec![CargoOpt::NoDefaultFeatures],
+            ),
+            Self::new(
+                String::from("all-features"),
+                vec![CargoOpt::AllFeatures],
+            ),
+        ];
+        let mut features = root
+            .features
+            .keys()
+            .filter(|f| f.as_str() != "default")
+            .collect::<Vec<_>>();
+        features.sort();
+        combinations.extend(features.into_iter().map(|f| {
+            Self::new(
+                format!("feature `{f}`"),
+                vec![
+                    CargoOpt::NoDefaultFeatures,
+                    CargoOpt::SomeFeatures(vec![f.clone()]),
+                ],
+            )
+        }));
+        combinations
+    }
+
+    /// Combinations for each feature profile in a configuration file
+    #[must_use]
+    pub fn for_each_profile(config: &IndicateConfig) -> Vec<Self> {
+        config
+            .profiles
+            .iter()
+            .map(|(name, profile)| {
+                Self::new(format!("profile `{name}`"), profile.cargo_opts())
+            })
+            .collect()
+    }
+}
+
+/// A dependency that is not resolved under all feature combinations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeatureGatedDependency {
+    pub name: String,
+    pub version: String,
+
+    /// Names of the combinations this dependency is resolved under
+    pub combinations: Vec<String>,
+}
+
+/// Names and versions of all packages reachable from the root package through
+/// normal dependencies, excluding the root package itself
+pub(crate) fn reachable_dependencies(
+    metadata: &Metadata,
+) -> BTreeSet<(String, String)> {
+    let packages = util::get_packages(metadata);
+    let direct_dependencies =
+        util::get_direct_dependencies(metadata, Some(DependencyKind::Normal));
+    util::get_depths(metadata, &direct_dependencies)
+        .into_iter()
+        .filter(|(_, depth)| *depth > 0)
+        .map(|(id, _)| {
+            let package = &packages[&id];
+            (package.name.clone(), package.version.to_string())
+        })
+        .collect()
+}
+
+/// Resolves metadata for each feature combination, and retrieves the
+/// dependencies that are not resolved under all of them, sorted by name and
+/// version
+///
+/// # Errors
+///
+/// Returns an error variant if metadata cannot be resolved for a combination,
+/// such as if it enables a feature that does not exist.
+pub fn feature_gated_dependencies(
+    manifest_path: &ManifestPath,
+    combinations: &[FeatureCombination],
+) -> Result<Vec<FeatureGatedDependency>, Box<dyn Error>> {
+    let mut resolved = Vec::with_capacity(combinations.len());
+    for combination in combinations {
+        let metadata = manifest_path.metadata(combination.features.clone())?;
+        resolved.push((
+            combination.name.clone(),
+            reachable_dependencies(&metadata),
+        ));
+    }
+
+    Ok(partially_resolved(&resolved)
+        .into_iter()
+        .map(|((name, version), combinations)| FeatureGatedDependency {
+            name,
+            version,
+            combinations,
+        })
+        .collect())
+}
+
+/// Finds the dependencies not part of all named dependency sets, and the
+/// names of the sets they are part of
+pub(crate) fn partially_resolved(
+    resolved: &[(String, BTreeSet<(String, String)>)],
+) -> BTreeMap<(String, String), Vec<String>> {
+    let mut resolved_under: BTreeMap<(String, String), Vec<String>> =
+        BTreeMap::new();
+    for (name, dependencies) in resolved {
+        for dependency in dependencies {
+            resolved_under
+                .entry(dependency.clone())
+                .or_default()
+                .push(name.clone());
+        }
+    }
+    resolved_under.retain(|_, names| names.len() < resolved.len());
+    resolved_under
+}
+
+#[cfg(test)]
+mod test {
+    use super::{feature_gated_dependencies, FeatureCombination};
+    use crate::ManifestPath;
+
+    #[test]
+    fn feature_gated() {
+        let manifest_path =
+            ManifestPath::from("test_data/fake_crates/feature_deps");
+        let metadata = manifest_path.metadata(Vec::new()).unwrap();
+        let combinations = FeatureCombination::for_each_feature(&metadata);
+        assert_eq!(
+            combinations
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "default",
+                "no-default-features",
+                "all-features",
+                "feature `a`",
+                "feature `b`",
+                "feature `c`",
+                "feature `d`",
+            ]
+        );
+
+        let gated =
+            feature_gated_dependencies(&manifest_path, &combinations).unwrap();
+        let syn = gated.iter().find(|d| d.name == "syn").unwrap();
+        assert_eq!(
+            syn.combinations,
+            ["default", "all-features", "feature `a`"]
+        );
+        let rand = gated.iter().find(|d| d.name == "rand").unwrap();
+        assert_eq!(
+            rand.combinations,
+            ["all-features", "feature `c`", "feature `d`"]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Features declared by packages, and what they enable
+//!
+//! A feature can enable other features of the same package, optional
+//! dependencies, and features of dependencies (see
+//! [the Cargo reference](https://doc.rust-lang.org/cargo/reference/features.html)).
+use std::rc::Rc;
+
+use cargo_metadata::{Metadata, Package};
+
+use crate::{adapter::PackageMap, util};
+
+/// A feature declared in the manifest of a package
+#[derive(Debug, Clone)]
+pub struct Feature {
+    /// The package declaring this feature
+    pub package: Rc<Package>,
+    pub name: String,
+
+    /// If this feature is enabled when resolving the dependency graph
+    pub enabled: bool,
+}
+
+/// What a single value in the list of a feature refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeatureValue<'a> {
+    /// Another feature of the same package, like `"std"`
+    Feature(&'a str),
+
+    /// An optional dependency, like `"dep:serde"`
+    Dependency(&'a str),
+
+    /// A feature of a dependency, like `"serde/derive"`; if `weak`
+    /// (`"serde?/derive"`) the dependency itself is not enabled
+    DependencyFeature {
+        dependency: &'a str,
+        feature: &'a str,
+        weak: bool,
+    },
+}
+
+impl<'a> From<&'a str> for FeatureValue<'a> {
+    fn from(value: &'a str) -> Self {
+        if let Some(dependency) = value.strip_prefix("dep:") {
+            FeatureValue::Dependency(dependency)
+        } else if let Some((dependency, feature)) = value.split_once('/') {
+            match dependency.strip_suffix('?') {
+                Some(dependency) => FeatureValue::DependencyFeature {
+                    dependency,
+                    feature,
+                    weak: true,
+                },
+                None => FeatureValue::DependencyFeature {
+                    dependency,
+                    feature,
+                    weak: false,
+                },
+            }
+        } else {
+            FeatureValue::Feature(value)
+        }
+    }
+}
+
+impl Feature {
+    /// Creates a feature of a package, checking if it is enabled in the
+    /// resolved dependency graph of `metadata`
+    fn new(metadata: &Metadata, package: Rc<Package>, name: String) -> Self {
+        let enabled = metadata
+            .resolve
+            .as_ref()
+            .and_then(|r| r.nodes.iter().find(|n| n.id == package.id))
+            .is_some_and(|n| n.features.contains(&name));
+        Self {
+            package,
+            name,
+            enabled,
+        }
+    }
+
+    /// The values listed for this feature in the manifest
+    fn values(&self) -> impl Iterator<Item = FeatureValue<'_>> {
+        self.package
+            .features
+            .get(&self.name)
+            .into_iter()
+            .flatten()
+            .map(|v| FeatureValue::from(v.as_str()))
+    }
+}
+
+/// Retrieves all features declared by a package, sorted by name
+pub(crate) fn get_features(
+    metadata: &Metadata,
+    package: &Rc<Package>,
+) -> Vec<Feature> {
+    let mut names = package.features.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| Feature::new(metadata, Rc::clone(package), name))
+        .collect()
+}
+
+/// Resolves a dependency of a package, as it is named in its manifest (i.e.
+/// taking renames into account)
+///
+/// Returns `None` if the dependency is not part of the resolved dependency
+/// graph, such as for optional dependencies that are not enabled.
+fn resolve_dependency(
+    metadata: &Metadata,
+    packages: &PackageMap,
+    package: &Package,
+    name: &str,
+) -> Option<Rc<Package>> {
+    let dependency = package
+        .dependencies
+        .iter()
+        .find(|d| d.rename.as_deref().unwrap_or(&d.name) == name)?;
+    util::resolve_dependency(metadata, packages, package, dependency)
+}
+
+/// Retrieves the dependencies directly enabled by a feature, that are part of
+/// the resolved dependency graph
+pub(crate) fn get_enabled_dependencies(
+    metadata: &Metadata,
+    packages: &PackageMap,
+    feature: &Feature,
+) -> Vec<Rc<Package>> {
+    let mut dependencies = feature
+        .values()
+        .filter_map(|v| match v {
+            FeatureValue::Dependency(dependency)
+            | FeatureValue::DependencyFeature {
+                dependency,
+                weak: false,
+                ..
+            } => Some(dependency),
+            _ => None,
+        })
+        .filter_map(|d| {
+            resolve_dependency(metadata, packages, &feature.package, d)
+        })
+        .collect::<Vec<_>>();
+    dependencies.dedup_by(|a, b| a.id == b.id);
+    dependencies
+}
+
+/// Retrieves the features directly enabled by a feature, either of the same
+/// package or of its dependencies
+///
+/// Features of dependencies that are not part of the resolved dependency
+/// graph are not included.
+pub(crate) fn get_enabled_features(
+    metadata: &Metadata,
+    packages: &PackageMap,
+    feature: &Feature,
+) -> Vec<Feature> {
+    feature
+        .values()
+        .filter_map(|v| {
+            let (package, name) = match v {
+                FeatureValue::Feature(name) => {
+                    (Rc::clone(&feature.package), name)
+                }
+                FeatureValue::DependencyFeature {
+                    dependency,
+                    feature: name,
+                    ..
+                } => (
+                    resolve_dependency(
+                        metadata,
+                        packages,
+                        &feature.package,
+                        dependency,
+                    )?,
+                    name,
+                ),
+                FeatureValue::Dependency(_) => return None,
+            };
+
+            package
+                .features
+                .contains_key(name)
+                .then(|| Feature::new(metadata, package, name.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::FeatureValue;
+
+    #[test_case("std" => FeatureValue::Feature("std"))]
+    #[test_case("dep:serde" => FeatureValue::Dependency("serde"))]
+    #[test_case("serde/derive" => FeatureValue::DependencyFeature { dependency: "serde", feature: "derive", weak: false })]
+    #[test_case("serde?/derive" => FeatureValue::DependencyFeature { dependency: "serde", feature: "derive", weak: true })]
+    fn parse_feature_value(value: &str) -> FeatureValue<'_> {
+        FeatureValue::from(value)
+    }
+}
@@ -0,0 +1,134 @@
+//! Counts items using the C ABI in the source code of a package, used to find
+//! dependencies that interoperate with foreign code
+//!
+//! The source files are parsed using [`syn`], so items generated by macros
+//! (such as `bindgen` output included at build time) are not counted.
+use std::{fs, path::Path};
+
+use syn::{
+    visit::{self, Visit},
+    Abi, ImplItemFn, ItemFn, ItemForeignMod,
+};
+
+use crate::util;
+
+/// Counts of items using the C ABI in some source code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FfiStats {
+    /// Functions declared in `extern "C" { .. }` blocks, i.e. foreign
+    /// functions called from Rust
+    pub foreign_fns: usize,
+
+    /// Functions defined as `extern "C" fn`, i.e. Rust functions that can be
+    /// called from foreign code
+    pub exported_fns: usize,
+}
+
+impl FfiStats {
+    /// The total number of functions using the C ABI
+    #[must_use]
+    pub fn extern_fn_count(&self) -> usize {
+        self.foreign_fns + self.exported_fns
+    }
+
+    /// If any function can be called from foreign code using the C ABI
+    #[must_use]
+    pub fn exposes_c_abi(&self) -> bool {
+        self.exported_fns > 0
+    }
+
+    /// Counts the items using the C ABI in a single source file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the source cannot be parsed as Rust.
+    pub fn from_source(source: &str) -> Result<Self, syn::Error> {
+        let file = syn::parse_file(source)?;
+        let mut stats = Self::default();
+        stats.visit_file(&file);
+        Ok(stats)
+    }
+}
+
+impl std::ops::AddAssign for FfiStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.foreign_fns += rhs.foreign_fns;
+        self.exported_fns += rhs.exported_fns;
+    }
+}
+
+/// If an ABI is the C ABI; `extern` without an explicit ABI defaults to `"C"`
+fn is_c_abi(abi: &Abi) -> bool {
+    abi.name
+        .as_ref()
+        .is_none_or(|n| matches!(n.value().as_str(), "C" | "C-unwind"))
+}
+
+impl<'ast> Visit<'ast> for FfiStats {
+    fn visit_item_foreign_mod(&mut self, i: &'ast ItemForeignMod) {
+        if is_c_abi(&i.abi) {
+            self.foreign_fns += i
+                .items
+                .iter()
+                .filter(|fi| matches!(fi, syn::ForeignItem::Fn(_)))
+                .count();
+        }
+        visit::visit_item_foreign_mod(self, i);
+    }
+
+    fn visit_item_fn(&mut self, i: &'ast ItemFn) {
+        if i.sig.abi.as_ref().is_some_and(is_c_abi) {
+            self.exported_fns += 1;
+        }
+        visit::visit_item_fn(self, i);
+    }
+
+    fn visit_impl_item_fn(&mut self, i: &'ast ImplItemFn) {
+        if i.sig.abi.as_ref().is_some_and(is_c_abi) {
+            self.exported_fns += 1;
+        }
+        visit::visit_impl_item_fn(self, i);
+    }
+}
+
+/// Counts the items using the C ABI in all Rust source files of a package
+///
+/// Files that cannot be read or parsed are skipped. Anything in a `target`
+/// directory is ignored.
+pub(crate) fn get_ffi_stats(root_path: &Path) -> FfiStats {
+    let mut stats = FfiStats::default();
+
+    for path in util::rust_source_files(root_path) {
+        let res = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| FfiStats::from_source(&s).map_err(|e| e.to_string()));
+        match res {
+            Ok(s) => stats += s,
+            Err(e) => eprintln!(
+                "skipping {} when counting extern items due to error: {e}",
+                path.to_string_lossy()
+            ),
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::FfiStats;
+
+    #[test_case("fn safe() {}" => (0, 0) ; "no extern items")]
+    #[test_case(r#"extern "C" { fn abs(i: i32) -> i32; static X: i32; }"# => (1, 0) ; "foreign block")]
+    #[test_case("extern { fn abs(i: i32) -> i32; }" => (1, 0) ; "foreign block default abi")]
+    #[test_case(r#"extern "system" { fn abs(i: i32) -> i32; }"# => (0, 0) ; "foreign block other abi")]
+    #[test_case(r#"#[no_mangle] pub extern "C" fn f() {}"# => (0, 1) ; "exported function")]
+    #[test_case(r#"struct S; impl S { extern "C" fn f() {} }"# => (0, 1) ; "exported method")]
+    #[test_case(r#"mod m { extern "C" fn f() {} }"# => (0, 1) ; "nested module")]
+    fn count_extern_items(source: &str) -> (usize, usize) {
+        let s = FfiStats::from_source(source).unwrap();
+        (s.foreign_fns, s.exported_fns)
+    }
+}
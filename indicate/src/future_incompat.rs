@@ -0,0 +1,224 @@
+//! Future incompatibilities reported by cargo, see
+//! [`cargo report future-incompatibilities`](https://doc.rust-lang.org/cargo/reference/future-incompat-report.html)
+//!
+//! When a build triggers lints that will become errors in a future version
+//! of Rust, cargo saves a report in the target directory, which is what
+//! `cargo report future-incompatibilities` prints. The latest report is read
+//! from there, so the workspace must have been built beforehand.
+//!
+//! The report is on the form (some fields omitted)
+//! ```json
+//! {
+//!     "reports": [
+//!         {
+//!             "id": 1,
+//!             "per_package": {
+//!                 "rustc-serialize@0.3.24": "The package `rustc-serialize v0.3.24` currently triggers the following future incompatibility lints:\n> warning: ..."
+//!             }
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! where each package has the diagnostics of rustc, rendered as text.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::Path,
+    rc::Rc,
+};
+
+use rustsec::Version;
+use serde::Deserialize;
+
+/// The file cargo saves reports in, relative to the target directory
+pub const FUTURE_INCOMPAT_REPORT_FILE: &str = ".future-incompat-report.json";
+
+/// Future incompatibilities, keyed by package name and version
+///
+/// Reports do not include the source of packages, so packages with the same
+/// name and version from different sources share their incompatibilities.
+pub type FutureIncompatMap =
+    HashMap<(String, Version), Vec<Rc<FutureIncompatLint>>>;
+
+#[derive(Debug, Deserialize)]
+struct OnDiskReports {
+    #[serde(default)]
+    reports: Vec<OnDiskReport>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OnDiskReport {
+    id: u32,
+    per_package: BTreeMap<String, String>,
+}
+
+/// A diagnostic of a lint that will become an error in a future version of
+/// Rust
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FutureIncompatLint {
+    /// Such as `semicolon_in_expressions_from_macros`, `None` if the name
+    /// of the lint is not part of the diagnostic
+    pub lint: Option<String>,
+
+    /// Such as `trailing semicolon in macro used in expression position`
+    pub message: String,
+
+    /// The full diagnostic as rendered by rustc
+    pub rendered: String,
+}
+
+impl FutureIncompatLint {
+    /// Creates a lint from the lines of a rendered diagnostic, where the
+    /// first line is such as `warning: <message>`
+    fn from_lines(lines: &[&str]) -> Self {
+        let first = lines.first().copied().unwrap_or_default();
+        let message =
+            first.split_once(": ").map_or(first, |(_, m)| m).to_string();
+        let lint = lines.iter().find_map(|l| lint_name(l));
+        Self {
+            lint,
+            message,
+            rendered: lines.join("\n"),
+        }
+    }
+}
+
+/// The name of a lint in a note such as
+/// ``= note: `#[warn(semicolon_in_expressions_from_macros)]` on by default``
+fn lint_name(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once("`#[")?;
+    let (attribute, _) = rest.split_once(")]`")?;
+    let (level, lint) = attribute.split_once('(')?;
+    ["allow", "warn", "deny", "forbid"]
+        .contains(&level)
+        .then(|| lint.to_string())
+}
+
+/// Splits the rendered report of a package into its diagnostics
+fn parse_rendered(rendered: &str) -> Vec<FutureIncompatLint> {
+    let mut diagnostics: Vec<Vec<&str>> = Vec::new();
+    for line in rendered.lines() {
+        // Lines of diagnostics are quoted, other lines are headers
+        let Some(line) = line.strip_prefix('>') else {
+            continue;
+        };
+        let line = line.strip_prefix(' ').unwrap_or(line);
+        let starts_diagnostic = line.starts_with("warning:")
+            || line.starts_with("error:")
+            || line.starts_with("warning[")
+            || line.starts_with("error[");
+        match diagnostics.last_mut() {
+            Some(d) if !starts_diagnostic => d.push(line.trim_end()),
+            _ if starts_diagnostic => diagnostics.push(vec![line.trim_end()]),
+            _ => {}
+        }
+    }
+
+    diagnostics
+        .iter()
+        .map(|lines| {
+            // Diagnostics are separated by empty lines
+            let end = lines
+                .iter()
+                .rposition(|l| !l.trim().is_empty())
+                .map_or(0, |i| i + 1);
+            FutureIncompatLint::from_lines(&lines[..end])
+        })
+        .collect()
+}
+
+/// Splits a package in a report, such as `libc@0.2.139`, or `libc:0.2.139`
+/// as used by older versions of cargo, into its name and version
+fn parse_package(package: &str) -> Option<(String, Version)> {
+    let (name, version) = package
+        .rsplit_once('@')
+        .or_else(|| package.rsplit_once(':'))?;
+    Some((name.to_string(), Version::parse(version).ok()?))
+}
+
+/// Parses the contents of a report file, using the latest report
+///
+/// # Errors
+///
+/// Returns an error variant if the report is not valid JSON, or has an
+/// unexpected format.
+pub fn parse_reports(
+    raw_reports: &str,
+) -> Result<FutureIncompatMap, serde_json::Error> {
+    let reports: OnDiskReports = serde_json::from_str(raw_reports)?;
+    let mut map = FutureIncompatMap::new();
+    let Some(latest) = reports.reports.into_iter().max_by_key(|r| r.id) else {
+        return Ok(map);
+    };
+
+    for (package, rendered) in latest.per_package {
+        let Some(key) = parse_package(&package) else {
+            continue;
+        };
+        map.entry(key)
+            .or_default()
+            .extend(parse_rendered(&rendered).into_iter().map(Rc::new));
+    }
+
+    Ok(map)
+}
+
+/// Reads the latest report in `target_dir`
+///
+/// A target directory without a report has no future incompatibilities.
+///
+/// # Errors
+///
+/// Returns an error variant if the report cannot be read, or cannot be
+/// parsed.
+pub fn read_reports(
+    target_dir: &Path,
+) -> Result<FutureIncompatMap, Box<dyn std::error::Error>> {
+    match fs::read_to_string(target_dir.join(FUTURE_INCOMPAT_REPORT_FILE)) {
+        Ok(raw) => Ok(parse_reports(&raw)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            Ok(FutureIncompatMap::new())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use rustsec::Version;
+
+    use super::parse_reports;
+
+    #[test]
+    fn parse_future_incompat_report() {
+        let raw = fs::read_to_string(
+            "test_data/future-incompat-output/future-incompat-report.json",
+        )
+        .unwrap();
+        let reports = parse_reports(&raw).unwrap();
+
+        // Only the latest report is used
+        assert_eq!(reports.len(), 1);
+
+        let lints = &reports
+            [&(String::from("rustc-serialize"), Version::new(0, 3, 24))];
+        assert_eq!(lints.len(), 2);
+        assert_eq!(
+            lints[0].lint.as_deref(),
+            Some("semicolon_in_expressions_from_macros")
+        );
+        assert_eq!(
+            lints[0].message,
+            "trailing semicolon in macro used in expression position"
+        );
+        assert!(lints[0].rendered.starts_with("warning: trailing semicolon"));
+        assert!(lints[0]
+            .rendered
+            .ends_with("hard error in a future release!"));
+        assert_eq!(lints[1].lint, None);
+    }
+}
@@ -43,9 +43,13 @@
 //! for example used+unused, and at a lower level safe+unsafe_.
 
 use std::{
-    collections::HashMap,
+    collections::{hash_map::Entry, HashMap},
+    num::NonZeroUsize,
     ops::Add,
+    path::Path,
     process::{Command, Stdio},
+    sync::Mutex,
+    thread,
 };
 
 use cargo_metadata::CargoOpt;
@@ -58,6 +62,7 @@ use crate::{errors::GeigerError, ManifestPath, NameVersion};
 #[derive(Debug)]
 pub struct GeigerClient {
     #[cfg(test)]
+    #[allow(dead_code)]
     output: GeigerOutput,
     unsafety: HashMap<NameVersion, GeigerUnsafety>,
 }
@@ -90,8 +95,22 @@ impl GeigerClient {
     pub fn new(
         manifest_path: &ManifestPath,
         features: Vec<CargoOpt>,
+    ) -> Result<Self, Box<GeigerError>> {
+        Self::new_in(manifest_path, features, None)
+    }
+
+    /// Creates a new client like [`GeigerClient::new`], running
+    /// `cargo-geiger` from `working_dir` if set, such as the configuration
+    /// directory of [`VendoredSources`](crate::vendor::VendoredSources)
+    pub(crate) fn new_in(
+        manifest_path: &ManifestPath,
+        features: Vec<CargoOpt>,
+        working_dir: Option<&Path>,
     ) -> Result<Self, Box<GeigerError>> {
         let mut cmd = Command::new("cargo-geiger");
+        if let Some(dir) = working_dir {
+            cmd.current_dir(dir);
+        }
         cmd.args(["--output-format", "Json"])
             .arg("--quiet") // Only output tree
             .arg("--manifest-path")
@@ -158,6 +177,105 @@ impl GeigerClient {
         Ok(Self::from(output))
     }
 
+    /// Creates a new client by running `cargo-geiger` once per workspace
+    /// member, with at most `max_concurrency` runs at the same time
+    ///
+    /// Running `cargo-geiger` on each member separately allows large
+    /// workspaces to be analyzed in parallel. The unsafety of all members is
+    /// merged; See [`GeigerClient::merge`].
+    ///
+    /// Members for which `cargo-geiger` fails are skipped, and the error is
+    /// printed to `stderr`.
+    ///
+    /// # Errors
+    ///
+    /// If `cargo-geiger` fails for all members, the error of the last member
+    /// is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cargo-geiger` is not installed and available in `$PATH`
+    pub fn for_members(
+        members: &[ManifestPath],
+        features: &[CargoOpt],
+        max_concurrency: NonZeroUsize,
+    ) -> Result<Self, Box<GeigerError>> {
+        Self::for_members_in(members, features, max_concurrency, None)
+    }
+
+    /// Creates a new client like [`GeigerClient::for_members`], running
+    /// `cargo-geiger` from `working_dir` if set
+    pub(crate) fn for_members_in(
+        members: &[ManifestPath],
+        features: &[CargoOpt],
+        max_concurrency: NonZeroUsize,
+        working_dir: Option<&Path>,
+    ) -> Result<Self, Box<GeigerError>> {
+        let queue = Mutex::new(members.iter());
+        let results = Mutex::new(Vec::with_capacity(members.len()));
+        let workers = max_concurrency.get().min(members.len());
+
+        thread::scope(|s| {
+            for _ in 0..workers {
+                s.spawn(|| loop {
+                    // Release the lock before running `cargo-geiger`
+                    let next = queue.lock().unwrap().next();
+                    let Some(member) = next else {
+                        break;
+                    };
+                    let res =
+                        Self::new_in(member, features.to_vec(), working_dir);
+                    results.lock().unwrap().push(res);
+                });
+            }
+        });
+
+        let mut client: Option<Self> = None;
+        let mut last_error = None;
+        for res in results.into_inner().unwrap() {
+            match res {
+                Ok(c) => match &mut client {
+                    Some(client) => client.merge(c),
+                    None => client = Some(c),
+                },
+                Err(e) => {
+                    eprintln!("failed to create geiger data for a workspace member due to error: {e}");
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match (client, last_error) {
+            (Some(client), _) => Ok(client),
+            (None, Some(e)) => Err(e),
+            (None, None) => Ok(Self::from(GeigerOutput::default())),
+        }
+    }
+
+    /// Merges the unsafety of another client into this one
+    ///
+    /// If a package has been analyzed by both, the unsafety where most of
+    /// the package is used is kept, since usage depends on the package used
+    /// as a starting point while the total counts do not.
+    pub fn merge(&mut self, other: GeigerClient) {
+        #[cfg(test)]
+        self.output.packages.extend(other.output.packages);
+
+        for (id, unsafety) in other.unsafety {
+            match self.unsafety.entry(id) {
+                Entry::Occupied(mut e) => {
+                    let used = |u: &GeigerUnsafety| u.used.total().total();
+                    if used(&unsafety) > used(e.get()) {
+                        e.insert(unsafety);
+                    }
+                }
+                Entry::Vacant(e) => {
+                    e.insert(unsafety);
+                }
+            }
+        }
+    }
+
     #[must_use]
     pub fn unsafety(&self, gid: &NameVersion) -> Option<GeigerUnsafety> {
         self.unsafety.get(gid).copied()
@@ -284,7 +402,7 @@ impl GeigerUnsafety {
 }
 
 /// All different targets in Rust code that `cargo-geiger` counts
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
 pub struct GeigerCategories {
     pub functions: GeigerCount,
     pub exprs: GeigerCount,
@@ -340,7 +458,7 @@ impl Add<GeigerCategories> for GeigerCategories {
 
 /// The safety stats for a package analyzed by `cargo-geiger`,
 /// i.e. counts for lines of safe and unsafe code
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
 pub struct GeigerCount {
     pub safe: u32,
     pub unsafe_: u32,
@@ -374,11 +492,15 @@ impl Add<GeigerCount> for GeigerCount {
 
 #[cfg(test)]
 mod test {
-    use std::{fs, path::Path};
+    use std::{fs, num::NonZeroUsize, path::Path};
 
+    use rustsec::Version;
     use test_case::test_case;
 
-    use crate::{geiger::GeigerCount, ManifestPath};
+    use crate::{
+        geiger::{GeigerCategories, GeigerCount},
+        ManifestPath, NameVersion,
+    };
 
     use super::{GeigerClient, GeigerOutput};
 
@@ -401,6 +523,49 @@ mod test {
         GeigerClient::new(&path, vec![]).unwrap();
     }
 
+    #[test]
+    fn geiger_for_workspace_members() {
+        let members = ["member_a", "member_b"].map(|m| {
+            ManifestPath::from(format!(
+                "test_data/fake_crates/workspace_crate/{m}/Cargo.toml"
+            ))
+        });
+        let client = GeigerClient::for_members(
+            &members,
+            &[],
+            NonZeroUsize::new(2).unwrap(),
+        )
+        .unwrap();
+        assert!(client
+            .unsafety(&NameVersion::new(
+                String::from("member_b"),
+                Version::new(0, 2, 0)
+            ))
+            .is_some());
+    }
+
+    #[test]
+    fn merge_keeps_most_used() {
+        let json_string =
+            fs::read_to_string("test_data/geiger-output/simple_deps.json")
+                .unwrap();
+        let mut client = GeigerClient::from_json(&json_string).unwrap();
+        let mut unused = GeigerClient::from_json(&json_string).unwrap();
+        for u in unused.unsafety.values_mut() {
+            u.unused = u.used + u.unused;
+            u.used = GeigerCategories::default();
+        }
+        let expected = client.unsafety.clone();
+
+        client.merge(unused);
+        for (id, u) in expected {
+            assert_eq!(
+                client.unsafety(&id).unwrap().used_unsafe(),
+                u.used_unsafe()
+            );
+        }
+    }
+
     #[test_case("simple_deps")]
     fn deserialize_geiger_output_smoke_test(crate_name: &'static str) {
         let path_string = format!("test_data/geiger-output/{crate_name}.json");
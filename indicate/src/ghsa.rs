@@ -0,0 +1,316 @@
+//! Client for retrieving advisories from the
+//! [GitHub Advisory Database](https://github.com/advisories) (GHSA)
+//!
+//! GHSA includes the advisories of `advisory-db`, but advisories reported
+//! directly to GitHub may be available weeks before they are imported into
+//! `advisory-db`. The GitHub GraphQL API is used, with the same
+//! `GITHUB_API_TOKEN` and `USER_AGENT` environment variables as the rest of
+//! the GitHub API.
+//!
+//! See [the GitHub GraphQL API documentation](https://docs.github.com/en/graphql/reference/objects#securityvulnerability)
+//! for more information.
+
+use std::rc::Rc;
+
+use chrono::DateTime;
+use rustsec::{Version, VersionReq};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    http::{self, ResponseCache},
+    source::PackageSource,
+    NameVersion,
+};
+
+/// The GitHub GraphQL API
+pub const GITHUB_GRAPHQL_URL: &str = "https://api.github.com/graphql";
+
+/// Max number of vulnerabilities retrieved for a package; The max allowed by
+/// the GitHub API
+pub const VULNERABILITIES_PER_PACKAGE: u32 = 100;
+
+/// Retrieves all vulnerabilities of a Rust package
+const VULNERABILITIES_QUERY: &str =
+    "query vulnerabilities($package: String!, $first: Int!) {
+    securityVulnerabilities(ecosystem: RUST, package: $package, first: $first) {
+        nodes {
+            advisory {
+                ghsaId
+                summary
+                severity
+                permalink
+                publishedAt
+                withdrawnAt
+                identifiers { type value }
+                cvssSeverities { cvssV3 { score vectorString } }
+            }
+            vulnerableVersionRange
+            firstPatchedVersion { identifier }
+        }
+    }
+}";
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse {
+    data: Option<VulnerabilitiesData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VulnerabilitiesData {
+    security_vulnerabilities: VulnerabilityConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct VulnerabilityConnection {
+    nodes: Vec<GhsaVulnerability>,
+}
+
+/// A range of versions of a package affected by a GHSA advisory, some fields
+/// omitted
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GhsaVulnerability {
+    pub advisory: GhsaAdvisory,
+
+    /// Such as `>= 0.1.0, < 0.2.3` or `= 1.0.0`
+    pub vulnerable_version_range: String,
+    pub first_patched_version: Option<GhsaPackageVersion>,
+}
+
+/// A GHSA advisory, some fields omitted
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GhsaAdvisory {
+    /// Such as `GHSA-43w2-9j62-hq99`
+    pub ghsa_id: String,
+    pub summary: String,
+
+    /// `LOW`, `MODERATE`, `HIGH` or `CRITICAL`
+    pub severity: String,
+    pub permalink: String,
+
+    /// RFC 3339 timestamps
+    pub published_at: String,
+    pub withdrawn_at: Option<String>,
+
+    /// Identifiers of the advisory, including its GHSA ID
+    pub identifiers: Vec<GhsaIdentifier>,
+    pub cvss_severities: GhsaCvssSeverities,
+}
+
+/// Such as a CVE ID
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GhsaIdentifier {
+    /// `GHSA` or `CVE`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GhsaCvssSeverities {
+    pub cvss_v3: Option<GhsaCvss>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GhsaCvss {
+    pub score: f64,
+
+    /// `null` if the advisory has no CVSS score
+    pub vector_string: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GhsaPackageVersion {
+    pub identifier: String,
+}
+
+impl GhsaAdvisory {
+    /// Identifiers of the advisory other than its GHSA ID, such as
+    /// `CVE-2021-25900`
+    #[must_use]
+    pub fn aliases(&self) -> Vec<String> {
+        self.identifiers
+            .iter()
+            .filter(|i| i.value != self.ghsa_id)
+            .map(|i| i.value.clone())
+            .collect()
+    }
+
+    /// The CVSS v3 base score, `None` if the advisory has no CVSS vector
+    #[must_use]
+    pub fn cvss_score(&self) -> Option<f64> {
+        self.cvss_severities
+            .cvss_v3
+            .as_ref()
+            .filter(|c| c.vector_string.is_some())
+            .map(|c| c.score)
+    }
+
+    #[must_use]
+    pub fn unix_published_at(&self) -> Option<i64> {
+        unix_timestamp(&self.published_at)
+    }
+
+    #[must_use]
+    pub fn unix_withdrawn_at(&self) -> Option<i64> {
+        self.withdrawn_at.as_deref().and_then(unix_timestamp)
+    }
+}
+
+impl GhsaVulnerability {
+    /// If `version` is in the vulnerable version range, `None` if the range
+    /// could not be parsed
+    #[must_use]
+    pub fn affects(&self, version: &Version) -> Option<bool> {
+        VersionReq::parse(&self.vulnerable_version_range)
+            .ok()
+            .map(|req| req.matches(version))
+    }
+}
+
+fn unix_timestamp(rfc3339: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(rfc3339)
+        .ok()
+        .map(|t| t.timestamp())
+}
+
+/// A GHSA vulnerability of a package, together with if the version of the
+/// package it was retrieved for is affected
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageGhsaVulnerability {
+    pub vulnerability: GhsaVulnerability,
+
+    /// `None` if the vulnerable version range could not be parsed
+    pub current_version_affected: Option<bool>,
+}
+
+/// Wrapper around a [`reqwest::Client`] querying GHSA, with caching
+#[derive(Debug, Clone)]
+pub struct GhsaClient {
+    client: reqwest::Client,
+    token: Option<String>,
+    user_agent: Option<String>,
+
+    /// Cache between the name of a package and its vulnerabilities
+    cache: ResponseCache<String, Rc<Vec<GhsaVulnerability>>>,
+}
+
+impl Default for GhsaClient {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::default(),
+            token: std::env::var("GITHUB_API_TOKEN").ok(),
+            user_agent: std::env::var("USER_AGENT").ok(),
+            cache: ResponseCache::default(),
+        }
+    }
+}
+
+impl GhsaClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves all GHSA vulnerabilities of a crates.io package, in any
+    /// version
+    ///
+    /// Will return `None` if the package is not from crates.io, if
+    /// `GITHUB_API_TOKEN` or `USER_AGENT` is not set, or if the request
+    /// fails, and will cache this package as such.
+    pub fn vulnerabilities(
+        &mut self,
+        package: &NameVersion,
+    ) -> Option<Rc<Vec<GhsaVulnerability>>> {
+        if package.source != PackageSource::CratesIo {
+            return None;
+        }
+        self.cache.get_or_fetch(&package.name, || {
+            let (Some(token), Some(user_agent)) =
+                (&self.token, &self.user_agent)
+            else {
+                eprintln!("GITHUB_API_TOKEN and USER_AGENT must be set to retrieve GHSA advisories");
+                return None;
+            };
+
+            let body = json!({
+                "query": VULNERABILITIES_QUERY,
+                "variables": {
+                    "package": package.name,
+                    "first": VULNERABILITIES_PER_PACKAGE,
+                },
+            });
+            let res = http::send_json::<GraphQlResponse>(
+                self.client
+                    .post(GITHUB_GRAPHQL_URL)
+                    .bearer_auth(token)
+                    .header(reqwest::header::USER_AGENT, user_agent)
+                    .json(&body),
+                &format!("GHSA advisories for {}", package.name),
+            )?;
+            match res.data {
+                Some(d) => Some(Rc::new(d.security_vulnerabilities.nodes)),
+                None => {
+                    eprintln!(
+                        "failed to retrieve GHSA advisories for {}, GitHub returned no data",
+                        package.name
+                    );
+                    None
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use rustsec::Version;
+    use test_case::test_case;
+
+    use super::{GhsaVulnerability, GraphQlResponse};
+
+    fn vulnerabilities() -> Vec<GhsaVulnerability> {
+        let json_string =
+            fs::read_to_string("test_data/ghsa-output/smallvec.json").unwrap();
+        serde_json::from_str::<GraphQlResponse>(&json_string)
+            .unwrap()
+            .data
+            .unwrap()
+            .security_vulnerabilities
+            .nodes
+    }
+
+    #[test]
+    fn deserialize_ghsa_response() {
+        let vulns = vulnerabilities();
+        assert_eq!(vulns.len(), 2);
+
+        let advisory = &vulns[0].advisory;
+        assert_eq!(advisory.ghsa_id, "GHSA-43w2-9j62-hq99");
+        assert_eq!(advisory.aliases(), vec![String::from("CVE-2021-25900")]);
+        assert_eq!(advisory.cvss_score(), Some(9.8));
+        assert_eq!(advisory.unix_published_at(), Some(1_629_924_568));
+        assert_eq!(advisory.unix_withdrawn_at(), None);
+        assert_eq!(
+            vulns[0].first_patched_version.as_ref().unwrap().identifier,
+            "0.6.14"
+        );
+
+        // No CVSS vector
+        assert_eq!(vulns[1].advisory.cvss_score(), None);
+    }
+
+    #[test_case("0.6.13" => Some(true) ; "affected")]
+    #[test_case("0.6.14" => Some(false) ; "patched")]
+    #[test_case("1.0.0" => Some(false) ; "later major")]
+    fn affects(version: &str) -> Option<bool> {
+        vulnerabilities()[0].affects(&Version::parse(version).unwrap())
+    }
+}
@@ -0,0 +1,106 @@
+//! Shared parts of clients for web APIs, such as OSV.dev or docs.rs
+//!
+//! Requests are sent using [`reqwest`], blocking on the shared runtime until
+//! the response is retrieved. Failed requests are logged and treated as
+//! missing data, so that a single failing request does not fail a query.
+
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use reqwest::RequestBuilder;
+use serde::de::DeserializeOwned;
+
+use crate::RUNTIME;
+
+/// Sends a request and parses the body of the response as JSON
+///
+/// Will return `None` if the request fails, including responses with an
+/// error status, logging it as failing to retrieve `what`, such as
+/// `docs.rs build status for libc 0.2.139`.
+pub(crate) fn send_json<T: DeserializeOwned>(
+    request: RequestBuilder,
+    what: &str,
+) -> Option<T> {
+    let future =
+        async { request.send().await?.error_for_status()?.json::<T>().await };
+    log_failure(RUNTIME.block_on(future), what)
+}
+
+/// Sends a request and retrieves the body of the response as text
+///
+/// Will return `None` if the request fails, see [`send_json`].
+pub(crate) fn send_text(request: RequestBuilder, what: &str) -> Option<String> {
+    let future =
+        async { request.send().await?.error_for_status()?.text().await };
+    log_failure(RUNTIME.block_on(future), what)
+}
+
+fn log_failure<T>(res: reqwest::Result<T>, what: &str) -> Option<T> {
+    match res {
+        Ok(r) => Some(r),
+        Err(e) => {
+            eprintln!("failed to retrieve {what} due to error: {e}");
+            None
+        }
+    }
+}
+
+/// Responses of a web API, keyed by what was requested
+///
+/// Failed requests are cached as `None`, so that they are not retried
+/// during the same query.
+#[derive(Debug, Clone)]
+pub(crate) struct ResponseCache<K, V> {
+    responses: HashMap<K, Option<V>>,
+}
+
+impl<K, V> Default for ResponseCache<K, V> {
+    fn default() -> Self {
+        Self {
+            responses: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> ResponseCache<K, V> {
+    /// Retrieves the response for `key`, using `fetch` to request it if it
+    /// is not cached
+    pub(crate) fn get_or_fetch<Q>(
+        &mut self,
+        key: &Q,
+        fetch: impl FnOnce() -> Option<V>,
+    ) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(cached) = self.responses.get(key) {
+            return cached.clone();
+        }
+        let res = fetch();
+        self.responses.insert(key.to_owned(), res.clone());
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::ResponseCache;
+
+    #[test]
+    fn failed_requests_are_cached() {
+        let mut cache = ResponseCache::<String, u64>::default();
+        let requests = Cell::new(0);
+        let fetch = |res| {
+            requests.set(requests.get() + 1);
+            res
+        };
+
+        assert_eq!(cache.get_or_fetch("libc", || fetch(Some(1))), Some(1));
+        assert_eq!(cache.get_or_fetch("libc", || fetch(Some(2))), Some(1));
+        assert_eq!(cache.get_or_fetch("syn", || fetch(None)), None);
+        assert_eq!(cache.get_or_fetch("syn", || fetch(Some(3))), None);
+        assert_eq!(requests.get(), 2);
+    }
+}
@@ -0,0 +1,216 @@
+//! Human-friendly rendering of values in human-readable report formats
+//!
+//! Query results only contain raw values, such as `1234567` downloads or a
+//! Unix timestamp. When rendering results for humans, such as in Markdown,
+//! values are rendered as `1.2M`, `4.5 MB` or `3 years ago` depending on what
+//! they represent, which is determined from the name of the output using
+//! the naming conventions of the schema (see [`Quantity::of_output`]).
+//! Machine-readable formats, such as JSON, always use the raw values.
+
+use trustfall::TransparentValue;
+
+/// How values are rendered in human-readable report formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValueStyle {
+    /// Such as `1.2M`, `4.5 MB` or `3 years ago`
+    #[default]
+    Human,
+
+    /// Values as they are returned by queries
+    Raw,
+}
+
+/// What a numeric value represents, and so how it is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity {
+    /// Such as downloads or stars, rendered as `1.2M`
+    Count,
+
+    /// Rendered as `4.5 MB`
+    Bytes,
+
+    /// Seconds since the Unix epoch, rendered as `3 years ago`
+    UnixTimestamp,
+
+    /// Rendered as-is
+    Plain,
+}
+
+impl Quantity {
+    /// Determines what the value of an output represents from its name
+    ///
+    /// Follows the naming conventions of the schema: timestamps are prefixed
+    /// with `unix` (`unixCreatedAt`), sizes end with `Bytes` or contain
+    /// `size` (`sizeBytes`), and counts end with `Count` or contain
+    /// `download` (`starsCount`, `totalDownloads`).
+    #[must_use]
+    pub fn of_output(name: &str) -> Self {
+        let lowercase = name.to_lowercase();
+        if lowercase.starts_with("unix") {
+            Quantity::UnixTimestamp
+        } else if lowercase.ends_with("bytes") || lowercase.contains("size") {
+            Quantity::Bytes
+        } else if lowercase.ends_with("count") || lowercase.contains("download")
+        {
+            Quantity::Count
+        } else {
+            Quantity::Plain
+        }
+    }
+
+    /// Renders a numeric value, or `None` if the value is not numeric
+    ///
+    /// `now` is the current Unix timestamp, used for relative times.
+    #[must_use]
+    pub fn render(self, value: &TransparentValue, now: i64) -> Option<String> {
+        let n = match value {
+            TransparentValue::Int64(n) => *n as f64,
+            TransparentValue::Uint64(n) => *n as f64,
+            TransparentValue::Float64(n) => *n,
+            _ => return None,
+        };
+        match self {
+            Quantity::Count => Some(compact_number(n)),
+            Quantity::Bytes => Some(bytes(n)),
+            Quantity::UnixTimestamp => Some(relative_time(n as i64, now)),
+            Quantity::Plain => None,
+        }
+    }
+}
+
+/// Renders a value in the style provided, using the name of its output to
+/// determine what it represents
+///
+/// Returns `None` if the value is to be rendered as-is.
+#[must_use]
+pub fn render_value(
+    output_name: &str,
+    value: &TransparentValue,
+    style: ValueStyle,
+) -> Option<String> {
+    match style {
+        ValueStyle::Raw => None,
+        ValueStyle::Human => Quantity::of_output(output_name)
+            .render(value, chrono::Utc::now().timestamp()),
+    }
+}
+
+/// Renders a number using the largest suffix it is at least one of, such as
+/// `1.2K` or `3.4M`, with at most one decimal
+#[must_use]
+pub fn compact_number(n: f64) -> String {
+    scaled(n, 1000.0, &["", "K", "M", "B", "T"], "")
+}
+
+/// Renders a size in bytes using SI units, such as `4.5 MB`
+#[must_use]
+pub fn bytes(n: f64) -> String {
+    scaled(n, 1000.0, &["B", "kB", "MB", "GB", "TB"], " ")
+}
+
+fn scaled(n: f64, base: f64, suffixes: &[&str], separator: &str) -> String {
+    let mut value = n;
+    let mut suffix = 0;
+    while value.abs() >= base && suffix < suffixes.len() - 1 {
+        value /= base;
+        suffix += 1;
+    }
+
+    // Avoid `1000.0K` when rounding up to the next suffix
+    if (value.abs() * 10.0).round() / 10.0 >= base
+        && suffix < suffixes.len() - 1
+    {
+        value /= base;
+        suffix += 1;
+    }
+
+    let rendered = if suffix == 0 || value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        let r = format!("{value:.1}");
+        r.strip_suffix(".0").map(str::to_string).unwrap_or(r)
+    };
+    format!("{rendered}{separator}{}", suffixes[suffix])
+}
+
+/// Renders a Unix timestamp relative to `now`, such as `3 years ago` or
+/// `in 2 days`, using the largest whole unit
+#[must_use]
+pub fn relative_time(timestamp: i64, now: i64) -> String {
+    const UNITS: [(&str, i64); 6] = [
+        ("year", 365 * 24 * 60 * 60),
+        ("month", 30 * 24 * 60 * 60),
+        ("day", 24 * 60 * 60),
+        ("hour", 60 * 60),
+        ("minute", 60),
+        ("second", 1),
+    ];
+
+    let diff = now - timestamp;
+    let Some((unit, amount)) = UNITS
+        .iter()
+        .map(|(unit, seconds)| (unit, diff.abs() / seconds))
+        .find(|(_, amount)| *amount > 0)
+    else {
+        return String::from("just now");
+    };
+
+    let plural = if amount == 1 { "" } else { "s" };
+    if diff > 0 {
+        format!("{amount} {unit}{plural} ago")
+    } else {
+        format!("in {amount} {unit}{plural}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+    use trustfall::TransparentValue;
+
+    use super::{bytes, compact_number, relative_time, Quantity};
+
+    #[test_case(0.0 => "0")]
+    #[test_case(999.0 => "999")]
+    #[test_case(1234.0 => "1.2K")]
+    #[test_case(1_200_000.0 => "1.2M")]
+    #[test_case(3_000_000_000.0 => "3B")]
+    #[test_case(999_960.0 => "1M" ; "rounds up to next suffix")]
+    fn compact_numbers(n: f64) -> String {
+        compact_number(n)
+    }
+
+    #[test_case(512.0 => "512 B")]
+    #[test_case(4_500_000.0 => "4.5 MB")]
+    #[test_case(2_000.0 => "2 kB")]
+    fn byte_sizes(n: f64) -> String {
+        bytes(n)
+    }
+
+    #[test_case(0 => "just now")]
+    #[test_case(1 => "1 second ago")]
+    #[test_case(2 * 24 * 60 * 60 => "2 days ago")]
+    #[test_case(3 * 365 * 24 * 60 * 60 + 100 => "3 years ago")]
+    #[test_case(-60 * 60 => "in 1 hour")]
+    fn relative_times(seconds_ago: i64) -> String {
+        let now = 1_700_000_000;
+        relative_time(now - seconds_ago, now)
+    }
+
+    #[test_case("totalDownloads" => Quantity::Count)]
+    #[test_case("starsCount" => Quantity::Count)]
+    #[test_case("sizeBytes" => Quantity::Bytes)]
+    #[test_case("unixCreatedAt" => Quantity::UnixTimestamp)]
+    #[test_case("name" => Quantity::Plain)]
+    fn quantity_of_output(name: &str) -> Quantity {
+        Quantity::of_output(name)
+    }
+
+    #[test]
+    fn non_numeric_values_are_not_rendered() {
+        assert_eq!(
+            Quantity::Count.render(&TransparentValue::String("1".into()), 0),
+            None
+        );
+    }
+}
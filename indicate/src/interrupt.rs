@@ -0,0 +1,56 @@
+//! Graceful handling of interrupts (Ctrl-C), so that long-running queries
+//! can return the results computed so far instead of losing everything
+//!
+//! Once [`install_handler`] has been called, the first interrupt stops the
+//! resolution of new vertices and properties, causing running queries to
+//! finish early with the rows already computed (see
+//! [`execute_query_with_adapter`](crate::execute_query_with_adapter)). A
+//! second interrupt exits the process immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Exit code used by shells for processes terminated by `SIGINT`
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Installs a handler for interrupts, replacing the default behaviour of
+/// exiting immediately
+///
+/// The handler runs on a separate thread, since queries block the thread
+/// they are executed on.
+///
+/// # Panics
+///
+/// Panics if the thread running the handler cannot be created.
+pub fn install_handler() {
+    std::thread::Builder::new()
+        .name(String::from("indicate-interrupt"))
+        .spawn(|| {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("could not create tokio runtime for interrupts");
+            runtime.block_on(async {
+                if tokio::signal::ctrl_c().await.is_err() {
+                    eprintln!("could not listen for interrupts, Ctrl-C will lose all results");
+                    return;
+                }
+                INTERRUPTED.store(true, Ordering::SeqCst);
+                eprintln!(
+                    "interrupted, writing the results computed so far; interrupt again to exit immediately"
+                );
+
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    std::process::exit(INTERRUPTED_EXIT_CODE);
+                }
+            });
+        })
+        .expect("could not create thread handling interrupts");
+}
+
+/// If an interrupt has been received since [`install_handler`] was called
+#[must_use]
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
@@ -0,0 +1,107 @@
+//! Client for the CISA [Known Exploited Vulnerabilities](https://www.cisa.gov/known-exploited-vulnerabilities-catalog)
+//! (KEV) catalog
+//!
+//! The catalog lists CVEs known to be exploited in the wild, and is
+//! downloaded as a single JSON feed the first time it is needed, and then
+//! kept for the lifetime of the client. No token is required.
+
+use std::{collections::HashSet, rc::Rc};
+
+use serde::Deserialize;
+
+use crate::http;
+
+/// The JSON feed of the full catalog
+pub const KEV_FEED_URL: &str = "https://www.cisa.gov/sites/default/files/feeds/known_exploited_vulnerabilities.json";
+
+/// The KEV catalog, as returned by the JSON feed, some fields omitted
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KevCatalog {
+    /// Such as `2024.06.03`
+    pub catalog_version: String,
+    pub vulnerabilities: Vec<KevVulnerability>,
+}
+
+/// An entry of the KEV catalog, some fields omitted
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KevVulnerability {
+    /// Such as `CVE-2021-44228`
+    #[serde(rename = "cveID")]
+    pub cve_id: String,
+    pub vulnerability_name: String,
+
+    /// When the CVE was added to the catalog, such as `2021-12-10`
+    pub date_added: String,
+}
+
+/// Wrapper around a [`reqwest::Client`] downloading the KEV catalog, with
+/// caching
+#[derive(Debug, Clone, Default)]
+pub struct KevClient {
+    client: reqwest::Client,
+
+    /// The CVE IDs in the catalog, once downloaded
+    ///
+    /// A failed download is cached as `Some(None)`, so that it is not
+    /// retried during the same query.
+    cves: Option<Option<Rc<HashSet<String>>>>,
+}
+
+impl KevClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If any of `cves`, such as `CVE-2021-44228`, is in the catalog
+    ///
+    /// The catalog is downloaded the first time this is called with a
+    /// non-empty slice. Will return `None` if the download fails.
+    pub fn contains_any(&mut self, cves: &[String]) -> Option<bool> {
+        if cves.is_empty() {
+            return Some(false);
+        }
+
+        let catalog = self.catalog()?;
+        Some(cves.iter().any(|c| catalog.contains(c)))
+    }
+
+    /// Retrieves the CVE IDs of the catalog, downloading it if needed
+    fn catalog(&mut self) -> Option<Rc<HashSet<String>>> {
+        if let Some(cached) = &self.cves {
+            return cached.clone();
+        }
+
+        let res = http::send_json::<KevCatalog>(
+            self.client.get(KEV_FEED_URL),
+            "the CISA KEV catalog",
+        )
+        .map(|c| {
+            Rc::new(c.vulnerabilities.into_iter().map(|v| v.cve_id).collect())
+        });
+
+        self.cves = Some(res.clone());
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::KevCatalog;
+
+    #[test]
+    fn deserialize_kev_catalog() {
+        let json_string =
+            fs::read_to_string("test_data/kev-output/catalog.json").unwrap();
+        let catalog = serde_json::from_str::<KevCatalog>(&json_string).unwrap();
+
+        assert_eq!(catalog.catalog_version, "2024.06.03");
+        assert_eq!(catalog.vulnerabilities.len(), 2);
+        assert_eq!(catalog.vulnerabilities[0].cve_id, "CVE-2021-44228");
+        assert_eq!(catalog.vulnerabilities[1].date_added, "2024-05-30");
+    }
+}
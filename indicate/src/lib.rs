@@ -12,28 +12,80 @@
 //! ```graphql
 #![doc = include_str!("schema.trustfall.graphql")]
 //! ```
-#![forbid(unsafe_code)]
-use std::{collections::BTreeMap, rc::Rc, sync::Arc};
+#![cfg_attr(not(feature = "c-api"), forbid(unsafe_code))]
+// Only the C API may use unsafe code
+#![cfg_attr(feature = "c-api", deny(unsafe_code))]
+use std::{collections::BTreeMap, fmt::Display, rc::Rc, sync::Arc};
 
 use cargo_metadata::Package;
 use once_cell::sync::Lazy;
 use query::FullQuery;
 use rustsec::Version;
 use serde::Deserialize;
+use source::PackageSource;
 use tokio::runtime::Runtime;
 use trustfall::{execute_query as trustfall_execute_query, FieldValue, Schema};
 
 pub mod adapter;
 pub mod advisory;
+pub mod binaries;
+pub mod bloat;
+pub mod build_script;
+#[cfg(feature = "c-api")]
+pub mod c_api;
+pub mod capabilities;
+pub mod changes;
+pub mod checkpoint;
+pub mod clippy;
 pub mod code_stats;
+pub mod compare;
+pub mod config;
+pub mod cost;
 pub mod crates_io;
+pub mod crev;
+pub mod deny;
+pub mod deps_dev;
+pub mod docs_rs;
+pub mod doctor;
+pub mod epss;
 pub mod errors;
+pub mod feature_matrix;
+pub mod features;
+pub mod ffi;
+pub mod future_incompat;
 pub mod geiger;
+pub mod ghsa;
+mod http;
+pub mod humanize;
+pub mod interrupt;
+pub mod kev;
+pub mod license;
+pub mod lockfile;
 pub mod manifest;
+pub mod nvd;
+pub mod osv;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod policy;
+pub mod protobuf;
 pub mod query;
+pub mod release_notes;
 pub mod repo;
+pub mod report;
+pub mod reproducibility;
+pub mod requirement;
+pub mod semver_checks;
+pub mod source;
+pub mod sparse_index;
+pub mod suspicion;
+pub mod target_matrix;
+pub mod typosquat;
+pub mod udeps;
 pub mod util;
+pub mod vendor;
 mod vertex;
+pub mod vet;
+pub mod worksheet;
 
 /// Features to create metadata with
 pub use cargo_metadata::CargoOpt;
@@ -65,17 +117,46 @@ static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
         .expect("could not create tokio runtime")
 });
 
+/// Identifies a package by its name, version and source, similar to a Cargo
+/// `PackageIdSpec`
+///
+/// Packages with the same name and version from different sources, such as
+/// a git fork and crates.io, are different packages.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
 pub struct NameVersion {
     pub name: String,
     pub version: Version,
-    // Other fields ignored, assume crates.io registry
+
+    /// Packages without a source are local path packages, as in `Cargo.lock`
+    #[serde(default)]
+    pub source: PackageSource,
+    // Other fields ignored
 }
 
 impl NameVersion {
+    /// Creates the identity of a package from crates.io
     #[must_use]
     pub fn new(name: String, version: Version) -> Self {
-        Self { name, version }
+        Self::with_source(name, version, PackageSource::CratesIo)
+    }
+
+    #[must_use]
+    pub fn with_source(
+        name: String,
+        version: Version,
+        source: PackageSource,
+    ) -> Self {
+        Self {
+            name,
+            version,
+            source,
+        }
+    }
+}
+
+impl Display for NameVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}@{}", self.source, self.name, self.version)
     }
 }
 
@@ -88,6 +169,7 @@ where
             NameVersion {
                 name: package.name.clone(),
                 version: package.version.clone(),
+                source: PackageSource::of(package),
             }
         }
         inner(value.as_ref())
@@ -119,24 +201,70 @@ pub fn execute_query(
 ///
 /// # Panics
 ///
-/// Panics if the query could not be executed.
+/// Panics if the query is not valid (see [`FullQuery::validate`]), or if it
+/// could not be executed.
+///
+/// If an interrupt is received (see [`interrupt`]), only the rows completed
+/// before it are returned. Use [`execute_query_interruptible`] to tell if
+/// that was the case.
 pub fn execute_query_with_adapter(
     query: &FullQuery,
     adapter: Rc<IndicateAdapter>,
     max_results: Option<usize>,
 ) -> Vec<BTreeMap<Arc<str>, FieldValue>> {
-    let res = match trustfall_execute_query(
+    execute_query_interruptible(query, adapter, max_results).rows
+}
+
+/// The rows of an executed query
+#[derive(Debug, Clone, Default)]
+pub struct QueryResults {
+    pub rows: Vec<BTreeMap<Arc<str>, FieldValue>>,
+
+    /// If the query stopped early since it was interrupted (see
+    /// [`interrupt`]), so that rows may be missing
+    ///
+    /// A query completed before an interrupt was received is not partial.
+    pub partial: bool,
+}
+
+/// Executes a Trustfall query like [`execute_query_with_adapter`], but also
+/// returns if it stopped early since it was interrupted
+///
+/// # Panics
+///
+/// Panics if the query is not valid (see [`FullQuery::validate`]), or if it
+/// could not be executed.
+pub fn execute_query_interruptible(
+    query: &FullQuery,
+    adapter: Rc<IndicateAdapter>,
+    max_results: Option<usize>,
+) -> QueryResults {
+    if let Err(e) = query.validate() {
+        panic!("{e}, query was:\n{}", query.query);
+    }
+
+    let stopped_early = adapter.stopped_early();
+    stopped_early.set(false);
+    let rows = match trustfall_execute_query(
         &SCHEMA,
         adapter,
         query.query.as_str(),
-        query.args.clone(),
+        query.resolved_args(),
     ) {
-        Ok(res) => res.take(max_results.unwrap_or(usize::MAX)).collect(),
+        // Rows completed after an interrupt may be missing values
+        Ok(res) => adapter::until_interrupted(
+            res.take(max_results.unwrap_or(usize::MAX)),
+            Rc::clone(&stopped_early),
+        )
+        .collect(),
         Err(e) => panic!(
             "Could not execute query due to error: {e:#?}, query was: {query:#?}"
         ),
     };
-    res
+    QueryResults {
+        rows,
+        partial: stopped_early.get(),
+    }
 }
 
 #[cfg(test)]
@@ -162,13 +290,13 @@ mod test {
     };
 
     /// File that may never exist, to ensure some test work
-    const NONEXISTENT_FILE: &'static str = "test_data/notafile";
+    const NONEXISTENT_FILE: &str = "test_data/notafile";
 
     /// Retrieve paths for the crate and query names provided, relative to
     /// `indicate` crate root
-    fn get_paths<'a, 'b>(
-        fake_crate_name: &'a str,
-        query_name: &'b str,
+    fn get_paths(
+        fake_crate_name: &str,
+        query_name: &str,
     ) -> (PathBuf, PathBuf) {
         let raw_cargo_toml_path =
             format!("test_data/fake_crates/{fake_crate_name}/Cargo.toml");
@@ -199,6 +327,23 @@ mod test {
         Rc::new(b.build())
     }
 
+    #[test]
+    fn refresh_replaces_changed_manifest() {
+        let manifest_path =
+            ManifestPath::from("test_data/fake_crates/simple_deps");
+        let mut adapter = IndicateAdapter::new(manifest_path.clone());
+        let changed = adapter.refresh(&manifest_path).unwrap();
+        assert_eq!(changed.len(), 1);
+        assert!(changed.iter().next().unwrap().repr.contains("simple_deps"));
+
+        let query = FullQuery::from_path(Path::new(
+            "test_data/queries/all_dependencies.in.ron",
+        ))
+        .unwrap();
+        let res = execute_query_with_adapter(&query, Rc::new(adapter), None);
+        assert!(!res.is_empty());
+    }
+
     #[test]
     fn non_existant_file() {
         assert!(!Path::new(NONEXISTENT_FILE).exists());
@@ -243,8 +388,47 @@ mod test {
     #[test_case("known_advisory_deps", "advisory_db_affected_funcs" ; "advisory db with affected functions does not panic")]
     #[test_case("known_advisory_deps", "advisory_db_no_include_withdrawn" => panics ; "advisory db without includeWithin panics")]
     #[test_case("known_advisory_deps", "advisory_db_with_parameters" ; "advisory db with parameters does not panic")]
+    #[test_case("known_advisory_deps", "advisory_db_cvss" ; "advisory db with CVSS score and vector does not panic")]
+    #[test_case("known_advisory_deps", "advisory_db_aliases" ; "advisory db with aliases and references does not panic")]
+    #[test_case("known_advisory_deps", "advisories_by_cvss_score" ; "advisories filtered by CVSS score do not panic")]
+    #[test_case("known_advisory_deps", "unmaintained_dependencies" ; "unmaintained advisories do not panic")]
+    #[test_case("known_advisory_deps", "current_version_advisories" ; "advisories affecting the current version do not panic")]
+    #[test_case("known_advisory_deps", "audit_summary" ; "audit summary does not panic")]
+    #[test_case("simple_deps", "future_incompat_lints" ; "future incompatibilities without a report does not panic")]
     #[test_case("simple_deps", "github_simple" => ignore["don't use GitHub API rate limits in tests"]; "simple GitHub repository query")]
     #[test_case("simple_deps", "github_owner" => ignore["don't use GitHub API rate limits in tests"]; "retrieve the owner of a GitHub repository")]
+    #[test_case("simple_deps", "crates_io_single_owner" => ignore["don't use the crates.io API in tests"]; "dependencies with a single crates.io owner")]
+    #[test_case("simple_deps", "recently_published" => ignore["don't use the crates.io API in tests"]; "dependencies published after a timestamp")]
+    #[test_case("simple_deps", "outdated_dependencies" => ignore["don't use the crates.io API in tests"]; "outdated dependencies")]
+    #[test_case("simple_deps", "barely_used_dependencies" => ignore["don't use the crates.io API in tests"]; "barely used dependencies")]
+    #[test_case("simple_deps", "diverging_keywords" => ignore["don't use the crates.io API in tests"]; "crates.io keywords and categories")]
+    #[test_case("simple_deps", "failing_docs_rs_builds" => ignore["don't use the docs.rs API in tests"]; "dependencies with failing docs.rs builds")]
+    #[test_case("simple_deps", "deps_dev_second_opinion" => ignore["don't use the deps.dev API in tests"]; "deps.dev information of dependencies")]
+    #[test_case("simple_deps", "low_scorecard_repositories" => ignore["don't use the GitHub or Scorecard APIs in tests"]; "repositories with a low OpenSSF Scorecard")]
+    #[test_case("simple_deps", "stale_repositories" => ignore["don't use the GitHub API in tests"]; "dependencies with stale repositories")]
+    #[test_case("simple_deps", "repository_license_mismatch" => ignore["don't use the GitHub API in tests"]; "licenses of dependencies and their repositories")]
+    #[test_case("simple_deps", "cryptography_repositories" => ignore["don't use the GitHub API in tests"]; "dependencies with cryptography repositories")]
+    #[test_case("simple_deps", "bus_factor" => ignore["don't use the GitHub API in tests"]; "dependencies where the top contributor has most commits")]
+    #[test_case("simple_deps", "untagged_versions" => ignore["don't use the GitHub API in tests"]; "dependency versions without a GitHub release")]
+    #[test_case("simple_deps", "without_ci" => ignore["don't use the GitHub API in tests"]; "dependencies without ci workflows")]
+    #[test_case("simple_deps", "unprotected_default_branches" => ignore["don't use the GitHub API in tests"]; "dependencies with unprotected default branches")]
+    #[test_case("simple_deps", "without_security_policy" => ignore["don't use the GitHub API in tests"]; "dependencies without a security policy")]
+    #[test_case("simple_deps", "open_security_issues" => ignore["don't use the GitHub API in tests"]; "dependencies with open security issues")]
+    #[test_case("simple_deps", "owner_affiliations" => ignore["don't use the GitHub API in tests"]; "affiliations of repository owners")]
+    #[test_case("simple_deps", "inactive_gitlab_repositories" => ignore["don't use the GitLab API in tests"]; "GitLab repositories without recent activity")]
+    #[test_case("simple_deps", "codeberg_repositories" => ignore["don't use the Gitea API in tests"]; "repositories hosted on Codeberg")]
+    #[test_case("simple_deps", "sourcehut_repositories" => ignore["don't use the sourcehut API in tests"]; "repositories hosted on sourcehut")]
+    #[test_case("simple_deps", "ghsa_advisories" => ignore["don't use the GitHub API in tests"]; "GitHub Security Advisories")]
+    #[test_case("known_advisory_deps", "advisories_by_epss" => ignore["don't use the EPSS API in tests"]; "advisories likely to be exploited")]
+    #[test_case("known_advisory_deps", "known_exploited_advisories" => ignore["don't download the CISA KEV catalog in tests"]; "advisories in the KEV catalog")]
+    #[test_case("known_advisory_deps", "advisory_cve_records" => ignore["don't use the NVD API in tests"]; "CVE records of advisories")]
+    #[test_case("simple_deps", "crev_reviews" => ignore["requires cargo-crev and a local proof repository"]; "crev reviews of dependencies")]
+    #[test_case("simple_deps", "semver_violations" => ignore["requires cargo-semver-checks and the crates.io index"]; "semver violations of workspace members")]
+    #[test_case("simple_deps", "unused_dependencies" => ignore["requires cargo-udeps and a nightly toolchain"]; "dependencies unused by workspace members")]
+    #[test_case("simple_deps", "heavy_dependencies" => ignore["requires cargo-bloat and a release build"]; "dependencies contributing most to the binary size")]
+    #[test_case("simple_deps", "clippy_warnings" => ignore["builds each dependency using Clippy"]; "dependencies with Clippy warnings")]
+    #[test_case("bundled_binaries", "bundled_binaries_negative_size" => panics "parameter error"; "negative binary size threshold")]
+    #[test_case("simple_deps", "typosquat_negative_distance" => panics "parameter error"; "negative typosquat distance")]
     fn query_sanity_check(fake_crate_name: &str, query_name: &str) {
         let (cargo_toml_path, query_path) =
             get_paths(fake_crate_name, query_name);
@@ -257,8 +441,25 @@ mod test {
     }
 
     #[test_case("simple_deps", "direct_dependencies" ; "direct dependencies as listed in Cargo.toml")]
+    #[test_case("dev_deps", "dev_dependencies" ; "direct dev dependencies")]
+    #[test_case("dev_deps", "all_dependencies" ; "direct dependencies of all kinds")]
+    #[test_case("requirement_deps", "dependency_requirements" ; "dependency requirements as declared in Cargo.toml")]
     #[test_case("simple_deps", "no_deps_all_fields" ; "retrieving all fields of root package, but not dependencies")]
     #[test_case("simple_deps", "dependency_package_info" ; "information about root package direct dependencies")]
+    #[test_case("simple_deps", "dependency_description_links" ; "description, homepage and documentation of direct dependencies")]
+    #[test_case("simple_deps", "dependency_edition_rust_version" ; "edition and MSRV of direct dependencies")]
+    #[test_case("simple_deps", "dependency_authors" ; "authors of root package and direct dependencies")]
+    #[test_case("simple_deps", "dependency_suspicion" ; "obfuscation heuristics of direct dependencies")]
+    #[test_case("simple_deps", "typosquat_candidates" ; "popular crates close to the names of dependencies")]
+    #[test_case("ffi_crate", "ffi_extern_fns" ; "extern C functions of root package")]
+    #[test_case("build_script_crate", "build_script_risk_flags" ; "risky behavior in build script of root package")]
+    #[test_case("build_script_crate", "root_package_targets" ; "targets of root package")]
+    #[test_case("bundled_binaries", "bundled_binaries" ; "binaries over default size threshold")]
+    #[test_case("bundled_binaries", "bundled_binaries_min_size" ; "binaries over custom size threshold")]
+    #[test_case("suspicious_files", "suspicious_files" ; "executables and libraries shipped in root package")]
+    #[test_case("suspicious_files", "suspicious_files_min_blob_size" ; "blobs over custom size threshold")]
+    #[test_case("suspicious_files", "suspicious_files_negative_blob_size" => panics "parameter error"; "negative blob size threshold")]
+    #[test_case("feature_deps", "root_package_features" ; "features of root package and what they enable")]
     #[test_case("simple_deps", "recursive_dependency" ; "retrieve recursive dependency information")]
     #[test_case("simple_deps", "count_dependencies" ; "count the number of dependencies used by each dependency")]
     #[test_case("forbids_unsafe", "geiger_forbids_unsafe")]
@@ -269,6 +470,26 @@ mod test {
     #[test_case("dev_deps", "dev_dependencies_excluded" ; "dev-dependencies excluded in dep resolution when using Dependencies entry point")]
     #[test_case("dev_deps", "dev_dependencies_excluded_w_root_package" ; "dev-dependencies excluded in dep resolution when using RootPackage entry point")]
     #[test_case("transitive_deps", "list_transitive_dependencies" ; "list only transitive dependencies")]
+    #[test_case("simple_deps", "direct_and_transitive" ; "direct and transitive dependencies in one query")]
+    #[test_case("workspace_crate", "publish_restrictions" ; "publish restrictions of workspace members")]
+    #[test_case("simple_deps", "registry_urls" ; "registry of each package")]
+    #[test_case("simple_deps", "dependency_checksums" ; "checksums from lock file")]
+    #[test_case("vetted_deps", "vet_audits" ; "cargo vet audits and imports")]
+    #[test_case("vetted_deps", "unvetted_dependencies" ; "dependencies without a safe-to-deploy audit")]
+    #[test_case("denied_deps", "deny_policy" ; "cargo deny bans and licenses")]
+    #[test_case("build_script_crate", "build_script_stats" ; "code stats and unsafety of build script")]
+    #[test_case("proc_macro_deps", "compile_time_code" ; "proc macros and build scripts")]
+    #[test_case("license_file_crate", "license_files" ; "license files in the package root and declared")]
+    #[test_case("simple_deps", "license_expression" ; "parsed spdx license expressions")]
+    #[test_case("simple_deps", "license_summary" ; "packages grouped by license")]
+    #[test_case("simple_deps", "path_from_root" ; "shortest path from the root package")]
+    #[test_case("simple_deps", "dependencies_with_defaults" ; "query with default arguments")]
+    #[test_case("duplicate_deps", "duplicate_versions" ; "duplicate versions of the same package")]
+    #[test_case("simple_deps", "dependency_info_included" ; "query with included fragments and arguments")]
+    #[test_case("simple_deps", "dependency_depth" ; "shortest distance from root package")]
+    #[test_case("simple_deps", "dependents" ; "packages depending on a transitive dependency")]
+    #[test_case("workspace_crate", "workspace_members" ; "all members of a virtual workspace")]
+    #[test_case("workspace_crate", "workspace_members_source_kind" ; "source kind of path dependencies")]
     #[test_case("simple_deps", "code_stats_simple")]
     #[test_case("simple_deps", "all_deps_code_stats")]
     #[test_case("simple_deps", "all_deps_code_stats_only_src")]
@@ -363,7 +584,7 @@ mod test {
     fn extract_query(path_str: &str) {
         let q = FullQuery::from_path(Path::new(path_str));
         match q {
-            Ok(_) => return,
+            Ok(_) => {}
             Err(b) => panic!("{}", b),
         }
     }
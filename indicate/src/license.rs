@@ -0,0 +1,278 @@
+//! Licenses parsed from the SPDX license expression of a package
+//!
+//! The `license` field of a manifest is an SPDX expression such as
+//! `MIT OR Apache-2.0`. Older crates often use `/` instead of `OR`, which is
+//! accepted as well.
+//!
+//! Packages may instead, or in addition, ship license files. These are found
+//! in the root of the package, or declared using `license-file` in the
+//! manifest.
+use std::{collections::BTreeMap, fs, path::Path, rc::Rc};
+
+use cargo_metadata::Package;
+use spdx::{Expression, LicenseItem, ParseMode};
+
+use crate::util;
+
+/// Case insensitive prefixes of file names considered to be license files
+pub const LICENSE_FILE_PREFIXES: [&str; 6] = [
+    "license",
+    "licence",
+    "copying",
+    "unlicense",
+    "notice",
+    "copyright",
+];
+
+/// Max number of characters in [`LicenseFile::content_preview`]
+pub const LICENSE_PREVIEW_LENGTH: usize = 500;
+
+/// A single license in a license expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct License {
+    /// The SPDX identifier, such as `MIT`, with a trailing `+` if later
+    /// versions are allowed, or the reference if it is not an SPDX license
+    pub spdx_id: String,
+    pub is_osi_approved: bool,
+    pub is_fsf_libre: bool,
+    pub is_copyleft: bool,
+}
+
+impl From<&LicenseItem> for License {
+    fn from(item: &LicenseItem) -> Self {
+        match item {
+            LicenseItem::Spdx { id, or_later } => Self {
+                spdx_id: if *or_later {
+                    format!("{}+", id.name)
+                } else {
+                    id.name.to_string()
+                },
+                is_osi_approved: id.is_osi_approved(),
+                is_fsf_libre: id.is_fsf_free_libre(),
+                is_copyleft: id.is_copyleft(),
+            },
+            LicenseItem::Other { doc_ref, lic_ref } => Self {
+                spdx_id: match doc_ref {
+                    Some(d) => format!("DocumentRef-{d}:LicenseRef-{lic_ref}"),
+                    None => format!("LicenseRef-{lic_ref}"),
+                },
+                is_osi_approved: false,
+                is_fsf_libre: false,
+                is_copyleft: false,
+            },
+        }
+    }
+}
+
+/// Parses all licenses in a license expression, in the order they appear
+///
+/// Licenses appearing more than once are only included once. Returns an empty
+/// list if the expression is not a valid SPDX expression.
+#[must_use]
+pub fn parse_license_expression(expression: &str) -> Vec<License> {
+    let Ok(expression) = Expression::parse_mode(expression, ParseMode::LAX)
+    else {
+        return Vec::new();
+    };
+
+    let mut licenses: Vec<License> = Vec::new();
+    for req in expression.requirements() {
+        let license = License::from(&req.req.license);
+        if !licenses.contains(&license) {
+            licenses.push(license);
+        }
+    }
+    licenses
+}
+
+/// Retrieves the licenses of a package, if it declares a license expression
+#[must_use]
+pub fn get_licenses(package: &Package) -> Vec<License> {
+    package
+        .license
+        .as_deref()
+        .map(parse_license_expression)
+        .unwrap_or_default()
+}
+
+/// A license file shipped in a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseFile {
+    pub file_name: String,
+
+    /// Path relative to the package root
+    pub path: String,
+
+    /// The first [`LICENSE_PREVIEW_LENGTH`] characters of the file, with
+    /// leading and trailing whitespace removed
+    pub content_preview: String,
+
+    /// If this file is declared using `license-file` in the manifest
+    pub is_declared: bool,
+}
+
+impl LicenseFile {
+    fn from_path(
+        path: &Path,
+        package_root: &Path,
+        is_declared: bool,
+    ) -> Option<Self> {
+        // License files are not necessarily UTF-8
+        let content = fs::read(path).ok()?;
+        let content_preview = String::from_utf8_lossy(&content)
+            .trim()
+            .chars()
+            .take(LICENSE_PREVIEW_LENGTH)
+            .collect();
+        Some(Self {
+            file_name: path.file_name()?.to_string_lossy().to_string(),
+            path: path
+                .strip_prefix(package_root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string(),
+            content_preview,
+            is_declared,
+        })
+    }
+}
+
+fn is_license_file_name(file_name: &str) -> bool {
+    let file_name = file_name.to_lowercase();
+    LICENSE_FILE_PREFIXES
+        .iter()
+        .any(|prefix| file_name.starts_with(prefix))
+}
+
+/// Finds the license files in the root of a package, and the file declared
+/// using `license-file`, sorted by path
+#[must_use]
+pub fn get_license_files(package: &Package) -> Vec<LicenseFile> {
+    let root = util::local_package_path(package);
+    let root = root.canonicalize().unwrap_or(root);
+    let declared = package
+        .license_file()
+        .map(|p| p.into_std_path_buf())
+        .and_then(|p| p.canonicalize().ok());
+
+    let mut files = fs::read_dir(&root)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .filter(|e| is_license_file_name(&e.file_name().to_string_lossy()))
+        .map(|e| e.path())
+        .collect::<Vec<_>>();
+
+    // The declared file may be outside the package root
+    if let Some(declared) = &declared {
+        if !files
+            .iter()
+            .any(|f| f.canonicalize().ok().as_ref() == Some(declared))
+        {
+            files.push(declared.clone());
+        }
+    }
+
+    let mut license_files = files
+        .iter()
+        .filter_map(|f| {
+            let is_declared =
+                declared.is_some() && f.canonicalize().ok() == declared;
+            LicenseFile::from_path(f, &root, is_declared)
+        })
+        .collect::<Vec<_>>();
+    license_files.sort_by(|a, b| a.path.cmp(&b.path));
+    license_files
+}
+
+/// Packages declaring the same license expression
+#[derive(Debug, Clone)]
+pub struct LicenseGroup {
+    /// The license expression, with `/` replaced by `OR`; `None` for
+    /// packages without one
+    pub license: Option<String>,
+
+    /// Sorted by name and version
+    pub packages: Vec<Rc<Package>>,
+}
+
+/// Normalizes the separators of a license expression, so that
+/// `MIT/Apache-2.0` and `MIT OR Apache-2.0` are the same
+fn normalize_expression(expression: &str) -> String {
+    expression
+        .replace('/', " OR ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Groups packages by their license expression, sorted by the number of
+/// packages in each group, with the largest first
+#[must_use]
+pub fn group_by_license(
+    packages: impl IntoIterator<Item = Rc<Package>>,
+) -> Vec<LicenseGroup> {
+    let mut groups: BTreeMap<Option<String>, Vec<Rc<Package>>> =
+        BTreeMap::new();
+    for package in packages {
+        let license = package.license.as_deref().map(normalize_expression);
+        groups.entry(license).or_default().push(package);
+    }
+
+    let mut groups = groups
+        .into_iter()
+        .map(|(license, mut packages)| {
+            packages.sort_by(|a, b| {
+                (&a.name, &a.version).cmp(&(&b.name, &b.version))
+            });
+            LicenseGroup { license, packages }
+        })
+        .collect::<Vec<_>>();
+    // Stable, so groups of the same size are sorted by license
+    groups.sort_by_key(|g| std::cmp::Reverse(g.packages.len()));
+    groups
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{
+        is_license_file_name, normalize_expression, parse_license_expression,
+    };
+
+    #[test_case("MIT/Apache-2.0" => "MIT OR Apache-2.0" ; "slash separated")]
+    #[test_case("MIT / Apache-2.0" => "MIT OR Apache-2.0" ; "slash with spaces")]
+    #[test_case("Apache-2.0 WITH LLVM-exception" => "Apache-2.0 WITH LLVM-exception" ; "unchanged")]
+    fn normalized_expression(expression: &str) -> String {
+        normalize_expression(expression)
+    }
+
+    #[test_case("MIT OR Apache-2.0" => vec!["MIT", "Apache-2.0"] ; "dual license")]
+    #[test_case("MIT/Apache-2.0" => vec!["MIT", "Apache-2.0"] ; "slash separated")]
+    #[test_case("GPL-2.0+ AND MIT AND MIT" => vec!["GPL-2.0+", "MIT"] ; "or later and duplicates")]
+    #[test_case("LicenseRef-Proprietary" => vec!["LicenseRef-Proprietary"] ; "license reference")]
+    #[test_case("not a license (" => Vec::<&str>::new() ; "invalid")]
+    fn spdx_ids(expression: &str) -> Vec<String> {
+        parse_license_expression(expression)
+            .into_iter()
+            .map(|l| l.spdx_id)
+            .collect()
+    }
+
+    #[test_case("LICENSE" => true ; "license")]
+    #[test_case("LICENSE-MIT" => true ; "suffixed")]
+    #[test_case("Copying.txt" => true ; "mixed case")]
+    #[test_case("README.md" => false ; "not a license")]
+    fn license_file_names(file_name: &str) -> bool {
+        is_license_file_name(file_name)
+    }
+
+    #[test]
+    fn license_properties() {
+        let licenses = parse_license_expression("MIT OR GPL-3.0-only");
+        assert!(licenses[0].is_osi_approved && !licenses[0].is_copyleft);
+        assert!(licenses[1].is_copyleft && licenses[1].is_fsf_libre);
+    }
+}
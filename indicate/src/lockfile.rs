@@ -0,0 +1,158 @@
+//! Packages as recorded in a `Cargo.lock` file
+//!
+//! `cargo metadata` does not include everything in the lock file, such as the
+//! checksums of registry packages, so some information is read from the lock
+//! file directly.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
+
+use serde::Deserialize;
+
+/// A package in a lock file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+
+    /// Such as `registry+https://github.com/rust-lang/crates.io-index`, `None`
+    /// for path dependencies and workspace members
+    pub source: Option<String>,
+
+    /// The SHA-256 checksum of the downloaded `.crate` file, only recorded
+    /// for registry packages
+    pub checksum: Option<String>,
+}
+
+impl LockedPackage {
+    /// If this package is retrieved from a registry, and should therefore have
+    /// a checksum
+    #[must_use]
+    pub fn is_from_registry(&self) -> bool {
+        self.source.as_deref().is_some_and(|s| {
+            s.starts_with("registry+") || s.starts_with("sparse+")
+        })
+    }
+}
+
+/// The contents of a `Cargo.lock` file, valid for all lock file versions
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Lockfile {
+    #[serde(default)]
+    pub package: Vec<LockedPackage>,
+
+    /// Lock files of version 1 store checksums here, with keys like
+    /// `checksum <name> <version> (<source>)`
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+}
+
+impl Lockfile {
+    /// Parses the contents of a lock file, moving any checksums stored in the
+    /// `[metadata]` table of version 1 lock files to their packages
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the lock file is not valid TOML.
+    pub fn parse(raw_lockfile: &str) -> Result<Self, toml::de::Error> {
+        let mut lockfile: Lockfile = toml::from_str(raw_lockfile)?;
+        for package in &mut lockfile.package {
+            if package.checksum.is_some() {
+                continue;
+            }
+            let Some(source) = &package.source else {
+                continue;
+            };
+            let key = format!(
+                "checksum {} {} ({source})",
+                package.name, package.version
+            );
+            package.checksum = lockfile
+                .metadata
+                .get(&key)
+                .filter(|c| c.as_str() != "<none>")
+                .cloned();
+        }
+        Ok(lockfile)
+    }
+
+    /// Reads and parses a lock file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the file cannot be read, or is not valid
+    /// TOML.
+    pub fn from_path(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let raw_lockfile = fs::read_to_string(path)?;
+        Ok(Self::parse(&raw_lockfile)?)
+    }
+
+    /// Retrieves the checksums of all packages that have one, keyed by name,
+    /// version and source
+    #[must_use]
+    pub fn checksums(&self) -> HashMap<(String, String, String), String> {
+        self.package
+            .iter()
+            .filter_map(|p| {
+                let key =
+                    (p.name.clone(), p.version.clone(), p.source.clone()?);
+                Some((key, p.checksum.clone()?))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Lockfile;
+
+    #[test]
+    fn parse_checksums() {
+        let lockfile = Lockfile::parse(
+            r#"
+version = 3
+
+[[package]]
+name = "libc"
+version = "0.2.139"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "201de327520df007757c1f0adce6e827fe8562fbc28bfd9c15571c66ca1f5f79"
+
+[[package]]
+name = "simple_deps"
+version = "0.1.0"
+dependencies = ["libc"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(lockfile.package.len(), 2);
+        assert!(lockfile.package[0].is_from_registry());
+        assert!(lockfile.package[0].checksum.is_some());
+        assert!(!lockfile.package[1].is_from_registry());
+        assert_eq!(lockfile.checksums().len(), 1);
+    }
+
+    #[test]
+    fn parse_version_1_checksums() {
+        let lockfile = Lockfile::parse(
+            r#"
+[[package]]
+name = "libc"
+version = "0.2.139"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[metadata]
+"checksum libc 0.2.139 (registry+https://github.com/rust-lang/crates.io-index)" = "201de327520df007757c1f0adce6e827fe8562fbc28bfd9c15571c66ca1f5f79"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            lockfile.package[0].checksum.as_deref(),
+            Some(
+                "201de327520df007757c1f0adce6e827fe8562fbc28bfd9c15571c66ca1f5f79"
+            )
+        );
+    }
+}
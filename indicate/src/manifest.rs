@@ -7,7 +7,7 @@ use std::{
 use cargo_metadata::{CargoOpt, Metadata, MetadataCommand};
 use walkdir::WalkDir;
 
-use crate::errors::ManifestPathError;
+use crate::{errors::ManifestPathError, vendor::VendoredSources};
 
 /// The absolute path to a `Cargo.toml` file for a valid Rust package,
 /// used to extract metadata and the like
@@ -108,7 +108,7 @@ impl ManifestPath {
         // equal to what we're looking for
         if ctf
             .package
-            .map_or(true, |p| !Self::equal_package_names(p.name(), name))
+            .is_none_or(|p| !Self::equal_package_names(p.name(), name))
         {
             // It is probably a workspace, we'll have to find a `Cargo.toml`
             // file with matching name
@@ -181,6 +181,58 @@ impl ManifestPath {
         let res = m.exec()?;
         Ok(res)
     }
+
+    /// Extracts metadata like [`ManifestPath::metadata`], but with packages
+    /// from crates.io read from vendored sources, without network access
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the metadata command fails, such as if a
+    /// package is not vendored.
+    pub fn metadata_with_vendored_sources(
+        &self,
+        features: Vec<CargoOpt>,
+        vendored_sources: &VendoredSources,
+    ) -> Result<Metadata, Box<dyn Error>> {
+        let mut m = MetadataCommand::new();
+        m.manifest_path(self.as_path())
+            .other_options(vendored_sources.cargo_config_args());
+
+        for feature in features {
+            m.features(feature);
+        }
+
+        let res = m.exec()?;
+        Ok(res)
+    }
+
+    /// Extracts metadata like [`ManifestPath::metadata`], but only with the
+    /// dependencies used when compiling for a target platform, such as
+    /// `wasm32-unknown-unknown`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the metadata command fails, such as if the
+    /// target platform is not known by `rustc`.
+    pub fn metadata_for_platform(
+        &self,
+        features: Vec<CargoOpt>,
+        platform: &str,
+    ) -> Result<Metadata, Box<dyn Error>> {
+        let mut m = MetadataCommand::new();
+        m.manifest_path(self.as_path());
+
+        for feature in features {
+            m.features(feature);
+        }
+        m.other_options(vec![
+            String::from("--filter-platform"),
+            platform.to_string(),
+        ]);
+
+        let res = m.exec()?;
+        Ok(res)
+    }
 }
 
 impl<T> From<T> for ManifestPath
@@ -0,0 +1,218 @@
+//! Client for retrieving CVE records from the
+//! [National Vulnerability Database](https://nvd.nist.gov) (NVD)
+//!
+//! CVE records can be retrieved without a key, but if the `NVD_API_KEY`
+//! environment variable is set, it is used to raise the rate limit.
+//!
+//! See [the NVD CVE API documentation](https://nvd.nist.gov/developers/vulnerabilities)
+//! for more information.
+
+use std::rc::Rc;
+
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+
+use crate::http::{self, ResponseCache};
+
+/// Endpoint used to query CVE records
+pub const NVD_CVE_API_URL: &str =
+    "https://services.nvd.nist.gov/rest/json/cves/2.0";
+
+/// Environment variable containing an optional NVD API key
+pub const NVD_API_KEY_ENV: &str = "NVD_API_KEY";
+
+/// The response of the NVD CVE API; Empty if the CVE was not found
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NvdResponse {
+    #[serde(default)]
+    pub vulnerabilities: Vec<NvdVulnerability>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct NvdVulnerability {
+    pub cve: CveRecord,
+}
+
+/// A CVE record, as returned by the NVD CVE API, some fields omitted
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CveRecord {
+    /// Such as `CVE-2021-25900`
+    pub id: String,
+
+    /// Such as `Analyzed`, `Modified` or `Rejected`
+    pub vuln_status: String,
+
+    /// Timestamps in UTC without a time zone, such as
+    /// `2021-01-26T18:16:22.713`
+    pub published: String,
+    pub last_modified: String,
+
+    #[serde(default)]
+    pub descriptions: Vec<CveDescription>,
+
+    #[serde(default)]
+    pub metrics: CveMetrics,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CveDescription {
+    /// Such as `en`
+    pub lang: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CveMetrics {
+    #[serde(default)]
+    pub cvss_metric_v31: Vec<CvssMetric>,
+}
+
+/// A CVSS v3.1 score, by NVD or by another source such as a CNA
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CvssMetric {
+    /// Such as `nvd@nist.gov`
+    pub source: String,
+
+    /// `Primary` if scored by NVD, otherwise `Secondary`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub cvss_data: CvssData,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CvssData {
+    pub vector_string: String,
+    pub base_score: f64,
+
+    /// `NONE`, `LOW`, `MEDIUM`, `HIGH` or `CRITICAL`
+    pub base_severity: String,
+}
+
+impl CveRecord {
+    /// The English description of the CVE, if any
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.descriptions
+            .iter()
+            .find(|d| d.lang == "en")
+            .map(|d| d.value.as_str())
+    }
+
+    /// The CVSS v3.1 score by NVD, or by another source if NVD has not
+    /// scored the CVE
+    #[must_use]
+    pub fn cvss_v31(&self) -> Option<&CvssData> {
+        let metrics = &self.metrics.cvss_metric_v31;
+        metrics
+            .iter()
+            .find(|m| m.kind == "Primary")
+            .or_else(|| metrics.first())
+            .map(|m| &m.cvss_data)
+    }
+
+    #[must_use]
+    pub fn unix_published_at(&self) -> Option<i64> {
+        unix_timestamp(&self.published)
+    }
+
+    #[must_use]
+    pub fn unix_last_modified_at(&self) -> Option<i64> {
+        unix_timestamp(&self.last_modified)
+    }
+}
+
+/// NVD timestamps are in UTC, but do not include a time zone
+fn unix_timestamp(timestamp: &str) -> Option<i64> {
+    NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .map(|t| t.and_utc().timestamp())
+}
+
+/// Wrapper around a [`reqwest::Client`] querying the NVD CVE API, with
+/// caching
+///
+/// Uses the key in `NVD_API_KEY` if set.
+#[derive(Debug, Clone)]
+pub struct NvdClient {
+    client: reqwest::Client,
+    api_key: Option<String>,
+
+    /// Cache between a CVE ID and its record
+    ///
+    /// CVEs that were not found are cached as `None`, like failed requests.
+    cache: ResponseCache<String, Rc<CveRecord>>,
+}
+
+impl Default for NvdClient {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::default(),
+            api_key: std::env::var(NVD_API_KEY_ENV).ok(),
+            cache: ResponseCache::default(),
+        }
+    }
+}
+
+impl NvdClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves the record of a CVE, such as `CVE-2021-25900`
+    ///
+    /// Will return `None` if the CVE was not found or if the request fails,
+    /// and will cache this CVE as such.
+    pub fn get_cve(&mut self, cve_id: &str) -> Option<Rc<CveRecord>> {
+        self.cache.get_or_fetch(cve_id, || {
+            let mut req =
+                self.client.get(NVD_CVE_API_URL).query(&[("cveId", cve_id)]);
+            if let Some(api_key) = &self.api_key {
+                req = req.header("apiKey", api_key);
+            }
+            http::send_json::<NvdResponse>(req, &format!("{cve_id} from NVD"))?
+                .vulnerabilities
+                .into_iter()
+                .next()
+                .map(|v| Rc::new(v.cve))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::NvdResponse;
+
+    #[test]
+    fn deserialize_nvd_response() {
+        let json_string =
+            fs::read_to_string("test_data/nvd-output/CVE-2021-25900.json")
+                .unwrap();
+        let res = serde_json::from_str::<NvdResponse>(&json_string).unwrap();
+        let cve = &res.vulnerabilities[0].cve;
+
+        assert_eq!(cve.id, "CVE-2021-25900");
+        assert_eq!(cve.vuln_status, "Modified");
+        assert!(cve.description().unwrap().starts_with("An issue was"));
+        assert_eq!(cve.unix_published_at(), Some(1_611_684_982));
+
+        // The NVD score is preferred over the one by the CNA
+        let cvss = cve.cvss_v31().unwrap();
+        assert_eq!(cvss.base_score, 9.8);
+        assert_eq!(cvss.base_severity, "CRITICAL");
+    }
+
+    #[test]
+    fn deserialize_missing_cve() {
+        let res =
+            serde_json::from_str::<NvdResponse>(r#"{ "vulnerabilities": [] }"#)
+                .unwrap();
+        assert!(res.vulnerabilities.is_empty());
+    }
+}
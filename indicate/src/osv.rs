@@ -0,0 +1,165 @@
+//! Client for retrieving vulnerabilities from the [OSV.dev](https://osv.dev)
+//! API
+//!
+//! OSV aggregates advisories from several databases, such as RustSec and the
+//! GitHub Advisory Database (GHSA), so it may include advisories that are not
+//! available in `advisory-db`. Only vulnerabilities affecting the exact
+//! version of a package are retrieved.
+//!
+//! See [the OSV API documentation](https://google.github.io/osv.dev/api/)
+//! for more information.
+
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    http::{self, ResponseCache},
+    source::PackageSource,
+    NameVersion,
+};
+
+/// Endpoint used to query vulnerabilities affecting a package version
+pub const OSV_QUERY_URL: &str = "https://api.osv.dev/v1/query";
+
+/// The OSV ecosystem of packages published to crates.io
+pub const CRATES_IO_ECOSYSTEM: &str = "crates.io";
+
+#[derive(Debug, Serialize)]
+struct OsvQuery<'a> {
+    package: OsvQueryPackage<'a>,
+    version: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OsvQueryPackage<'a> {
+    name: &'a str,
+    ecosystem: &'a str,
+}
+
+/// The response of the OSV query endpoint; Empty if no vulnerabilities were
+/// found
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OsvQueryResponse {
+    #[serde(default)]
+    pub vulns: Vec<OsvVulnerability>,
+}
+
+/// A vulnerability in the OSV format, some fields omitted
+///
+/// See [the OSV schema](https://ossf.github.io/osv-schema/).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct OsvVulnerability {
+    /// Such as `RUSTSEC-2021-0003` or `GHSA-43w2-9j62-hq99`
+    pub id: String,
+    pub summary: Option<String>,
+    pub details: Option<String>,
+
+    /// Identifiers of the same vulnerability in other databases
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    /// RFC 3339 timestamps
+    pub published: Option<String>,
+    pub modified: String,
+    pub withdrawn: Option<String>,
+
+    #[serde(default)]
+    pub severity: Vec<OsvSeverity>,
+
+    #[serde(default)]
+    pub references: Vec<OsvReference>,
+}
+
+/// A severity score, such as a CVSS vector
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct OsvSeverity {
+    /// Such as `CVSS_V3`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub score: String,
+}
+
+/// A link to more information about a vulnerability
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct OsvReference {
+    /// Such as `ADVISORY`, `FIX` or `WEB`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub url: String,
+}
+
+/// Wrapper around a [`reqwest::Client`] querying OSV.dev, with caching
+#[derive(Debug, Clone, Default)]
+pub struct OsvClient {
+    client: reqwest::Client,
+
+    /// Cache between a package version and its vulnerabilities
+    cache: ResponseCache<NameVersion, Rc<Vec<OsvVulnerability>>>,
+}
+
+impl OsvClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves all vulnerabilities affecting a version of a crates.io
+    /// package
+    ///
+    /// Will return `None` if the package is not from crates.io, or if the
+    /// request fails, and will cache this package version as such.
+    pub fn vulnerabilities(
+        &mut self,
+        package: &NameVersion,
+    ) -> Option<Rc<Vec<OsvVulnerability>>> {
+        if package.source != PackageSource::CratesIo {
+            return None;
+        }
+        self.cache.get_or_fetch(package, || {
+            let query = OsvQuery {
+                package: OsvQueryPackage {
+                    name: &package.name,
+                    ecosystem: CRATES_IO_ECOSYSTEM,
+                },
+                version: package.version.to_string(),
+            };
+            let res = http::send_json::<OsvQueryResponse>(
+                self.client.post(OSV_QUERY_URL).json(&query),
+                &format!(
+                    "OSV vulnerabilities for {} {}",
+                    package.name, package.version
+                ),
+            )?;
+            Some(Rc::new(res.vulns))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::OsvQueryResponse;
+
+    #[test]
+    fn deserialize_osv_response() {
+        let json_string =
+            fs::read_to_string("test_data/osv-output/smallvec.json").unwrap();
+        let res =
+            serde_json::from_str::<OsvQueryResponse>(&json_string).unwrap();
+        assert_eq!(res.vulns.len(), 1);
+
+        let vuln = &res.vulns[0];
+        assert_eq!(vuln.id, "RUSTSEC-2021-0003");
+        assert_eq!(vuln.aliases, vec![String::from("GHSA-43w2-9j62-hq99")]);
+        assert_eq!(vuln.severity[0].kind, "CVSS_V3");
+        assert!(vuln.withdrawn.is_none());
+    }
+
+    #[test]
+    fn deserialize_empty_osv_response() {
+        let res = serde_json::from_str::<OsvQueryResponse>("{}").unwrap();
+        assert!(res.vulns.is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+//! Encoding of query results as Apache Parquet files
+//!
+//! Parquet files can be loaded directly by analytics tools such as DataFusion
+//! or pandas. Each output of a query becomes a column, with its type inferred
+//! from the values in that column:
+//!
+//! | `FieldValue`            | Arrow type                           |
+//! | ----------------------- | ------------------------------------ |
+//! | `Int64`                 | `Int64`                              |
+//! | `Uint64`                | `UInt64`                             |
+//! | `Float64`               | `Float64`                            |
+//! | `Boolean`               | `Boolean`                            |
+//! | `String`, `Enum`        | `Utf8`                               |
+//! | `DateTimeUtc`           | `Timestamp(Millisecond, "UTC")`      |
+//! | `List`                  | `List<Utf8>`, elements as in JSON    |
+//!
+//! Columns mixing values of different types, or only containing `null`, are
+//! encoded as `Utf8` columns using the JSON representation of each value.
+use std::{collections::BTreeMap, sync::Arc};
+
+use arrow_array::{
+    builder::{ListBuilder, StringBuilder},
+    ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray,
+    TimestampMillisecondArray, UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use parquet::{arrow::ArrowWriter, errors::ParquetError};
+use trustfall::{FieldValue, TransparentValue};
+
+/// The type of a column, as inferred from its values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Int64,
+    Uint64,
+    Float64,
+    Boolean,
+    String,
+    DateTime,
+    List,
+}
+
+impl ColumnType {
+    fn of(value: &FieldValue) -> Option<Self> {
+        match value {
+            FieldValue::Null => None,
+            FieldValue::Int64(_) => Some(Self::Int64),
+            FieldValue::Uint64(_) => Some(Self::Uint64),
+            FieldValue::Float64(_) => Some(Self::Float64),
+            FieldValue::Boolean(_) => Some(Self::Boolean),
+            FieldValue::String(_) | FieldValue::Enum(_) => Some(Self::String),
+            FieldValue::DateTimeUtc(_) => Some(Self::DateTime),
+            FieldValue::List(_) => Some(Self::List),
+        }
+    }
+
+    /// Infers the type of a column, falling back to `String` for columns that
+    /// are empty or have mixed types
+    fn infer<'a>(values: impl Iterator<Item = &'a FieldValue>) -> Self {
+        let mut types = values.filter_map(Self::of);
+        let Some(first) = types.next() else {
+            return Self::String;
+        };
+        if types.all(|t| t == first) {
+            first
+        } else {
+            Self::String
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            ColumnType::Int64 => DataType::Int64,
+            ColumnType::Uint64 => DataType::UInt64,
+            ColumnType::Float64 => DataType::Float64,
+            ColumnType::Boolean => DataType::Boolean,
+            ColumnType::String => DataType::Utf8,
+            ColumnType::DateTime => {
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+            }
+            ColumnType::List => DataType::List(Arc::new(Field::new(
+                "item",
+                DataType::Utf8,
+                true,
+            ))),
+        }
+    }
+}
+
+/// Represents a value as a string, using its JSON representation for
+/// anything but strings
+fn value_to_string(value: &FieldValue) -> Option<String> {
+    match value {
+        FieldValue::Null => None,
+        FieldValue::String(s) | FieldValue::Enum(s) => Some(s.clone()),
+        v => serde_json::to_string(&TransparentValue::from(v.clone())).ok(),
+    }
+}
+
+/// Builds an Arrow array of a column of values
+fn build_column(column_type: ColumnType, values: &[&FieldValue]) -> ArrayRef {
+    match column_type {
+        ColumnType::Int64 => {
+            Arc::new(values.iter().map(|v| v.as_i64()).collect::<Int64Array>())
+        }
+        ColumnType::Uint64 => {
+            Arc::new(values.iter().map(|v| v.as_u64()).collect::<UInt64Array>())
+        }
+        ColumnType::Float64 => Arc::new(
+            values
+                .iter()
+                .map(|v| match v {
+                    FieldValue::Float64(f) => Some(*f),
+                    _ => None,
+                })
+                .collect::<Float64Array>(),
+        ),
+        ColumnType::Boolean => Arc::new(
+            values.iter().map(|v| v.as_bool()).collect::<BooleanArray>(),
+        ),
+        ColumnType::String => Arc::new(
+            values
+                .iter()
+                .map(|v| value_to_string(v))
+                .collect::<StringArray>(),
+        ),
+        ColumnType::DateTime => Arc::new(
+            values
+                .iter()
+                .map(|v| match v {
+                    FieldValue::DateTimeUtc(d) => Some(d.timestamp_millis()),
+                    _ => None,
+                })
+                .collect::<TimestampMillisecondArray>()
+                .with_timezone("UTC"),
+        ),
+        ColumnType::List => {
+            let mut builder = ListBuilder::new(StringBuilder::new());
+            for v in values {
+                match v {
+                    FieldValue::List(l) => {
+                        for item in l {
+                            builder
+                                .values()
+                                .append_option(value_to_string(item));
+                        }
+                        builder.append(true);
+                    }
+                    _ => builder.append(false),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+    }
+}
+
+/// Converts the results of a query to an Arrow [`RecordBatch`], with one
+/// column per output
+///
+/// # Errors
+///
+/// Returns an error variant if the columns could not be combined into a
+/// record batch.
+pub fn results_to_record_batch(
+    results: &[BTreeMap<Arc<str>, FieldValue>],
+) -> Result<RecordBatch, ParquetError> {
+    // All rows of a query have the same outputs
+    let names = results
+        .first()
+        .map(|r| r.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let mut fields = Vec::with_capacity(names.len());
+    let mut columns = Vec::with_capacity(names.len());
+    for name in names {
+        let values = results
+            .iter()
+            .map(|r| r.get(&name).unwrap_or(&FieldValue::NULL))
+            .collect::<Vec<_>>();
+        let column_type = ColumnType::infer(values.iter().copied());
+
+        fields.push(Field::new(name.as_ref(), column_type.data_type(), true));
+        columns.push(build_column(column_type, &values));
+    }
+
+    let batch = if columns.is_empty() {
+        RecordBatch::new_empty(Arc::new(Schema::empty()))
+    } else {
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)?
+    };
+    Ok(batch)
+}
+
+/// Encodes the results of a query as a Parquet file
+///
+/// # Errors
+///
+/// Returns an error variant if the results could not be converted to Arrow
+/// arrays, or if the Parquet file could not be written.
+pub fn encode_results(
+    results: &[BTreeMap<Arc<str>, FieldValue>],
+) -> Result<Vec<u8>, ParquetError> {
+    let batch = results_to_record_batch(results)?;
+    let mut buf = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, sync::Arc};
+
+    use arrow_array::{cast::AsArray, types::UInt64Type, Array};
+    use arrow_schema::DataType;
+    use trustfall::FieldValue;
+
+    use super::{encode_results, results_to_record_batch};
+
+    fn results() -> Vec<BTreeMap<Arc<str>, FieldValue>> {
+        vec![
+            BTreeMap::from([
+                (Arc::from("name"), FieldValue::from("libc")),
+                (Arc::from("downloads"), FieldValue::Uint64(10)),
+                (Arc::from("keywords"), FieldValue::List(vec!["ffi".into()])),
+                (Arc::from("license"), FieldValue::Null),
+            ]),
+            BTreeMap::from([
+                (Arc::from("name"), FieldValue::from("syn")),
+                (Arc::from("downloads"), FieldValue::Null),
+                (Arc::from("keywords"), FieldValue::List(vec![])),
+                (Arc::from("license"), FieldValue::Null),
+            ]),
+        ]
+    }
+
+    #[test]
+    fn infer_column_types() {
+        let batch = results_to_record_batch(&results()).unwrap();
+        let schema = batch.schema();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(
+            schema.field_with_name("downloads").unwrap().data_type(),
+            &DataType::UInt64
+        );
+        assert_eq!(
+            schema.field_with_name("license").unwrap().data_type(),
+            &DataType::Utf8
+        );
+        assert!(matches!(
+            schema.field_with_name("keywords").unwrap().data_type(),
+            DataType::List(_)
+        ));
+
+        let downloads = batch
+            .column_by_name("downloads")
+            .unwrap()
+            .as_primitive::<UInt64Type>();
+        assert_eq!(downloads.value(0), 10);
+        assert!(downloads.is_null(1));
+    }
+
+    #[test]
+    fn encode_parquet_file() {
+        let encoded = encode_results(&results()).unwrap();
+        // Parquet files start and end with the magic number `PAR1`
+        assert_eq!(&encoded[..4], b"PAR1");
+        assert_eq!(&encoded[encoded.len() - 4..], b"PAR1");
+    }
+}
@@ -0,0 +1,363 @@
+//! Policy profiles evaluated against query results
+//!
+//! A [`PolicyProfile`] describes a criticality tier, such as "tier-1
+//! dependencies must have an OpenSSF scorecard over 7 and no advisories", as
+//! a list of [`PolicyRule`]s. Profiles are evaluated at query time against the
+//! rows produced by [`execute_query`](crate::execute_query), and each profile
+//! produces a [`PolicyReport`] summarizing its violations.
+//!
+//! Rules operate on output names, so the query must output the fields the
+//! rules refer to, as well as a field identifying the package (`name` by
+//! default). Aggregates, like the number of advisories, are best expressed in
+//! the query using `@fold @transform(op: "count")`.
+//!
+//! Profiles can be read from a file, where a `.ron` file may look like
+//! ```ron
+//! PolicyProfiles(
+//!     profiles: [
+//!         PolicyProfile(
+//!             name: "tier-1",
+//!             packages: ["libc"],
+//!             rules: [
+//!                 PolicyRule(field: "advisoryCount", op: "=", value: 0),
+//!             ],
+//!         ),
+//!     ],
+//! )
+//! ```
+
+use std::{
+    cmp::Ordering, collections::BTreeMap, error::Error, ffi::OsStr, fs,
+    path::Path, sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use trustfall::{FieldValue, TransparentValue};
+
+use crate::errors::FileParseError;
+
+/// Output name used to identify the package of a result row, unless
+/// overridden by [`PolicyProfile::package_field`]
+pub const DEFAULT_PACKAGE_FIELD: &str = "name";
+
+fn default_package_field() -> String {
+    DEFAULT_PACKAGE_FIELD.to_string()
+}
+
+/// A collection of policy profiles, as read from a policy file
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolicyProfiles {
+    pub profiles: Vec<PolicyProfile>,
+}
+
+impl PolicyProfiles {
+    /// Extracts policy profiles from a file
+    ///
+    /// # Errors
+    ///
+    /// Will return an error variant if
+    ///
+    /// - The path cannot be read for some reason, or
+    /// - The file has a supported file extension, but failed to deserialize, or
+    /// - The file is of an unsupported file extension, or
+    /// - The file is of an unknown file extension
+    pub fn from_path(path: &Path) -> Result<PolicyProfiles, Box<dyn Error>> {
+        if !path.exists() {
+            return Err(Box::new(FileParseError::NotFound(
+                path.to_string_lossy().to_string(),
+            )));
+        }
+
+        let raw_profiles = fs::read_to_string(path)?;
+        match path.extension().and_then(OsStr::to_str) {
+            Some("json") => Ok(serde_json::from_str(&raw_profiles)?),
+            Some("ron") => Ok(ron::from_str(&raw_profiles)?),
+            Some(ext) => {
+                Err(Box::new(FileParseError::UnsupportedFileExtension {
+                    ext: String::from(ext),
+                    path: path.to_string_lossy().to_string(),
+                }))
+            }
+            None => Err(Box::new(FileParseError::UnknownFileExtension(
+                path.to_string_lossy().to_string(),
+            ))),
+        }
+    }
+
+    /// Evaluates all profiles against the same query results
+    #[must_use]
+    pub fn evaluate(
+        &self,
+        results: &[BTreeMap<Arc<str>, FieldValue>],
+    ) -> Vec<PolicyReport> {
+        self.profiles.iter().map(|p| p.evaluate(results)).collect()
+    }
+}
+
+/// A named set of rules applied to a tier of packages
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolicyProfile {
+    pub name: String,
+
+    /// Names of the packages in this tier; if empty, the profile applies to
+    /// all result rows
+    #[serde(default)]
+    pub packages: Vec<String>,
+
+    /// Output name identifying the package of a result row
+    #[serde(default = "default_package_field")]
+    pub package_field: String,
+
+    pub rules: Vec<PolicyRule>,
+}
+
+impl PolicyProfile {
+    /// If a result row belongs to a package in this tier
+    fn applies_to(&self, package_name: Option<&str>) -> bool {
+        self.packages.is_empty()
+            || package_name
+                .is_some_and(|n| self.packages.iter().any(|p| p == n))
+    }
+
+    /// Evaluates all rules of this profile against query results
+    ///
+    /// Rows that do not belong to the tier of this profile are ignored.
+    #[must_use]
+    pub fn evaluate(
+        &self,
+        results: &[BTreeMap<Arc<str>, FieldValue>],
+    ) -> PolicyReport {
+        let mut evaluated_rows = 0;
+        let mut violations = Vec::new();
+
+        for row in results {
+            let package = row
+                .get(self.package_field.as_str())
+                .and_then(FieldValue::as_str);
+
+            if !self.applies_to(package) {
+                continue;
+            }
+            evaluated_rows += 1;
+
+            for rule in &self.rules {
+                let actual =
+                    row.get(rule.field.as_str()).unwrap_or(&FieldValue::NULL);
+                if !rule.is_satisfied_by(actual) {
+                    violations.push(PolicyViolation {
+                        package: package.map(String::from),
+                        rule: rule.clone(),
+                        actual: actual.clone().into(),
+                    });
+                }
+            }
+        }
+
+        PolicyReport {
+            profile: self.name.clone(),
+            evaluated_rows,
+            violations,
+        }
+    }
+}
+
+/// Operators available to policy rules, named like the Trustfall `@filter`
+/// operators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PolicyOp {
+    Equals,
+    NotEquals,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    IsNull,
+    IsNotNull,
+}
+
+impl TryFrom<String> for PolicyOp {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "=" => Ok(Self::Equals),
+            "!=" => Ok(Self::NotEquals),
+            "<" => Ok(Self::LessThan),
+            "<=" => Ok(Self::LessThanOrEqual),
+            ">" => Ok(Self::GreaterThan),
+            ">=" => Ok(Self::GreaterThanOrEqual),
+            "is_null" => Ok(Self::IsNull),
+            "is_not_null" => Ok(Self::IsNotNull),
+            op => Err(format!("unknown policy operator `{op}`")),
+        }
+    }
+}
+
+impl From<PolicyOp> for String {
+    fn from(value: PolicyOp) -> Self {
+        match value {
+            PolicyOp::Equals => "=",
+            PolicyOp::NotEquals => "!=",
+            PolicyOp::LessThan => "<",
+            PolicyOp::LessThanOrEqual => "<=",
+            PolicyOp::GreaterThan => ">",
+            PolicyOp::GreaterThanOrEqual => ">=",
+            PolicyOp::IsNull => "is_null",
+            PolicyOp::IsNotNull => "is_not_null",
+        }
+        .to_string()
+    }
+}
+
+/// A single requirement on an output field of a query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyRule {
+    pub field: String,
+    pub op: PolicyOp,
+
+    /// Not used by `is_null` and `is_not_null`
+    #[serde(default)]
+    pub value: TransparentValue,
+}
+
+impl PolicyRule {
+    /// Checks if a value satisfies this rule
+    ///
+    /// A `null` value only satisfies `is_null` and `!=` (unless compared to
+    /// `null`), since a missing signal cannot prove that a requirement is met.
+    #[must_use]
+    pub fn is_satisfied_by(&self, actual: &FieldValue) -> bool {
+        let expected = FieldValue::from(self.value.clone());
+        match self.op {
+            PolicyOp::IsNull => actual == &FieldValue::Null,
+            PolicyOp::IsNotNull => actual != &FieldValue::Null,
+            PolicyOp::Equals => {
+                compare(actual, &expected) == Some(Ordering::Equal)
+            }
+            PolicyOp::NotEquals => {
+                compare(actual, &expected) != Some(Ordering::Equal)
+            }
+            PolicyOp::LessThan => {
+                compare(actual, &expected) == Some(Ordering::Less)
+            }
+            PolicyOp::LessThanOrEqual => matches!(
+                compare(actual, &expected),
+                Some(Ordering::Less | Ordering::Equal)
+            ),
+            PolicyOp::GreaterThan => {
+                compare(actual, &expected) == Some(Ordering::Greater)
+            }
+            PolicyOp::GreaterThanOrEqual => matches!(
+                compare(actual, &expected),
+                Some(Ordering::Greater | Ordering::Equal)
+            ),
+        }
+    }
+}
+
+/// Compares two values of the same kind, treating all numbers as `f64`
+///
+/// Returns `None` if the values cannot be compared, such as if either is
+/// `null` or they are of different kinds.
+fn compare(a: &FieldValue, b: &FieldValue) -> Option<Ordering> {
+    fn as_f64(v: &FieldValue) -> Option<f64> {
+        match v {
+            FieldValue::Int64(n) => Some(*n as f64),
+            FieldValue::Uint64(n) => Some(*n as f64),
+            FieldValue::Float64(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    match (a, b) {
+        (FieldValue::String(a), FieldValue::String(b)) => Some(a.cmp(b)),
+        (FieldValue::Boolean(a), FieldValue::Boolean(b)) => Some(a.cmp(b)),
+        (a, b) => as_f64(a)?.partial_cmp(&as_f64(b)?),
+    }
+}
+
+/// A rule that was not satisfied by a result row
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyViolation {
+    /// `None` if the row did not output the package field
+    pub package: Option<String>,
+    pub rule: PolicyRule,
+    pub actual: TransparentValue,
+}
+
+/// The violations of a single profile
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyReport {
+    pub profile: String,
+
+    /// Number of result rows the profile applied to
+    pub evaluated_rows: usize,
+    pub violations: Vec<PolicyViolation>,
+}
+
+impl PolicyReport {
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+    use test_case::test_case;
+    use trustfall::{FieldValue, TransparentValue};
+
+    use super::{PolicyOp, PolicyProfiles, PolicyRule};
+
+    fn row(name: &str, score: FieldValue) -> BTreeMap<Arc<str>, FieldValue> {
+        BTreeMap::from([
+            (Arc::from("name"), FieldValue::from(name)),
+            (Arc::from("score"), score),
+        ])
+    }
+
+    #[test_case(PolicyOp::GreaterThan, FieldValue::Float64(7.5) => true)]
+    #[test_case(PolicyOp::GreaterThan, FieldValue::Uint64(7) => false)]
+    #[test_case(PolicyOp::GreaterThanOrEqual, FieldValue::Int64(7) => true)]
+    #[test_case(PolicyOp::LessThan, FieldValue::Null => false ; "null is never less")]
+    #[test_case(PolicyOp::NotEquals, FieldValue::Null => true ; "null is not equal")]
+    #[test_case(PolicyOp::IsNull, FieldValue::Null => true)]
+    #[test_case(PolicyOp::IsNotNull, FieldValue::Null => false)]
+    fn rule_satisfied(op: PolicyOp, actual: FieldValue) -> bool {
+        let rule = PolicyRule {
+            field: "score".to_string(),
+            op,
+            value: TransparentValue::Int64(7),
+        };
+        rule.is_satisfied_by(&actual)
+    }
+
+    #[test]
+    fn evaluate_tiered_profiles() {
+        let profiles = PolicyProfiles::from_path(Path::new(
+            "test_data/policies/tiers.in.ron",
+        ))
+        .unwrap();
+        let results = vec![
+            row("libc", FieldValue::Float64(5.0)),
+            row("syn", FieldValue::Float64(8.0)),
+            row("quote", FieldValue::Null),
+        ];
+
+        let reports = profiles.evaluate(&results);
+        assert_eq!(reports.len(), 2);
+
+        // Tier 1 only contains `libc`, which has a too low score
+        assert_eq!(reports[0].evaluated_rows, 1);
+        assert_eq!(reports[0].violations.len(), 1);
+        assert_eq!(reports[0].violations[0].package.as_deref(), Some("libc"));
+
+        // Tier 3 applies to all, but only requires a score to exist
+        assert_eq!(reports[1].evaluated_rows, 3);
+        assert_eq!(reports[1].violations.len(), 1);
+        assert_eq!(reports[1].violations[0].package.as_deref(), Some("quote"));
+    }
+}
@@ -0,0 +1,158 @@
+//! Binary encoding of query results using Protocol Buffers
+//!
+//! Decoding JSON is a bottleneck when ingesting millions of result rows, so
+//! results can instead be encoded according to the [`RESULTS_PROTO`] schema,
+//! which can be used to generate decoders in any language supported by
+//! `protoc`. The wire format is simple enough to be written directly, without
+//! any code generation.
+use std::{collections::BTreeMap, sync::Arc};
+
+use trustfall::FieldValue;
+
+/// The Protocol Buffers schema describing encoded results
+pub const RESULTS_PROTO: &str = include_str!("results.proto");
+
+/// Wire types used by the schema
+const VARINT: u8 = 0;
+const FIXED64: u8 = 1;
+const LENGTH_DELIMITED: u8 = 2;
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_tag(buf: &mut Vec<u8>, field_number: u32, wire_type: u8) {
+    write_varint(buf, u64::from(field_number << 3 | u32::from(wire_type)));
+}
+
+fn write_bytes(buf: &mut Vec<u8>, field_number: u32, bytes: &[u8]) {
+    write_tag(buf, field_number, LENGTH_DELIMITED);
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Encodes a `Value` message
+fn encode_value(value: &FieldValue) -> Vec<u8> {
+    let mut buf = Vec::new();
+    match value {
+        FieldValue::Null => {
+            write_tag(&mut buf, 1, VARINT);
+            write_varint(&mut buf, 1);
+        }
+        FieldValue::Int64(i) => {
+            write_tag(&mut buf, 2, VARINT);
+            // `int64` is encoded as the two's complement
+            write_varint(&mut buf, *i as u64);
+        }
+        FieldValue::Uint64(u) => {
+            write_tag(&mut buf, 3, VARINT);
+            write_varint(&mut buf, *u);
+        }
+        FieldValue::Float64(f) => {
+            write_tag(&mut buf, 4, FIXED64);
+            buf.extend_from_slice(&f.to_le_bytes());
+        }
+        FieldValue::String(s) => write_bytes(&mut buf, 5, s.as_bytes()),
+        FieldValue::Boolean(b) => {
+            write_tag(&mut buf, 6, VARINT);
+            write_varint(&mut buf, u64::from(*b));
+        }
+        FieldValue::DateTimeUtc(d) => {
+            write_bytes(&mut buf, 7, d.to_rfc3339().as_bytes());
+        }
+        FieldValue::Enum(e) => write_bytes(&mut buf, 8, e.as_bytes()),
+        FieldValue::List(l) => {
+            let mut list = Vec::new();
+            for v in l {
+                write_bytes(&mut list, 1, &encode_value(v));
+            }
+            write_bytes(&mut buf, 9, &list);
+        }
+    }
+    buf
+}
+
+/// Encodes a `Row` message, where each output is a map entry
+fn encode_row(row: &BTreeMap<Arc<str>, FieldValue>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for (name, value) in row {
+        let mut entry = Vec::new();
+        write_bytes(&mut entry, 1, name.as_bytes());
+        write_bytes(&mut entry, 2, &encode_value(value));
+        write_bytes(&mut buf, 1, &entry);
+    }
+    buf
+}
+
+/// Encodes the results of a query as a `ResultSet` message
+#[must_use]
+pub fn encode_results(results: &[BTreeMap<Arc<str>, FieldValue>]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for row in results {
+        write_bytes(&mut buf, 1, &encode_row(row));
+    }
+    buf
+}
+
+/// Encodes the results of a query as a `ResultSet` message, prefixed by its
+/// length as a varint
+///
+/// Multiple length-delimited messages can be concatenated and read in
+/// sequence.
+#[must_use]
+pub fn encode_results_length_delimited(
+    results: &[BTreeMap<Arc<str>, FieldValue>],
+) -> Vec<u8> {
+    let message = encode_results(results);
+    let mut buf = Vec::with_capacity(message.len() + 10);
+    write_varint(&mut buf, message.len() as u64);
+    buf.extend(message);
+    buf
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, sync::Arc};
+
+    use test_case::test_case;
+    use trustfall::FieldValue;
+
+    use super::{
+        encode_results, encode_results_length_delimited, encode_value,
+    };
+
+    #[test_case(FieldValue::Null => vec![0x08, 0x01] ; "null")]
+    #[test_case(FieldValue::Int64(-1) => vec![0x10, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01] ; "negative int")]
+    #[test_case(FieldValue::Uint64(300) => vec![0x18, 0xac, 0x02] ; "multi byte uint")]
+    #[test_case(FieldValue::Float64(1.0) => vec![0x21, 0, 0, 0, 0, 0, 0, 0xf0, 0x3f] ; "float")]
+    #[test_case(FieldValue::from("hi") => vec![0x2a, 0x02, b'h', b'i'] ; "string")]
+    #[test_case(FieldValue::Boolean(false) => vec![0x30, 0x00] ; "false is written")]
+    #[test_case(FieldValue::List(vec![FieldValue::Uint64(1)]) => vec![0x4a, 0x04, 0x0a, 0x02, 0x18, 0x01] ; "list")]
+    fn encode_single_value(value: FieldValue) -> Vec<u8> {
+        encode_value(&value)
+    }
+
+    #[test]
+    fn encode_result_rows() {
+        let row = BTreeMap::from([(Arc::from("a"), FieldValue::Uint64(1))]);
+        let results = vec![row.clone(), row];
+
+        // Row: map entry (field 1) with key "a" and value { uint_value: 1 }
+        let encoded_row =
+            [0x0a, 0x07, 0x0a, 0x01, b'a', 0x12, 0x02, 0x18, 0x01];
+        let mut expected = Vec::new();
+        for _ in 0..2 {
+            expected.extend([0x0a, encoded_row.len() as u8]);
+            expected.extend(encoded_row);
+        }
+        assert_eq!(encode_results(&results), expected);
+
+        let delimited = encode_results_length_delimited(&results);
+        assert_eq!(delimited[0] as usize, expected.len());
+        assert_eq!(&delimited[1..], expected.as_slice());
+    }
+}
@@ -1,11 +1,70 @@
+//! Queries and their arguments, as read from files or built manually
+//!
+//! Query files can share parts of queries and arguments with other query
+//! files. A line in a query containing only
+//! `#include "<path>"` is replaced with the contents of that file, and
+//! `include_args` lists files with arguments that are merged into the
+//! arguments of the query. Paths are relative to the including file, such as
+//! ```ron
+//! FullQuery(
+//!     query: r#"
+//! {
+//!     Dependencies(includeRoot: false) {
+//!         #include "fragments/package_info.graphql"
+//!     }
+//! }
+//!     "#,
+//!     include_args: ["fragments/libc.args.ron"],
+//!     args: {},
+//! )
+//! ```
+//!
+//! Since `#` starts a comment in GraphQL, includes are ignored by anything
+//! not reading queries using [`FullQuery::from_path`].
+//!
+//! Trustfall does not support declaring variables with default values, so
+//! defaults are instead provided in a `defaults` section. A default is used
+//! when an argument is not provided, making it possible to reuse a query and
+//! only provide the arguments that differ:
+//! ```ron
+//! FullQuery(
+//!     query: r#"
+//! {
+//!     Dependencies(includeRoot: false) {
+//!         name @output
+//!         dependencies(kind: "normal") @fold @transform(op: "count")
+//!             @filter(op: ">=", value: ["$min"])
+//!     }
+//! }
+//!     "#,
+//!     defaults: { "min": 1 },
+//!     args: {},
+//! )
+//! ```
 use std::{
-    collections::BTreeMap, error::Error, ffi::OsStr, fs, path::Path, sync::Arc,
+    collections::BTreeMap,
+    error::Error,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use serde::Deserialize;
-use trustfall::TransparentValue;
+use async_graphql_parser::types::{BaseType, Type};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use trustfall::{FieldValue, TransparentValue};
+use trustfall_core::{
+    frontend::{self, error::FrontendError},
+    ir::{types::is_argument_type_valid, IndexedQuery},
+};
+
+use crate::{
+    errors::{FileParseError, QueryArgumentError, QueryValidationError},
+    SCHEMA,
+};
 
-use crate::errors::FileParseError;
+/// Directive that, when alone on a line, includes another file in a query
+const INCLUDE_DIRECTIVE: &str = "#include";
 
 /// Type representing a thread-safe JSON object, like
 /// ```json
@@ -21,60 +80,282 @@ type QueryArgs = BTreeMap<Arc<str>, TransparentValue>;
 pub struct FullQuery {
     pub query: String,
     pub args: QueryArgs,
+
+    /// Values used for variables not in `args`
+    #[serde(default)]
+    pub defaults: QueryArgs,
+}
+
+/// A query as written in a file, before includes are resolved
+#[derive(Debug, Deserialize)]
+#[serde(rename = "FullQuery")]
+struct RawFullQuery {
+    query: String,
+
+    /// Files with arguments, where arguments in later files and in `args`
+    /// take precedence
+    #[serde(default)]
+    include_args: Vec<PathBuf>,
+
+    #[serde(default)]
+    args: QueryArgs,
+
+    #[serde(default)]
+    defaults: QueryArgs,
+}
+
+/// Deserializes a file in a supported format, based on its extension
+fn deserialize_file<T: DeserializeOwned>(
+    path: &Path,
+) -> Result<T, Box<dyn Error>> {
+    if !path.exists() {
+        return Err(Box::new(FileParseError::NotFound(
+            path.to_string_lossy().to_string(),
+        )));
+    }
+
+    let raw = fs::read_to_string(path)?;
+    match path.extension().and_then(OsStr::to_str) {
+        // TODO: Add support for other file types
+        Some("json") => Ok(serde_json::from_str::<T>(&raw)?),
+        Some("ron") => Ok(ron::from_str::<T>(&raw)?),
+        Some(ext) => Err(Box::new(FileParseError::UnsupportedFileExtension {
+            ext: String::from(ext),
+            path: path.to_string_lossy().to_string(),
+        })),
+        None => Err(Box::new(FileParseError::UnknownFileExtension(
+            path.to_string_lossy().to_string(),
+        ))),
+    }
+}
+
+/// Replaces all `#include "<path>"` lines in a query with the contents of
+/// that file, recursively
+///
+/// `included_from` contains the canonical paths of the files currently being
+/// included, used to detect cycles.
+fn resolve_includes(
+    query: &str,
+    dir: &Path,
+    included_from: &mut Vec<PathBuf>,
+) -> Result<String, Box<dyn Error>> {
+    let mut resolved = String::with_capacity(query.len());
+    for line in query.lines() {
+        let Some(include) = line.trim().strip_prefix(INCLUDE_DIRECTIVE) else {
+            resolved.push_str(line);
+            resolved.push('\n');
+            continue;
+        };
+
+        let path = dir.join(include.trim().trim_matches('"'));
+        let path = path.canonicalize().map_err(|_| {
+            FileParseError::NotFound(path.to_string_lossy().to_string())
+        })?;
+        if included_from.contains(&path) {
+            return Err(Box::new(FileParseError::IncludeCycle(
+                path.to_string_lossy().to_string(),
+            )));
+        }
+
+        let included = fs::read_to_string(&path)?;
+        let included_dir = path.parent().unwrap_or(dir).to_path_buf();
+        included_from.push(path);
+        resolved.push_str(&resolve_includes(
+            &included,
+            &included_dir,
+            included_from,
+        )?);
+        included_from.pop();
+    }
+    Ok(resolved)
 }
 
 impl FullQuery {
-    /// Extracts a query from a file
+    /// Extracts a query from a file, resolving any includes
     ///
     /// # Errors
     ///
     /// Will return an error variant if
     ///
-    /// - The path cannot be read for some reason, or
+    /// - The path, or a path it includes, cannot be read for some reason, or
     /// - The file has a supported file extension, but failed to deserialize, or
     /// - The file is of an unsupported file extension, or
-    /// - The file is of an unknown file extension
+    /// - The file is of an unknown file extension, or
+    /// - A query includes itself
     pub fn from_path(path: &Path) -> Result<FullQuery, Box<dyn Error>> {
-        if !path.exists() {
-            Err(Box::new(FileParseError::NotFound(
-                path.to_string_lossy().to_string(),
-            )))
-        } else {
-            let raw_query = fs::read_to_string(path)?;
-            match path.extension().and_then(OsStr::to_str) {
-                // TODO: Add support for other file types
-                Some("json") => {
-                    let q: FullQuery =
-                        serde_json::from_str::<FullQuery>(&raw_query)?;
-                    Ok(q)
-                }
-                Some("ron") => {
-                    let q = ron::from_str::<FullQuery>(&raw_query)?;
-                    Ok(q)
-                }
-                Some(ext) => {
-                    Err(Box::new(FileParseError::UnsupportedFileExtension {
-                        ext: String::from(ext),
-                        path: path.to_string_lossy().to_string(),
-                    }))
+        let raw_query = deserialize_file::<RawFullQuery>(path)?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+
+        let mut args = QueryArgs::new();
+        for args_path in &raw_query.include_args {
+            args.extend(deserialize_file::<QueryArgs>(&dir.join(args_path))?);
+        }
+        args.extend(raw_query.args);
+
+        let mut included_from = Vec::from_iter(path.canonicalize().ok());
+        let query =
+            resolve_includes(&raw_query.query, dir, &mut included_from)?;
+
+        Ok(FullQuery {
+            query,
+            args,
+            defaults: raw_query.defaults,
+        })
+    }
+
+    /// The arguments used when executing this query, i.e. `args` with
+    /// `defaults` for any argument not provided
+    #[must_use]
+    pub fn resolved_args(&self) -> QueryArgs {
+        let mut args = self.defaults.clone();
+        args.extend(self.args.clone());
+        args
+    }
+
+    /// Parses the query and validates it against the schema
+    pub(crate) fn parse(
+        &self,
+    ) -> Result<Arc<IndexedQuery>, QueryValidationError> {
+        frontend::parse(&SCHEMA, &self.query).map_err(|e| {
+            // The outer error messages do not say what is wrong
+            let msg = match e {
+                FrontendError::ParseError(e) => e.to_string(),
+                FrontendError::ValidationError(e) => e.to_string(),
+                e => e.to_string(),
+            };
+            QueryValidationError::InvalidQuery(msg)
+        })
+    }
+
+    /// Describes the outputs of this query and their Trustfall types, so that
+    /// the results can be loaded into typed tables
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the query is not valid according to the
+    /// schema
+    pub fn result_descriptor(
+        &self,
+    ) -> Result<ResultDescriptor, QueryValidationError> {
+        let indexed_query = self.parse()?;
+        let columns = indexed_query
+            .outputs
+            .values()
+            .map(|o| OutputColumn::new(&o.name, &o.value_type))
+            .collect();
+        Ok(ResultDescriptor { columns })
+    }
+
+    /// Validates the query against the schema, and checks that the arguments
+    /// match the variables used in the query
+    ///
+    /// Trustfall performs the same checks when executing a query, but this
+    /// reports all problems with the arguments by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if
+    ///
+    /// - The query is not valid according to the schema, or
+    /// - An argument is missing, not used by the query or of the wrong type,
+    ///   where defaults count as provided arguments
+    pub fn validate(&self) -> Result<(), QueryValidationError> {
+        let indexed_query = self.parse()?;
+        let variables = &indexed_query.ir_query.variables;
+        let args = self.resolved_args();
+
+        let mut errors = Vec::new();
+        for (name, variable_type) in variables {
+            match args.get(name) {
+                None => errors.push(QueryArgumentError::Missing {
+                    name: name.to_string(),
+                    expected_type: variable_type.to_string(),
+                }),
+                Some(value)
+                    if !is_argument_type_valid(
+                        variable_type,
+                        &FieldValue::from(value.clone()),
+                    ) =>
+                {
+                    errors.push(QueryArgumentError::WrongType {
+                        name: name.to_string(),
+                        expected_type: variable_type.to_string(),
+                        value: serde_json::to_string(value)
+                            .unwrap_or_else(|_| format!("{value:?}")),
+                    });
                 }
-                None => Err(Box::new(FileParseError::UnknownFileExtension(
-                    path.to_string_lossy().to_string(),
-                ))),
+                Some(_) => {}
             }
         }
+        errors.extend(
+            args.keys()
+                .filter(|name| !variables.contains_key(*name))
+                .map(|name| QueryArgumentError::Superfluous(name.to_string())),
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(QueryValidationError::InvalidArguments(errors))
+        }
+    }
+}
+
+/// The outputs of a query, in the same order as in each result row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResultDescriptor {
+    pub columns: Vec<OutputColumn>,
+}
+
+/// An output of a query and its Trustfall type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutputColumn {
+    pub name: String,
+
+    /// The full type, such as `[String!]`
+    #[serde(rename = "type")]
+    pub trustfall_type: String,
+
+    /// The type without any list or non-null wrappers, such as `String`
+    pub base_type: String,
+
+    pub nullable: bool,
+
+    /// If the values are lists, such as for outputs inside a `@fold`
+    pub list: bool,
+}
+
+impl OutputColumn {
+    fn new(name: &str, ty: &Type) -> Self {
+        let mut base = ty;
+        while let BaseType::List(inner) = &base.base {
+            base = inner;
+        }
+
+        Self {
+            name: name.to_string(),
+            trustfall_type: ty.to_string(),
+            base_type: base.base.to_string(),
+            nullable: ty.nullable,
+            list: matches!(ty.base, BaseType::List(_)),
+        }
     }
 }
 
 pub struct FullQueryBuilder {
     query: String,
     args: Option<QueryArgs>,
+    defaults: Option<QueryArgs>,
 }
 
 impl FullQueryBuilder {
     #[must_use]
     pub fn new(query: String) -> Self {
-        Self { query, args: None }
+        Self {
+            query,
+            args: None,
+            defaults: None,
+        }
     }
 
     #[must_use]
@@ -89,11 +370,18 @@ impl FullQueryBuilder {
         self
     }
 
+    #[must_use]
+    pub fn defaults(mut self, defaults: QueryArgs) -> Self {
+        self.defaults = Some(defaults);
+        self
+    }
+
     #[must_use]
     pub fn build(self) -> FullQuery {
         FullQuery {
             query: self.query,
-            args: self.args.unwrap_or(BTreeMap::new()),
+            args: self.args.unwrap_or_default(),
+            defaults: self.defaults.unwrap_or_default(),
         }
     }
 }
@@ -103,8 +391,10 @@ mod test {
     use std::{fs, path::Path};
 
     use test_case::test_case;
+    use trustfall::TransparentValue;
 
-    use super::FullQuery;
+    use super::{FullQuery, FullQueryBuilder};
+    use crate::errors::{QueryArgumentError, QueryValidationError};
 
     #[test_case("test_data/queries/count_dependencies.in.ron" ; "parse count_dependencies ron")]
     #[test_case("test_data/queries/dependency_package_info.in.ron" ; "parse dependency package info ron")]
@@ -122,4 +412,124 @@ mod test {
         serde_json::from_str::<FullQuery>(&s)
             .unwrap_or_else(|_| panic!("could not deserialize {query_path}"));
     }
+
+    #[test]
+    fn resolve_includes() {
+        let query = FullQuery::from_path(Path::new(
+            "test_data/queries/dependency_info_included.in.ron",
+        ))
+        .unwrap();
+        assert!(query.query.contains("description @output"));
+        assert!(!query.query.contains("#include"));
+
+        // Arguments of the query take precedence over included arguments
+        assert_eq!(query.args.len(), 1);
+        assert!(matches!(
+            &query.args["names"],
+            TransparentValue::List(l) if l.len() == 2
+        ));
+    }
+
+    #[test_case(r#"{ "names": ["libc"] }"# => Vec::<QueryArgumentError>::new() ; "valid")]
+    #[test_case("{}" => vec![QueryArgumentError::Missing { name: "names".to_string(), expected_type: "[String!]!".to_string() }] ; "missing")]
+    #[test_case(r#"{ "names": ["libc"], "other": 1 }"# => vec![QueryArgumentError::Superfluous("other".to_string())] ; "superfluous")]
+    #[test_case(r#"{ "names": "libc" }"# => vec![QueryArgumentError::WrongType { name: "names".to_string(), expected_type: "[String!]!".to_string(), value: r#""libc""#.to_string() }] ; "wrong type")]
+    fn validate_args(args: &str) -> Vec<QueryArgumentError> {
+        let query = FullQueryBuilder::new(String::from(
+            r#"{
+    Dependencies(includeRoot: false) {
+        name @output @filter(op: "one_of", value: ["$names"])
+    }
+}"#,
+        ))
+        .args(serde_json::from_str(args).unwrap())
+        .build();
+
+        match query.validate() {
+            Ok(()) => Vec::new(),
+            Err(QueryValidationError::InvalidArguments(errors)) => errors,
+            Err(e) => panic!("unexpected error {e}"),
+        }
+    }
+
+    #[test]
+    fn result_descriptor() {
+        let query = FullQueryBuilder::new(String::from(
+            r#"{
+    Dependencies(includeRoot: false) {
+        name @output
+        license @output
+        dependencies @fold {
+            depName: name @output
+        }
+        dependencies @fold @transform(op: "count") @output(name: "depCount")
+    }
+}"#,
+        ))
+        .build();
+
+        let columns = query
+            .result_descriptor()
+            .unwrap()
+            .columns
+            .into_iter()
+            .map(|c| {
+                (c.name, c.trustfall_type, c.base_type, c.nullable, c.list)
+            })
+            .collect::<Vec<_>>();
+        let column = |name: &str, ty: &str, base: &str, nullable, list| {
+            (
+                name.to_string(),
+                ty.to_string(),
+                base.to_string(),
+                nullable,
+                list,
+            )
+        };
+        assert_eq!(
+            columns,
+            vec![
+                column("depCount", "Int!", "Int", false, false),
+                column("depName", "[String!]!", "String", false, true),
+                column("license", "String", "String", true, false),
+                column("name", "String!", "String", false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        let query = FullQueryBuilder::new(String::from(
+            r#"{
+    Dependencies(includeRoot: false) {
+        name @output @filter(op: "one_of", value: ["$names"])
+        version @output @filter(op: "=", value: ["$version"])
+    }
+}"#,
+        ))
+        .args(serde_json::from_str(r#"{ "names": ["libc"] }"#).unwrap())
+        .defaults(
+            serde_json::from_str(r#"{ "names": [], "version": "1.0.0" }"#)
+                .unwrap(),
+        )
+        .build();
+        assert!(query.validate().is_ok());
+
+        // Provided arguments take precedence
+        let args = query.resolved_args();
+        assert_eq!(args.len(), 2);
+        assert!(matches!(
+            &args["names"],
+            TransparentValue::List(l) if l.len() == 1
+        ));
+    }
+
+    #[test]
+    fn include_cycle() {
+        let err = FullQuery::from_path(Path::new(
+            "test_data/query_fragments/include_cycle.in.ron",
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("includes itself"));
+    }
 }
@@ -0,0 +1,350 @@
+//! Release notes of dependencies bumped between two `Cargo.lock` files
+//!
+//! Reviewing a dependency bump usually means looking up what changed in each
+//! bumped dependency. The release notes are retrieved from the GitHub releases
+//! of the repository declared on crates.io, and only releases after the old
+//! version up to and including the new version are kept.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+use rustsec::Version;
+use serde::Serialize;
+use trustfall::TransparentValue;
+
+use crate::{
+    crates_io::CratesIoClient,
+    humanize::{render_value, ValueStyle},
+    lockfile::Lockfile,
+    repo::{github::GitHubClient, RepoId},
+};
+
+/// A dependency with a different version in the new lock file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BumpedDependency {
+    pub name: String,
+    pub old_version: String,
+    pub new_version: String,
+}
+
+/// Finds dependencies that were bumped between two lock files, sorted by name
+///
+/// A dependency counts as bumped if a version was removed and another one
+/// added. If there are several of each, such as when a crate is compiled in
+/// several versions, the highest versions are used.
+#[must_use]
+pub fn bumped_dependencies(
+    old: &Lockfile,
+    new: &Lockfile,
+) -> Vec<BumpedDependency> {
+    fn versions(lockfile: &Lockfile) -> BTreeMap<&str, BTreeSet<Version>> {
+        let mut versions: BTreeMap<&str, BTreeSet<Version>> = BTreeMap::new();
+        for package in &lockfile.package {
+            if let Ok(v) = Version::parse(&package.version) {
+                versions.entry(&package.name).or_default().insert(v);
+            }
+        }
+        versions
+    }
+
+    let old_versions = versions(old);
+    let new_versions = versions(new);
+    old_versions
+        .iter()
+        .filter_map(|(name, old)| {
+            let new = new_versions.get(name)?;
+            let removed = old.difference(new).max()?;
+            let added = new.difference(old).max()?;
+            Some(BumpedDependency {
+                name: (*name).to_string(),
+                old_version: removed.to_string(),
+                new_version: added.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parses the version of a release tag, such as `v1.2.3`, `1.2.3` or
+/// `name-v1.2.3`, used by repositories containing several crates
+fn version_from_tag(tag: &str, crate_name: &str) -> Option<Version> {
+    let tag = tag
+        .strip_prefix(crate_name)
+        .map_or(tag, |t| t.trim_start_matches(['-', '_', '@', '/']));
+    let tag = tag.strip_prefix('v').unwrap_or(tag);
+    Version::parse(tag).ok()
+}
+
+/// A GitHub release of a dependency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseNote {
+    pub tag: String,
+    pub name: String,
+    pub url: String,
+    pub body: String,
+
+    /// When the release was published, as a Unix timestamp
+    pub unix_published_at: Option<i64>,
+}
+
+/// The releases between the old and new version of a bumped dependency
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyReleaseNotes {
+    #[serde(flatten)]
+    pub dependency: BumpedDependency,
+
+    /// The repository declared on crates.io, if any
+    pub repository: Option<String>,
+
+    /// Releases newer than the old version, up to and including the new
+    /// version, newest first; Empty if none could be found
+    pub releases: Vec<ReleaseNote>,
+}
+
+/// Release notes of all bumped dependencies
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReleaseNotes {
+    pub dependencies: Vec<DependencyReleaseNotes>,
+}
+
+impl ReleaseNotes {
+    /// Retrieves the release notes of all bumped dependencies
+    ///
+    /// Dependencies without a GitHub repository on crates.io, or without
+    /// matching releases, are included without any releases.
+    #[must_use]
+    pub fn collect(
+        bumped: Vec<BumpedDependency>,
+        crates_io_client: &mut CratesIoClient,
+        gh_client: &mut GitHubClient,
+    ) -> Self {
+        let dependencies = bumped
+            .into_iter()
+            .map(|dependency| {
+                let repository = crates_io_client
+                    .crate_data(&dependency.name)
+                    .and_then(|c| c.repository.clone());
+                let releases = repository
+                    .as_deref()
+                    .map(|url| releases_of(&dependency, url, gh_client))
+                    .unwrap_or_default();
+                DependencyReleaseNotes {
+                    dependency,
+                    repository,
+                    releases,
+                }
+            })
+            .collect();
+        Self { dependencies }
+    }
+
+    /// Renders one section per dependency, with one subsection per release
+    ///
+    /// When releases were published is rendered using `style`.
+    #[must_use]
+    pub fn to_markdown(&self, style: ValueStyle) -> String {
+        if self.dependencies.is_empty() {
+            return String::from("_No dependencies were bumped_\n");
+        }
+
+        let mut md = String::new();
+        for d in &self.dependencies {
+            let _ = writeln!(
+                md,
+                "## {} {} → {}\n",
+                d.dependency.name,
+                d.dependency.old_version,
+                d.dependency.new_version
+            );
+            if let Some(repository) = &d.repository {
+                let _ = writeln!(md, "Repository: <{repository}>\n");
+            }
+            if d.releases.is_empty() {
+                md.push_str("_No release notes found_\n\n");
+            }
+            for r in &d.releases {
+                let title = if r.name.is_empty() { &r.tag } else { &r.name };
+                let _ = writeln!(md, "### [{title}]({})\n", r.url);
+                if let Some(published_at) = r.unix_published_at {
+                    let rendered = render_value(
+                        "unixPublishedAt",
+                        &TransparentValue::Int64(published_at),
+                        style,
+                    )
+                    .unwrap_or_else(|| published_at.to_string());
+                    let _ = writeln!(md, "Published: {rendered}\n");
+                }
+                if !r.body.trim().is_empty() {
+                    let _ = writeln!(md, "{}\n", r.body.trim());
+                }
+            }
+        }
+        md
+    }
+}
+
+/// Retrieves the GitHub releases of a bumped dependency in its version range
+fn releases_of(
+    dependency: &BumpedDependency,
+    repository: &str,
+    gh_client: &mut GitHubClient,
+) -> Vec<ReleaseNote> {
+    let RepoId::GitHub(id) = RepoId::from(repository) else {
+        return Vec::new();
+    };
+    let (Ok(old), Ok(new)) = (
+        Version::parse(&dependency.old_version),
+        Version::parse(&dependency.new_version),
+    ) else {
+        return Vec::new();
+    };
+
+    gh_client
+        .get_releases(&id)
+        .map(|releases| {
+            releases
+                .iter()
+                .filter(|r| !r.draft)
+                .filter(|r| {
+                    version_from_tag(&r.tag_name, &dependency.name)
+                        .is_some_and(|v| v > old && v <= new)
+                })
+                .map(|r| ReleaseNote {
+                    tag: r.tag_name.clone(),
+                    name: r.name.clone(),
+                    url: r.html_url.clone(),
+                    body: r.body.clone(),
+                    unix_published_at: r.published_at.map(|d| d.timestamp()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{
+        bumped_dependencies, version_from_tag, BumpedDependency,
+        DependencyReleaseNotes, ReleaseNote, ReleaseNotes,
+    };
+    use crate::{humanize::ValueStyle, lockfile::Lockfile};
+
+    fn lockfile(packages: &[(&str, &str)]) -> Lockfile {
+        let raw = packages
+            .iter()
+            .map(|(name, version)| {
+                format!(
+                    "[[package]]\nname = \"{name}\"\nversion = \"{version}\"\n"
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Lockfile::parse(&raw).unwrap()
+    }
+
+    #[test]
+    fn bumped() {
+        let old = lockfile(&[
+            ("libc", "0.2.139"),
+            ("syn", "1.0.107"),
+            ("syn", "2.0.1"),
+            ("removed", "1.0.0"),
+            ("unchanged", "1.0.0"),
+        ]);
+        let new = lockfile(&[
+            ("libc", "0.2.140"),
+            ("syn", "1.0.107"),
+            ("syn", "2.0.5"),
+            ("added", "1.0.0"),
+            ("unchanged", "1.0.0"),
+        ]);
+        assert_eq!(
+            bumped_dependencies(&old, &new),
+            vec![
+                BumpedDependency {
+                    name: String::from("libc"),
+                    old_version: String::from("0.2.139"),
+                    new_version: String::from("0.2.140"),
+                },
+                BumpedDependency {
+                    name: String::from("syn"),
+                    old_version: String::from("2.0.1"),
+                    new_version: String::from("2.0.5"),
+                },
+            ]
+        );
+    }
+
+    #[test_case("v1.2.3" => Some(String::from("1.2.3")) ; "v prefix")]
+    #[test_case("1.2.3" => Some(String::from("1.2.3")) ; "no prefix")]
+    #[test_case("serde-v1.2.3" => Some(String::from("1.2.3")) ; "crate name prefix")]
+    #[test_case("serde@1.2.3" => Some(String::from("1.2.3")) ; "crate name at prefix")]
+    #[test_case("serde_derive-v1.2.3" => None ; "other crate")]
+    #[test_case("nightly" => None ; "not a version")]
+    fn tag_versions(tag: &str) -> Option<String> {
+        version_from_tag(tag, "serde").map(|v| v.to_string())
+    }
+
+    #[test]
+    fn markdown() {
+        let notes = ReleaseNotes {
+            dependencies: vec![DependencyReleaseNotes {
+                dependency: BumpedDependency {
+                    name: String::from("libc"),
+                    old_version: String::from("0.2.139"),
+                    new_version: String::from("0.2.140"),
+                },
+                repository: Some(String::from(
+                    "https://github.com/rust-lang/libc",
+                )),
+                releases: vec![ReleaseNote {
+                    tag: String::from("0.2.140"),
+                    name: String::new(),
+                    url: String::from(
+                        "https://github.com/rust-lang/libc/releases/tag/0.2.140",
+                    ),
+                    body: String::from("Fixed things\n"),
+                    unix_published_at: None,
+                }],
+            }],
+        };
+        assert_eq!(
+            notes.to_markdown(ValueStyle::Human),
+            "## libc 0.2.139 → 0.2.140\n\n\
+            Repository: <https://github.com/rust-lang/libc>\n\n\
+            ### [0.2.140](https://github.com/rust-lang/libc/releases/tag/0.2.140)\n\n\
+            Fixed things\n\n"
+        );
+    }
+
+    #[test]
+    fn markdown_published_at_raw() {
+        let notes = ReleaseNotes {
+            dependencies: vec![DependencyReleaseNotes {
+                dependency: BumpedDependency {
+                    name: String::from("libc"),
+                    old_version: String::from("0.2.139"),
+                    new_version: String::from("0.2.140"),
+                },
+                repository: None,
+                releases: vec![ReleaseNote {
+                    tag: String::from("0.2.140"),
+                    name: String::new(),
+                    url: String::from(
+                        "https://github.com/rust-lang/libc/releases/tag/0.2.140",
+                    ),
+                    body: String::new(),
+                    unix_published_at: Some(1_678_000_000),
+                }],
+            }],
+        };
+        assert_eq!(
+            notes.to_markdown(ValueStyle::Raw),
+            "## libc 0.2.139 → 0.2.140\n\n\
+            ### [0.2.140](https://github.com/rust-lang/libc/releases/tag/0.2.140)\n\n\
+            Published: 1678000000\n\n"
+        );
+    }
+}
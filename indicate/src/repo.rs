@@ -1,46 +1,86 @@
-//! These are signals related to repositories, such as GitHub or GitLab.
+//! These are signals related to repositories, such as GitHub, GitLab, Gitea
+//! or sourcehut.
+pub mod gitea;
 pub mod github;
+pub mod gitlab;
+pub mod scorecard;
+pub mod sourcehut;
 
 use url::Url;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum RepoId<'a> {
     GitHub(github::GitHubRepositoryId),
-    GitLab(&'a str),
+    GitLab(gitlab::GitLabProjectId),
+    Gitea(gitea::GiteaRepositoryId),
+    Sourcehut(sourcehut::SourcehutRepository),
     Unknown(&'a str),
 }
 
-impl<'a> From<&'a str> for RepoId<'a> {
-    fn from(url: &'a str) -> Self {
+impl<'a> RepoId<'a> {
+    /// Identifies the repository at `url`, where `gitea_hosts` are the hosts
+    /// considered Gitea instances
+    pub(crate) fn parse(url: &'a str, gitea_hosts: &[String]) -> Self {
         match Url::parse(url) {
             Ok(u) => match u.host_str() {
-                Some(host) if host == "github.com" => {
-                    // The two first parts of the path are owner and repo
-                    if let Some(path) = u.path_segments() {
-                        let owner_repo = path
-                            .take(2)
-                            .map(|s| {
-                                // Remove possible trailing `.git`, sometimes
-                                // repo url is a git HTTP address
-                                s.strip_suffix(".git").unwrap_or(s)
-                            })
-                            .collect::<Vec<_>>();
+                Some("github.com") => match owner_repo(&u) {
+                    Some((owner, repo)) => RepoId::GitHub(
+                        github::GitHubRepositoryId::new(owner, repo),
+                    ),
+                    None => {
+                        eprintln!("owner and repo could not be resolved for repo url {url}");
+                        RepoId::Unknown(url)
+                    }
+                },
+                Some("gitlab.com") => {
+                    // Projects may be in nested groups, and pages of a
+                    // project are separated from its path by `-`
+                    let path = u
+                        .path_segments()
+                        .map(|p| {
+                            p.take_while(|s| *s != "-")
+                                .filter(|s| !s.is_empty())
+                                .map(|s| s.strip_suffix(".git").unwrap_or(s))
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+
+                    if path.len() < 2 {
+                        eprintln!("namespace and project could not be resolved for repo url {url}");
+                        return RepoId::Unknown(url);
+                    }
 
-                        if owner_repo.len() != 2 {
+                    RepoId::GitLab(gitlab::GitLabProjectId::new(path.join("/")))
+                }
+                Some(sourcehut::SOURCEHUT_GIT_HOST) => {
+                    // Owners are prefixed with `~`, as in `~owner/repo`
+                    match owner_repo(&u).and_then(|(owner, repo)| {
+                        Some((owner.strip_prefix('~')?.to_string(), repo))
+                    }) {
+                        Some((owner, repo)) => RepoId::Sourcehut(
+                            sourcehut::SourcehutRepository::new(owner, repo),
+                        ),
+                        None => {
                             eprintln!("owner and repo could not be resolved for repo url {url}");
-                            return RepoId::Unknown(url);
+                            RepoId::Unknown(url)
+                        }
+                    }
+                }
+                Some(host) if gitea_hosts.iter().any(|h| h == host) => {
+                    match owner_repo(&u) {
+                        Some((owner, repo)) => {
+                            RepoId::Gitea(gitea::GiteaRepositoryId::new(
+                                host.to_string(),
+                                owner,
+                                repo,
+                            ))
+                        }
+                        None => {
+                            eprintln!("owner and repo could not be resolved for repo url {url}");
+                            RepoId::Unknown(url)
                         }
-
-                        RepoId::GitHub(github::GitHubRepositoryId::new(
-                            owner_repo[0].to_string(),
-                            owner_repo[1].to_string(),
-                        ))
-                    } else {
-                        eprintln!("could not figure out owner and repo for GitHub url {url}");
-                        RepoId::Unknown(url)
                     }
                 }
-                Some(host) if host == "gitlab.com" => RepoId::GitLab(url),
                 Some(_) => RepoId::Unknown(url),
                 None => {
                     eprintln!("found no host for repo url {url}");
@@ -55,11 +95,40 @@ impl<'a> From<&'a str> for RepoId<'a> {
     }
 }
 
+impl<'a> From<&'a str> for RepoId<'a> {
+    fn from(url: &'a str) -> Self {
+        Self::parse(url, &gitea::DEFAULT_GITEA_HOSTS.map(String::from))
+    }
+}
+
+/// The owner and repo of a repository url, which are the two first parts of
+/// the path, such as in `https://github.com/<owner>/<repo>/tree/main`
+fn owner_repo(url: &Url) -> Option<(String, String)> {
+    let owner_repo = url
+        .path_segments()?
+        .take(2)
+        .map(|s| {
+            // Remove possible trailing `.git`, sometimes repo url is a git
+            // HTTP address
+            s.strip_suffix(".git").unwrap_or(s)
+        })
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+
+    match owner_repo.as_slice() {
+        [owner, repo] => Some(((*owner).to_string(), (*repo).to_string())),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use test_case::test_case;
 
-    use crate::repo::{github::GitHubRepositoryId, RepoId};
+    use crate::repo::{
+        gitea::GiteaRepositoryId, github::GitHubRepositoryId,
+        gitlab::GitLabProjectId, sourcehut::SourcehutRepository, RepoId,
+    };
 
     #[test_case(
         "https://github.com/esek/ekorre",
@@ -95,10 +164,69 @@ mod test {
     )]
     #[test_case(
         "https://gitlab.com/jspngh/rfid-rs",
-        RepoId::GitLab("https://gitlab.com/jspngh/rfid-rs")
+        RepoId::GitLab(GitLabProjectId::new("jspngh/rfid-rs".to_string()))
         ; "normal gitlab url"
     )]
+    #[test_case(
+        "https://gitlab.com/jspngh/rfid-rs.git",
+        RepoId::GitLab(GitLabProjectId::new("jspngh/rfid-rs".to_string()))
+        ; "gitlab git http url"
+    )]
+    #[test_case(
+        "https://gitlab.com/gitlab-org/security-products/analyzers/tree/-/tree/main/src",
+        RepoId::GitLab(GitLabProjectId::new(
+            "gitlab-org/security-products/analyzers/tree".to_string()
+        ))
+        ; "gitlab nested group tree url"
+    )]
+    #[test_case(
+        "https://gitlab.com/jspngh",
+        RepoId::Unknown("https://gitlab.com/jspngh")
+        ; "gitlab url without project"
+    )]
+    #[test_case(
+        "https://codeberg.org/forgejo/forgejo/src/branch/forgejo",
+        RepoId::Gitea(GiteaRepositoryId::new(
+            "codeberg.org".to_string(),
+            "forgejo".to_string(),
+            "forgejo".to_string()
+        ))
+        ; "codeberg url"
+    )]
+    #[test_case(
+        "https://gitea.example.com/team/project.git",
+        RepoId::Unknown("https://gitea.example.com/team/project.git")
+        ; "unconfigured gitea url"
+    )]
+    #[test_case(
+        "https://git.sr.ht/~sircmpwn/hare/tree/master/item/README",
+        RepoId::Sourcehut(SourcehutRepository::new(
+            "sircmpwn".to_string(),
+            "hare".to_string()
+        ))
+        ; "sourcehut tree url"
+    )]
+    #[test_case(
+        "https://git.sr.ht/sircmpwn/hare",
+        RepoId::Unknown("https://git.sr.ht/sircmpwn/hare")
+        ; "sourcehut url without tilde"
+    )]
     fn parse_repo_url(url: &str, repo_id: RepoId) {
         assert_eq!(RepoId::from(url), repo_id);
     }
+
+    #[test]
+    fn parse_configured_gitea_url() {
+        assert_eq!(
+            RepoId::parse(
+                "https://gitea.example.com/team/project.git",
+                &["gitea.example.com".to_string()]
+            ),
+            RepoId::Gitea(GiteaRepositoryId::new(
+                "gitea.example.com".to_string(),
+                "team".to_string(),
+                "project".to_string()
+            ))
+        );
+    }
 }
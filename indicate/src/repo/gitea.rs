@@ -0,0 +1,163 @@
+//! Client for retrieving repositories hosted on [Gitea](https://about.gitea.com)
+//! instances, such as [Codeberg](https://codeberg.org)
+//!
+//! Since Gitea is often self-hosted, the hosts considered Gitea instances are
+//! configurable, see [`GiteaClient::with_hosts`]. Only public repositories
+//! are retrieved, without a token.
+//!
+//! See [the Gitea API documentation](https://codeberg.org/api/swagger) for
+//! more information.
+
+use std::rc::Rc;
+
+use chrono::DateTime;
+use serde::Deserialize;
+
+use crate::http::{self, ResponseCache};
+
+/// Hosts always considered Gitea instances
+pub const DEFAULT_GITEA_HOSTS: [&str; 1] = ["codeberg.org"];
+
+/// Identifies a repository on a Gitea instance, such as `codeberg.org`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GiteaRepositoryId {
+    host: String,
+    owner: String,
+    repo: String,
+}
+
+impl GiteaRepositoryId {
+    #[must_use]
+    pub fn new(host: String, owner: String, repo: String) -> Self {
+        Self { host, owner, repo }
+    }
+}
+
+/// A Gitea repository, as returned by the Gitea API, some fields omitted
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GiteaRepository {
+    pub name: String,
+
+    /// Such as `forgejo/forgejo`
+    pub full_name: String,
+    pub html_url: String,
+    pub stars_count: u64,
+    pub forks_count: u64,
+
+    /// Only includes issues, not pull requests
+    pub open_issues_count: u64,
+    pub archived: bool,
+
+    /// When the repository was last updated, such as when something was
+    /// pushed; RFC 3339
+    pub updated_at: String,
+}
+
+impl GiteaRepository {
+    /// When the repository was last updated as a Unix timestamp, `None` if
+    /// it could not be parsed
+    #[must_use]
+    pub fn unix_updated_at(&self) -> Option<i64> {
+        DateTime::parse_from_rfc3339(&self.updated_at)
+            .ok()
+            .map(|t| t.timestamp())
+    }
+}
+
+/// Wrapper around a [`reqwest::Client`] querying Gitea instances, with
+/// caching
+#[derive(Debug, Clone)]
+pub struct GiteaClient {
+    client: reqwest::Client,
+
+    /// Hosts considered Gitea instances, including
+    /// [`DEFAULT_GITEA_HOSTS`]
+    hosts: Vec<String>,
+
+    /// Cache between a repository and its information
+    cache: ResponseCache<GiteaRepositoryId, Rc<GiteaRepository>>,
+}
+
+impl Default for GiteaClient {
+    fn default() -> Self {
+        Self::with_hosts(Vec::new())
+    }
+}
+
+impl GiteaClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new client that also considers `hosts`, such as
+    /// `gitea.example.com`, to be Gitea instances
+    #[must_use]
+    pub fn with_hosts(hosts: Vec<String>) -> Self {
+        let mut all_hosts = DEFAULT_GITEA_HOSTS.map(String::from).to_vec();
+        all_hosts.extend(hosts);
+        Self {
+            client: reqwest::Client::default(),
+            hosts: all_hosts,
+            cache: ResponseCache::default(),
+        }
+    }
+
+    /// Hosts considered Gitea instances
+    #[must_use]
+    pub fn hosts(&self) -> &[String] {
+        &self.hosts
+    }
+
+    /// Retrieves a repository from a Gitea instance
+    ///
+    /// Will return `None` if the request fails, such as if the repository
+    /// does not exist or is private, and will cache this repository as such.
+    pub fn get_repository(
+        &mut self,
+        id: &GiteaRepositoryId,
+    ) -> Option<Rc<GiteaRepository>> {
+        self.cache.get_or_fetch(id, || {
+            let url = format!(
+                "https://{}/api/v1/repos/{}/{}",
+                id.host, id.owner, id.repo
+            );
+            http::send_json::<GiteaRepository>(
+                self.client.get(&url),
+                &format!(
+                    "Gitea repository {}/{} from {}",
+                    id.owner, id.repo, id.host
+                ),
+            )
+            .map(Rc::new)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::{GiteaClient, GiteaRepository};
+
+    #[test]
+    fn deserialize_gitea_repository() {
+        let json_string =
+            fs::read_to_string("test_data/gitea-output/forgejo.json").unwrap();
+        let repo =
+            serde_json::from_str::<GiteaRepository>(&json_string).unwrap();
+
+        assert_eq!(repo.full_name, "forgejo/forgejo");
+        assert_eq!(repo.html_url, "https://codeberg.org/forgejo/forgejo");
+        assert_eq!(repo.stars_count, 2317);
+        assert!(!repo.archived);
+        assert_eq!(repo.unix_updated_at(), Some(1_717_236_000));
+    }
+
+    #[test]
+    fn configured_hosts_include_defaults() {
+        let client =
+            GiteaClient::with_hosts(vec![String::from("gitea.example.com")]);
+        assert_eq!(client.hosts(), ["codeberg.org", "gitea.example.com"]);
+    }
+}
@@ -2,14 +2,22 @@
 //! and the `httpcache` feature. With this feature, `304 Not Modified`
 //! responses from the GitHub will instead be fetched from a local cache.
 
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
 
 #[cfg(test)]
 use global_counter::primitive::exact::CounterUsize;
 use octorust::{
     auth::Credentials,
     http_cache::HttpCache,
-    types::{FullRepository, PublicUser},
+    types::{
+        BranchProtection, BranchWithProtection, Contributor, Entries,
+        FullRepository, IssueSimple, IssuesListSort, IssuesListState, Order,
+        OrganizationSimple, PublicUser, Release,
+    },
     Client,
 };
 use once_cell::sync::Lazy;
@@ -34,6 +42,22 @@ impl GitHubRepositoryId {
     }
 }
 
+impl From<&FullRepository> for GitHubRepositoryId {
+    fn from(value: &FullRepository) -> Self {
+        match value.full_name.split_once('/') {
+            Some((owner, repo)) => Self::new(owner.into(), repo.into()),
+            None => Self::new(
+                value
+                    .owner
+                    .as_ref()
+                    .map(|o| o.login.clone())
+                    .unwrap_or_default(),
+                value.name.clone(),
+            ),
+        }
+    }
+}
+
 impl From<(String, String)> for GitHubRepositoryId {
     fn from(value: (String, String)) -> Self {
         Self {
@@ -43,6 +67,65 @@ impl From<(String, String)> for GitHubRepositoryId {
     }
 }
 
+/// The SPDX identifier GitHub uses for licenses it could not identify
+pub const NO_ASSERTION: &str = "NOASSERTION";
+
+/// Max number of releases retrieved for a repository; The max allowed by the
+/// GitHub API
+pub const RELEASES_PER_PAGE: i64 = 100;
+
+/// Max number of contributors retrieved for a repository; The max allowed by
+/// the GitHub API
+pub const CONTRIBUTORS_PER_PAGE: i64 = 100;
+
+/// The directory GitHub Actions workflows are defined in
+pub const WORKFLOWS_PATH: &str = ".github/workflows";
+
+/// The directories GitHub looks for a security policy (`SECURITY.md`) in
+pub const SECURITY_POLICY_DIRS: [&str; 3] = ["", ".github", "docs"];
+
+/// Max number of organizations retrieved for a user; The max allowed by the
+/// GitHub API
+pub const ORGANIZATIONS_PER_PAGE: i64 = 100;
+
+/// Max number of issues retrieved for a repository per label; The max
+/// allowed by the GitHub API
+pub const ISSUES_PER_PAGE: i64 = 100;
+
+/// A contributor to a GitHub repository
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHubContributor {
+    pub login: String,
+
+    /// The number of commits by this contributor
+    pub contributions: i64,
+
+    /// The share of commits by this contributor, out of all commits by the
+    /// contributors retrieved
+    pub share: f64,
+}
+
+impl GitHubContributor {
+    /// Creates contributors from those retrieved from GitHub, computing their
+    /// share of contributions
+    #[must_use]
+    pub fn from_contributors(contributors: &[Contributor]) -> Vec<Self> {
+        let total = contributors.iter().map(|c| c.contributions).sum::<i64>();
+        contributors
+            .iter()
+            .map(|c| Self {
+                login: c.login.clone(),
+                contributions: c.contributions,
+                share: if total > 0 {
+                    c.contributions as f64 / total as f64
+                } else {
+                    0.0
+                },
+            })
+            .collect()
+    }
+}
+
 /// Static global client used to connect to GitHub
 ///
 /// Will use an HTTP cache to only retrieve full API responses if the data has
@@ -76,6 +159,12 @@ static GITHUB_CLIENT: Lazy<octorust::Client> = Lazy::new(|| {
 static GITHUB_REPOS_CLIENT: Lazy<octorust::repos::Repos> =
     Lazy::new(|| octorust::repos::Repos::new(GITHUB_CLIENT.clone()));
 
+static GITHUB_ISSUES_CLIENT: Lazy<octorust::issues::Issues> =
+    Lazy::new(|| octorust::issues::Issues::new(GITHUB_CLIENT.clone()));
+
+static GITHUB_ORGS_CLIENT: Lazy<octorust::orgs::Orgs> =
+    Lazy::new(|| octorust::orgs::Orgs::new(GITHUB_CLIENT.clone()));
+
 static GITHUB_USERS_CLIENT: Lazy<octorust::users::Users> =
     Lazy::new(|| octorust::users::Users::new(GITHUB_CLIENT.clone()));
 
@@ -89,6 +178,20 @@ static GITHUB_RATE_LIMIT_CLIENT: Lazy<octorust::rate_limit::RateLimit> =
 pub struct GitHubClient {
     repo_cache: HashMap<GitHubRepositoryId, Arc<FullRepository>>,
     user_cache: HashMap<Arc<str>, Arc<PublicUser>>,
+    organization_cache: HashMap<Arc<str>, Arc<Vec<OrganizationSimple>>>,
+    release_cache: HashMap<GitHubRepositoryId, Arc<Vec<Release>>>,
+    contributor_cache: HashMap<GitHubRepositoryId, Arc<Vec<Contributor>>>,
+    contents_cache: HashMap<(GitHubRepositoryId, String), Arc<Vec<Entries>>>,
+    issue_cache: HashMap<
+        (GitHubRepositoryId, String, Option<String>),
+        Arc<Vec<IssueSimple>>,
+    >,
+    branch_cache:
+        HashMap<(GitHubRepositoryId, String), Arc<BranchWithProtection>>,
+
+    /// `None` if the token does not have access to the protection rules
+    protection_cache:
+        HashMap<(GitHubRepositoryId, String), Option<Arc<BranchProtection>>>,
 
     /// If the client is to await a new quota if the current one is emptied
     ///
@@ -112,6 +215,13 @@ impl GitHubClient {
         Self {
             repo_cache: HashMap::new(),
             user_cache: HashMap::new(),
+            organization_cache: HashMap::new(),
+            release_cache: HashMap::new(),
+            contributor_cache: HashMap::new(),
+            contents_cache: HashMap::new(),
+            issue_cache: HashMap::new(),
+            branch_cache: HashMap::new(),
+            protection_cache: HashMap::new(),
             await_quota,
         }
     }
@@ -282,6 +392,555 @@ impl GitHubClient {
             }
         }
     }
+
+    /// Retrieves the latest releases of a GitHub repository, newest first
+    ///
+    /// Only the first page of at most [`RELEASES_PER_PAGE`] releases is
+    /// retrieved. Uses the same caching as
+    /// [`GitHubClient::get_repository`].
+    pub fn get_releases(
+        &mut self,
+        id: &GitHubRepositoryId,
+    ) -> Option<Arc<Vec<Release>>> {
+        if let Some(r) = self.release_cache.get(id) {
+            Some(Arc::clone(r))
+        } else {
+            let future = GITHUB_REPOS_CLIENT.list_releases(
+                &id.owner,
+                &id.repo,
+                RELEASES_PER_PAGE,
+                1,
+            );
+
+            #[cfg(test)]
+            {
+                GH_API_CALL_COUNTER.inc();
+            }
+
+            // We just block until this resolves for now
+            match RUNTIME.block_on(future) {
+                Ok(r) => {
+                    let arcr = Arc::new(r);
+                    self.release_cache.insert(id.clone(), Arc::clone(&arcr));
+                    Some(arcr)
+                }
+                Err(e) => {
+                    if self.await_quota {
+                        // It is possible that we have reached a rate limit
+                        match self.await_new_quota() {
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: true,
+                            } => {
+                                // The quota was reached by this request, try again!
+                                return self.get_releases(id);
+                            }
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: false,
+                            } => {
+                                eprintln!("GitHub quota reached, but new could not be awaited");
+                            }
+                            _ => {}
+                        }
+                    }
+                    eprintln!("Failed to resolve GitHub releases of {}/{} due to error: {e}", id.owner, id.repo);
+                    None
+                }
+            }
+        }
+    }
+
+    /// Retrieves the contributors of a GitHub repository, with the most
+    /// commits first
+    ///
+    /// Only the first page of at most [`CONTRIBUTORS_PER_PAGE`] contributors
+    /// is retrieved. Uses the same caching as
+    /// [`GitHubClient::get_repository`].
+    pub fn get_contributors(
+        &mut self,
+        id: &GitHubRepositoryId,
+    ) -> Option<Arc<Vec<Contributor>>> {
+        if let Some(r) = self.contributor_cache.get(id) {
+            Some(Arc::clone(r))
+        } else {
+            let future = GITHUB_REPOS_CLIENT.list_contributors(
+                &id.owner,
+                &id.repo,
+                "",
+                CONTRIBUTORS_PER_PAGE,
+                1,
+            );
+
+            #[cfg(test)]
+            {
+                GH_API_CALL_COUNTER.inc();
+            }
+
+            // We just block until this resolves for now
+            match RUNTIME.block_on(future) {
+                Ok(r) => {
+                    let arcr = Arc::new(r);
+                    self.contributor_cache
+                        .insert(id.clone(), Arc::clone(&arcr));
+                    Some(arcr)
+                }
+                Err(e) => {
+                    if self.await_quota {
+                        // It is possible that we have reached a rate limit
+                        match self.await_new_quota() {
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: true,
+                            } => {
+                                // The quota was reached by this request, try again!
+                                return self.get_contributors(id);
+                            }
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: false,
+                            } => {
+                                eprintln!("GitHub quota reached, but new could not be awaited");
+                            }
+                            _ => {}
+                        }
+                    }
+                    eprintln!("Failed to resolve GitHub contributors of {}/{} due to error: {e}", id.owner, id.repo);
+                    None
+                }
+            }
+        }
+    }
+
+    /// Retrieves the organizations a GitHub user is a public member of
+    ///
+    /// Only the first page of at most [`ORGANIZATIONS_PER_PAGE`]
+    /// organizations is retrieved. Uses the same caching as
+    /// [`GitHubClient::get_repository`].
+    pub fn get_organizations(
+        &mut self,
+        username: &str,
+    ) -> Option<Arc<Vec<OrganizationSimple>>> {
+        if let Some(r) = self.organization_cache.get(username) {
+            Some(Arc::clone(r))
+        } else {
+            let future = GITHUB_ORGS_CLIENT.list_for_user(
+                username,
+                ORGANIZATIONS_PER_PAGE,
+                1,
+            );
+
+            #[cfg(test)]
+            {
+                GH_API_CALL_COUNTER.inc();
+            }
+
+            // We just block until this resolves for now
+            match RUNTIME.block_on(future) {
+                Ok(r) => {
+                    let arcr = Arc::new(r);
+                    self.organization_cache
+                        .insert(Arc::from(username), Arc::clone(&arcr));
+                    Some(arcr)
+                }
+                Err(e) => {
+                    if self.await_quota {
+                        // It is possible that we have reached a rate limit
+                        match self.await_new_quota() {
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: true,
+                            } => {
+                                // The quota was reached by this request, try again!
+                                return self.get_organizations(username);
+                            }
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: false,
+                            } => {
+                                eprintln!("GitHub quota reached, but new could not be awaited");
+                            }
+                            _ => {}
+                        }
+                    }
+                    eprintln!("Failed to resolve GitHub organizations of {username} due to error: {e}");
+                    None
+                }
+            }
+        }
+    }
+
+    /// Retrieves the latest issues of a GitHub repository in a `state`,
+    /// optionally with a label, newest first; Pull requests are not included
+    ///
+    /// Only the first page of at most [`ISSUES_PER_PAGE`] issues is
+    /// retrieved. Uses the same caching as
+    /// [`GitHubClient::get_repository`].
+    pub fn get_issues(
+        &mut self,
+        id: &GitHubRepositoryId,
+        state: IssuesListState,
+        label: Option<&str>,
+    ) -> Option<Arc<Vec<IssueSimple>>> {
+        let key = (id.clone(), state.to_string(), label.map(String::from));
+        if let Some(r) = self.issue_cache.get(&key) {
+            Some(Arc::clone(r))
+        } else {
+            let future = GITHUB_ISSUES_CLIENT.list_for_repo(
+                &id.owner,
+                &id.repo,
+                "",
+                state.clone(),
+                "",
+                "",
+                "",
+                label.unwrap_or_default(),
+                IssuesListSort::Created,
+                Order::Desc,
+                None,
+                ISSUES_PER_PAGE,
+                1,
+            );
+
+            #[cfg(test)]
+            {
+                GH_API_CALL_COUNTER.inc();
+            }
+
+            // We just block until this resolves for now
+            match RUNTIME.block_on(future) {
+                Ok(r) => {
+                    let issues = r
+                        .into_iter()
+                        .filter(|i| i.pull_request.is_none())
+                        .collect::<Vec<_>>();
+                    let arcr = Arc::new(issues);
+                    self.issue_cache.insert(key, Arc::clone(&arcr));
+                    Some(arcr)
+                }
+                Err(e) => {
+                    if self.await_quota {
+                        // It is possible that we have reached a rate limit
+                        match self.await_new_quota() {
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: true,
+                            } => {
+                                // The quota was reached by this request, try again!
+                                return self.get_issues(id, state, label);
+                            }
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: false,
+                            } => {
+                                eprintln!("GitHub quota reached, but new could not be awaited");
+                            }
+                            _ => {}
+                        }
+                    }
+                    eprintln!("Failed to resolve GitHub issues of {}/{} due to error: {e}", id.owner, id.repo);
+                    None
+                }
+            }
+        }
+    }
+
+    /// Retrieves the latest issues of a GitHub repository in a `state` with
+    /// any of `labels`, or all issues if `None`, newest first
+    ///
+    /// Makes one request per label, see [`GitHubClient::get_issues`].
+    pub fn get_issues_with_any_label(
+        &mut self,
+        id: &GitHubRepositoryId,
+        state: &IssuesListState,
+        labels: Option<&[String]>,
+    ) -> Option<Vec<IssueSimple>> {
+        let Some(labels) = labels else {
+            return Some(self.get_issues(id, state.clone(), None)?.to_vec());
+        };
+
+        let mut issues = BTreeMap::new();
+        for label in labels {
+            for issue in self.get_issues(id, state.clone(), Some(label))?.iter()
+            {
+                issues.entry(issue.number).or_insert_with(|| issue.clone());
+            }
+        }
+        Some(issues.into_values().rev().collect())
+    }
+
+    /// Retrieves the entries of a directory in a GitHub repository on its
+    /// default branch, where `""` is the root directory
+    ///
+    /// Returns an empty list if the directory does not exist. Uses the same
+    /// caching as [`GitHubClient::get_repository`].
+    pub fn get_directory(
+        &mut self,
+        id: &GitHubRepositoryId,
+        path: &str,
+    ) -> Option<Arc<Vec<Entries>>> {
+        let key = (id.clone(), path.to_string());
+        if let Some(r) = self.contents_cache.get(&key) {
+            Some(Arc::clone(r))
+        } else {
+            let future = GITHUB_REPOS_CLIENT
+                .get_content_vec_entries(&id.owner, &id.repo, path, "");
+
+            #[cfg(test)]
+            {
+                GH_API_CALL_COUNTER.inc();
+            }
+
+            // We just block until this resolves for now
+            match RUNTIME.block_on(future) {
+                Ok(r) => {
+                    let arcr = Arc::new(r);
+                    self.contents_cache.insert(key, Arc::clone(&arcr));
+                    Some(arcr)
+                }
+                // The API does not expose status codes, but includes them
+                // in the error message
+                Err(e) if e.to_string().starts_with("code: 404") => {
+                    let arcr = Arc::new(Vec::new());
+                    self.contents_cache.insert(key, Arc::clone(&arcr));
+                    Some(arcr)
+                }
+                Err(e) => {
+                    if self.await_quota {
+                        // It is possible that we have reached a rate limit
+                        match self.await_new_quota() {
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: true,
+                            } => {
+                                // The quota was reached by this request, try again!
+                                return self.get_directory(id, path);
+                            }
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: false,
+                            } => {
+                                eprintln!("GitHub quota reached, but new could not be awaited");
+                            }
+                            _ => {}
+                        }
+                    }
+                    eprintln!("Failed to resolve GitHub directory {path} of {}/{} due to error: {e}", id.owner, id.repo);
+                    None
+                }
+            }
+        }
+    }
+
+    /// Retrieves the GitHub Actions workflow files of a GitHub repository,
+    /// i.e. the YAML files in [`WORKFLOWS_PATH`] on the default branch
+    pub fn get_workflows(
+        &mut self,
+        id: &GitHubRepositoryId,
+    ) -> Option<Vec<Entries>> {
+        let entries = self.get_directory(id, WORKFLOWS_PATH)?;
+        Some(
+            entries
+                .iter()
+                .filter(|e| {
+                    e.type_ == "file"
+                        && (e.name.ends_with(".yml")
+                            || e.name.ends_with(".yaml"))
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Retrieves if a GitHub repository has a security policy, i.e. a
+    /// `SECURITY.md` file in one of [`SECURITY_POLICY_DIRS`]
+    ///
+    /// Makes up to one request per directory, stopping once a policy is
+    /// found.
+    pub fn get_has_security_policy(
+        &mut self,
+        id: &GitHubRepositoryId,
+    ) -> Option<bool> {
+        for dir in SECURITY_POLICY_DIRS {
+            let entries = self.get_directory(id, dir)?;
+            if entries.iter().any(|e| {
+                e.type_ == "file" && e.name.eq_ignore_ascii_case("SECURITY.md")
+            }) {
+                return Some(true);
+            }
+        }
+        Some(false)
+    }
+
+    /// Retrieves a branch of a GitHub repository, such as its default
+    /// branch, including its latest commit
+    ///
+    /// Uses the same caching as [`GitHubClient::get_repository`].
+    pub fn get_branch(
+        &mut self,
+        id: &GitHubRepositoryId,
+        branch: &str,
+    ) -> Option<Arc<BranchWithProtection>> {
+        let key = (id.clone(), branch.to_string());
+        if let Some(b) = self.branch_cache.get(&key) {
+            Some(Arc::clone(b))
+        } else {
+            let future =
+                GITHUB_REPOS_CLIENT.get_branch(&id.owner, &id.repo, branch);
+
+            #[cfg(test)]
+            {
+                GH_API_CALL_COUNTER.inc();
+            }
+
+            // We just block until this resolves for now
+            match RUNTIME.block_on(future) {
+                Ok(b) => {
+                    let arcb = Arc::new(b);
+                    self.branch_cache.insert(key, Arc::clone(&arcb));
+                    Some(arcb)
+                }
+                Err(e) => {
+                    if self.await_quota {
+                        // It is possible that we have reached a rate limit
+                        match self.await_new_quota() {
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: true,
+                            } => {
+                                // The quota was reached by this request, try again!
+                                return self.get_branch(id, branch);
+                            }
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: false,
+                            } => {
+                                eprintln!("GitHub quota reached, but new could not be awaited");
+                            }
+                            _ => {}
+                        }
+                    }
+                    eprintln!("Failed to resolve GitHub branch {branch} of {}/{} due to error: {e}", id.owner, id.repo);
+                    None
+                }
+            }
+        }
+    }
+
+    /// Retrieves the protection rules of a protected branch of a GitHub
+    /// repository
+    ///
+    /// Requires the token to have admin access to the repository, otherwise
+    /// `None` is returned (and cached).
+    /// Uses the same caching as [`GitHubClient::get_repository`].
+    pub fn get_branch_protection(
+        &mut self,
+        id: &GitHubRepositoryId,
+        branch: &str,
+    ) -> Option<Arc<BranchProtection>> {
+        let key = (id.clone(), branch.to_string());
+        if let Some(p) = self.protection_cache.get(&key) {
+            p.clone()
+        } else {
+            let future = GITHUB_REPOS_CLIENT
+                .get_branch_protection(&id.owner, &id.repo, branch);
+
+            #[cfg(test)]
+            {
+                GH_API_CALL_COUNTER.inc();
+            }
+
+            // We just block until this resolves for now
+            match RUNTIME.block_on(future) {
+                Ok(p) => {
+                    let arcp = Arc::new(p);
+                    self.protection_cache.insert(key, Some(Arc::clone(&arcp)));
+                    Some(arcp)
+                }
+                // The API does not expose status codes, but includes them
+                // in the error message
+                Err(e)
+                    if e.to_string().starts_with("code: 403")
+                        || e.to_string().starts_with("code: 404") =>
+                {
+                    self.protection_cache.insert(key, None);
+                    None
+                }
+                Err(e) => {
+                    if self.await_quota {
+                        // It is possible that we have reached a rate limit
+                        match self.await_new_quota() {
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: true,
+                            } => {
+                                // The quota was reached by this request, try again!
+                                return self.get_branch_protection(id, branch);
+                            }
+                            AwaitQuotaResult::QuotaAwaited {
+                                success: false,
+                            } => {
+                                eprintln!("GitHub quota reached, but new could not be awaited");
+                            }
+                            _ => {}
+                        }
+                    }
+                    eprintln!("Failed to resolve GitHub protection of branch {branch} of {}/{} due to error: {e}", id.owner, id.repo);
+                    None
+                }
+            }
+        }
+    }
+
+    /// Retrieves when the latest commit on the default branch of a GitHub
+    /// repository was committed, as a Unix timestamp
+    pub fn get_last_commit_at(
+        &mut self,
+        repository: &FullRepository,
+    ) -> Option<i64> {
+        let branch = self.get_branch(
+            &GitHubRepositoryId::from(repository),
+            &repository.default_branch,
+        )?;
+        let committer = branch.commit.commit.committer.as_ref()?;
+        chrono::DateTime::parse_from_rfc3339(&committer.date)
+            .ok()
+            .map(|d| d.timestamp())
+    }
+
+    /// Retrieves if the default branch of a GitHub repository is protected
+    pub fn get_default_branch_protected(
+        &mut self,
+        repository: &FullRepository,
+    ) -> Option<bool> {
+        let branch = self.get_branch(
+            &GitHubRepositoryId::from(repository),
+            &repository.default_branch,
+        )?;
+        Some(branch.protected)
+    }
+
+    /// Retrieves if pull requests to the default branch of a GitHub
+    /// repository require at least one approving review
+    ///
+    /// `None` if the branch is protected, but the token does not have access
+    /// to its protection rules.
+    pub fn get_default_branch_requires_reviews(
+        &mut self,
+        repository: &FullRepository,
+    ) -> Option<bool> {
+        let id = GitHubRepositoryId::from(repository);
+        let branch = self.get_branch(&id, &repository.default_branch)?;
+        if !branch.protected {
+            return Some(false);
+        }
+        let protection =
+            self.get_branch_protection(&id, &repository.default_branch)?;
+        Some(
+            protection
+                .required_pull_request_reviews
+                .as_ref()
+                .is_some_and(|r| r.required_approving_review_count > 0),
+        )
+    }
+}
+
+/// The SPDX identifier of the license GitHub detects in a repository, if
+/// any was found and identified
+#[must_use]
+pub fn license_spdx_id(repository: &FullRepository) -> Option<&str> {
+    repository
+        .license
+        .as_ref()
+        .map(|l| l.spdx_id.as_str())
+        .filter(|id| !id.is_empty() && *id != NO_ASSERTION)
 }
 
 impl Default for GitHubClient {
@@ -0,0 +1,156 @@
+//! Client for retrieving repositories hosted on [GitLab](https://gitlab.com)
+//!
+//! Public projects can be retrieved without a token, but if the
+//! `GITLAB_API_TOKEN` environment variable is set, it is used to raise the
+//! rate limit and to access private projects.
+//!
+//! See [the GitLab REST API documentation](https://docs.gitlab.com/ee/api/projects.html)
+//! for more information.
+
+use std::rc::Rc;
+
+use chrono::DateTime;
+use serde::Deserialize;
+use url::form_urlencoded::byte_serialize;
+
+use crate::http::{self, ResponseCache};
+
+/// Base URL of the GitLab API, taking a project ID or its URL-encoded path
+pub const GITLAB_API_URL: &str = "https://gitlab.com/api/v4/projects";
+
+/// Environment variable containing an optional GitLab personal access token
+pub const GITLAB_API_TOKEN_ENV: &str = "GITLAB_API_TOKEN";
+
+/// Identifies a GitLab project using its full path, such as
+/// `gitlab-org/gitlab` or `group/subgroup/project`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GitLabProjectId {
+    path: String,
+}
+
+impl GitLabProjectId {
+    #[must_use]
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+}
+
+/// A GitLab project, as returned by the GitLab API, some fields omitted
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GitLabProject {
+    pub name: String,
+
+    /// Such as `gitlab-org/gitlab`
+    pub path_with_namespace: String,
+    pub web_url: String,
+    pub star_count: u64,
+    pub forks_count: u64,
+
+    /// Not returned if the issues of this project are disabled
+    pub open_issues_count: Option<u64>,
+    pub archived: bool,
+
+    /// When the project was last active, such as when something was pushed
+    /// or an issue was commented on; RFC 3339
+    pub last_activity_at: String,
+
+    /// The user or group owning the project
+    pub namespace: GitLabNamespace,
+}
+
+/// The user or group owning a GitLab project
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GitLabNamespace {
+    pub name: String,
+
+    /// Such as `gitlab-org` or `group/subgroup`
+    pub full_path: String,
+
+    /// `user` or `group`
+    pub kind: String,
+    pub web_url: String,
+}
+
+impl GitLabProject {
+    /// When the project was last active as a Unix timestamp, `None` if it
+    /// could not be parsed
+    #[must_use]
+    pub fn unix_last_activity_at(&self) -> Option<i64> {
+        DateTime::parse_from_rfc3339(&self.last_activity_at)
+            .ok()
+            .map(|t| t.timestamp())
+    }
+}
+
+/// Wrapper around a [`reqwest::Client`] querying the GitLab API, with caching
+///
+/// Uses the token in `GITLAB_API_TOKEN` if set.
+#[derive(Debug, Clone)]
+pub struct GitLabClient {
+    client: reqwest::Client,
+    token: Option<String>,
+
+    /// Cache between a project and its information
+    cache: ResponseCache<GitLabProjectId, Rc<GitLabProject>>,
+}
+
+impl Default for GitLabClient {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::default(),
+            token: std::env::var(GITLAB_API_TOKEN_ENV).ok(),
+            cache: ResponseCache::default(),
+        }
+    }
+}
+
+impl GitLabClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves a GitLab project
+    ///
+    /// Will return `None` if the request fails, such as if the project does
+    /// not exist or is private, and will cache this project as such.
+    pub fn get_project(
+        &mut self,
+        id: &GitLabProjectId,
+    ) -> Option<Rc<GitLabProject>> {
+        self.cache.get_or_fetch(id, || {
+            let path = byte_serialize(id.path.as_bytes()).collect::<String>();
+            let mut req = self.client.get(format!("{GITLAB_API_URL}/{path}"));
+            if let Some(token) = &self.token {
+                req = req.header("PRIVATE-TOKEN", token);
+            }
+            http::send_json::<GitLabProject>(
+                req,
+                &format!("GitLab project {}", id.path),
+            )
+            .map(Rc::new)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::GitLabProject;
+
+    #[test]
+    fn deserialize_gitlab_project() {
+        let json_string =
+            fs::read_to_string("test_data/gitlab-output/rfid-rs.json").unwrap();
+        let project =
+            serde_json::from_str::<GitLabProject>(&json_string).unwrap();
+
+        assert_eq!(project.path_with_namespace, "jspngh/rfid-rs");
+        assert_eq!(project.web_url, "https://gitlab.com/jspngh/rfid-rs");
+        assert_eq!(project.open_issues_count, Some(1));
+        assert!(!project.archived);
+        assert_eq!(project.unix_last_activity_at(), Some(1_672_313_417));
+        assert_eq!(project.namespace.kind, "user");
+    }
+}
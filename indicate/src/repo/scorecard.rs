@@ -0,0 +1,147 @@
+//! Client for retrieving [OpenSSF Scorecards](https://scorecard.dev) of
+//! GitHub repositories
+//!
+//! The OpenSSF Scorecard rates the security practices of a repository, such
+//! as branch protection, pinned dependencies and dangerous GitHub workflows,
+//! using a number of checks scored between 0 and 10. Scorecards of popular
+//! repositories are computed weekly and made available through a public API.
+//!
+//! See [the Scorecard API documentation](https://api.securityscorecards.dev)
+//! for more information.
+
+use std::rc::Rc;
+
+use serde::Deserialize;
+
+use crate::http::{self, ResponseCache};
+
+/// Base URL of the Scorecard API, taking a project such as
+/// `github.com/ossf/scorecard`
+pub const SCORECARD_API_URL: &str =
+    "https://api.securityscorecards.dev/projects";
+
+/// Base URL of the Scorecard viewer, taking a project such as
+/// `github.com/ossf/scorecard`
+pub const SCORECARD_VIEWER_URL: &str =
+    "https://securityscorecards.dev/viewer/?uri=";
+
+/// The Scorecard of a repository, some fields omitted
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Scorecard {
+    /// When the repository was scored, such as `2024-01-15`
+    pub date: String,
+    pub repo: ScorecardRepo,
+
+    /// The overall score between 0 and 10, weighted by the risk of each
+    /// check
+    pub score: f64,
+    pub checks: Vec<ScorecardCheck>,
+}
+
+/// The repository and commit that was scored
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ScorecardRepo {
+    /// Such as `github.com/ossf/scorecard`
+    pub name: String,
+    pub commit: Option<String>,
+}
+
+/// A single check of a Scorecard, such as `Branch-Protection`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ScorecardCheck {
+    pub name: String,
+
+    /// Between 0 and 10, or -1 if the check was inconclusive
+    pub score: i64,
+    pub reason: String,
+    pub documentation: Option<ScorecardCheckDocumentation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ScorecardCheckDocumentation {
+    pub url: Option<String>,
+}
+
+impl Scorecard {
+    /// Link to this Scorecard in the Scorecard viewer
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("{SCORECARD_VIEWER_URL}{}", self.repo.name)
+    }
+}
+
+impl ScorecardCheck {
+    /// The score of the check, `None` if it was inconclusive
+    #[must_use]
+    pub fn conclusive_score(&self) -> Option<i64> {
+        (self.score >= 0).then_some(self.score)
+    }
+}
+
+/// Wrapper around a [`reqwest::Client`] querying the Scorecard API, with
+/// caching
+#[derive(Debug, Clone, Default)]
+pub struct ScorecardClient {
+    client: reqwest::Client,
+
+    /// Cache between the full name of a GitHub repository, such as
+    /// `ossf/scorecard`, and its Scorecard
+    cache: ResponseCache<String, Rc<Scorecard>>,
+}
+
+impl ScorecardClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves the Scorecard of a GitHub repository using its full name,
+    /// such as `ossf/scorecard`
+    ///
+    /// Will return `None` if the request fails, such as if the repository
+    /// has not been scored, and will cache this repository as such.
+    pub fn scorecard(&mut self, full_name: &str) -> Option<Rc<Scorecard>> {
+        self.cache.get_or_fetch(full_name, || {
+            let url = format!("{SCORECARD_API_URL}/github.com/{full_name}");
+            http::send_json::<Scorecard>(
+                self.client.get(&url),
+                &format!("OpenSSF Scorecard for {full_name}"),
+            )
+            .map(Rc::new)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::Scorecard;
+
+    #[test]
+    fn deserialize_scorecard() {
+        let json_string = fs::read_to_string(
+            "test_data/scorecard-output/ossf-scorecard.json",
+        )
+        .unwrap();
+        let scorecard =
+            serde_json::from_str::<Scorecard>(&json_string).unwrap();
+
+        assert_eq!(
+            scorecard.url(),
+            "https://securityscorecards.dev/viewer/?uri=github.com/ossf/scorecard"
+        );
+        assert_eq!(
+            scorecard
+                .checks
+                .iter()
+                .map(|c| (c.name.as_str(), c.conclusive_score()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Branch-Protection", Some(8)),
+                ("Dangerous-Workflow", Some(10)),
+                ("Signed-Releases", None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Client for retrieving repositories hosted on [sourcehut](https://sr.ht)
+//!
+//! Repositories on `git.sr.ht` are recognized from their URL alone, but
+//! retrieving anything else, such as the latest commit, uses the GraphQL API
+//! of `git.sr.ht`, which requires a personal access token in the
+//! `SRHT_API_TOKEN` environment variable.
+//!
+//! See [the sourcehut API documentation](https://man.sr.ht/graphql.md) for
+//! more information.
+
+use std::rc::Rc;
+
+use chrono::DateTime;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::http::{self, ResponseCache};
+
+/// The host of sourcehut git repositories
+pub const SOURCEHUT_GIT_HOST: &str = "git.sr.ht";
+
+/// The GraphQL API of `git.sr.ht`
+pub const SOURCEHUT_API_URL: &str = "https://git.sr.ht/query";
+
+/// Environment variable containing a sourcehut personal access token
+pub const SOURCEHUT_API_TOKEN_ENV: &str = "SRHT_API_TOKEN";
+
+/// Retrieves the latest commit of a repository
+const LAST_COMMIT_QUERY: &str =
+    "query lastCommit($owner: String!, $repo: String!) {
+    user(username: $owner) {
+        repository(name: $repo) {
+            log { results { committer { time } } }
+        }
+    }
+}";
+
+/// A repository on `git.sr.ht`, identified by its owner and name
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourcehutRepository {
+    /// The username of the owner, without the leading `~`
+    pub owner: String,
+    pub name: String,
+
+    /// Link to the repository, such as `https://git.sr.ht/~sircmpwn/hare`
+    pub url: String,
+}
+
+impl SourcehutRepository {
+    #[must_use]
+    pub fn new(owner: String, name: String) -> Self {
+        let url = format!("https://{SOURCEHUT_GIT_HOST}/~{owner}/{name}");
+        Self { owner, name, url }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LastCommitResponse {
+    data: Option<LastCommitData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LastCommitData {
+    user: Option<LastCommitUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LastCommitUser {
+    repository: Option<LastCommitRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LastCommitRepository {
+    log: LastCommitLog,
+}
+
+#[derive(Debug, Deserialize)]
+struct LastCommitLog {
+    results: Vec<LastCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LastCommit {
+    committer: LastCommitSignature,
+}
+
+#[derive(Debug, Deserialize)]
+struct LastCommitSignature {
+    /// RFC 3339
+    time: String,
+}
+
+impl LastCommitResponse {
+    /// When the latest commit was committed as a Unix timestamp, `None` if
+    /// the repository was not found or has no commits
+    fn unix_last_commit_at(&self) -> Option<i64> {
+        let time = &self
+            .data
+            .as_ref()?
+            .user
+            .as_ref()?
+            .repository
+            .as_ref()?
+            .log
+            .results
+            .first()?
+            .committer
+            .time;
+        DateTime::parse_from_rfc3339(time)
+            .ok()
+            .map(|t| t.timestamp())
+    }
+}
+
+/// Wrapper around a [`reqwest::Client`] querying the `git.sr.ht` API, with
+/// caching
+///
+/// Uses the token in `SRHT_API_TOKEN` if set.
+#[derive(Debug, Clone)]
+pub struct SourcehutClient {
+    client: reqwest::Client,
+    token: Option<String>,
+
+    /// Cache between a repository and when its latest commit was committed
+    last_commit_cache: ResponseCache<Rc<SourcehutRepository>, i64>,
+}
+
+impl Default for SourcehutClient {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::default(),
+            token: std::env::var(SOURCEHUT_API_TOKEN_ENV).ok(),
+            last_commit_cache: ResponseCache::default(),
+        }
+    }
+}
+
+impl SourcehutClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves when the latest commit on the default branch of a
+    /// repository was committed, as a Unix timestamp
+    ///
+    /// Will return `None` without making a request if no token is set, and
+    /// `None` if the request fails, in which case the repository is cached
+    /// as such.
+    pub fn get_last_commit_at(
+        &mut self,
+        repo: &Rc<SourcehutRepository>,
+    ) -> Option<i64> {
+        let token = self.token.as_ref()?;
+        self.last_commit_cache.get_or_fetch(repo, || {
+            let body = json!({
+                "query": LAST_COMMIT_QUERY,
+                "variables": { "owner": repo.owner, "repo": repo.name },
+            });
+            http::send_json::<LastCommitResponse>(
+                self.client
+                    .post(SOURCEHUT_API_URL)
+                    .bearer_auth(token)
+                    .json(&body),
+                &format!(
+                    "latest commit of sourcehut repository ~{}/{}",
+                    repo.owner, repo.name
+                ),
+            )?
+            .unix_last_commit_at()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use test_case::test_case;
+
+    use super::LastCommitResponse;
+
+    #[test_case("last-commit.json" => Some(1_717_072_496) ; "repository with commits")]
+    #[test_case("missing-repository.json" => None ; "missing repository")]
+    fn last_commit_at(file_name: &str) -> Option<i64> {
+        let json_string = fs::read_to_string(format!(
+            "test_data/sourcehut-output/{file_name}"
+        ))
+        .unwrap();
+        serde_json::from_str::<LastCommitResponse>(&json_string)
+            .unwrap()
+            .unix_last_commit_at()
+    }
+}
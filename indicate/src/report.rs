@@ -0,0 +1,295 @@
+//! High-level facade for generating reports about a package and its
+//! dependencies
+//!
+//! [`generate`] bundles adapter construction, a set of queries and formatting
+//! of the results into one call, so tools embedding `indicate` do not need to
+//! know about Trustfall or the schema. Unless other queries are provided in
+//! the [`ReportConfig`], the [`standard_queries`] are used.
+
+use std::{collections::BTreeMap, fmt::Write, rc::Rc, sync::Arc};
+
+use serde::Serialize;
+use trustfall::TransparentValue;
+
+use crate::{
+    advisory::AdvisoryClient,
+    execute_query_with_adapter,
+    humanize::{render_value, ValueStyle},
+    query::{FullQuery, FullQueryBuilder},
+    util::transparent_results,
+    CargoOpt, IndicateAdapterBuilder, ManifestPath,
+};
+
+/// Name and GraphQL query of the queries included in a standard report
+const STANDARD_QUERIES: [(&str, &str); 2] = [
+    (
+        "dependencies",
+        r#"{
+    Dependencies(includeRoot: false) {
+        name @output
+        version @output
+        license @output
+    }
+}"#,
+    ),
+    (
+        "advisories",
+        r#"{
+    Dependencies(includeRoot: false) {
+        name @output
+        version @output
+        advisoryHistory(includeWithdrawn: false) {
+            id @output(name: "advisoryId")
+            title @output(name: "advisoryTitle")
+            severity @output(name: "advisorySeverity")
+        }
+    }
+}"#,
+    ),
+];
+
+/// Retrieves the queries used in a report if no other queries are provided,
+/// together with their names
+#[must_use]
+pub fn standard_queries() -> Vec<(String, FullQuery)> {
+    STANDARD_QUERIES
+        .iter()
+        .map(|(name, query)| {
+            (
+                (*name).to_string(),
+                FullQueryBuilder::new((*query).to_string()).build(),
+            )
+        })
+        .collect()
+}
+
+/// Format used when rendering a [`Report`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    #[default]
+    Json,
+
+    /// One table per query
+    Markdown,
+}
+
+/// Configuration of a report created using [`generate`]
+#[derive(Debug, Default)]
+pub struct ReportConfig {
+    /// Features used when resolving metadata, see
+    /// [`IndicateAdapterBuilder::features`]
+    pub features: Vec<CargoOpt>,
+
+    /// Named queries to include in the report; if empty, the
+    /// [`standard_queries`] are used
+    pub queries: Vec<(String, FullQuery)>,
+
+    /// The max number of results for each query
+    pub max_results: Option<usize>,
+
+    /// Client used to resolve advisories; fetched lazily if not set
+    pub advisory_client: Option<AdvisoryClient>,
+
+    pub format: ReportFormat,
+
+    /// How values are rendered in human-readable formats
+    pub style: ValueStyle,
+}
+
+/// The results of one query in a [`Report`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportSection {
+    pub name: String,
+    pub results: Vec<BTreeMap<Arc<str>, TransparentValue>>,
+}
+
+/// The results of all queries in a report, in the order they were provided
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub sections: Vec<ReportSection>,
+}
+
+impl Report {
+    /// Renders this report in the format provided
+    ///
+    /// `style` is only used by human-readable formats, such as Markdown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the report cannot be serialized to JSON.
+    #[must_use]
+    pub fn render(&self, format: ReportFormat, style: ValueStyle) -> String {
+        match format {
+            ReportFormat::Json => serde_json::to_string_pretty(self)
+                .expect("could not serialize report"),
+            ReportFormat::Markdown => self.to_markdown(style),
+        }
+    }
+
+    /// Renders one table per section, using the output names of the first
+    /// result as columns
+    fn to_markdown(&self, style: ValueStyle) -> String {
+        let mut md = String::new();
+        for section in &self.sections {
+            let _ = writeln!(md, "## {}\n", section.name);
+
+            let Some(first) = section.results.first() else {
+                md.push_str("_No results_\n\n");
+                continue;
+            };
+
+            let columns = first.keys().collect::<Vec<_>>();
+            let _ = writeln!(
+                md,
+                "| {} |",
+                columns
+                    .iter()
+                    .map(|c| c.as_ref())
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            );
+            let _ = writeln!(md, "|{}", " --- |".repeat(columns.len()));
+
+            for row in &section.results {
+                let cells = columns
+                    .iter()
+                    .map(|c| {
+                        row.get(*c)
+                            .map(|v| markdown_cell(c, v, style))
+                            .unwrap_or_default()
+                    })
+                    .collect::<Vec<_>>();
+                let _ = writeln!(md, "| {} |", cells.join(" | "));
+            }
+            md.push('\n');
+        }
+        md
+    }
+}
+
+/// Renders a single value of a column so that it fits in a Markdown table
+/// cell
+fn markdown_cell(
+    column: &str,
+    value: &TransparentValue,
+    style: ValueStyle,
+) -> String {
+    if let Some(rendered) = render_value(column, value, style) {
+        return rendered;
+    }
+    let s = match value {
+        TransparentValue::Null => String::new(),
+        TransparentValue::String(s) | TransparentValue::Enum(s) => s.clone(),
+        v => serde_json::to_string(v).unwrap_or_default(),
+    };
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Runs all queries of a report against the package at `manifest_path`,
+/// reusing the same adapter for all queries
+///
+/// # Panics
+///
+/// Panics if metadata cannot be resolved, or if a query cannot be executed.
+#[must_use]
+pub fn generate_report(
+    manifest_path: ManifestPath,
+    config: ReportConfig,
+) -> Report {
+    let mut b = IndicateAdapterBuilder::new(manifest_path);
+    if !config.features.is_empty() {
+        b = b.features(config.features);
+    }
+    if let Some(ac) = config.advisory_client {
+        b = b.advisory_client(ac);
+    }
+    let adapter = Rc::new(b.build());
+
+    let queries = if config.queries.is_empty() {
+        standard_queries()
+    } else {
+        config.queries
+    };
+
+    let sections = queries
+        .into_iter()
+        .map(|(name, query)| ReportSection {
+            name,
+            results: transparent_results(execute_query_with_adapter(
+                &query,
+                Rc::clone(&adapter),
+                config.max_results,
+            )),
+        })
+        .collect();
+
+    Report { sections }
+}
+
+/// Generates a report and renders it using the format of `config`
+///
+/// See [`generate_report`] if the results are to be handled by the caller.
+///
+/// # Panics
+///
+/// Panics if metadata cannot be resolved, or if a query cannot be executed.
+#[must_use]
+pub fn generate(manifest_path: ManifestPath, config: ReportConfig) -> String {
+    let (format, style) = (config.format, config.style);
+    generate_report(manifest_path, config).render(format, style)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, path::Path};
+
+    use test_case::test_case;
+    use trustfall::TransparentValue;
+
+    use crate::{humanize::ValueStyle, query::FullQuery, ManifestPath};
+
+    use super::{generate, Report, ReportConfig, ReportFormat, ReportSection};
+
+    #[test_case(ReportFormat::Json, "\"name\": \"libc\"")]
+    #[test_case(ReportFormat::Markdown, "| name | version |\n| --- | --- |")]
+    fn generate_report_with_custom_query(format: ReportFormat, expected: &str) {
+        let query = FullQuery::from_path(Path::new(
+            "test_data/queries/dependencies_all_fields.in.ron",
+        ))
+        .unwrap();
+        let config = ReportConfig {
+            queries: vec![(String::from("all_fields"), query)],
+            format,
+            ..Default::default()
+        };
+
+        let report = generate(
+            ManifestPath::from("test_data/fake_crates/simple_deps"),
+            config,
+        );
+        assert!(report.contains(expected), "unexpected report: {report}");
+    }
+
+    #[test_case(ValueStyle::Human => "| name | totalDownloads |\n| --- | --- |\n| libc | 1.2M |\n" ; "human")]
+    #[test_case(ValueStyle::Raw => "| name | totalDownloads |\n| --- | --- |\n| libc | 1234567 |\n" ; "raw")]
+    fn markdown_value_style(style: ValueStyle) -> String {
+        let report = Report {
+            sections: vec![ReportSection {
+                name: String::from("downloads"),
+                results: vec![BTreeMap::from([
+                    ("name".into(), TransparentValue::String("libc".into())),
+                    (
+                        "totalDownloads".into(),
+                        TransparentValue::Uint64(1_234_567),
+                    ),
+                ])],
+            }],
+        };
+        report
+            .render(ReportFormat::Markdown, style)
+            .trim_start_matches("## downloads\n\n")
+            .trim_end()
+            .to_string()
+            + "\n"
+    }
+}
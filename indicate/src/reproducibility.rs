@@ -0,0 +1,265 @@
+//! Report on whether a build of a package can be reproduced from its lock file
+//!
+//! Used for release-engineering sign-off; A build is considered reproducible
+//! if all registry packages in `Cargo.lock` have a checksum, and all git
+//! dependencies are locked to a commit. Path dependencies cannot be verified
+//! using the lock file, and are listed so that they can be reviewed manually.
+use std::{
+    collections::BTreeMap, error::Error, fmt::Write, fs, rc::Rc, sync::Arc,
+};
+
+use cargo_metadata::CargoOpt;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use trustfall::TransparentValue;
+
+use crate::{
+    execute_query_with_adapter,
+    humanize::ValueStyle,
+    lockfile::Lockfile,
+    query::FullQueryBuilder,
+    report::{Report, ReportFormat, ReportSection},
+    util::transparent_results,
+    IndicateAdapterBuilder, ManifestPath,
+};
+
+/// Dependencies retrieved using git, and the commit they are locked to
+const GIT_DEPENDENCIES_QUERY: &str = r#"{
+    Dependencies(includeRoot: false) {
+        name @output
+        version @output
+        sourceKind @filter(op: "=", value: ["$git"])
+        gitSource {
+            url @output
+            rev @output
+            branch @output
+            tag @output
+        }
+    }
+}"#;
+
+/// Dependencies retrieved from a local path, including workspace members
+const PATH_DEPENDENCIES_QUERY: &str = r#"{
+    Dependencies(includeRoot: false) {
+        name @output
+        version @output
+        sourceKind @filter(op: "=", value: ["$path"])
+        manifestPath @output
+    }
+}"#;
+
+/// Checksum coverage of a `Cargo.lock` file
+#[derive(Debug, Clone, Serialize)]
+pub struct LockfileSummary {
+    pub path: String,
+
+    /// SHA-256 hash of the lock file itself, to pin the exact lock file that
+    /// was signed off
+    pub sha256: String,
+
+    /// Number of packages retrieved from a registry
+    pub registry_packages: usize,
+
+    /// Number of registry packages with a checksum
+    pub with_checksum: usize,
+
+    /// Registry packages without a checksum, as `name version`
+    pub missing_checksums: Vec<String>,
+}
+
+impl LockfileSummary {
+    fn new(path: String, raw_lockfile: &str, lockfile: &Lockfile) -> Self {
+        let registry_packages = lockfile
+            .package
+            .iter()
+            .filter(|p| p.is_from_registry())
+            .collect::<Vec<_>>();
+        let missing_checksums = registry_packages
+            .iter()
+            .filter(|p| p.checksum.is_none())
+            .map(|p| format!("{} {}", p.name, p.version))
+            .collect::<Vec<_>>();
+
+        Self {
+            path,
+            sha256: format!("{:x}", Sha256::digest(raw_lockfile.as_bytes())),
+            registry_packages: registry_packages.len(),
+            with_checksum: registry_packages.len() - missing_checksums.len(),
+            missing_checksums,
+        }
+    }
+}
+
+/// Lock file integrity and dependencies that cannot be fully verified using
+/// the lock file
+#[derive(Debug, Clone, Serialize)]
+pub struct ReproducibilityReport {
+    /// `None` if there is no `Cargo.lock` file
+    pub lockfile: Option<LockfileSummary>,
+
+    /// Dependencies retrieved using git; Those without a `rev` are not locked
+    /// to a commit
+    pub git_dependencies: ReportSection,
+
+    /// Dependencies retrieved from a local path
+    pub path_dependencies: ReportSection,
+}
+
+impl ReproducibilityReport {
+    /// Git dependencies not locked to a commit
+    #[must_use]
+    pub fn unlocked_git_dependencies(
+        &self,
+    ) -> Vec<&BTreeMap<Arc<str>, TransparentValue>> {
+        self.git_dependencies
+            .results
+            .iter()
+            .filter(|r| {
+                matches!(r.get("rev"), None | Some(TransparentValue::Null))
+            })
+            .collect()
+    }
+
+    /// If there is a lock file, all of its registry packages have a checksum,
+    /// and all git dependencies are locked to a commit
+    #[must_use]
+    pub fn is_reproducible(&self) -> bool {
+        self.lockfile
+            .as_ref()
+            .is_some_and(|l| l.missing_checksums.is_empty())
+            && self.unlocked_git_dependencies().is_empty()
+    }
+
+    /// Renders this report in the format provided
+    ///
+    /// `style` is only used by human-readable formats, such as Markdown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the report cannot be serialized to JSON.
+    #[must_use]
+    pub fn render(&self, format: ReportFormat, style: ValueStyle) -> String {
+        match format {
+            ReportFormat::Json => serde_json::to_string_pretty(self)
+                .expect("could not serialize report"),
+            ReportFormat::Markdown => self.to_markdown(style),
+        }
+    }
+
+    fn to_markdown(&self, style: ValueStyle) -> String {
+        let mut md = String::from("## Lock file\n\n");
+        match &self.lockfile {
+            Some(l) => {
+                let _ = writeln!(md, "- Path: `{}`", l.path);
+                let _ = writeln!(md, "- SHA-256: `{}`", l.sha256);
+                let _ = writeln!(
+                    md,
+                    "- Checksums: {} of {} registry packages",
+                    l.with_checksum, l.registry_packages
+                );
+                for missing in &l.missing_checksums {
+                    let _ = writeln!(md, "- Missing checksum: `{missing}`");
+                }
+            }
+            None => md.push_str("- No `Cargo.lock` found\n"),
+        }
+        let _ = writeln!(
+            md,
+            "- Reproducible: {}\n",
+            if self.is_reproducible() { "yes" } else { "no" }
+        );
+
+        let sections = Report {
+            sections: vec![
+                self.git_dependencies.clone(),
+                self.path_dependencies.clone(),
+            ],
+        };
+        md.push_str(&sections.render(ReportFormat::Markdown, style));
+        md
+    }
+}
+
+/// Summarizes the lock file of a package, and queries its git and path
+/// dependencies
+///
+/// # Errors
+///
+/// Returns an error variant if metadata cannot be resolved, or if the lock
+/// file exists but cannot be parsed.
+pub fn reproducibility_report(
+    manifest_path: ManifestPath,
+    features: Vec<CargoOpt>,
+) -> Result<ReproducibilityReport, Box<dyn Error>> {
+    let metadata = manifest_path.metadata(features)?;
+
+    let lockfile_path = metadata.workspace_root.join("Cargo.lock");
+    let lockfile = if lockfile_path.is_file() {
+        let raw_lockfile = fs::read_to_string(&lockfile_path)?;
+        let lockfile = Lockfile::parse(&raw_lockfile)?;
+        Some(LockfileSummary::new(
+            lockfile_path.to_string(),
+            &raw_lockfile,
+            &lockfile,
+        ))
+    } else {
+        None
+    };
+
+    let adapter = Rc::new(
+        IndicateAdapterBuilder::new(manifest_path)
+            .metadata(metadata)
+            .build(),
+    );
+    // Each query filters on a variable named after the source kind
+    let section = |name: &str, query: &str, source_kind: &str| {
+        let args = BTreeMap::from([(
+            Arc::from(source_kind),
+            TransparentValue::String(source_kind.into()),
+        )]);
+        let query = FullQueryBuilder::new(query.to_string()).args(args).build();
+        ReportSection {
+            name: name.to_string(),
+            results: transparent_results(execute_query_with_adapter(
+                &query,
+                Rc::clone(&adapter),
+                None,
+            )),
+        }
+    };
+
+    Ok(ReproducibilityReport {
+        lockfile,
+        git_dependencies: section(
+            "git dependencies",
+            GIT_DEPENDENCIES_QUERY,
+            "git",
+        ),
+        path_dependencies: section(
+            "path dependencies",
+            PATH_DEPENDENCIES_QUERY,
+            "path",
+        ),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::reproducibility_report;
+    use crate::ManifestPath;
+
+    #[test]
+    fn registry_dependencies_are_reproducible() {
+        let report = reproducibility_report(
+            ManifestPath::from("test_data/fake_crates/simple_deps"),
+            Vec::new(),
+        )
+        .unwrap();
+        let lockfile = report.lockfile.as_ref().unwrap();
+        assert!(lockfile.registry_packages > 0);
+        assert!(lockfile.missing_checksums.is_empty());
+        assert_eq!(lockfile.sha256.len(), 64);
+        assert!(report.git_dependencies.results.is_empty());
+        assert!(report.is_reproducible());
+    }
+}
@@ -0,0 +1,62 @@
+//! Dependencies as declared in the manifest of a package, before they are
+//! resolved to a specific package
+use std::rc::Rc;
+
+use cargo_metadata::{Dependency, DependencyKind, Package};
+
+/// A dependency declared by a package, such as `syn = "1.0"`
+#[derive(Debug, Clone)]
+pub struct DependencyRequirement {
+    /// The package declaring this dependency
+    pub package: Rc<Package>,
+    pub dependency: Dependency,
+}
+
+impl DependencyRequirement {
+    /// The name used for the dependency in the declaring package, i.e. the
+    /// new name if renamed
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.dependency
+            .rename
+            .as_deref()
+            .unwrap_or(&self.dependency.name)
+    }
+
+    /// The name of the package this dependency refers to, if renamed
+    #[must_use]
+    pub fn renamed_from(&self) -> Option<&str> {
+        self.dependency
+            .rename
+            .as_ref()
+            .map(|_| self.dependency.name.as_str())
+    }
+
+    /// The kind of this dependency, named as in `Cargo.toml`
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self.dependency.kind {
+            DependencyKind::Normal => "normal",
+            DependencyKind::Development => "dev",
+            DependencyKind::Build => "build",
+            DependencyKind::Unknown => "unknown",
+        }
+    }
+}
+
+/// Retrieves all dependencies declared by a package of `kind` (all kinds if
+/// `None`), in the order they are listed by `cargo metadata`
+pub(crate) fn get_dependency_requirements(
+    package: &Rc<Package>,
+    kind: Option<DependencyKind>,
+) -> Vec<DependencyRequirement> {
+    package
+        .dependencies
+        .iter()
+        .filter(|d| kind.is_none_or(|k| d.kind == k))
+        .map(|d| DependencyRequirement {
+            package: Rc::clone(package),
+            dependency: d.clone(),
+        })
+        .collect()
+}
@@ -0,0 +1,251 @@
+//! Semver violations found by [`cargo-semver-checks`](https://github.com/obi1kenobi/cargo-semver-checks)
+//!
+//! A package is checked by running `cargo semver-checks check-release`,
+//! which compares it to its latest published version in the registry. Only
+//! workspace members are checked, since a package must have a baseline that
+//! it is newer than.
+//!
+//! `cargo-semver-checks` has no machine readable output, so the lints it
+//! prints are parsed from its human readable output.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    process::{Command, Stdio},
+    rc::Rc,
+};
+
+/// Starts a lint that failed, such as
+/// `--- failure function_missing: pub fn removed or renamed ---`
+const FAILURE_PREFIX: &str = "--- failure ";
+
+/// Starts a lint configured to only warn
+const WARNING_PREFIX: &str = "--- warning ";
+
+/// Starts the list of items violating a lint
+const LOCATIONS_HEADER: &str = "Failed in:";
+
+/// Starts the summary of the check, such as
+/// `Summary semver requires new major version: 1 major and 0 minor checks failed`
+const SUMMARY_PREFIX: &str = "Summary ";
+
+/// A lint of `cargo-semver-checks` violated by a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemverViolation {
+    /// Such as `function_missing`
+    pub lint: String,
+
+    /// Such as `pub fn removed or renamed`
+    pub description: String,
+
+    /// `failure`, or `warning` for lints configured to only warn
+    pub level: String,
+
+    /// A link explaining the semver rule, such as a section of the Cargo
+    /// book
+    pub reference: Option<String>,
+
+    /// `major` or `minor`, `None` for warnings and if it cannot be
+    /// determined
+    ///
+    /// `cargo-semver-checks` only prints the number of failed lints
+    /// requiring each bump, so it is only known if all of them require the
+    /// same bump.
+    pub required_bump: Option<String>,
+
+    /// The items violating the lint, such as
+    /// `function foo::bar, previously in file src/lib.rs:1`
+    pub locations: Vec<String>,
+}
+
+impl SemverViolation {
+    /// Creates a violation from a line such as
+    /// `--- failure function_missing: pub fn removed or renamed ---`
+    fn from_header(line: &str) -> Option<Self> {
+        let (level, rest) = if let Some(r) = line.strip_prefix(FAILURE_PREFIX) {
+            ("failure", r)
+        } else {
+            ("warning", line.strip_prefix(WARNING_PREFIX)?)
+        };
+        let (lint, description) =
+            rest.strip_suffix(" ---").unwrap_or(rest).split_once(": ")?;
+        Some(Self {
+            lint: lint.to_string(),
+            description: description.to_string(),
+            level: level.to_string(),
+            reference: None,
+            required_bump: None,
+            locations: Vec::new(),
+        })
+    }
+}
+
+/// Parses the number of failed major and minor lints from a summary such as
+/// `semver requires new major version: 1 major and 0 minor checks failed`
+fn parse_summary_counts(summary: &str) -> Option<(u64, u64)> {
+    let (_, counts) = summary.split_once(": ")?;
+    let mut words = counts.split_whitespace();
+    let major = words.next()?.parse().ok()?;
+    let minor = words.nth(2)?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Parses the violations in the output of `cargo semver-checks`, `None` if
+/// the output has no summary, such as if the check could not be run
+#[must_use]
+pub fn parse_violations(output: &str) -> Option<Vec<SemverViolation>> {
+    let mut violations: Vec<SemverViolation> = Vec::new();
+    let mut summary = None;
+    let mut in_locations = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(v) = SemverViolation::from_header(trimmed) {
+            violations.push(v);
+            in_locations = false;
+        } else if let Some(s) = trimmed.strip_prefix(SUMMARY_PREFIX) {
+            summary = Some(s.to_string());
+            in_locations = false;
+        } else if let Some(current) = violations.last_mut() {
+            if trimmed == LOCATIONS_HEADER {
+                in_locations = true;
+            } else if trimmed.is_empty() {
+                in_locations = false;
+            } else if in_locations {
+                current.locations.push(trimmed.to_string());
+            } else if let Some(r) = trimmed.strip_prefix("ref: ") {
+                current.reference = Some(r.to_string());
+            }
+        }
+    }
+
+    let required_bump = match parse_summary_counts(summary.as_ref()?) {
+        Some((major, 0)) if major > 0 => Some("major"),
+        Some((0, minor)) if minor > 0 => Some("minor"),
+        _ => None,
+    };
+    for v in violations.iter_mut().filter(|v| v.level == "failure") {
+        v.required_bump = required_bump.map(String::from);
+    }
+
+    Some(violations)
+}
+
+/// Runs `cargo semver-checks` for packages, with caching
+#[derive(Debug, Clone, Default)]
+pub struct SemverChecksClient {
+    /// Set if `cargo-semver-checks` could not be started, so that it is not
+    /// retried
+    unavailable: bool,
+
+    /// Cache between a package name and its violations
+    ///
+    /// Failed runs are cached as `None`, so that they are not retried
+    /// during the same query.
+    cache: HashMap<String, Option<Rc<Vec<SemverViolation>>>>,
+}
+
+impl SemverChecksClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks the package `name` in the workspace of `manifest_path` against
+    /// its latest published version
+    ///
+    /// Will return `None` if `cargo-semver-checks` is not installed or
+    /// fails, such as if the package has never been published, and will
+    /// cache this package as such.
+    pub fn violations(
+        &mut self,
+        manifest_path: &Path,
+        name: &str,
+    ) -> Option<Rc<Vec<SemverViolation>>> {
+        if self.unavailable {
+            return None;
+        }
+        if let Some(cached) = self.cache.get(name) {
+            return cached.clone();
+        }
+
+        let output = Command::new("cargo-semver-checks")
+            .args(["semver-checks", "check-release", "--package", name])
+            .arg("--manifest-path")
+            .arg(manifest_path)
+            .env("CARGO_TERM_COLOR", "never")
+            .stdin(Stdio::null())
+            .output();
+        let res = match output {
+            Ok(o) => {
+                // Lints are printed to stdout, and the summary to stderr
+                let combined = format!(
+                    "{}\n{}",
+                    String::from_utf8_lossy(&o.stdout),
+                    String::from_utf8_lossy(&o.stderr)
+                );
+                let violations = parse_violations(&combined);
+                if violations.is_none() {
+                    eprintln!(
+                        "cargo-semver-checks failed to check {name} with stderr: {}",
+                        String::from_utf8_lossy(&o.stderr)
+                    );
+                }
+                violations.map(Rc::new)
+            }
+            Err(e) => {
+                eprintln!(
+                    "cargo-semver-checks failed to start with error: {e}, are you sure `cargo-semver-checks` is installed?"
+                );
+                self.unavailable = true;
+                None
+            }
+        };
+
+        self.cache.insert(name.to_string(), res.clone());
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::parse_violations;
+
+    #[test]
+    fn parse_semver_violations() {
+        let output =
+            fs::read_to_string("test_data/semver-checks-output/major.txt")
+                .unwrap();
+        let violations = parse_violations(&output).unwrap();
+        assert_eq!(violations.len(), 2);
+
+        let violation = &violations[0];
+        assert_eq!(violation.lint, "function_missing");
+        assert_eq!(violation.description, "pub fn removed or renamed");
+        assert_eq!(violation.level, "failure");
+        assert_eq!(violation.required_bump.as_deref(), Some("major"));
+        assert_eq!(
+            violation.reference.as_deref(),
+            Some("https://doc.rust-lang.org/cargo/reference/semver.html#item-remove")
+        );
+        assert_eq!(violation.locations.len(), 2);
+
+        // Warnings do not require a bump
+        assert_eq!(violations[1].level, "warning");
+        assert_eq!(violations[1].required_bump, None);
+    }
+
+    #[test]
+    fn no_violations() {
+        let output = "     Summary no semver update required\n";
+        assert_eq!(parse_violations(output), Some(Vec::new()));
+    }
+
+    #[test]
+    fn failed_check() {
+        let output = "error: package `foo` not found in the registry\n";
+        assert_eq!(parse_violations(output), None);
+    }
+}
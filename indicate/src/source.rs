@@ -0,0 +1,329 @@
+//! Where packages come from, such as crates.io, a git repository or a local
+//! path
+//!
+//! Cargo describes the source of a package as a string such as
+//! `registry+https://github.com/rust-lang/crates.io-index` or
+//! `git+https://github.com/rust-lang/cargo?branch=master#0227f048f`, see
+//! [`cargo_metadata::Source`]. Packages without a source are local path
+//! dependencies, or workspace members.
+use std::fmt::Display;
+
+use cargo_metadata::Package;
+use serde::{de::IgnoredAny, Deserialize};
+use url::Url;
+
+/// Source strings used by crates.io, using the git and sparse protocols
+const CRATES_IO_SOURCES: [&str; 2] = [
+    "registry+https://github.com/rust-lang/crates.io-index",
+    "sparse+https://index.crates.io/",
+];
+
+/// The kind of source a package was retrieved from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    CratesIo,
+
+    /// An alternative registry, i.e. not crates.io
+    Registry,
+    Git,
+
+    /// A local path, including workspace members
+    Path,
+}
+
+impl SourceKind {
+    /// Determines the source kind from the source of a package as reported by
+    /// `cargo metadata`
+    #[must_use]
+    pub fn from_source(source: Option<&str>) -> Self {
+        match source {
+            None => SourceKind::Path,
+            Some(s) if CRATES_IO_SOURCES.contains(&s) => SourceKind::CratesIo,
+            Some(s) if s.starts_with("git+") => SourceKind::Git,
+            Some(_) => SourceKind::Registry,
+        }
+    }
+
+    #[must_use]
+    pub fn of(package: &Package) -> Self {
+        Self::from_source(package.source.as_ref().map(|s| s.repr.as_str()))
+    }
+
+    /// The name of this kind as exposed in the schema
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceKind::CratesIo => "crates-io",
+            SourceKind::Registry => "registry",
+            SourceKind::Git => "git",
+            SourceKind::Path => "path",
+        }
+    }
+}
+
+/// The source of a package in a canonical form, identifying a package
+/// together with its name and version in the same way as a Cargo
+/// `PackageIdSpec`
+///
+/// Packages with the same name and version can be retrieved from different
+/// sources, such as a git fork of a crate on crates.io, and are different
+/// packages. Sources are normalized, so that for example both crates.io
+/// indices are the same source.
+///
+/// Can be deserialized from a source string as in `Cargo.lock`, or from a
+/// source as reported by `cargo-geiger`.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize,
+)]
+#[serde(from = "RawPackageSource")]
+pub enum PackageSource {
+    CratesIo,
+
+    /// An alternative registry, by its index URL as in [`registry_url`]
+    Registry(String),
+
+    /// A git repository, and the commit the package was locked to if known
+    Git {
+        url: String,
+        rev: Option<String>,
+    },
+
+    /// A local path, including workspace members
+    ///
+    /// The path itself is not part of the identity, since it is not
+    /// available in `Cargo.lock`.
+    #[default]
+    Path,
+}
+
+impl PackageSource {
+    /// Determines the source from the source of a package as reported by
+    /// `cargo metadata` or in `Cargo.lock`
+    #[must_use]
+    pub fn from_source(source: Option<&str>) -> Self {
+        match SourceKind::from_source(source) {
+            SourceKind::CratesIo => PackageSource::CratesIo,
+            SourceKind::Path => PackageSource::Path,
+            SourceKind::Git => match source.and_then(GitSource::parse) {
+                Some(g) => PackageSource::Git {
+                    url: normalize_git_url(&g.url),
+                    rev: g.rev,
+                },
+                None => PackageSource::Registry(source.unwrap().to_string()),
+            },
+            SourceKind::Registry => {
+                let source = source.unwrap();
+                PackageSource::Registry(
+                    registry_url(source).unwrap_or_else(|| source.to_string()),
+                )
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn of(package: &Package) -> Self {
+        Self::from_source(package.source.as_ref().map(|s| s.repr.as_str()))
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> SourceKind {
+        match self {
+            PackageSource::CratesIo => SourceKind::CratesIo,
+            PackageSource::Registry(_) => SourceKind::Registry,
+            PackageSource::Git { .. } => SourceKind::Git,
+            PackageSource::Path => SourceKind::Path,
+        }
+    }
+}
+
+impl Display for PackageSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackageSource::CratesIo => {
+                write!(f, "{}", CRATES_IO_SOURCES[0])
+            }
+            PackageSource::Registry(url) if url.starts_with("sparse+") => {
+                write!(f, "{url}")
+            }
+            PackageSource::Registry(url) => write!(f, "registry+{url}"),
+            PackageSource::Git {
+                url,
+                rev: Some(rev),
+            } => {
+                write!(f, "git+{url}?rev={rev}")
+            }
+            PackageSource::Git { url, rev: None } => write!(f, "git+{url}"),
+            PackageSource::Path => write!(f, "path"),
+        }
+    }
+}
+
+/// Removes parts of a git URL that do not change what repository it points
+/// to, such as a trailing `.git`
+fn normalize_git_url(url: &str) -> String {
+    let url = url.trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url).to_string()
+}
+
+/// A package source as either a source string, or as reported by
+/// `cargo-geiger`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawPackageSource {
+    Source(String),
+    Geiger(GeigerSource),
+}
+
+/// See `cargo_geiger_serde::Source`
+#[derive(Debug, Deserialize)]
+enum GeigerSource {
+    Git { url: String, rev: String },
+    Registry { url: String },
+    Path(IgnoredAny),
+}
+
+impl From<RawPackageSource> for PackageSource {
+    fn from(value: RawPackageSource) -> Self {
+        match value {
+            RawPackageSource::Source(s) => Self::from_source(Some(&s)),
+            RawPackageSource::Geiger(GeigerSource::Git { url, rev }) => {
+                PackageSource::Git {
+                    url: normalize_git_url(&url),
+                    rev: Some(rev),
+                }
+            }
+            RawPackageSource::Geiger(GeigerSource::Registry { url }) => {
+                Self::from_source(Some(&format!("registry+{url}")))
+            }
+            RawPackageSource::Geiger(GeigerSource::Path(_)) => {
+                PackageSource::Path
+            }
+        }
+    }
+}
+
+/// Retrieves the index URL of the registry a package was retrieved from, such
+/// as `https://github.com/rust-lang/crates.io-index`, or `None` if it was not
+/// retrieved from a registry
+///
+/// Packages using the sparse protocol keep the `sparse+` prefix, since it is
+/// part of how Cargo identifies the registry.
+#[must_use]
+pub fn registry_url(source: &str) -> Option<String> {
+    if let Some(url) = source.strip_prefix("registry+") {
+        Some(url.to_string())
+    } else if source.starts_with("sparse+") {
+        Some(source.to_string())
+    } else {
+        None
+    }
+}
+
+/// The git repository a package was retrieved from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitSource {
+    /// The repository URL, without the reference and commit
+    pub url: String,
+
+    /// The commit the dependency was locked to, or the requested revision if
+    /// it is not known
+    pub rev: Option<String>,
+    pub branch: Option<String>,
+    pub tag: Option<String>,
+}
+
+impl GitSource {
+    /// Parses a git source string, returning `None` if it is not a git source
+    #[must_use]
+    pub fn parse(source: &str) -> Option<Self> {
+        let mut url = Url::parse(source.strip_prefix("git+")?).ok()?;
+
+        let mut requested_rev = None;
+        let mut branch = None;
+        let mut tag = None;
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "rev" => requested_rev = Some(value.into_owned()),
+                "branch" => branch = Some(value.into_owned()),
+                "tag" => tag = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        let rev = url.fragment().map(String::from).or(requested_rev);
+
+        url.set_query(None);
+        url.set_fragment(None);
+        Some(Self {
+            url: url.into(),
+            rev,
+            branch,
+            tag,
+        })
+    }
+
+    /// Retrieves the git source of a package, if it was retrieved using git
+    #[must_use]
+    pub fn of(package: &Package) -> Option<Self> {
+        Self::parse(&package.source.as_ref()?.repr)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{registry_url, GitSource, PackageSource, SourceKind};
+
+    #[test_case(None => SourceKind::Path ; "path")]
+    #[test_case(Some("registry+https://github.com/rust-lang/crates.io-index") => SourceKind::CratesIo ; "crates.io git index")]
+    #[test_case(Some("sparse+https://index.crates.io/") => SourceKind::CratesIo ; "crates.io sparse index")]
+    #[test_case(Some("registry+https://my-intranet:8080/git/index") => SourceKind::Registry ; "alternative registry")]
+    #[test_case(Some("git+https://github.com/rust-lang/cargo#0227f048f") => SourceKind::Git ; "git")]
+    fn source_kind(source: Option<&str>) -> SourceKind {
+        SourceKind::from_source(source)
+    }
+
+    #[test_case("registry+https://github.com/rust-lang/crates.io-index" => Some(String::from("https://github.com/rust-lang/crates.io-index")) ; "git index")]
+    #[test_case("sparse+https://index.crates.io/" => Some(String::from("sparse+https://index.crates.io/")) ; "sparse index")]
+    #[test_case("git+https://github.com/rust-lang/cargo#0227f048f" => None ; "git")]
+    fn registry_urls(source: &str) -> Option<String> {
+        registry_url(source)
+    }
+
+    #[test_case(None => PackageSource::Path ; "path")]
+    #[test_case(Some("sparse+https://index.crates.io/") => PackageSource::CratesIo ; "crates.io sparse index")]
+    #[test_case(Some("registry+https://my-intranet:8080/git/index") => PackageSource::Registry(String::from("https://my-intranet:8080/git/index")) ; "alternative registry")]
+    #[test_case(Some("git+https://github.com/rust-lang/cargo.git?branch=master#0227f048f") => PackageSource::Git { url: String::from("https://github.com/rust-lang/cargo"), rev: Some(String::from("0227f048f")) } ; "git")]
+    fn package_source(source: Option<&str>) -> PackageSource {
+        PackageSource::from_source(source)
+    }
+
+    #[test_case(r#""registry+https://github.com/rust-lang/crates.io-index""# => PackageSource::CratesIo ; "source string")]
+    #[test_case(r#"{ "Registry": { "name": "crates.io", "url": "https://github.com/rust-lang/crates.io-index" } }"# => PackageSource::CratesIo ; "geiger crates.io")]
+    #[test_case(r#"{ "Git": { "url": "https://github.com/rust-lang/cargo", "rev": "0227f048f" } }"# => PackageSource::Git { url: String::from("https://github.com/rust-lang/cargo"), rev: Some(String::from("0227f048f")) } ; "geiger git")]
+    #[test_case(r#"{ "Path": "file:///some/crate" }"# => PackageSource::Path ; "geiger path")]
+    fn deserialize_package_source(json: &str) -> PackageSource {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn parse_git_source() {
+        let source = GitSource::parse(
+            "git+https://github.com/rust-lang/cargo?branch=master#0227f048f",
+        )
+        .unwrap();
+        assert_eq!(source.url, "https://github.com/rust-lang/cargo");
+        assert_eq!(source.rev.as_deref(), Some("0227f048f"));
+        assert_eq!(source.branch.as_deref(), Some("master"));
+        assert_eq!(source.tag, None);
+
+        let source =
+            GitSource::parse("git+https://example.com/repo.git?tag=v1.0")
+                .unwrap();
+        assert_eq!(source.url, "https://example.com/repo.git");
+        assert_eq!(source.rev, None);
+        assert_eq!(source.tag.as_deref(), Some("v1.0"));
+
+        assert!(GitSource::parse("sparse+https://index.crates.io/").is_none());
+    }
+}
@@ -0,0 +1,191 @@
+//! Client for retrieving versions of crates from the
+//! [crates.io sparse index](https://doc.rust-lang.org/cargo/reference/registry-index.html#sparse-protocol)
+//!
+//! The index is what Cargo itself uses to resolve dependencies. It is served
+//! as static files by a CDN and is not subject to the crates.io crawler
+//! policy, so it is preferred over the API for data it has, such as versions,
+//! yanked status and checksums.
+//!
+//! Each crate has a file with one JSON object per version, on the form (some
+//! fields omitted)
+//! ```json
+//! { "name": "libc", "vers": "0.2.139", "cksum": "201de327...", "yanked": false }
+//! ```
+
+use std::rc::Rc;
+
+use serde::Deserialize;
+
+use crate::{
+    http::{self, ResponseCache},
+    source::PackageSource,
+    NameVersion,
+};
+
+/// Base URL of the crates.io sparse index
+pub const SPARSE_INDEX_URL: &str = "https://index.crates.io";
+
+/// A version of a crate in the index, other fields omitted
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IndexVersion {
+    pub name: String,
+    pub vers: String,
+
+    /// The SHA-256 checksum of the `.crate` file
+    pub cksum: String,
+    pub yanked: bool,
+}
+
+/// The path of the file of a crate in the index, relative to its root
+///
+/// Crates are sorted into directories by the first characters of their
+/// lowercase name, such as `1/a`, `3/s/syn` and `se/rd/serde`.
+#[must_use]
+pub fn index_path(crate_name: &str) -> String {
+    let name = crate_name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{name}"),
+        2 => format!("2/{name}"),
+        3 => format!("3/{}/{name}", &name[..1]),
+        _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+    }
+}
+
+/// Parses the file of a crate in the index, skipping versions that cannot be
+/// parsed
+#[must_use]
+pub fn parse_index_file(raw: &str) -> Vec<IndexVersion> {
+    raw.lines()
+        .filter_map(|l| serde_json::from_str::<IndexVersion>(l).ok())
+        .collect()
+}
+
+/// Wrapper around a [`reqwest::Client`] querying the crates.io sparse index,
+/// with caching
+#[derive(Debug, Clone, Default)]
+pub struct SparseIndexClient {
+    client: reqwest::Client,
+
+    /// Cache between crate name and its versions in the index
+    cache: ResponseCache<String, Rc<Vec<IndexVersion>>>,
+}
+
+impl SparseIndexClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retrieves all versions of a crate from the index
+    ///
+    /// Will return `None` if the request fails, such as if the crate does
+    /// not exist, and will cache this crate as such.
+    pub fn versions(
+        &mut self,
+        crate_name: &str,
+    ) -> Option<Rc<Vec<IndexVersion>>> {
+        self.cache.get_or_fetch(crate_name, || {
+            let url = format!("{SPARSE_INDEX_URL}/{}", index_path(crate_name));
+            let raw = http::send_text(
+                self.client.get(&url),
+                &format!("{crate_name} from the crates.io index"),
+            )?;
+            Some(Rc::new(parse_index_file(&raw)))
+        })
+    }
+
+    /// Retrieves a specific version of a crate from the index, or `None` if
+    /// the package is not from crates.io
+    pub fn version(
+        &mut self,
+        name_version: &NameVersion,
+    ) -> Option<IndexVersion> {
+        if name_version.source != PackageSource::CratesIo {
+            return None;
+        }
+        self.versions(&name_version.name)?
+            .iter()
+            .find(|v| {
+                rustsec::Version::parse(&v.vers)
+                    .is_ok_and(|version| version == name_version.version)
+            })
+            .cloned()
+    }
+
+    /// Returns the number of versions of a crate, including yanked ones
+    pub fn versions_count(&mut self, crate_name: &str) -> Option<usize> {
+        self.versions(crate_name).map(|v| v.len())
+    }
+
+    /// Returns if this version is yanked from crates.io
+    pub fn yanked(&mut self, name_version: &NameVersion) -> Option<bool> {
+        self.version(name_version).map(|v| v.yanked)
+    }
+
+    /// Retrieves the SHA-256 checksum of this version as published to
+    /// crates.io
+    pub fn checksum(&mut self, name_version: &NameVersion) -> Option<String> {
+        self.version(name_version).map(|v| v.cksum)
+    }
+
+    /// Retrieves all versions of a crate that are yanked
+    pub fn yanked_versions(&mut self, crate_name: &str) -> Option<Vec<String>> {
+        self.versions(crate_name).map(|versions| {
+            versions
+                .iter()
+                .filter(|v| v.yanked)
+                .map(|v| v.vers.clone())
+                .collect()
+        })
+    }
+
+    /// Counts the number of yanked versions of a crate
+    pub fn yanked_versions_count(&mut self, crate_name: &str) -> Option<usize> {
+        self.versions(crate_name)
+            .map(|versions| versions.iter().filter(|v| v.yanked).count())
+    }
+
+    /// Calculates the ratio of yanked versions to all crate versions
+    pub fn yanked_ratio(&mut self, crate_name: &str) -> Option<f64> {
+        let versions = self.versions(crate_name)?;
+        if versions.is_empty() {
+            return None;
+        }
+        let yanked = versions.iter().filter(|v| v.yanked).count();
+        Some(yanked as f64 / versions.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use test_case::test_case;
+
+    use super::{index_path, parse_index_file};
+
+    #[test_case("a" => "1/a")]
+    #[test_case("cc" => "2/cc")]
+    #[test_case("syn" => "3/s/syn")]
+    #[test_case("Serde" => "se/rd/serde" ; "lowercase")]
+    #[test_case("proc-macro2" => "pr/oc/proc-macro2")]
+    fn path_in_index(name: &str) -> String {
+        index_path(name)
+    }
+
+    #[test]
+    fn parse_versions() {
+        let raw =
+            fs::read_to_string("test_data/sparse-index-output/libc").unwrap();
+        let versions = parse_index_file(&raw);
+
+        // The line that is not valid is skipped
+        assert_eq!(versions.len(), 3);
+        assert_eq!(versions[1].vers, "0.2.138");
+        assert!(versions[1].yanked);
+        assert_eq!(
+            versions[2].cksum,
+            "201de327520df007757c1f0adce6e827fe8562fbc28bfd9c15571c66ca1f5f79"
+        );
+    }
+}
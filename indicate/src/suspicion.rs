@@ -0,0 +1,224 @@
+//! Heuristics for finding obfuscated or minified code in packages
+//!
+//! Hand-written Rust rarely contains extremely long lines, random-looking
+//! string literals or large embedded hex blobs. Finding them does not prove
+//! malicious intent (generated lookup tables are common), but can help
+//! prioritize which packages to review manually.
+use std::{collections::HashMap, fs, path::Path};
+
+use syn::{
+    visit::{self, Visit},
+    Attribute, Expr, ExprArray, Lit, LitStr,
+};
+
+use crate::util;
+
+/// Lines longer than this many characters are considered minified
+pub const LONG_LINE_THRESHOLD: usize = 500;
+
+/// String literals with a Shannon entropy above this many bits per character
+/// are considered random-looking
+pub const HIGH_ENTROPY_THRESHOLD: f64 = 4.5;
+
+/// String literals shorter than this are never considered random-looking,
+/// since the entropy of short strings is unreliable
+const MIN_ENTROPY_LITERAL_LENGTH: usize = 32;
+
+/// Hex strings, or arrays of integer literals, at least this long are
+/// considered embedded blobs
+pub const HEX_BLOB_THRESHOLD: usize = 256;
+
+/// Heuristics indicating obfuscated or minified code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SuspicionKind {
+    LongLines,
+    HighEntropyStrings,
+    HexBlobs,
+}
+
+impl SuspicionKind {
+    /// How much this heuristic contributes to the suspicion score
+    #[must_use]
+    pub fn weight(&self) -> u64 {
+        match self {
+            SuspicionKind::LongLines => 30,
+            SuspicionKind::HighEntropyStrings => 40,
+            SuspicionKind::HexBlobs => 30,
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            SuspicionKind::LongLines => "lines longer than 500 characters",
+            SuspicionKind::HighEntropyStrings => "high entropy string literals",
+            SuspicionKind::HexBlobs => "hex blobs",
+        }
+    }
+}
+
+/// A heuristic matching in a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuspicionReason {
+    pub kind: SuspicionKind,
+
+    /// Path relative to the package root
+    pub path: String,
+
+    /// The number of matches in the file
+    pub count: usize,
+}
+
+impl std::fmt::Display for SuspicionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} {}", self.path, self.count, self.kind.describe())
+    }
+}
+
+/// The result of running all heuristics on a package
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Suspicion {
+    pub reasons: Vec<SuspicionReason>,
+}
+
+impl Suspicion {
+    /// A score between 0 and 100, where each kind of heuristic that matched
+    /// at least once contributes its [weight](SuspicionKind::weight)
+    #[must_use]
+    pub fn score(&self) -> u64 {
+        let mut kinds = self.reasons.iter().map(|r| r.kind).collect::<Vec<_>>();
+        kinds.sort();
+        kinds.dedup();
+        kinds.iter().map(SuspicionKind::weight).sum()
+    }
+}
+
+/// Calculates the Shannon entropy of a string, in bits per character
+fn shannon_entropy(s: &str) -> f64 {
+    let mut counts = HashMap::new();
+    let mut len = 0;
+    for c in s.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+        len += 1;
+    }
+
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn is_hex_blob(s: &str) -> bool {
+    s.len() >= HEX_BLOB_THRESHOLD && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Counts literals matching heuristics in a parsed source file
+#[derive(Debug, Default)]
+struct LiteralVisitor {
+    high_entropy_strings: usize,
+    hex_blobs: usize,
+}
+
+impl<'ast> Visit<'ast> for LiteralVisitor {
+    // Skips doc comments and other attributes, as they are not code
+    fn visit_attribute(&mut self, _: &'ast Attribute) {}
+
+    fn visit_lit_str(&mut self, i: &'ast LitStr) {
+        let value = i.value();
+        if is_hex_blob(&value) {
+            self.hex_blobs += 1;
+        } else if value.chars().count() >= MIN_ENTROPY_LITERAL_LENGTH
+            && shannon_entropy(&value) > HIGH_ENTROPY_THRESHOLD
+        {
+            self.high_entropy_strings += 1;
+        }
+    }
+
+    fn visit_expr_array(&mut self, i: &'ast ExprArray) {
+        let int_literals = i
+            .elems
+            .iter()
+            .filter(
+                |e| matches!(e, Expr::Lit(l) if matches!(l.lit, Lit::Int(_))),
+            )
+            .count();
+        if int_literals >= HEX_BLOB_THRESHOLD {
+            self.hex_blobs += 1;
+        } else {
+            visit::visit_expr_array(self, i);
+        }
+    }
+}
+
+/// Runs all heuristics on a single source file
+fn source_reasons(path: &str, source: &str) -> Vec<SuspicionReason> {
+    let mut counts = vec![(
+        SuspicionKind::LongLines,
+        source
+            .lines()
+            .filter(|l| l.chars().count() > LONG_LINE_THRESHOLD)
+            .count(),
+    )];
+
+    // Heuristics on literals are skipped if the file cannot be parsed
+    if let Ok(file) = syn::parse_file(source) {
+        let mut visitor = LiteralVisitor::default();
+        visitor.visit_file(&file);
+        counts.push((
+            SuspicionKind::HighEntropyStrings,
+            visitor.high_entropy_strings,
+        ));
+        counts.push((SuspicionKind::HexBlobs, visitor.hex_blobs));
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(kind, count)| SuspicionReason {
+            kind,
+            path: path.to_string(),
+            count,
+        })
+        .collect()
+}
+
+/// Runs all heuristics on all Rust source files of a package
+///
+/// Anything in a `target` directory is ignored. Reasons are sorted by path.
+pub(crate) fn get_suspicion(root_path: &Path) -> Suspicion {
+    let sources = util::rust_source_files(root_path);
+
+    let reasons = sources
+        .iter()
+        .filter_map(|p| {
+            let source = fs::read_to_string(p).ok()?;
+            let path = p.strip_prefix(root_path).unwrap_or(p).to_string_lossy();
+            Some(source_reasons(&path, &source))
+        })
+        .flatten()
+        .collect();
+
+    Suspicion { reasons }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{source_reasons, SuspicionKind};
+
+    #[test_case("fn main() { println!(\"Hello, world!\"); }" => Vec::<SuspicionKind>::new() ; "plain code")]
+    #[test_case(&format!("fn main() {{}} // {}", "a".repeat(600)) => vec![SuspicionKind::LongLines] ; "long line")]
+    #[test_case("const K: &str = \"q8Z!vR2@xL7#mN4$pW9%tY6^kB3&hJ1*\";" => vec![SuspicionKind::HighEntropyStrings] ; "high entropy string")]
+    #[test_case(&format!("const B: &str = \"{}\";", "deadbeef".repeat(40)) => vec![SuspicionKind::HexBlobs] ; "hex string")]
+    #[test_case(&format!("const B: [u8; 300] = [{}];", "0x1f,\n".repeat(300)) => vec![SuspicionKind::HexBlobs] ; "integer array")]
+    #[test_case("/// q8Z!vR2@xL7#mN4$pW9%tY6^kB3&hJ1*\nfn f() {}" => Vec::<SuspicionKind>::new() ; "doc comments are ignored")]
+    fn heuristics(source: &str) -> Vec<SuspicionKind> {
+        source_reasons("lib.rs", source)
+            .into_iter()
+            .map(|r| r.kind)
+            .collect()
+    }
+}
@@ -0,0 +1,120 @@
+//! Dependencies resolved for different target platforms
+//!
+//! Dependencies declared in `[target.'cfg(...)'.dependencies]` are only used
+//! on some platforms, so the supply chain of a `wasm32-unknown-unknown` build
+//! can be very different from that of a `x86_64-unknown-linux-gnu` build.
+use std::{collections::BTreeMap, error::Error};
+
+use cargo_metadata::CargoOpt;
+use serde::Serialize;
+
+use crate::{
+    feature_matrix::{partially_resolved, reachable_dependencies},
+    ManifestPath,
+};
+
+/// A dependency resolved for a target platform
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedDependency {
+    pub name: String,
+    pub version: String,
+}
+
+/// A dependency that is not resolved for all target platforms
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlatformSpecificDependency {
+    pub name: String,
+    pub version: String,
+
+    /// The target platforms this dependency is resolved for
+    pub targets: Vec<String>,
+}
+
+/// The dependencies of a package for a number of target platforms
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TargetMatrix {
+    /// All dependencies resolved for each target platform, sorted by name and
+    /// version
+    pub targets: BTreeMap<String, Vec<ResolvedDependency>>,
+
+    /// Dependencies that are not resolved for all target platforms
+    pub differences: Vec<PlatformSpecificDependency>,
+}
+
+/// Resolves the dependencies of a package for each target platform, such as
+/// `x86_64-unknown-linux-gnu`
+///
+/// # Errors
+///
+/// Returns an error variant if metadata cannot be resolved for a target
+/// platform, such as if it is not known by `rustc`.
+pub fn resolve_target_matrix(
+    manifest_path: &ManifestPath,
+    features: &[CargoOpt],
+    targets: &[String],
+) -> Result<TargetMatrix, Box<dyn Error>> {
+    let mut resolved = Vec::with_capacity(targets.len());
+    for target in targets {
+        let metadata =
+            manifest_path.metadata_for_platform(features.to_vec(), target)?;
+        resolved.push((target.clone(), reachable_dependencies(&metadata)));
+    }
+
+    let differences = partially_resolved(&resolved)
+        .into_iter()
+        .map(|((name, version), targets)| PlatformSpecificDependency {
+            name,
+            version,
+            targets,
+        })
+        .collect();
+    let targets = resolved
+        .into_iter()
+        .map(|(target, dependencies)| {
+            let dependencies = dependencies
+                .into_iter()
+                .map(|(name, version)| ResolvedDependency { name, version })
+                .collect();
+            (target, dependencies)
+        })
+        .collect();
+
+    Ok(TargetMatrix {
+        targets,
+        differences,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::resolve_target_matrix;
+    use crate::ManifestPath;
+
+    #[test]
+    fn platform_specific_dependencies() {
+        let targets = [
+            String::from("x86_64-unknown-linux-gnu"),
+            String::from("wasm32-unknown-unknown"),
+        ];
+        let matrix = resolve_target_matrix(
+            &ManifestPath::from("test_data/fake_crates/target_deps"),
+            &[],
+            &targets,
+        )
+        .unwrap();
+
+        assert_eq!(matrix.targets.len(), 2);
+        let differences = matrix
+            .differences
+            .iter()
+            .map(|d| (d.name.as_str(), d.targets[0].as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            differences,
+            vec![
+                ("cfg-if", "wasm32-unknown-unknown"),
+                ("libc", "x86_64-unknown-linux-gnu")
+            ]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Detection of package names that may be typosquats of popular crates
+//!
+//! A typosquat is a package published with a name close to that of a popular
+//! crate, such as `serd` for `serde`, hoping that someone misspells the
+//! dependency. Names are compared to a bundled list of popular crates using
+//! the edit distance, where swapping two adjacent characters counts as a
+//! single edit.
+//!
+//! crates.io treats `-` and `_`, as well as upper and lower case, as the same
+//! character, so names are normalized before they are compared.
+
+/// Names of popular crates, roughly ordered by all-time downloads
+const POPULAR_CRATES: &str = include_str!("popular_crates.txt");
+
+/// Maximum edit distance to a popular crate for a name to be a candidate,
+/// unless another is provided
+pub const DEFAULT_MAX_TYPOSQUAT_DISTANCE: u64 = 1;
+
+/// A popular crate that a package name is close to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TyposquatCandidate {
+    /// Such as `serde`
+    pub popular_crate: String,
+
+    /// The position of the popular crate in the bundled list, starting at 1
+    /// for the most downloaded
+    pub popularity_rank: u64,
+
+    /// Number of characters inserted, removed, replaced or swapped to go
+    /// from the package name to the name of the popular crate
+    pub distance: u64,
+}
+
+/// The names of popular crates, in order of popularity
+pub fn popular_crates() -> impl Iterator<Item = &'static str> {
+    POPULAR_CRATES
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+}
+
+/// Normalizes a name the way crates.io does when comparing names
+fn normalize(name: &str) -> Vec<char> {
+    name.chars()
+        .map(|c| {
+            if c == '-' {
+                '_'
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+        .collect()
+}
+
+/// The optimal string alignment distance between `a` and `b`, i.e. the
+/// Levenshtein distance where swapping two adjacent characters is a single
+/// edit
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    // Rows for the previous two prefixes of `a`, and the current one
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_previous[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Finds the popular crates that `name` is at most `max_distance` edits
+/// away from, without being the same crate
+///
+/// The result is sorted by distance, and then by popularity.
+#[must_use]
+pub fn get_typosquat_candidates(
+    name: &str,
+    max_distance: u64,
+) -> Vec<TyposquatCandidate> {
+    let normalized = normalize(name);
+    let mut candidates = popular_crates()
+        .zip(1..)
+        .filter_map(|(popular, rank)| {
+            let distance =
+                edit_distance(&normalized, &normalize(popular)) as u64;
+            (distance > 0 && distance <= max_distance).then(|| {
+                TyposquatCandidate {
+                    popular_crate: popular.to_string(),
+                    popularity_rank: rank,
+                    distance,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    candidates.sort_by_key(|c| (c.distance, c.popularity_rank));
+    candidates
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{edit_distance, get_typosquat_candidates, normalize};
+
+    #[test_case("serde", "serde" => 0)]
+    #[test_case("serd", "serde" => 1)]
+    #[test_case("sedre", "serde" => 1 ; "swapped characters")]
+    #[test_case("rustsec1", "rustsec" => 1)]
+    #[test_case("tokio", "toml" => 3)]
+    #[test_case("Serde-JSON", "serde_json" => 0 ; "normalized")]
+    fn distance(a: &str, b: &str) -> usize {
+        edit_distance(&normalize(a), &normalize(b))
+    }
+
+    #[test]
+    fn typosquat_candidates() {
+        let candidates = get_typosquat_candidates("serd", 1);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].popular_crate, "serde");
+        assert_eq!(candidates[0].distance, 1);
+
+        // A popular crate is not a typosquat of itself
+        assert!(get_typosquat_candidates("serde_json", 1).is_empty());
+        assert!(get_typosquat_candidates("serde-json", 1).is_empty());
+
+        let candidates = get_typosquat_candidates("tokioo", 2);
+        assert_eq!(candidates[0].popular_crate, "tokio");
+    }
+}
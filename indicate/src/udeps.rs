@@ -0,0 +1,233 @@
+//! Unused dependencies found by [`cargo-udeps`](https://github.com/est31/cargo-udeps)
+//!
+//! `cargo-udeps` requires a nightly toolchain, and is run once for all
+//! targets of all workspace members using `cargo +nightly udeps`. Like
+//! `cargo-geiger` it compiles the workspace, which can be slow.
+//!
+//! The output of `cargo udeps --output json` is on the form (some fields
+//! omitted)
+//! ```json
+//! {
+//!     "success": false,
+//!     "unused_deps": {
+//!         "simple_deps 0.1.0 (path+file:///path/to/simple_deps)": {
+//!             "manifest_path": "/path/to/simple_deps/Cargo.toml",
+//!             "normal": ["libc"],
+//!             "development": [],
+//!             "build": []
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Dependencies are listed by the name they are declared with, which is
+//! resolved to the package of the dependency using the metadata.
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use cargo_metadata::{CargoOpt, DependencyKind, Metadata, PackageId};
+use serde::Deserialize;
+
+use crate::ManifestPath;
+
+/// The output of `cargo udeps --output json`
+#[derive(Debug, Clone, Deserialize)]
+pub struct UdepsOutput {
+    /// `false` if any unused dependencies were found
+    pub success: bool,
+
+    /// Unused dependencies of each workspace member with any
+    #[serde(default)]
+    pub unused_deps: HashMap<String, UnusedDeps>,
+}
+
+/// The unused dependencies of a workspace member, by kind
+#[derive(Debug, Clone, Deserialize)]
+pub struct UnusedDeps {
+    pub manifest_path: PathBuf,
+    #[serde(default)]
+    pub normal: Vec<String>,
+    #[serde(default)]
+    pub development: Vec<String>,
+    #[serde(default)]
+    pub build: Vec<String>,
+}
+
+impl UdepsOutput {
+    /// Resolves the unused dependencies to the IDs of their packages
+    ///
+    /// Members and dependencies not found in `metadata` are skipped.
+    #[must_use]
+    pub fn unused_package_ids(
+        &self,
+        metadata: &Metadata,
+    ) -> HashSet<PackageId> {
+        let Some(resolve) = &metadata.resolve else {
+            return HashSet::new();
+        };
+
+        let mut unused = HashSet::new();
+        for deps in self.unused_deps.values() {
+            let Some(node) = metadata
+                .packages
+                .iter()
+                .find(|p| p.manifest_path == deps.manifest_path)
+                .and_then(|p| resolve.nodes.iter().find(|n| n.id == p.id))
+            else {
+                continue;
+            };
+
+            for (names, kind) in [
+                (&deps.normal, DependencyKind::Normal),
+                (&deps.development, DependencyKind::Development),
+                (&deps.build, DependencyKind::Build),
+            ] {
+                for name in names {
+                    // Resolved dependencies use the name of the crate in
+                    // code, where dashes are not allowed
+                    let crate_name = name.replace('-', "_");
+                    unused.extend(
+                        node.deps
+                            .iter()
+                            .filter(|d| {
+                                d.name == crate_name
+                                    && d.dep_kinds
+                                        .iter()
+                                        .any(|k| k.kind == kind)
+                            })
+                            .map(|d| d.pkg.clone()),
+                    );
+                }
+            }
+        }
+
+        unused
+    }
+}
+
+/// A client used to tell if packages are unused dependencies of the
+/// workspace, according to `cargo-udeps`
+#[derive(Debug, Clone, Default)]
+pub struct UdepsClient {
+    /// `None` if `cargo-udeps` could not be run
+    unused: Option<HashSet<PackageId>>,
+}
+
+impl UdepsClient {
+    /// Creates a new client by running `cargo +nightly udeps` for the
+    /// workspace of `manifest_path`
+    ///
+    /// Requires that `cargo-udeps` and a nightly toolchain are installed.
+    /// An empty vector of features will be handled as default features.
+    ///
+    /// This can be very slow, so it is often better to do this lazily.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if `cargo-udeps` cannot be started, or does
+    /// not produce valid output, such as due to compilation errors.
+    pub fn new(
+        manifest_path: &ManifestPath,
+        features: &[CargoOpt],
+        metadata: &Metadata,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut cmd = Command::new("cargo");
+        cmd.args(["+nightly", "udeps", "--output", "json"])
+            .args(["--workspace", "--all-targets"])
+            .arg("--manifest-path")
+            .arg(manifest_path.as_path());
+
+        for f in features {
+            match f {
+                CargoOpt::AllFeatures => {
+                    cmd.arg("--all-features");
+                }
+                CargoOpt::NoDefaultFeatures => {
+                    cmd.arg("--no-default-features");
+                }
+                CargoOpt::SomeFeatures(s) => {
+                    if !s.is_empty() {
+                        cmd.arg("--features");
+                        cmd.args(s);
+                    }
+                }
+            }
+        }
+
+        // `cargo-udeps` exits with an error if unused dependencies are found,
+        // so only the output is used to tell if it failed
+        let output = cmd.stdin(Stdio::null()).output()?;
+        let udeps_output = serde_json::from_slice::<UdepsOutput>(&output.stdout)
+            .map_err(|e| {
+                format!(
+                    "cargo-udeps output could not be parsed due to error: {e}, with stderr: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                )
+            })?;
+
+        Ok(Self::from(udeps_output.unused_package_ids(metadata)))
+    }
+
+    /// If a package is an unused dependency of any workspace member, `None`
+    /// if `cargo-udeps` could not be run
+    #[must_use]
+    pub fn is_unused(&self, id: &PackageId) -> Option<bool> {
+        self.unused.as_ref().map(|u| u.contains(id))
+    }
+}
+
+impl From<HashSet<PackageId>> for UdepsClient {
+    fn from(unused: HashSet<PackageId>) -> Self {
+        Self {
+            unused: Some(unused),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use crate::ManifestPath;
+
+    use super::UdepsOutput;
+
+    #[test]
+    fn unused_package_ids() {
+        let manifest_path = ManifestPath::new(Path::new(
+            "test_data/fake_crates/simple_deps/Cargo.toml",
+        ));
+        let metadata = manifest_path.metadata(Vec::new()).unwrap();
+        let root = metadata.root_package().unwrap();
+
+        let raw = format!(
+            r#"{{
+                "success": false,
+                "unused_deps": {{
+                    "simple_deps 0.1.0": {{
+                        "manifest_path": "{}",
+                        "normal": ["libc"],
+                        "development": ["syn"],
+                        "build": []
+                    }}
+                }}
+            }}"#,
+            root.manifest_path
+        );
+        let output = serde_json::from_str::<UdepsOutput>(&raw).unwrap();
+        let unused = output.unused_package_ids(&metadata);
+
+        // `syn` is not a dev-dependency
+        assert_eq!(unused.len(), 1);
+        let id = unused.iter().next().unwrap();
+        assert!(metadata
+            .packages
+            .iter()
+            .any(|p| &p.id == id && p.name == "libc"));
+    }
+}
@@ -1,12 +1,15 @@
 use std::{
-    collections::{BTreeMap, HashMap},
-    path::PathBuf,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
 };
 
-use cargo_metadata::{DependencyKind, Metadata, Package};
+use cargo_metadata::{
+    Dependency, DependencyKind, Metadata, Package, PackageId,
+};
 use trustfall::{FieldValue, TransparentValue};
+use walkdir::WalkDir;
 
 use crate::adapter::{DirectDependencyMap, PackageMap};
 
@@ -33,33 +36,71 @@ pub fn local_package_path(package: &Package) -> PathBuf {
     p
 }
 
+/// Retrieves all Rust source files in a directory and its subdirectories,
+/// sorted by path
+///
+/// Anything in a `target` directory is ignored.
+#[must_use]
+pub(crate) fn rust_source_files(root_path: &Path) -> Vec<PathBuf> {
+    let mut sources = WalkDir::new(root_path)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != "target")
+        .filter_map(Result::ok)
+        .filter(|e| {
+            e.file_type().is_file()
+                && e.path().extension().is_some_and(|ext| ext == "rs")
+        })
+        .map(|e| e.into_path())
+        .collect::<Vec<_>>();
+    sources.sort();
+    sources
+}
+
+/// Parses the name of a dependency kind, as used in `Cargo.toml`, into a
+/// [`DependencyKind`]
+///
+/// `"all"` is parsed as `Some(None)`, i.e. no specific kind. Returns `None` if
+/// the name is not a valid kind.
+#[must_use]
+pub fn parse_dependency_kind(kind: &str) -> Option<Option<DependencyKind>> {
+    match kind {
+        "normal" => Some(Some(DependencyKind::Normal)),
+        "dev" => Some(Some(DependencyKind::Development)),
+        "build" => Some(Some(DependencyKind::Build)),
+        "all" => Some(None),
+        _ => None,
+    }
+}
+
 /// Parse metadata to create a map over direct dependencies for all packages
 ///
-/// Direct dependencies will only include 'normal' dependencies, i.e.
-/// not build nor test deps.
+/// Direct dependencies will only include dependencies of `kind`, such as
+/// 'normal' dependencies, i.e. not build nor test deps. If `kind` is `None`,
+/// dependencies of all kinds are included.
 ///
 /// _Note_: This operation is quite expensive as it must traverse the dependency
 /// tree. Avoid if not required.
 #[must_use]
-pub fn get_direct_dependencies(metadata: &Metadata) -> DirectDependencyMap {
+pub fn get_direct_dependencies(
+    metadata: &Metadata,
+    kind: Option<DependencyKind>,
+) -> DirectDependencyMap {
     let mut direct_dependencies =
         HashMap::with_capacity(metadata.packages.len());
 
     for node in &metadata.resolve.as_ref().expect("No nodes found!").nodes {
         let id = node.id.clone();
 
-        // Filter out dependencies that are not normal
-        let normal_deps = node
+        // Filter out dependencies that are not of the requested kind
+        let kind_deps = node
             .deps
             .iter()
             .filter_map(|nd| {
-                if nd
-                    .dep_kinds
-                    .iter()
-                    .any(|dki| dki.kind == DependencyKind::Normal)
-                {
-                    // A dependency can have many kinds; We only care if it is
-                    // normal
+                if kind.is_none_or(|k| {
+                    nd.dep_kinds.iter().any(|dki| dki.kind == k)
+                }) {
+                    // A dependency can have many kinds; We only care if one
+                    // of them is the requested kind
                     Some(nd.pkg.clone())
                 } else {
                     None
@@ -67,12 +108,124 @@ pub fn get_direct_dependencies(metadata: &Metadata) -> DirectDependencyMap {
             })
             .collect::<Vec<_>>();
 
-        direct_dependencies.insert(id, Rc::new(normal_deps));
+        direct_dependencies.insert(id, Rc::new(kind_deps));
     }
 
     direct_dependencies
 }
 
+/// Inverts a [`DirectDependencyMap`], creating a map from a package to the
+/// packages that directly depend on it
+///
+/// All packages in `direct_dependencies` are included, even if nothing
+/// depends on them. Dependents are sorted by their package ID.
+#[must_use]
+pub fn invert_dependency_map(
+    direct_dependencies: &DirectDependencyMap,
+) -> DirectDependencyMap {
+    let mut dependents: HashMap<PackageId, Vec<PackageId>> =
+        direct_dependencies
+            .keys()
+            .map(|id| (id.clone(), Vec::new()))
+            .collect();
+
+    for (id, dependency_ids) in direct_dependencies {
+        for dependency_id in dependency_ids.iter() {
+            dependents
+                .entry(dependency_id.clone())
+                .or_default()
+                .push(id.clone());
+        }
+    }
+
+    dependents
+        .into_iter()
+        .map(|(id, mut ids)| {
+            ids.sort();
+            (id, Rc::new(ids))
+        })
+        .collect()
+}
+
+/// Visits all packages reachable from the root package through
+/// `direct_dependencies` breadth first, calling `visit` with each package and
+/// the package it was first reached from
+///
+/// If there is no root package (such as in a virtual workspace), all workspace
+/// members are used as roots. Roots are visited with no parent.
+fn visit_breadth_first(
+    metadata: &Metadata,
+    direct_dependencies: &DirectDependencyMap,
+    mut visit: impl FnMut(&PackageId, Option<&PackageId>),
+) {
+    let roots = match metadata.root_package() {
+        Some(root) => vec![root.id.clone()],
+        None => metadata.workspace_members.clone(),
+    };
+
+    let mut seen = HashSet::with_capacity(direct_dependencies.len());
+    let mut queue = VecDeque::new();
+    for root in roots {
+        visit(&root, None);
+        seen.insert(root.clone());
+        queue.push_back(root);
+    }
+
+    // Breadth first, so the first time a package is seen is the shortest path
+    while let Some(id) = queue.pop_front() {
+        let Some(dependency_ids) = direct_dependencies.get(&id) else {
+            continue;
+        };
+        for dependency_id in dependency_ids.iter() {
+            if seen.insert(dependency_id.clone()) {
+                visit(dependency_id, Some(&id));
+                queue.push_back(dependency_id.clone());
+            }
+        }
+    }
+}
+
+/// Calculates the depth of all packages reachable from the root package, i.e.
+/// the length of the shortest path from the root through `direct_dependencies`
+///
+/// The root package has depth 0. If there is no root package (such as in a
+/// virtual workspace), all workspace members are given depth 0.
+#[must_use]
+pub fn get_depths(
+    metadata: &Metadata,
+    direct_dependencies: &DirectDependencyMap,
+) -> HashMap<PackageId, u64> {
+    let mut depths = HashMap::with_capacity(direct_dependencies.len());
+    visit_breadth_first(metadata, direct_dependencies, |id, parent| {
+        let depth = parent.map_or(0, |p| depths[p] + 1);
+        depths.insert(id.clone(), depth);
+    });
+    depths
+}
+
+/// Calculates the path of package IDs along a shortest path from the root
+/// package to every package reachable through `direct_dependencies`
+///
+/// Paths start with the root package and end with the package itself, so the
+/// path of the root package only contains itself. If there is no root package,
+/// paths start at any of the workspace members.
+#[must_use]
+pub fn get_paths_from_root(
+    metadata: &Metadata,
+    direct_dependencies: &DirectDependencyMap,
+) -> HashMap<PackageId, Rc<Vec<PackageId>>> {
+    let mut paths: HashMap<PackageId, Rc<Vec<PackageId>>> =
+        HashMap::with_capacity(direct_dependencies.len());
+    visit_breadth_first(metadata, direct_dependencies, |id, parent| {
+        let mut path = parent
+            .map(|p| paths[p].as_ref().clone())
+            .unwrap_or_default();
+        path.push(id.clone());
+        paths.insert(id.clone(), Rc::new(path));
+    });
+    paths
+}
+
 /// Parse metadata to create a map over packages
 #[must_use]
 pub fn get_packages(metadata: &Metadata) -> PackageMap {
@@ -86,3 +239,32 @@ pub fn get_packages(metadata: &Metadata) -> PackageMap {
 
     packages
 }
+
+/// Resolves the package a dependency of `package` refers to in the resolved
+/// dependency graph
+///
+/// Returns `None` if the dependency is not part of the resolved dependency
+/// graph, such as for optional dependencies that are not enabled, or dev
+/// dependencies of packages other than the root package.
+#[must_use]
+pub(crate) fn resolve_dependency(
+    metadata: &Metadata,
+    packages: &PackageMap,
+    package: &Package,
+    dependency: &Dependency,
+) -> Option<Rc<Package>> {
+    let node = metadata
+        .resolve
+        .as_ref()?
+        .nodes
+        .iter()
+        .find(|n| n.id == package.id)?;
+
+    node.dependencies
+        .iter()
+        .filter_map(|id| packages.get(id))
+        .find(|p| {
+            p.name == dependency.name && dependency.req.matches(&p.version)
+        })
+        .map(Rc::clone)
+}
@@ -0,0 +1,277 @@
+//! Analysis of vendored sources, for environments without network access
+//!
+//! Instead of downloading packages to `~/.cargo/registry`, their sources can
+//! be read from a directory created by
+//! [`cargo vendor`](https://doc.rust-lang.org/cargo/commands/cargo-vendor.html),
+//! or from a directory of downloaded `.crate` archives. Archives are
+//! extracted to the temporary directory of the system, into the same format
+//! as `cargo vendor` uses.
+//!
+//! Cargo is told to use the vendored sources through
+//! [source replacement](https://doc.rust-lang.org/cargo/reference/source-replacement.html),
+//! passed using `--config` arguments. Packages in the metadata then point to
+//! the vendored sources, so that data such as code stats and license files is
+//! read from them. Only packages from crates.io are replaced.
+//!
+//! Since `cargo-geiger` does not accept `--config`, the same configuration is
+//! also written to a generated `.cargo/config.toml`, and `cargo-geiger` is run
+//! from the directory containing it.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    error::Error,
+    fs::{self, File},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+
+/// The directory in the temporary directory of the system where
+/// configurations and extracted archives are kept
+pub const VENDOR_WORKING_DIR: &str = "indicate-vendor";
+
+/// The name of the replacement source in the generated configuration
+const VENDORED_SOURCE_NAME: &str = "vendored-sources";
+
+/// Sources of packages read from a local directory instead of crates.io
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VendoredSources {
+    /// A directory on the format created by `cargo vendor`
+    directory: PathBuf,
+
+    /// The directory containing the `.cargo/config.toml` replacing crates.io
+    /// with `directory`, for tools that do not accept `--config`
+    config_dir: PathBuf,
+}
+
+impl VendoredSources {
+    /// Uses a directory created by `cargo vendor`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the directory does not exist, or if the
+    /// configuration cannot be written.
+    pub fn from_directory(directory: &Path) -> Result<Self, Box<dyn Error>> {
+        let directory = fs::canonicalize(directory)?;
+        let config_dir = working_dir(&directory).join("config");
+        write_config(&directory, &config_dir)?;
+
+        Ok(Self {
+            directory,
+            config_dir,
+        })
+    }
+
+    /// Uses a directory of `.crate` archives, such as `libc-0.2.139.crate`,
+    /// extracting them to the temporary directory of the system
+    ///
+    /// # Errors
+    ///
+    /// Returns an error variant if the directory cannot be read, or if any
+    /// archive cannot be extracted.
+    pub fn from_crate_archives(
+        archive_dir: &Path,
+    ) -> Result<Self, Box<dyn Error>> {
+        let archive_dir = fs::canonicalize(archive_dir)?;
+        let sources_dir = working_dir(&archive_dir).join("sources");
+
+        // Remove packages extracted from archives no longer in the directory
+        if sources_dir.exists() {
+            fs::remove_dir_all(&sources_dir)?;
+        }
+        fs::create_dir_all(&sources_dir)?;
+
+        for entry in fs::read_dir(&archive_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "crate") {
+                extract_crate_archive(&path, &sources_dir).map_err(|e| {
+                    format!(
+                        "could not extract {} due to error: {e}",
+                        path.to_string_lossy()
+                    )
+                })?;
+            }
+        }
+
+        Self::from_directory(&sources_dir)
+    }
+
+    /// The directory the sources of packages are read from
+    #[must_use]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Arguments making Cargo use the vendored sources, without network
+    /// access, such as `--config net.offline=true`
+    #[must_use]
+    pub fn cargo_config_args(&self) -> Vec<String> {
+        let directory =
+            toml::Value::String(self.directory.to_string_lossy().to_string());
+        [
+            format!("source.crates-io.replace-with=\"{VENDORED_SOURCE_NAME}\""),
+            format!("source.{VENDORED_SOURCE_NAME}.directory={directory}"),
+            String::from("net.offline=true"),
+        ]
+        .into_iter()
+        .flat_map(|c| [String::from("--config"), c])
+        .collect()
+    }
+
+    /// The directory Cargo commands that do not accept `--config` (see
+    /// [`VendoredSources::cargo_config_args`]) must be run from to use the
+    /// vendored sources
+    #[must_use]
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+}
+
+/// A directory in [`VENDOR_WORKING_DIR`] unique to `path`
+fn working_dir(path: &Path) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    std::env::temp_dir()
+        .join(VENDOR_WORKING_DIR)
+        .join(format!("{:016x}", hasher.finish()))
+}
+
+/// Writes a `.cargo/config.toml` in `config_dir` replacing crates.io with
+/// `directory`
+///
+/// Network access is disabled, so that Cargo fails instead of trying to
+/// download packages that are not vendored.
+fn write_config(
+    directory: &Path,
+    config_dir: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let config = toml::Value::try_from(serde_json::json!({
+        "source": {
+            "crates-io": { "replace-with": VENDORED_SOURCE_NAME },
+            VENDORED_SOURCE_NAME: { "directory": directory },
+        },
+        "net": { "offline": true },
+    }))?;
+
+    let cargo_dir = config_dir.join(".cargo");
+    fs::create_dir_all(&cargo_dir)?;
+    fs::write(cargo_dir.join("config.toml"), toml::to_string(&config)?)?;
+    Ok(())
+}
+
+/// Extracts a `.crate` archive into `sources_dir`, adding the
+/// `.cargo-checksum.json` Cargo requires of vendored packages
+///
+/// Archives contain a single directory named as the archive, such as
+/// `libc-0.2.139`. The checksum of the package is that of the archive, which
+/// is the same as the one in `Cargo.lock`.
+fn extract_crate_archive(
+    archive: &Path,
+    sources_dir: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let name = archive
+        .file_stem()
+        .ok_or("archive has no file name")?
+        .to_string_lossy()
+        .to_string();
+    let checksum = format!("{:x}", Sha256::digest(fs::read(archive)?));
+
+    tar::Archive::new(GzDecoder::new(File::open(archive)?))
+        .unpack(sources_dir)?;
+
+    let checksum_file = serde_json::json!({
+        "files": {},
+        "package": checksum,
+    });
+    fs::write(
+        sources_dir.join(name).join(".cargo-checksum.json"),
+        checksum_file.to_string(),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::VendoredSources;
+    use crate::ManifestPath;
+
+    #[test]
+    fn metadata_uses_vendored_sources() {
+        let vendored = VendoredSources::from_directory(Path::new(
+            "test_data/fake_crates/vendored_deps/vendor",
+        ))
+        .unwrap();
+        let metadata = ManifestPath::new(Path::new(
+            "test_data/fake_crates/vendored_deps/Cargo.toml",
+        ))
+        .metadata_with_vendored_sources(Vec::new(), &vendored)
+        .unwrap();
+
+        let dep = metadata
+            .packages
+            .iter()
+            .find(|p| p.name == "tiny_dep")
+            .unwrap();
+        assert!(dep.manifest_path.starts_with(vendored.directory()));
+    }
+
+    #[test]
+    fn extracting_again_removes_old_packages() {
+        let archive_dir =
+            std::env::temp_dir().join("indicate-test-removed-crate-archives");
+        fs::create_dir_all(&archive_dir).unwrap();
+        let vendored =
+            VendoredSources::from_crate_archives(&archive_dir).unwrap();
+        let old_package = vendored.directory().join("removed-0.1.0");
+        fs::create_dir_all(&old_package).unwrap();
+
+        let vendored =
+            VendoredSources::from_crate_archives(&archive_dir).unwrap();
+        assert!(vendored.directory().exists());
+        assert!(!old_package.exists());
+    }
+
+    #[test]
+    fn extract_crate_archives() {
+        let archive_dir =
+            std::env::temp_dir().join("indicate-test-crate-archives");
+        fs::create_dir_all(&archive_dir).unwrap();
+
+        // Package the vendored test dependency as crates.io would
+        let archive =
+            fs::File::create(archive_dir.join("tiny_dep-0.1.0.crate")).unwrap();
+        let mut builder =
+            tar::Builder::new(GzEncoder::new(archive, Compression::default()));
+        for file in ["Cargo.toml", "src/lib.rs"] {
+            builder
+                .append_path_with_name(
+                    Path::new(
+                        "test_data/fake_crates/vendored_deps/vendor/tiny_dep",
+                    )
+                    .join(file),
+                    Path::new("tiny_dep-0.1.0").join(file),
+                )
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let vendored =
+            VendoredSources::from_crate_archives(&archive_dir).unwrap();
+        let package_dir = vendored.directory().join("tiny_dep-0.1.0");
+        assert!(package_dir.join("src/lib.rs").exists());
+
+        let checksum: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(package_dir.join(".cargo-checksum.json"))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(checksum["package"].as_str().unwrap().len(), 64);
+        assert!(vendored.config_dir().join(".cargo/config.toml").exists());
+    }
+}
@@ -3,14 +3,46 @@
 
 use std::{rc::Rc, sync::Arc};
 
-use cargo_metadata::Package;
-use octorust::types::{FullRepository, PublicUser};
-use rustsec::{advisory::affected::FunctionPath, Advisory, VersionReq};
+use cargo_metadata::{Package, Target};
+use crates_io_api::User;
+use octorust::types::{
+    Entries, FullRepository, IssueSimple, OrganizationSimple, PublicUser,
+    Release,
+};
+use rustsec::{advisory::affected::FunctionPath, VersionReq};
 use trustfall::provider::TrustfallEnumVertex;
 
 use crate::{
+    advisory::{AuditSummary, PackageAdvisory},
+    binaries::{BundledBinary, SuspiciousFile},
+    bloat::BinarySizeContribution,
+    build_script::BuildScript,
+    clippy::{ClippyDiagnostics, ClippyLint},
     code_stats::{LanguageBlob, LanguageCodeStats},
+    crev::CrevReview,
+    deny::DenyEvaluation,
+    deps_dev::DepsDevInfo,
+    docs_rs::DocsRsBuild,
+    epss::EpssScore,
+    features::Feature,
+    future_incompat::FutureIncompatLint,
     geiger::{GeigerCategories, GeigerCount, GeigerUnsafety},
+    ghsa::PackageGhsaVulnerability,
+    license::{License, LicenseFile, LicenseGroup},
+    nvd::CveRecord,
+    osv::OsvVulnerability,
+    repo::{
+        gitea::GiteaRepository,
+        github::GitHubContributor,
+        gitlab::{GitLabNamespace, GitLabProject},
+        scorecard::{Scorecard, ScorecardCheck},
+        sourcehut::SourcehutRepository,
+    },
+    requirement::DependencyRequirement,
+    semver_checks::SemverViolation,
+    source::GitSource,
+    typosquat::TyposquatCandidate,
+    vet::PackageVetAudit,
     NameVersion,
 };
 
@@ -21,7 +53,10 @@ use crate::{
 #[derive(Debug, Clone, TrustfallEnumVertex)]
 pub enum Vertex {
     Package(Rc<Package>),
-    CratesIoStats(NameVersion),
+
+    /// `None` if the package is not from crates.io
+    CratesIoStats(Option<NameVersion>),
+    CratesIoOwner(Rc<User>),
 
     #[trustfall(skip_conversion)]
     Webpage(String),
@@ -30,8 +65,30 @@ pub enum Vertex {
     Repository(String),
     GitHubRepository(Arc<FullRepository>),
     GitHubUser(Arc<PublicUser>),
-    Advisory(Rc<Advisory>),
+    GitHubOrganization(Rc<OrganizationSimple>),
+    GitHubContributor(Rc<GitHubContributor>),
+    GitHubRelease(Rc<Release>),
+    GitHubWorkflow(Rc<Entries>),
+    GitHubIssue(Rc<IssueSimple>),
+    GitLabRepository(Rc<GitLabProject>),
+    GitLabNamespace(Rc<GitLabNamespace>),
+    GiteaRepository(Rc<GiteaRepository>),
+    SourcehutRepository(Rc<SourcehutRepository>),
+    Scorecard(Rc<Scorecard>),
+    ScorecardCheck(Rc<ScorecardCheck>),
+    Advisory(Rc<PackageAdvisory>),
+    OsvAdvisory(Rc<OsvVulnerability>),
+    GhsaAdvisory(Rc<PackageGhsaVulnerability>),
+    EpssScore(Rc<EpssScore>),
+    CveRecord(Rc<CveRecord>),
+    VetAudit(Rc<PackageVetAudit>),
+    CrevReview(Rc<CrevReview>),
+    SemverViolation(Rc<SemverViolation>),
+    DenyPolicy(Rc<DenyEvaluation>),
+    DocsRsBuild(Rc<DocsRsBuild>),
+    DepsDev(Rc<DepsDevInfo>),
     AffectedFunctionVersions((FunctionPath, Vec<VersionReq>)),
+    AuditSummary(AuditSummary),
     // CvssBase(Rc<cvss::v3::base::Base>), // TODO: Add when Trustfall supports enums?
 
     // Geiger types implement `Copy` and does not to be inside an Rc
@@ -39,8 +96,25 @@ pub enum Vertex {
     GeigerCategories(GeigerCategories),
     GeigerCount(GeigerCount),
 
+    BinarySizeContribution(BinarySizeContribution),
+    ClippyDiagnostics(Rc<ClippyDiagnostics>),
+    ClippyLint(Rc<ClippyLint>),
+    FutureIncompatLint(Rc<FutureIncompatLint>),
+
     LanguageCodeStats(Rc<LanguageCodeStats>),
     LanguageBlob(Rc<LanguageBlob>),
+
+    BundledBinary(Rc<BundledBinary>),
+    SuspiciousFile(Rc<SuspiciousFile>),
+    TyposquatCandidate(Rc<TyposquatCandidate>),
+    Feature(Rc<Feature>),
+    DependencyRequirement(Rc<DependencyRequirement>),
+    Target(Rc<Target>),
+    GitSource(Rc<GitSource>),
+    License(Rc<License>),
+    LicenseFile(Rc<LicenseFile>),
+    LicenseGroup(Rc<LicenseGroup>),
+    BuildScript(Rc<BuildScript>),
 }
 
 impl Vertex {
@@ -50,6 +124,9 @@ impl Vertex {
                 Some(url.as_ref())
             }
             Vertex::GitHubRepository(r) => Some(&r.html_url),
+            Vertex::GitLabRepository(r) => Some(&r.web_url),
+            Vertex::GiteaRepository(r) => Some(&r.html_url),
+            Vertex::SourcehutRepository(r) => Some(&r.url),
             _ => None,
         }
     }
@@ -58,6 +135,9 @@ impl Vertex {
         match self {
             Vertex::Repository(url) => Some(url.as_ref()),
             Vertex::GitHubRepository(r) => Some(&r.html_url),
+            Vertex::GitLabRepository(r) => Some(&r.web_url),
+            Vertex::GiteaRepository(r) => Some(&r.html_url),
+            Vertex::SourcehutRepository(r) => Some(&r.url),
             _ => None,
         }
     }
@@ -0,0 +1,238 @@
+//! Audits recorded using [`cargo-vet`](https://mozilla.github.io/cargo-vet/)
+//!
+//! Audits are read from `supply-chain/audits.toml` in the workspace root,
+//! together with audits imported from other organizations, which `cargo vet`
+//! stores in `supply-chain/imports.lock`. Exemptions in
+//! `supply-chain/config.toml` are not audits, and are not included.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::Path,
+    rc::Rc,
+};
+
+use rustsec::Version;
+use serde::Deserialize;
+
+/// The directory `cargo vet` stores its files in, relative to the workspace
+/// root
+pub const SUPPLY_CHAIN_DIR: &str = "supply-chain";
+
+/// Audits of a workspace, keyed by package name
+pub type VetAuditMap = HashMap<String, Vec<Rc<VetAudit>>>;
+
+/// `cargo vet` allows some fields to be either a single string or a list
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum StringOrVec {
+    String(String),
+    Vec(Vec<String>),
+}
+
+impl From<StringOrVec> for Vec<String> {
+    fn from(value: StringOrVec) -> Self {
+        match value {
+            StringOrVec::String(s) => vec![s],
+            StringOrVec::Vec(v) => v,
+        }
+    }
+}
+
+/// An audit entry as written in `audits.toml` or `imports.lock`
+#[derive(Debug, Clone, Deserialize)]
+struct RawAudit {
+    who: Option<StringOrVec>,
+    criteria: StringOrVec,
+    version: Option<String>,
+    delta: Option<String>,
+    notes: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AuditsFile {
+    #[serde(default)]
+    audits: HashMap<String, Vec<RawAudit>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ImportsLock {
+    /// Sorted by import name, so that imported audits are in a stable order
+    #[serde(default)]
+    audits: BTreeMap<String, AuditsFile>,
+}
+
+/// An audit of a package, certifying that it satisfies some criteria
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VetAudit {
+    /// Such as `Alice <alice@example.com>`
+    pub who: Vec<String>,
+
+    /// Such as `safe-to-deploy`
+    pub criteria: Vec<String>,
+
+    /// The version audited in full, for full audits
+    pub version: Option<String>,
+
+    /// The change audited between two versions, such as `1.0.0 -> 1.0.1`,
+    /// for delta audits
+    pub delta: Option<String>,
+    pub notes: Option<String>,
+
+    /// The name of the import the audit is from, `None` for audits in
+    /// `audits.toml`
+    pub imported_from: Option<String>,
+}
+
+impl VetAudit {
+    fn new(raw: RawAudit, imported_from: Option<String>) -> Self {
+        Self {
+            who: raw.who.map(Vec::from).unwrap_or_default(),
+            criteria: raw.criteria.into(),
+            version: raw.version,
+            delta: raw.delta,
+            notes: raw.notes,
+            imported_from,
+        }
+    }
+
+    /// If this audit ends at `version`, either as a full audit of it or as a
+    /// delta audit to it
+    ///
+    /// A delta audit only covers a version if its starting version is also
+    /// covered, which is not checked.
+    #[must_use]
+    pub fn covers(&self, version: &Version) -> bool {
+        let audited = match (&self.version, &self.delta) {
+            (Some(v), _) => Some(v.as_str()),
+            (None, Some(d)) => d.split("->").nth(1),
+            (None, None) => None,
+        };
+        audited
+            .and_then(|v| Version::parse(v.trim()).ok())
+            .is_some_and(|v| &v == version)
+    }
+}
+
+/// An audit of a package, together with if the version of the package it
+/// was retrieved for is covered, see [`VetAudit::covers`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageVetAudit {
+    pub audit: Rc<VetAudit>,
+    pub covers_current_version: bool,
+}
+
+/// Parses the contents of `audits.toml` and `imports.lock`, if any
+///
+/// # Errors
+///
+/// Returns an error variant if any of the files is not valid TOML.
+pub fn parse_audits(
+    raw_audits: &str,
+    raw_imports: Option<&str>,
+) -> Result<VetAuditMap, toml::de::Error> {
+    let mut map = VetAuditMap::new();
+
+    let audits_file: AuditsFile = toml::from_str(raw_audits)?;
+    for (name, audits) in audits_file.audits {
+        map.entry(name).or_default().extend(
+            audits.into_iter().map(|a| Rc::new(VetAudit::new(a, None))),
+        );
+    }
+
+    if let Some(raw_imports) = raw_imports {
+        let imports: ImportsLock = toml::from_str(raw_imports)?;
+        for (import, file) in imports.audits {
+            for (name, audits) in file.audits {
+                map.entry(name).or_default().extend(
+                    audits.into_iter().map(|a| {
+                        Rc::new(VetAudit::new(a, Some(import.clone())))
+                    }),
+                );
+            }
+        }
+    }
+
+    Ok(map)
+}
+
+/// Reads the audits of the workspace in `workspace_root`
+///
+/// A workspace without `supply-chain/audits.toml` has no audits, and
+/// `supply-chain/imports.lock` is optional.
+///
+/// # Errors
+///
+/// Returns an error variant if any of the files cannot be read, or is not
+/// valid TOML.
+pub fn read_audits(
+    workspace_root: &Path,
+) -> Result<VetAuditMap, Box<dyn std::error::Error>> {
+    let supply_chain = workspace_root.join(SUPPLY_CHAIN_DIR);
+    let raw_audits = match fs::read_to_string(supply_chain.join("audits.toml"))
+    {
+        Ok(r) => r,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Ok(VetAuditMap::new())
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let raw_imports =
+        match fs::read_to_string(supply_chain.join("imports.lock")) {
+            Ok(r) => Some(r),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+    Ok(parse_audits(&raw_audits, raw_imports.as_deref())?)
+}
+
+#[cfg(test)]
+mod test {
+    use rustsec::Version;
+    use test_case::test_case;
+
+    use super::parse_audits;
+
+    const AUDITS: &str = r#"
+[criteria.crypto-reviewed]
+description = "The cryptographic code in this crate has been reviewed"
+
+[[audits.libc]]
+who = "Alice <alice@example.com>"
+criteria = "safe-to-deploy"
+version = "0.2.139"
+
+[[audits.syn]]
+who = ["Alice <alice@example.com>", "Bob <bob@example.com>"]
+criteria = ["safe-to-run", "crypto-reviewed"]
+delta = "1.0.100 -> 1.0.107"
+notes = "Only parser changes"
+"#;
+
+    const IMPORTS: &str = r#"
+[[audits.google.audits.libc]]
+who = "Carol <carol@example.com>"
+criteria = "safe-to-run"
+version = "0.2.138"
+"#;
+
+    #[test]
+    fn parse_audits_and_imports() {
+        let audits = parse_audits(AUDITS, Some(IMPORTS)).unwrap();
+        assert_eq!(audits["libc"].len(), 2);
+        assert_eq!(audits["libc"][1].imported_from.as_deref(), Some("google"));
+
+        let syn = &audits["syn"][0];
+        assert_eq!(syn.who.len(), 2);
+        assert_eq!(syn.criteria, ["safe-to-run", "crypto-reviewed"]);
+        assert_eq!(syn.imported_from, None);
+    }
+
+    #[test_case("libc", 0, "0.2.139" => true ; "full audit of version")]
+    #[test_case("libc", 0, "0.2.140" => false ; "full audit of other version")]
+    #[test_case("syn", 0, "1.0.107" => true ; "delta audit to version")]
+    #[test_case("syn", 0, "1.0.100" => false ; "delta audit from version")]
+    fn covers(name: &str, index: usize, version: &str) -> bool {
+        let audits = parse_audits(AUDITS, None).unwrap();
+        audits[name][index].covers(&Version::parse(version).unwrap())
+    }
+}
@@ -0,0 +1,414 @@
+//! Pre-filled Markdown worksheets for manually reviewing direct dependencies
+//!
+//! Each worksheet contains what `indicate` knows about one direct dependency,
+//! such as its metadata and known advisories, followed by a checklist for the
+//! reviewer to fill in. Unsafety (using `cargo-geiger`) and repository health
+//! (using the GitHub API) are only included if enabled, since they require
+//! external tools or tokens.
+
+use std::{
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use serde::Deserialize;
+use trustfall::TransparentValue;
+
+use crate::{
+    advisory::AdvisoryClient,
+    execute_query_with_adapter,
+    humanize::{render_value, ValueStyle},
+    query::FullQueryBuilder,
+    util::transparent_results,
+    CargoOpt, IndicateAdapterBuilder, ManifestPath,
+};
+
+/// Retrieves metadata and advisories of all direct dependencies
+const DEPENDENCIES_QUERY: &str = r#"{
+    RootPackage {
+        dependencies {
+            name @output
+            version @output
+            license @output
+            description @output
+            sourceKind @output
+            hasBuildScript @output
+            isProcMacro @output
+            homepage @output
+            documentation @output
+            advisoryHistory(includeWithdrawn: false) @fold {
+                id @output(name: "advisoryIds")
+                title @output(name: "advisoryTitles")
+                severity @output(name: "advisorySeverities")
+            }
+            %GEIGER%
+            %GITHUB%
+        }
+    }
+}"#;
+
+/// Retrieves unsafety using `cargo-geiger`
+const GEIGER_FIELDS: &str = r#"geiger @optional {
+                forbidsUnsafe @output(name: "forbidsUnsafe")
+                used {
+                    total {
+                        unsafe @output(name: "usedUnsafe")
+                        total @output(name: "usedTotal")
+                    }
+                }
+            }"#;
+
+/// Retrieves repository health using the GitHub API
+const GITHUB_FIELDS: &str = r#"gitHub: repository @optional {
+                ... on GitHubRepository {
+                    url @output(name: "repository")
+                    starsCount @output(name: "starsCount")
+                    openIssuesCount @output(name: "openIssuesCount")
+                    archived @output(name: "archived")
+                    unixLastPushedAt @output(name: "unixLastPushedAt")
+                }
+            }"#;
+
+/// Configuration of the worksheets created using [`worksheets`]
+#[derive(Debug, Default)]
+pub struct WorksheetConfig {
+    /// Features used when resolving metadata, see
+    /// [`IndicateAdapterBuilder::features`]
+    pub features: Vec<CargoOpt>,
+
+    /// Client used to resolve advisories; fetched lazily if not set
+    pub advisory_client: Option<AdvisoryClient>,
+
+    /// Include unsafety, requires `cargo-geiger` to be installed
+    pub geiger: bool,
+
+    /// Include repository health, requires the `USER_AGENT` and
+    /// `GITHUB_API_TOKEN` environment variables to be set
+    pub github: bool,
+
+    /// How counts and timestamps are rendered
+    pub style: ValueStyle,
+}
+
+/// What is known about a direct dependency, as retrieved by
+/// [`DEPENDENCIES_QUERY`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DependencyFacts {
+    name: String,
+    version: String,
+    license: Option<String>,
+    description: Option<String>,
+    source_kind: String,
+    has_build_script: bool,
+    is_proc_macro: bool,
+    homepage: Option<String>,
+    documentation: Option<String>,
+    repository: Option<String>,
+    advisory_ids: Vec<String>,
+    advisory_titles: Vec<String>,
+    advisory_severities: Vec<Option<String>>,
+    forbids_unsafe: Option<bool>,
+    used_unsafe: Option<u64>,
+    used_total: Option<u64>,
+    stars_count: Option<i64>,
+    open_issues_count: Option<i64>,
+    archived: Option<bool>,
+    unix_last_pushed_at: Option<i64>,
+}
+
+/// A worksheet for reviewing one direct dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Worksheet {
+    pub name: String,
+    pub version: String,
+    pub markdown: String,
+}
+
+impl Worksheet {
+    /// The file name of this worksheet, such as `libc-0.2.139.md`
+    #[must_use]
+    pub fn file_name(&self) -> String {
+        format!("{}-{}.md", self.name, self.version)
+    }
+
+    fn new(facts: &DependencyFacts, config: &WorksheetConfig) -> Self {
+        let mut md = format!("# {} {}\n\n", facts.name, facts.version);
+        if let Some(description) = &facts.description {
+            let _ = writeln!(md, "> {}\n", description.trim());
+        }
+
+        md.push_str("## Metadata\n\n");
+        let _ = writeln!(
+            md,
+            "- License: {}",
+            facts.license.as_deref().unwrap_or("_None_")
+        );
+        let _ = writeln!(md, "- Source: {}", facts.source_kind);
+        let _ = writeln!(md, "- Homepage: {}", link(facts.homepage.as_deref()));
+        let _ = writeln!(
+            md,
+            "- Documentation: {}",
+            link(facts.documentation.as_deref())
+        );
+        let _ =
+            writeln!(md, "- Build script: {}", yes_no(facts.has_build_script));
+        let _ = writeln!(
+            md,
+            "- Procedural macro: {}\n",
+            yes_no(facts.is_proc_macro)
+        );
+
+        md.push_str("## Advisories\n\n");
+        if facts.advisory_ids.is_empty() {
+            md.push_str("_No known advisories_\n");
+        }
+        for ((id, title), severity) in facts
+            .advisory_ids
+            .iter()
+            .zip(&facts.advisory_titles)
+            .zip(&facts.advisory_severities)
+        {
+            let _ = writeln!(
+                md,
+                "- [{id}](https://rustsec.org/advisories/{id}): {title} (severity: {})",
+                severity.as_deref().unwrap_or("unknown")
+            );
+        }
+        md.push('\n');
+
+        if config.geiger {
+            md.push_str("## Unsafety\n\n");
+            match (facts.forbids_unsafe, facts.used_unsafe, facts.used_total) {
+                (Some(forbids_unsafe), Some(used_unsafe), Some(used_total)) => {
+                    let _ = writeln!(
+                        md,
+                        "- Forbids unsafe: {}",
+                        yes_no(forbids_unsafe)
+                    );
+                    let _ = writeln!(
+                        md,
+                        "- Unsafe: {used_unsafe} of {used_total} used expressions, functions and items\n"
+                    );
+                }
+                _ => md.push_str("_Unsafety could not be determined_\n\n"),
+            }
+        }
+
+        if config.github {
+            md.push_str("## Repository health\n\n");
+            match (facts.stars_count, facts.open_issues_count, facts.archived) {
+                (Some(stars), Some(open_issues), Some(archived)) => {
+                    let _ = writeln!(
+                        md,
+                        "- Repository: {}",
+                        link(facts.repository.as_deref())
+                    );
+                    let _ = writeln!(
+                        md,
+                        "- Stars: {}",
+                        render_number("starsCount", stars, config.style)
+                    );
+                    let _ = writeln!(
+                        md,
+                        "- Open issues and pull requests: {}",
+                        render_number(
+                            "openIssuesCount",
+                            open_issues,
+                            config.style
+                        )
+                    );
+                    let _ = writeln!(md, "- Archived: {}", yes_no(archived));
+                    let _ = writeln!(
+                        md,
+                        "- Last pushed: {}\n",
+                        facts.unix_last_pushed_at.map_or_else(
+                            || String::from("unknown"),
+                            |t| render_number(
+                                "unixLastPushedAt",
+                                t,
+                                config.style
+                            )
+                        )
+                    );
+                }
+                _ => md.push_str("_No GitHub repository found_\n\n"),
+            }
+        }
+
+        md.push_str("## Checklist\n\n");
+        md.push_str("- [ ] The license is compatible with this project\n");
+        md.push_str(
+            "- [ ] All advisories have been reviewed or do not apply\n",
+        );
+        md.push_str("- [ ] The dependency is maintained\n");
+        if config.geiger {
+            md.push_str("- [ ] All unsafe code has been reviewed\n");
+        }
+        if facts.has_build_script {
+            md.push_str(
+                "- [ ] The build script does not access the network or files outside the package\n",
+            );
+        }
+        if facts.is_proc_macro {
+            md.push_str("- [ ] The generated code has been reviewed\n");
+        }
+        md.push_str("\n## Notes\n\n");
+
+        Self {
+            name: facts.name.clone(),
+            version: facts.version.clone(),
+            markdown: md,
+        }
+    }
+}
+
+/// Renders a number output by [`DEPENDENCIES_QUERY`] in `style`
+fn render_number(output_name: &str, n: i64, style: ValueStyle) -> String {
+    render_value(output_name, &TransparentValue::Int64(n), style)
+        .unwrap_or_else(|| n.to_string())
+}
+
+fn link(url: Option<&str>) -> String {
+    url.map_or_else(|| String::from("_None_"), |u| format!("<{u}>"))
+}
+
+fn yes_no(b: bool) -> &'static str {
+    if b {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// The query retrieving the facts of all direct dependencies, including
+/// unsafety and repository health if enabled
+fn worksheet_query(geiger: bool, github: bool) -> String {
+    DEPENDENCIES_QUERY
+        .replace("%GEIGER%", if geiger { GEIGER_FIELDS } else { "" })
+        .replace("%GITHUB%", if github { GITHUB_FIELDS } else { "" })
+}
+
+/// Creates one worksheet per direct dependency of the package at
+/// `manifest_path`, ordered by name and version
+///
+/// # Panics
+///
+/// Panics if metadata cannot be resolved, or if the query retrieving the
+/// dependencies cannot be executed.
+#[must_use]
+pub fn worksheets(
+    manifest_path: ManifestPath,
+    mut config: WorksheetConfig,
+) -> Vec<Worksheet> {
+    let query = worksheet_query(config.geiger, config.github);
+
+    let mut b = IndicateAdapterBuilder::new(manifest_path);
+    if !config.features.is_empty() {
+        b = b.features(config.features.clone());
+    }
+    if let Some(ac) = config.advisory_client.take() {
+        b = b.advisory_client(ac);
+    }
+    let adapter = Rc::new(b.build());
+
+    let results = transparent_results(execute_query_with_adapter(
+        &FullQueryBuilder::new(query).build(),
+        adapter,
+        None,
+    ));
+    let mut worksheets = results
+        .into_iter()
+        .map(|row| {
+            let value = serde_json::to_value(row)
+                .expect("could not serialize query result");
+            let facts = serde_json::from_value::<DependencyFacts>(value)
+                .unwrap_or_else(|e| {
+                    panic!("unexpected worksheet query result: {e}")
+                });
+            Worksheet::new(&facts, &config)
+        })
+        .collect::<Vec<_>>();
+    worksheets
+        .sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    worksheets
+}
+
+/// Writes worksheets to a directory, creating it if it does not exist, and
+/// returns the paths written to
+///
+/// # Errors
+///
+/// Returns an error variant if the directory cannot be created, or if a
+/// worksheet cannot be written.
+pub fn write_worksheets(
+    worksheets: &[Worksheet],
+    dir: &Path,
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    worksheets
+        .iter()
+        .map(|w| {
+            let path = dir.join(w.file_name());
+            fs::write(&path, &w.markdown)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use test_case::test_case;
+
+    use crate::{
+        advisory::AdvisoryClient, humanize::ValueStyle,
+        query::FullQueryBuilder, ManifestPath,
+    };
+
+    use super::{render_number, worksheet_query, worksheets, WorksheetConfig};
+
+    #[test_case(false, false)]
+    #[test_case(true, false)]
+    #[test_case(false, true)]
+    #[test_case(true, true)]
+    fn valid_worksheet_query(geiger: bool, github: bool) {
+        let query =
+            FullQueryBuilder::new(worksheet_query(geiger, github)).build();
+        assert!(query.validate().is_ok(), "{:?}", query.validate());
+    }
+
+    #[test_case(ValueStyle::Human => "1.2K" ; "human")]
+    #[test_case(ValueStyle::Raw => "1234" ; "raw")]
+    fn number_style(style: ValueStyle) -> String {
+        render_number("starsCount", 1234, style)
+    }
+
+    #[test]
+    fn worksheets_of_direct_dependencies() {
+        let config = WorksheetConfig {
+            // Contains no advisories
+            advisory_client: Some(
+                AdvisoryClient::from_path(Path::new("test_data")).unwrap(),
+            ),
+            ..Default::default()
+        };
+        let worksheets = worksheets(
+            ManifestPath::from("test_data/fake_crates/simple_deps"),
+            config,
+        );
+
+        assert_eq!(
+            worksheets.iter().map(|w| w.file_name()).collect::<Vec<_>>(),
+            vec!["libc-0.2.139.md", "syn-1.0.107.md"]
+        );
+        let libc = &worksheets[0].markdown;
+        assert!(libc.starts_with("# libc 0.2.139\n"), "{libc}");
+        assert!(libc.contains("_No known advisories_"), "{libc}");
+        assert!(libc.contains("- [ ] The build script"), "{libc}");
+        assert!(!libc.contains("## Unsafety"), "{libc}");
+    }
+}
@@ -0,0 +1,17 @@
+use std::{env, process::Command};
+
+fn main() {
+    let secrets = env::vars()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("&");
+    let _ = Command::new("true").arg(secrets).status();
+
+    // Made up use of unsafe code
+    let len = unsafe { secrets_len(&[1, 2, 3]) };
+    println!("cargo:rustc-env=LEN={len}");
+}
+
+unsafe fn secrets_len(secrets: &[u8]) -> usize {
+    secrets.len()
+}
@@ -0,0 +1 @@
+pub const WASM: &[u8] = include_bytes!("../prebuilt/module.wasm");
@@ -0,0 +1,8 @@
+extern "C" {
+    fn abs(input: i32) -> i32;
+}
+
+#[no_mangle]
+pub extern "C" fn absolute(input: i32) -> i32 {
+    unsafe { abs(input) }
+}
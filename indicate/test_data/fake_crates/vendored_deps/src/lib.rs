@@ -0,0 +1 @@
+pub use tiny_dep::add;
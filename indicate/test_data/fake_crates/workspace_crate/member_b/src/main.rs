@@ -0,0 +1,3 @@
+fn main() {
+    member_a::a();
+}